@@ -0,0 +1,90 @@
+//! Converts a `GnuCash` book between the XML and `SQLite` formats without the `GnuCash` GUI.
+//!
+//! ```text
+//! rucash-convert --to <sqlite|xml> <input> <output>
+//! ```
+//!
+//! The input format is detected from the file content.
+
+use std::fs::File;
+use std::io::Read;
+use std::process::ExitCode;
+
+use rucash::{Book, Error, SQLiteQuery, XMLQuery};
+
+const USAGE: &str = "usage: rucash-convert --to <sqlite|xml> <input> <output>";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Sqlite,
+    Xml,
+}
+
+fn parse_args(args: &[String]) -> Option<(Format, &str, &str)> {
+    match args {
+        [flag, format, input, output] if flag == "--to" => {
+            let format = match format.as_str() {
+                "sqlite" => Format::Sqlite,
+                "xml" => Format::Xml,
+                _ => return None,
+            };
+            Some((format, input, output))
+        }
+        _ => None,
+    }
+}
+
+/// Tells the format from the file header: gzip compressed or plain XML, or an `SQLite` database.
+fn detect(path: &str) -> Result<Format, Error> {
+    let mut header = Vec::new();
+    File::open(path)?.take(16).read_to_end(&mut header)?;
+    if header.starts_with(&[0x1f, 0x8b]) || header.starts_with(b"<?xml") {
+        Ok(Format::Xml)
+    } else if header.starts_with(b"SQLite format 3\0") {
+        Ok(Format::Sqlite)
+    } else {
+        Err(Error::IO(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{path} is neither a GnuCash XML nor an SQLite file"),
+        )))
+    }
+}
+
+async fn write<Q: rucash::Query>(
+    book: &Book<Q>,
+    format: Format,
+    output: &str,
+) -> Result<(), Error> {
+    match format {
+        Format::Sqlite => rucash::convert::to_sqlite(book, output).await,
+        Format::Xml => rucash::convert::to_xml(book, output).await,
+    }
+}
+
+async fn convert(format: Format, input: &str, output: &str) -> Result<(), Error> {
+    match detect(input)? {
+        Format::Sqlite => write(&Book::new(SQLiteQuery::new(input)?).await?, format, output).await,
+        Format::Xml => write(&Book::new(XMLQuery::new(input)?).await?, format, output).await,
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some((format, input, output)) = parse_args(&args) else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let result = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .map_err(Error::from)
+        .and_then(|rt| rt.block_on(convert(format, input, output)));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("rucash-convert: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,11 +1,22 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::instrument;
 
+use crate::builder::{ChangeSet, NewPrice, TransactionBuilder};
 use crate::error::Error;
 use crate::exchange::Exchange;
-use crate::model::{Account, Commodity, Price, Split, Transaction};
-use crate::query::Query;
+use crate::model::aging;
+use crate::model::book_info;
+use crate::model::slot::{self, Slot};
+use crate::model::{
+    Account, AccountTree, AgingKind, AgingLine, BillTerm, BookInfo, Budget, Commodity, Customer,
+    Employee, Invoice, Job, Lock, Lot, Order, Price, ScheduledTransaction, Split, TaxTable,
+    Transaction, Vendor,
+};
+use crate::query::{
+    AccountQ, BookQ, BookT, ChangeSetQ, LockQ, PriceMutQ, PriceQ, Query, TransactionMutQ,
+};
 
 #[derive(Debug, Clone)]
 pub struct Book<Q>
@@ -43,6 +54,37 @@ where
         Ok(book)
     }
 
+    /// The book's guid, its root accounts, the schema versions and the features it relies on.
+    ///
+    /// Check [`BookInfo::check_versions`] before relying on the rest of the book.
+    #[instrument(skip(self))]
+    pub async fn info(&self) -> Result<BookInfo, Error> {
+        book_info::load(&*self.query).await
+    }
+
+    /// The top of the account tree, as recorded for the book.
+    #[instrument(skip(self))]
+    pub async fn root_account(&self) -> Result<Account<Q>, Error> {
+        let book = BookQ::book(&*self.query)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch book: {e}"))?;
+
+        tracing::debug!("fetching root account");
+        let account = AccountQ::guid(&*self.query, book.root_account_guid())
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch account: {e}"))?;
+
+        if let Some(a) = account {
+            Ok(Account::from_with_query(&a, self.query.clone()))
+        } else {
+            tracing::error!("root account not found");
+            Err(Error::GuidNotFound {
+                model: "Account".to_string(),
+                guid: book.root_account_guid().to_string(),
+            })
+        }
+    }
+
     #[instrument(skip(self))]
     pub async fn accounts(&self) -> Result<Vec<Account<Q>>, Error> {
         tracing::debug!("fetching all accounts");
@@ -62,6 +104,21 @@ where
         Ok(result)
     }
 
+    /// The whole account hierarchy, read in one query.
+    #[instrument(skip(self))]
+    pub async fn account_tree(&self) -> Result<AccountTree<Q>, Error> {
+        AccountTree::load(self.query.clone()).await
+    }
+
+    /// The account at a colon-joined path below the root, e.g. `Assets:Broker:NASDAQ`.
+    ///
+    /// Unlike a name search, the path tells apart same-named accounts in different branches.
+    #[instrument(skip(self))]
+    pub async fn account_by_path(&self, path: &str) -> Result<Option<Account<Q>>, Error> {
+        let tree = self.account_tree().await?;
+        Ok(tree.by_path(path).cloned())
+    }
+
     #[instrument(skip(self))]
     pub async fn accounts_contains_name_ignore_case(
         &self,
@@ -128,6 +185,244 @@ where
         Ok(result)
     }
 
+    #[instrument(skip(self))]
+    pub async fn budgets(&self) -> Result<Vec<Budget<Q>>, Error> {
+        tracing::debug!("fetching all budgets");
+
+        let budgets = self
+            .query
+            .budgets()
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch budgets: {e}"))?;
+
+        let result: Vec<_> = budgets
+            .into_iter()
+            .map(|x| Budget::from_with_query(&x, self.query.clone()))
+            .collect();
+
+        tracing::debug!(count = result.len(), "budgets fetched successfully");
+        Ok(result)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn bill_terms(&self) -> Result<Vec<BillTerm>, Error> {
+        tracing::debug!("fetching all bill terms");
+
+        let bill_terms = self
+            .query
+            .bill_terms()
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch bill terms: {e}"))?;
+
+        let result: Vec<_> = bill_terms.iter().map(BillTerm::from_item).collect();
+
+        tracing::debug!(count = result.len(), "bill terms fetched successfully");
+        Ok(result)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn tax_tables(&self) -> Result<Vec<TaxTable<Q>>, Error> {
+        tracing::debug!("fetching all tax tables");
+
+        let tax_tables = self
+            .query
+            .tax_tables()
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch tax tables: {e}"))?;
+
+        let result: Vec<_> = tax_tables
+            .into_iter()
+            .map(|x| TaxTable::from_with_query(&x, self.query.clone()))
+            .collect();
+
+        tracing::debug!(count = result.len(), "tax tables fetched successfully");
+        Ok(result)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn customers(&self) -> Result<Vec<Customer<Q>>, Error> {
+        tracing::debug!("fetching all customers");
+
+        let customers = self
+            .query
+            .customers()
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch customers: {e}"))?;
+
+        let result: Vec<_> = customers
+            .into_iter()
+            .map(|x| Customer::from_with_query(&x, self.query.clone()))
+            .collect();
+
+        tracing::debug!(count = result.len(), "customers fetched successfully");
+        Ok(result)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn vendors(&self) -> Result<Vec<Vendor<Q>>, Error> {
+        tracing::debug!("fetching all vendors");
+
+        let vendors = self
+            .query
+            .vendors()
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch vendors: {e}"))?;
+
+        let result: Vec<_> = vendors
+            .into_iter()
+            .map(|x| Vendor::from_with_query(&x, self.query.clone()))
+            .collect();
+
+        tracing::debug!(count = result.len(), "vendors fetched successfully");
+        Ok(result)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn employees(&self) -> Result<Vec<Employee<Q>>, Error> {
+        tracing::debug!("fetching all employees");
+
+        let employees = self
+            .query
+            .employees()
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch employees: {e}"))?;
+
+        let result: Vec<_> = employees
+            .into_iter()
+            .map(|x| Employee::from_with_query(&x, self.query.clone()))
+            .collect();
+
+        tracing::debug!(count = result.len(), "employees fetched successfully");
+        Ok(result)
+    }
+
+    /// Customer invoices, vendor bills and employee expense vouchers, posted or not.
+    #[instrument(skip(self))]
+    pub async fn invoices(&self) -> Result<Vec<Invoice<Q>>, Error> {
+        tracing::debug!("fetching all invoices");
+
+        let invoices = self
+            .query
+            .invoices()
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch invoices: {e}"))?;
+
+        let result: Vec<_> = invoices
+            .into_iter()
+            .map(|x| Invoice::from_with_query(&x, self.query.clone()))
+            .collect();
+
+        tracing::debug!(count = result.len(), "invoices fetched successfully");
+        Ok(result)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn jobs(&self) -> Result<Vec<Job<Q>>, Error> {
+        tracing::debug!("fetching all jobs");
+
+        let jobs = self
+            .query
+            .jobs()
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch jobs: {e}"))?;
+
+        let result: Vec<_> = jobs
+            .into_iter()
+            .map(|x| Job::from_with_query(&x, self.query.clone()))
+            .collect();
+
+        tracing::debug!(count = result.len(), "jobs fetched successfully");
+        Ok(result)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn orders(&self) -> Result<Vec<Order<Q>>, Error> {
+        tracing::debug!("fetching all orders");
+
+        let orders = self
+            .query
+            .orders()
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch orders: {e}"))?;
+
+        let result: Vec<_> = orders
+            .into_iter()
+            .map(|x| Order::from_with_query(&x, self.query.clone()))
+            .collect();
+
+        tracing::debug!(count = result.len(), "orders fetched successfully");
+        Ok(result)
+    }
+
+    /// Open balances per customer or vendor as of a date, bucketed by days past due.
+    #[instrument(skip(self))]
+    pub async fn aging(
+        &self,
+        kind: AgingKind,
+        as_of: chrono::NaiveDate,
+    ) -> Result<Vec<AgingLine<Q>>, Error> {
+        aging::report(self.query.clone(), kind, as_of).await
+    }
+
+    #[instrument(skip(self))]
+    pub async fn scheduled_transactions(&self) -> Result<Vec<ScheduledTransaction<Q>>, Error> {
+        tracing::debug!("fetching all scheduled transactions");
+
+        let scheduled_transactions = self
+            .query
+            .scheduled_transactions()
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch scheduled transactions: {e}"))?;
+
+        let result: Vec<_> = scheduled_transactions
+            .into_iter()
+            .map(|x| ScheduledTransaction::from_with_query(&x, self.query.clone()))
+            .collect();
+
+        tracing::debug!(
+            count = result.len(),
+            "scheduled transactions fetched successfully"
+        );
+        Ok(result)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn lots(&self) -> Result<Vec<Lot<Q>>, Error> {
+        tracing::debug!("fetching all lots");
+
+        let lots = self
+            .query
+            .lots()
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch lots: {e}"))?;
+
+        let result: Vec<_> = lots
+            .into_iter()
+            .map(|x| Lot::from_with_query(&x, self.query.clone()))
+            .collect();
+
+        tracing::debug!(count = result.len(), "lots fetched successfully");
+        Ok(result)
+    }
+
+    /// The book-level slots, which hold the file's `options` and `features`.
+    #[instrument(skip(self))]
+    pub async fn slots(&self) -> Result<BTreeMap<String, Slot>, Error> {
+        let book = BookQ::book(&*self.query)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch book: {e}"))?;
+        slot::load(&*self.query, book.guid()).await
+    }
+
+    /// The book slot at `path`, e.g. `options/Accounts/Use Trading Accounts`.
+    #[instrument(skip(self))]
+    pub async fn slot(&self, path: &str) -> Result<Option<Slot>, Error> {
+        let book = BookQ::book(&*self.query)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch book: {e}"))?;
+        slot::find(&*self.query, book.guid(), path).await
+    }
+
     #[instrument(skip(self))]
     pub async fn transactions(&self) -> Result<Vec<Transaction<Q>>, Error> {
         tracing::debug!("fetching all transactions");
@@ -244,6 +539,148 @@ where
     }
 }
 
+impl<Q> Book<Q>
+where
+    Q: Query + TransactionMutQ,
+{
+    /// Starts a new transaction. Nothing is written until [`TransactionBuilder::commit`].
+    #[must_use]
+    pub fn new_transaction(&self) -> TransactionBuilder<Q> {
+        TransactionBuilder::new(self.query.clone())
+    }
+}
+
+impl<Q> Book<Q>
+where
+    Q: Query + PriceMutQ,
+{
+    /// Stores a price of `commodity` in `currency` and refreshes the exchange graph.
+    ///
+    /// Fails if a price for the same pair already exists on that date.
+    #[instrument(skip(self, commodity, currency), fields(
+        commodity_mnemonic = %commodity.mnemonic,
+        currency_mnemonic = %currency.mnemonic
+    ))]
+    pub async fn add_price(
+        &self,
+        commodity: &Commodity<Q>,
+        currency: &Commodity<Q>,
+        datetime: chrono::NaiveDateTime,
+        value: crate::Num,
+        source: &str,
+        r#type: &str,
+    ) -> Result<Price<Q>, Error> {
+        tracing::debug!("adding price");
+
+        let record = NewPrice {
+            commodity,
+            currency,
+            datetime,
+            value,
+            source,
+            r#type,
+        }
+        .build()
+        .inspect_err(|e| tracing::error!("invalid price: {e}"))?;
+
+        let inserted = PriceMutQ::insert(&*self.query, std::slice::from_ref(&record))
+            .await
+            .inspect_err(|e| tracing::error!("failed to insert price: {e}"))?;
+        if inserted.is_empty() {
+            tracing::warn!("duplicate price");
+            return Err(Error::Invalid {
+                model: "Price".to_string(),
+                reason: format!(
+                    "{} already has a price in {} on {}",
+                    commodity.mnemonic,
+                    currency.mnemonic,
+                    datetime.date()
+                ),
+            });
+        }
+
+        self.update_exchange_graph().await?;
+
+        let item = PriceQ::guid(&*self.query, &record.guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch price: {e}"))?
+            .ok_or_else(|| Error::GuidNotFound {
+                model: "Price".to_string(),
+                guid: record.guid.clone(),
+            })?;
+
+        tracing::info!(price_guid = %record.guid, "price added");
+        Ok(Price::from_with_query(&item, self.query.clone()))
+    }
+
+    /// Stores all prices atomically and refreshes the exchange graph.
+    ///
+    /// Prices sharing commodity, currency and date with a stored or earlier price
+    /// are skipped. Returns the number of prices stored.
+    #[instrument(skip(self, prices))]
+    pub async fn import_prices<'a, I>(&self, prices: I) -> Result<usize, Error>
+    where
+        I: IntoIterator<Item = NewPrice<'a, Q>>,
+        Q: 'a,
+    {
+        tracing::debug!("importing prices");
+
+        let records = prices
+            .into_iter()
+            .map(|x| x.build())
+            .collect::<Result<Vec<_>, _>>()
+            .inspect_err(|e| tracing::error!("invalid price: {e}"))?;
+
+        let inserted = PriceMutQ::insert(&*self.query, &records)
+            .await
+            .inspect_err(|e| tracing::error!("failed to insert prices: {e}"))?;
+
+        self.update_exchange_graph().await?;
+
+        tracing::info!(
+            count = inserted.len(),
+            skipped = records.len() - inserted.len(),
+            "prices imported"
+        );
+        Ok(inserted.len())
+    }
+}
+
+impl<Q> Book<Q>
+where
+    Q: Query + ChangeSetQ,
+{
+    /// Starts a change set. Nothing is written until [`ChangeSet::commit`].
+    #[must_use]
+    pub fn begin(&self) -> ChangeSet<'_, Q> {
+        ChangeSet::new(self)
+    }
+}
+
+impl<Q> Book<Q>
+where
+    Q: Query + LockQ,
+{
+    /// Returns who else holds a `gnclock` entry, i.e. has the book open in `GnuCash`, if
+    /// anyone. The entries this process takes while writing are skipped.
+    #[instrument(skip(self))]
+    pub async fn lock_status(&self) -> Result<Option<Lock>, Error> {
+        tracing::debug!("fetching lock status");
+
+        let locks = self
+            .query
+            .locks()
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch locks: {e}"))?;
+
+        let hostname = crate::query::hostname();
+        let pid = i64::from(std::process::id());
+        Ok(locks
+            .into_iter()
+            .find(|lock| lock.hostname != hostname || lock.pid != pid))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(not(feature = "decimal"))]
@@ -260,6 +697,7 @@ mod tests {
         use test_log::test;
 
         use crate::SQLiteQuery;
+        use crate::query::sqlite::tests::setup_writable;
 
         use super::*;
 
@@ -358,6 +796,24 @@ mod tests {
             assert_eq!(currencies.len(), 4);
         }
 
+        #[test(tokio::test)]
+        async fn test_slots() {
+            let book = setup().await;
+            let slots = book.slots().await.unwrap();
+            assert_eq!(slots.len(), 3);
+            assert_eq!(
+                slots["remove-color-not-set-slots"],
+                Slot::String("true".to_string())
+            );
+            assert_eq!(
+                book.slot("options/Accounts/Use Trading Accounts")
+                    .await
+                    .unwrap(),
+                Some(Slot::String("t".to_string()))
+            );
+            assert_eq!(book.slot("options/Budgeting/x").await.unwrap(), None);
+        }
+
         #[test(tokio::test)]
         async fn test_exchange() {
             let book = setup().await;
@@ -382,6 +838,202 @@ mod tests {
             #[cfg(feature = "decimal")]
             assert_eq!(rate, Decimal::new(15, 1));
         }
+
+        async fn commodity(book: &Book<SQLiteQuery>, mnemonic: &str) -> Commodity<SQLiteQuery> {
+            book.commodities()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.mnemonic == mnemonic)
+                .unwrap()
+        }
+
+        fn datetime(s: &str) -> chrono::NaiveDateTime {
+            chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_add_price() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let adf = commodity(&book, "ADF").await;
+            let aed = commodity(&book, "AED").await;
+
+            #[cfg(not(feature = "decimal"))]
+            let value = 1.75;
+            #[cfg(feature = "decimal")]
+            let value = Decimal::new(175, 2);
+            let price = book
+                .add_price(
+                    &adf,
+                    &aed,
+                    datetime("2018-03-01 12:00:00"),
+                    value,
+                    "Finance::Quote",
+                    "last",
+                )
+                .await
+                .unwrap();
+            assert_eq!(price.commodity_guid, adf.guid);
+            assert_eq!(price.currency_guid, aed.guid);
+            assert_eq!(price.source, "Finance::Quote");
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, price.value, value);
+            #[cfg(feature = "decimal")]
+            assert_eq!(price.value, value);
+            assert_eq!(book.prices().await.unwrap().len(), 6);
+
+            // the exchange graph picks up the newer price
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, book.exchange(&adf, &aed).await.unwrap(), value);
+            #[cfg(feature = "decimal")]
+            assert_eq!(book.exchange(&adf, &aed).await.unwrap(), value);
+
+            let result = book
+                .add_price(
+                    &adf,
+                    &aed,
+                    datetime("2018-03-01 18:00:00"),
+                    value,
+                    "Finance::Quote",
+                    "last",
+                )
+                .await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+
+            let result = book
+                .add_price(&adf, &adf, datetime("2018-03-02 12:00:00"), value, "", "")
+                .await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+        }
+
+        #[test(tokio::test)]
+        async fn test_import_prices() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let adf = commodity(&book, "ADF").await;
+            let aed = commodity(&book, "AED").await;
+            let foo = commodity(&book, "FOO").await;
+
+            #[cfg(not(feature = "decimal"))]
+            let (v1, v2) = (2.0, 42.5);
+            #[cfg(feature = "decimal")]
+            let (v1, v2) = (Decimal::new(2, 0), Decimal::new(425, 1));
+            let prices = [
+                (&adf, "2018-02-20 10:00:00", v1),
+                (&adf, "2018-03-01 10:00:00", v1),
+                (&adf, "2018-03-01 11:00:00", v1),
+                (&foo, "2018-03-01 10:00:00", v2),
+            ]
+            .map(|(commodity, date, value)| NewPrice {
+                commodity,
+                currency: &aed,
+                datetime: datetime(date),
+                value,
+                source: "Finance::Quote",
+                r#type: "last",
+            });
+
+            let count = book.import_prices(prices).await.unwrap();
+            assert_eq!(count, 2);
+            assert_eq!(book.prices().await.unwrap().len(), 7);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, book.exchange(&adf, &aed).await.unwrap(), v1);
+            #[cfg(feature = "decimal")]
+            assert_eq!(book.exchange(&adf, &aed).await.unwrap(), v1);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, book.exchange(&foo, &aed).await.unwrap(), v2);
+            #[cfg(feature = "decimal")]
+            assert_eq!(book.exchange(&foo, &aed).await.unwrap(), v2);
+        }
+
+        #[test(tokio::test)]
+        async fn test_lock_status() {
+            let book = setup().await;
+            assert_eq!(book.lock_status().await.unwrap(), None);
+        }
+
+        #[test(tokio::test)]
+        async fn test_lock_status_skips_own() {
+            let (dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let conn =
+                rusqlite::Connection::open(dir.path().join("complex_sample.gnucash")).unwrap();
+            conn.execute(
+                "INSERT INTO gnclock (Hostname, PID) VALUES (?1, ?2)",
+                rusqlite::params![crate::query::hostname(), std::process::id()],
+            )
+            .unwrap();
+            assert_eq!(book.lock_status().await.unwrap(), None);
+
+            conn.execute(
+                "INSERT INTO gnclock (Hostname, PID) VALUES ('other-host', 4242)",
+                [],
+            )
+            .unwrap();
+            assert_eq!(
+                book.lock_status().await.unwrap(),
+                Some(Lock {
+                    hostname: "other-host".to_string(),
+                    pid: 4242
+                })
+            );
+        }
+
+        #[test(tokio::test)]
+        async fn test_add_price_locked() {
+            let (dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            rusqlite::Connection::open(dir.path().join("complex_sample.gnucash"))
+                .unwrap()
+                .execute(
+                    "INSERT INTO gnclock (Hostname, PID) VALUES ('other-host', 4242)",
+                    [],
+                )
+                .unwrap();
+            let adf = commodity(&book, "ADF").await;
+            let aed = commodity(&book, "AED").await;
+            let datetime = datetime("2024-01-01 10:00:00");
+
+            assert_eq!(
+                book.lock_status().await.unwrap(),
+                Some(Lock {
+                    hostname: "other-host".to_string(),
+                    pid: 4242
+                })
+            );
+            let result = book
+                .add_price(
+                    &adf,
+                    &aed,
+                    datetime,
+                    crate::Num::from(2),
+                    "user:price",
+                    "last",
+                )
+                .await;
+            assert!(matches!(result, Err(Error::Locked { pid: 4242, .. })));
+            assert_eq!(book.prices().await.unwrap().len(), 5);
+
+            let query = SQLiteQuery::new_writable(
+                dir.path().join("complex_sample.gnucash").to_str().unwrap(),
+            )
+            .unwrap()
+            .ignore_lock(true);
+            let book = Book::new(query).await.unwrap();
+            book.add_price(
+                &adf,
+                &aed,
+                datetime,
+                crate::Num::from(2),
+                "user:price",
+                "last",
+            )
+            .await
+            .unwrap();
+            assert_eq!(book.prices().await.unwrap().len(), 6);
+            assert_eq!(book.lock_status().await.unwrap().map(|x| x.pid), Some(4242));
+        }
     }
 
     #[cfg(feature = "mysql")]
@@ -412,6 +1064,12 @@ mod tests {
             Book::new(query).await.unwrap();
         }
 
+        #[test(tokio::test)]
+        async fn test_lock_status() {
+            let book = setup().await;
+            assert_eq!(book.lock_status().await.unwrap(), None);
+        }
+
         #[test(tokio::test)]
         async fn test_new_fail() {
             assert!(matches!(
@@ -480,6 +1138,24 @@ mod tests {
             assert_eq!(currencies.len(), 4);
         }
 
+        #[test(tokio::test)]
+        async fn test_slots() {
+            let book = setup().await;
+            let slots = book.slots().await.unwrap();
+            assert_eq!(slots.len(), 3);
+            assert_eq!(
+                slots["remove-color-not-set-slots"],
+                Slot::String("true".to_string())
+            );
+            assert_eq!(
+                book.slot("options/Accounts/Use Trading Accounts")
+                    .await
+                    .unwrap(),
+                Some(Slot::String("t".to_string()))
+            );
+            assert_eq!(book.slot("options/Budgeting/x").await.unwrap(), None);
+        }
+
         #[test(tokio::test)]
         async fn test_exchange() {
             let book = setup().await;
@@ -534,6 +1210,12 @@ mod tests {
             Book::new(query).await.unwrap();
         }
 
+        #[test(tokio::test)]
+        async fn test_lock_status() {
+            let book = setup().await;
+            assert_eq!(book.lock_status().await.unwrap(), None);
+        }
+
         #[test(tokio::test)]
         async fn test_new_fail() {
             assert!(matches!(
@@ -602,6 +1284,24 @@ mod tests {
             assert_eq!(currencies.len(), 4);
         }
 
+        #[test(tokio::test)]
+        async fn test_slots() {
+            let book = setup().await;
+            let slots = book.slots().await.unwrap();
+            assert_eq!(slots.len(), 3);
+            assert_eq!(
+                slots["remove-color-not-set-slots"],
+                Slot::String("true".to_string())
+            );
+            assert_eq!(
+                book.slot("options/Accounts/Use Trading Accounts")
+                    .await
+                    .unwrap(),
+                Some(Slot::String("t".to_string()))
+            );
+            assert_eq!(book.slot("options/Budgeting/x").await.unwrap(), None);
+        }
+
         #[test(tokio::test)]
         async fn test_exchange() {
             let book = setup().await;
@@ -732,6 +1432,24 @@ mod tests {
             assert_eq!(currencies.len(), 4);
         }
 
+        #[test(tokio::test)]
+        async fn test_slots() {
+            let book = setup().await;
+            let slots = book.slots().await.unwrap();
+            assert_eq!(slots.len(), 3);
+            assert_eq!(
+                slots["remove-color-not-set-slots"],
+                Slot::String("true".to_string())
+            );
+            assert_eq!(
+                book.slot("options/Accounts/Use Trading Accounts")
+                    .await
+                    .unwrap(),
+                Some(Slot::String("t".to_string()))
+            );
+            assert_eq!(book.slot("options/Budgeting/x").await.unwrap(), None);
+        }
+
         #[test(tokio::test)]
         async fn test_exchange() {
             let book = setup().await;
@@ -0,0 +1,162 @@
+mod change_set;
+mod price;
+mod transaction;
+
+pub use change_set::ChangeSet;
+pub use price::NewPrice;
+pub use transaction::TransactionBuilder;
+
+use crate::error::Error;
+
+/// Generates a new `GnuCash`-style GUID: 32 lowercase hex digits.
+pub(crate) fn new_guid() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Converts `value` into the numerator of a fraction with the given denominator,
+/// rounding half away from zero like `GnuCash` does.
+#[cfg(not(feature = "decimal"))]
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+pub(crate) fn to_fraction(value: f64, denom: i64) -> Result<i64, Error> {
+    let num = (value * denom as f64).round();
+    if !num.is_finite() || num.abs() >= i64::MAX as f64 {
+        return Err(Error::Invalid {
+            model: "Amount".to_string(),
+            reason: format!("{value} cannot be stored with denominator {denom}"),
+        });
+    }
+    Ok(num as i64)
+}
+
+/// Converts `value` into the numerator of a fraction with the given denominator,
+/// rounding half away from zero like `GnuCash` does.
+#[cfg(feature = "decimal")]
+pub(crate) fn to_fraction(value: rust_decimal::Decimal, denom: i64) -> Result<i64, Error> {
+    use rust_decimal::RoundingStrategy;
+    use rust_decimal::prelude::ToPrimitive;
+
+    value
+        .checked_mul(rust_decimal::Decimal::new(denom, 0))
+        .and_then(|x| {
+            x.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+                .to_i64()
+        })
+        .ok_or_else(|| Error::Invalid {
+            model: "Amount".to_string(),
+            reason: format!("{value} cannot be stored with denominator {denom}"),
+        })
+}
+
+#[cfg(not(feature = "decimal"))]
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn from_fraction(num: i64, denom: i64) -> f64 {
+    num as f64 / denom as f64
+}
+
+#[cfg(feature = "decimal")]
+pub(crate) fn from_fraction(num: i64, denom: i64) -> rust_decimal::Decimal {
+    rust_decimal::Decimal::new(num, 0) / rust_decimal::Decimal::new(denom, 0)
+}
+
+/// Denominator used for prices given as floats, matching `GnuCash` quote precision.
+#[cfg(not(feature = "decimal"))]
+const PRICE_DENOM: i64 = 1_000_000_000;
+
+fn gcd(mut a: i64, mut b: i64) -> i64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+/// Converts a price into the smallest fraction representing it.
+#[cfg(not(feature = "decimal"))]
+pub(crate) fn to_price_fraction(value: f64) -> Result<(i64, i64), Error> {
+    let num = to_fraction(value, PRICE_DENOM)?;
+    let divisor = gcd(num, PRICE_DENOM).max(1);
+    Ok((num / divisor, PRICE_DENOM / divisor))
+}
+
+/// Converts a price into the smallest fraction representing it.
+#[cfg(feature = "decimal")]
+pub(crate) fn to_price_fraction(value: rust_decimal::Decimal) -> Result<(i64, i64), Error> {
+    let value = value.normalize();
+    let num = i64::try_from(value.mantissa());
+    let denom = 10_i64.checked_pow(value.scale());
+    let (Ok(num), Some(denom)) = (num, denom) else {
+        return Err(Error::Invalid {
+            model: "Price".to_string(),
+            reason: format!("{value} cannot be stored as a fraction"),
+        });
+    };
+    let divisor = gcd(num, denom).max(1);
+    Ok((num / divisor, denom / divisor))
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "decimal"))]
+    use float_cmp::assert_approx_eq;
+    use pretty_assertions::assert_eq;
+    #[cfg(feature = "decimal")]
+    use rust_decimal::Decimal;
+
+    use super::*;
+
+    #[test]
+    fn test_new_guid() {
+        let guid = new_guid();
+        assert_eq!(guid.len(), 32);
+        assert!(
+            guid.chars()
+                .all(|c| c.is_ascii_hexdigit() && !c.is_uppercase())
+        );
+        assert_ne!(guid, new_guid());
+    }
+
+    #[test]
+    fn test_to_fraction() {
+        #[cfg(not(feature = "decimal"))]
+        {
+            assert_eq!(to_fraction(12.346, 100).unwrap(), 1235);
+            assert_eq!(to_fraction(-0.005, 100).unwrap(), -1);
+            assert!(to_fraction(f64::NAN, 100).is_err());
+        }
+        #[cfg(feature = "decimal")]
+        {
+            assert_eq!(to_fraction(Decimal::new(12346, 3), 100).unwrap(), 1235);
+            assert_eq!(to_fraction(Decimal::new(-5, 3), 100).unwrap(), -1);
+            assert!(to_fraction(Decimal::MAX, 100).is_err());
+        }
+    }
+
+    #[test]
+    fn test_from_fraction() {
+        #[cfg(not(feature = "decimal"))]
+        assert_approx_eq!(f64, from_fraction(-1235, 100), -12.35);
+        #[cfg(feature = "decimal")]
+        assert_eq!(from_fraction(-1235, 100), Decimal::new(-1235, 2));
+    }
+
+    #[test]
+    fn test_to_price_fraction() {
+        #[cfg(not(feature = "decimal"))]
+        {
+            assert_eq!(to_price_fraction(1.5).unwrap(), (3, 2));
+            assert_eq!(
+                to_price_fraction(1.234_567).unwrap(),
+                (1_234_567, 1_000_000)
+            );
+            assert_eq!(to_price_fraction(0.0).unwrap(), (0, 1));
+        }
+        #[cfg(feature = "decimal")]
+        {
+            assert_eq!(to_price_fraction(Decimal::new(150, 2)).unwrap(), (3, 2));
+            assert_eq!(
+                to_price_fraction(Decimal::new(1_234_567, 6)).unwrap(),
+                (1_234_567, 1_000_000)
+            );
+            assert_eq!(to_price_fraction(Decimal::ZERO).unwrap(), (0, 1));
+        }
+    }
+}
@@ -0,0 +1,552 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use tracing::instrument;
+
+use super::{NewPrice, TransactionBuilder, new_guid};
+use crate::Book;
+use crate::error::Error;
+use crate::model::{Account, AccountType, Commodity, check_name, check_scu, types_compatible};
+use crate::query::{AccountRecord, AccountT, Change, ChangeSetQ, CommodityQ, Query, StoredState};
+
+/// Buffers account, transaction and price mutations and writes them atomically.
+///
+/// Created by [`Book::begin`](crate::Book::begin). Nothing is written until
+/// [`ChangeSet::commit`] has validated all changes together; dropping the change set,
+/// or calling [`ChangeSet::rollback`], discards them.
+#[derive(Debug)]
+pub struct ChangeSet<'a, Q>
+where
+    Q: Query + ChangeSetQ,
+{
+    book: &'a Book<Q>,
+    changes: Vec<Change>,
+}
+
+fn invalid(model: &str, reason: impl Into<String>) -> Error {
+    Error::Invalid {
+        model: model.to_string(),
+        reason: reason.into(),
+    }
+}
+
+fn not_found(model: &str, guid: &str) -> Error {
+    Error::GuidNotFound {
+        model: model.to_string(),
+        guid: guid.to_string(),
+    }
+}
+
+/// The book as it would look after the changes validated so far.
+struct State<Q>
+where
+    Q: Query,
+{
+    accounts: HashMap<String, AccountRecord>,
+    commodities: HashMap<String, Commodity<Q>>,
+    /// Accounts referenced by splits, stored or buffered.
+    used: HashSet<String>,
+    /// Commodity, currency and date of the prices, stored or buffered.
+    prices: HashSet<(String, String, chrono::NaiveDate)>,
+}
+
+impl<'a, Q> ChangeSet<'a, Q>
+where
+    Q: Query + ChangeSetQ,
+{
+    pub(crate) fn new(book: &'a Book<Q>) -> Self {
+        Self {
+            book,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Number of buffered changes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Buffers a new account below `parent`, which may itself be buffered.
+    ///
+    /// The returned account can be used as parent or split target of later changes.
+    pub fn create_account(
+        &mut self,
+        parent: &Account<Q>,
+        name: &str,
+        account_type: &AccountType,
+        commodity: &Commodity<Q>,
+        commodity_scu: i64,
+    ) -> Account<Q> {
+        let record = AccountRecord {
+            guid: new_guid(),
+            name: name.to_string(),
+            account_type: account_type.as_str().to_string(),
+            commodity_guid: commodity.guid.clone(),
+            commodity_scu,
+            non_std_scu: commodity_scu != commodity.fraction,
+            parent_guid: parent.guid.clone(),
+            code: String::new(),
+            description: String::new(),
+            hidden: false,
+            placeholder: false,
+        };
+        let account = Account::from_with_query(&record, self.book.query.clone());
+        self.changes.push(Change::InsertAccount(record));
+        account
+    }
+
+    /// Buffers the current fields of `account` to overwrite the stored ones.
+    pub fn update_account(&mut self, account: &Account<Q>) {
+        self.changes
+            .push(Change::UpdateAccount(account.to_record()));
+    }
+
+    pub fn delete_account(&mut self, account: &Account<Q>) {
+        self.changes
+            .push(Change::DeleteAccount(account.guid.clone()));
+    }
+
+    /// Buffers a transaction started with [`Book::new_transaction`](crate::Book::new_transaction).
+    ///
+    /// Fails right away if the transaction does not balance.
+    pub fn add_transaction(&mut self, transaction: &TransactionBuilder<Q>) -> Result<(), Error> {
+        let (transaction, splits) = transaction
+            .build()
+            .inspect_err(|e| tracing::error!("invalid transaction: {e}"))?;
+        self.changes
+            .push(Change::InsertTransaction(transaction, splits));
+        Ok(())
+    }
+
+    pub fn add_price(&mut self, price: &NewPrice<'_, Q>) -> Result<(), Error> {
+        let record = price
+            .build()
+            .inspect_err(|e| tracing::error!("invalid price: {e}"))?;
+        self.changes.push(Change::InsertPrice(record));
+        Ok(())
+    }
+
+    /// Discards all buffered changes.
+    pub fn rollback(self) {
+        tracing::debug!(count = self.changes.len(), "change set rolled back");
+    }
+
+    /// Validates the changes together and writes them in a single database transaction,
+    /// refreshing the exchange graph if prices were added.
+    ///
+    /// The changes are validated against the book as read inside that transaction, so
+    /// concurrent writers cannot break them in between.
+    /// Nothing is written if any change is invalid or fails to apply.
+    #[instrument(skip(self), fields(count = self.changes.len()))]
+    pub async fn commit(self) -> Result<(), Error>
+    where
+        Q: 'static,
+    {
+        tracing::debug!("committing change set");
+
+        if self.changes.is_empty() {
+            return Ok(());
+        }
+
+        let query = self.book.query.clone();
+        let commodities = CommodityQ::all(&*query)
+            .await?
+            .iter()
+            .map(|x| {
+                let commodity = Commodity::from_with_query(x, query.clone());
+                (commodity.guid.clone(), commodity)
+            })
+            .collect();
+        let changes = self.changes.clone();
+        let check = {
+            let query = query.clone();
+            move |stored: &StoredState| {
+                validate(&query, &changes, stored, commodities)
+                    .inspect_err(|e| tracing::error!("invalid change set: {e}"))
+            }
+        };
+
+        query
+            .apply(&self.changes, check)
+            .await
+            .inspect_err(|e| tracing::error!("failed to apply change set: {e}"))?;
+
+        if self
+            .changes
+            .iter()
+            .any(|x| matches!(x, Change::InsertPrice(_)))
+        {
+            self.book.update_exchange_graph().await?;
+        }
+
+        tracing::info!("change set committed");
+        Ok(())
+    }
+}
+
+/// Checks `changes` in order against the `stored` book and the changes before them.
+fn validate<Q>(
+    query: &Arc<Q>,
+    changes: &[Change],
+    stored: &StoredState,
+    commodities: HashMap<String, Commodity<Q>>,
+) -> Result<(), Error>
+where
+    Q: Query,
+{
+    let mut state = State {
+        accounts: stored
+            .accounts
+            .iter()
+            .map(|x| (x.guid.clone(), x.clone()))
+            .collect(),
+        commodities,
+        used: stored.used_accounts.clone(),
+        prices: stored.prices.clone(),
+    };
+
+    for change in changes {
+        match change {
+            Change::InsertAccount(account) => {
+                if state.accounts.contains_key(&account.guid) {
+                    return Err(invalid(
+                        "Account",
+                        format!("{} already exists", account.guid),
+                    ));
+                }
+                check_account(query, &state, account)?;
+                state.accounts.insert(account.guid.clone(), account.clone());
+            }
+            Change::UpdateAccount(account) => {
+                let stored = state
+                    .accounts
+                    .get(&account.guid)
+                    .ok_or_else(|| not_found("Account", &account.guid))?;
+                if stored.account_type() == AccountType::Root {
+                    if stored != account {
+                        return Err(invalid("Account", "the root account cannot be changed"));
+                    }
+                    continue;
+                }
+                check_account(query, &state, account)?;
+                state.accounts.insert(account.guid.clone(), account.clone());
+            }
+            Change::DeleteAccount(guid) => {
+                check_delete(&state, guid)?;
+                state.accounts.remove(guid);
+            }
+            Change::InsertTransaction(transaction, splits) => {
+                if !state.commodities.contains_key(&transaction.currency_guid) {
+                    return Err(not_found("Commodity", &transaction.currency_guid));
+                }
+                for split in splits {
+                    if !state.accounts.contains_key(&split.account_guid) {
+                        return Err(not_found("Account", &split.account_guid));
+                    }
+                    state.used.insert(split.account_guid.clone());
+                }
+            }
+            Change::InsertPrice(price) => {
+                let commodity = state
+                    .commodities
+                    .get(&price.commodity_guid)
+                    .ok_or_else(|| not_found("Commodity", &price.commodity_guid))?;
+                let currency = state
+                    .commodities
+                    .get(&price.currency_guid)
+                    .ok_or_else(|| not_found("Commodity", &price.currency_guid))?;
+
+                let date = price.datetime.date();
+                let key = (
+                    price.commodity_guid.clone(),
+                    price.currency_guid.clone(),
+                    date,
+                );
+                if !state.prices.insert(key) {
+                    return Err(invalid(
+                        "Price",
+                        format!(
+                            "{} already has a price in {} on {date}",
+                            commodity.mnemonic, currency.mnemonic
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks a new or updated account against its parent, siblings and children.
+fn check_account<Q>(query: &Arc<Q>, state: &State<Q>, account: &AccountRecord) -> Result<(), Error>
+where
+    Q: Query,
+{
+    check_scu(account.commodity_scu)?;
+
+    let parent = state
+        .accounts
+        .get(&account.parent_guid)
+        .ok_or_else(|| not_found("Account", &account.parent_guid))?;
+    check_name(
+        &account.name,
+        &parent.name,
+        state
+            .accounts
+            .values()
+            .filter(|x| x.parent_guid == account.parent_guid && x.guid != account.guid)
+            .map(|x| x.name.as_str()),
+    )?;
+
+    let mut ancestor = Some(parent);
+    while let Some(a) = ancestor {
+        if a.guid == account.guid {
+            return Err(invalid(
+                "Account",
+                format!(
+                    "{} cannot be moved below itself or its descendant {}",
+                    account.name, parent.name
+                ),
+            ));
+        }
+        ancestor = state.accounts.get(&a.parent_guid);
+    }
+
+    let commodity = state
+        .commodities
+        .get(&account.commodity_guid)
+        .ok_or_else(|| not_found("Commodity", &account.commodity_guid))?;
+    Account::from_with_query(parent, query.clone())
+        .check_child(&account.account_type(), commodity)?;
+
+    if let Some(child) = state.accounts.values().find(|x| {
+        x.parent_guid == account.guid
+            && !types_compatible(&account.account_type(), &x.account_type())
+    }) {
+        return Err(invalid(
+            "Account",
+            format!(
+                "{} account {} cannot hold {} account {}",
+                account.account_type, account.name, child.account_type, child.name
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_delete<Q>(state: &State<Q>, guid: &str) -> Result<(), Error>
+where
+    Q: Query,
+{
+    let account = state
+        .accounts
+        .get(guid)
+        .ok_or_else(|| not_found("Account", guid))?;
+    if account.account_type() == AccountType::Root || account.parent_guid.is_empty() {
+        return Err(invalid("Account", "the root account cannot be deleted"));
+    }
+    if state.accounts.values().any(|x| x.parent_guid == guid) {
+        return Err(invalid(
+            "Account",
+            format!("{} still has children", account.name),
+        ));
+    }
+    if state.used.contains(guid) {
+        return Err(invalid(
+            "Account",
+            format!("{} still has splits", account.name),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        use chrono::NaiveDateTime;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::SQLiteQuery;
+        #[cfg(not(feature = "decimal"))]
+        use crate::builder::from_fraction;
+        use crate::query::sqlite::tests::setup_writable;
+
+        use super::super::*;
+
+        async fn account(book: &Book<SQLiteQuery>, name: &str) -> Account<SQLiteQuery> {
+            book.accounts()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.name == name)
+                .unwrap()
+        }
+
+        async fn commodity(book: &Book<SQLiteQuery>, mnemonic: &str) -> Commodity<SQLiteQuery> {
+            book.commodities()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.mnemonic == mnemonic)
+                .unwrap()
+        }
+
+        #[cfg(not(feature = "decimal"))]
+        fn num(value: i64, scale: u32) -> f64 {
+            from_fraction(value, 10_i64.pow(scale))
+        }
+        #[cfg(feature = "decimal")]
+        fn num(value: i64, scale: u32) -> Decimal {
+            Decimal::new(value, scale)
+        }
+
+        #[test(tokio::test)]
+        async fn test_commit() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let eur = commodity(&book, "EUR").await;
+            let usd = commodity(&book, "USD").await;
+            let current = account(&book, "Current").await;
+            let cash = account(&book, "Cash").await;
+            let mut savings = account(&book, "Savings").await;
+            let datetime =
+                NaiveDateTime::parse_from_str("2024-03-01 10:59:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+            let mut changes = book.begin();
+            let wallet = changes.create_account(&current, "Wallet", &AccountType::Cash, &eur, 100);
+            let coins = changes.create_account(&wallet, "Coins", &AccountType::Cash, &eur, 100);
+            savings.name = "Rainy Day".to_string();
+            changes.update_account(&savings);
+            changes
+                .add_transaction(
+                    &book
+                        .new_transaction()
+                        .currency(&eur)
+                        .post_datetime(datetime)
+                        .split(&cash, num(-2000, 2), num(-2000, 2), "", "")
+                        .split(&coins, num(2000, 2), num(2000, 2), "", ""),
+                )
+                .unwrap();
+            changes
+                .add_price(&NewPrice {
+                    commodity: &usd,
+                    currency: &eur,
+                    datetime,
+                    value: num(9, 1),
+                    source: "user:price-editor",
+                    r#type: "last",
+                })
+                .unwrap();
+            assert_eq!(changes.len(), 5);
+            changes.commit().await.unwrap();
+
+            let wallet = account(&book, "Wallet").await;
+            assert_eq!(wallet.parent_guid, current.guid);
+            let coins = account(&book, "Coins").await;
+            assert_eq!(coins.parent_guid, wallet.guid);
+            assert_eq!(coins.splits().await.unwrap().len(), 1);
+            assert_eq!(account(&book, "Rainy Day").await.guid, savings.guid);
+            assert_eq!(book.transactions().await.unwrap().len(), 12);
+            assert_eq!(book.prices().await.unwrap().len(), 6);
+        }
+
+        #[test(tokio::test)]
+        async fn test_commit_invalid() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let eur = commodity(&book, "EUR").await;
+            let current = account(&book, "Current").await;
+            let cash = account(&book, "Cash").await;
+            let savings = account(&book, "Savings").await;
+
+            let mut changes = book.begin();
+            changes.create_account(&current, "Wallet", &AccountType::Cash, &eur, 100);
+            changes.delete_account(&cash);
+            let result = changes.commit().await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+
+            let mut changes = book.begin();
+            changes.create_account(&current, "Cash", &AccountType::Cash, &eur, 100);
+            let result = changes.commit().await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+
+            let mut changes = book.begin();
+            let mut moved = current.clone();
+            moved.parent_guid.clone_from(&cash.guid);
+            changes.update_account(&moved);
+            let result = changes.commit().await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+
+            let mut changes = book.begin();
+            changes
+                .add_transaction(
+                    &book
+                        .new_transaction()
+                        .currency(&eur)
+                        .split(&cash, num(-1, 0), num(-1, 0), "", "")
+                        .split(&savings, num(1, 0), num(1, 0), "", ""),
+                )
+                .unwrap();
+            changes.delete_account(&savings);
+            let result = changes.commit().await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+
+            let mut changes = book.begin();
+            let result = changes.add_transaction(&book.new_transaction().currency(&eur).split(
+                &cash,
+                num(-1, 0),
+                num(-1, 0),
+                "",
+                "",
+            ));
+            assert!(matches!(result, Err(Error::Unbalanced(_))));
+
+            assert!(
+                book.accounts()
+                    .await
+                    .unwrap()
+                    .iter()
+                    .all(|x| x.name != "Wallet")
+            );
+            assert_eq!(book.transactions().await.unwrap().len(), 11);
+            assert_eq!(
+                account(&book, "Current").await.parent_guid,
+                current.parent_guid
+            );
+        }
+
+        #[test(tokio::test)]
+        async fn test_rollback() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let eur = commodity(&book, "EUR").await;
+            let current = account(&book, "Current").await;
+
+            let mut changes = book.begin();
+            assert!(changes.is_empty());
+            changes.create_account(&current, "Wallet", &AccountType::Cash, &eur, 100);
+            assert_eq!(changes.len(), 1);
+            changes.rollback();
+
+            assert!(
+                book.accounts()
+                    .await
+                    .unwrap()
+                    .iter()
+                    .all(|x| x.name != "Wallet")
+            );
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use chrono::NaiveDateTime;
+
+use super::{new_guid, to_price_fraction};
+use crate::error::Error;
+use crate::model::Commodity;
+use crate::query::{PriceRecord, Query};
+
+/// A quote to be stored by [`Book::import_prices`](crate::Book::import_prices).
+#[derive(Clone, Debug)]
+pub struct NewPrice<'a, Q>
+where
+    Q: Query,
+{
+    pub commodity: &'a Commodity<Q>,
+    pub currency: &'a Commodity<Q>,
+    pub datetime: NaiveDateTime,
+    /// Price of one unit of `commodity` in `currency`.
+    pub value: crate::Num,
+    /// Where the quote comes from, e.g. `Finance::Quote` or `user:price-editor`.
+    pub source: &'a str,
+    /// `GnuCash` uses `last`, `bid`, `ask`, `nav`, `transaction` or `unknown`.
+    pub r#type: &'a str,
+}
+
+impl<Q> NewPrice<'_, Q>
+where
+    Q: Query,
+{
+    fn invalid(reason: impl Into<String>) -> Error {
+        Error::Invalid {
+            model: "Price".to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Validates the price and converts it into the row to be written.
+    pub(crate) fn build(&self) -> Result<PriceRecord, Error> {
+        if self.commodity.guid == self.currency.guid {
+            return Err(Self::invalid(format!(
+                "{} cannot be priced in itself",
+                self.commodity.mnemonic
+            )));
+        }
+        if self.currency.namespace != "CURRENCY" {
+            return Err(Self::invalid(format!(
+                "{} is not a currency",
+                self.currency.mnemonic
+            )));
+        }
+        if self.value <= crate::Num::default() {
+            return Err(Self::invalid(format!(
+                "value {} must be positive",
+                self.value
+            )));
+        }
+
+        let (value_num, value_denom) = to_price_fraction(self.value)?;
+        Ok(PriceRecord {
+            guid: new_guid(),
+            commodity_guid: self.commodity.guid.clone(),
+            currency_guid: self.currency.guid.clone(),
+            datetime: self.datetime,
+            source: self.source.to_string(),
+            r#type: self.r#type.to_string(),
+            value_num,
+            value_denom,
+        })
+    }
+}
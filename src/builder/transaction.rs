@@ -0,0 +1,406 @@
+use chrono::{NaiveDateTime, SubsecRound, Utc};
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::{from_fraction, new_guid, to_fraction};
+use crate::error::Error;
+use crate::model::{Account, Commodity, Transaction};
+use crate::query::{Query, SplitRecord, TransactionMutQ, TransactionQ, TransactionRecord};
+
+#[derive(Clone, Debug)]
+struct SplitEntry {
+    account_guid: String,
+    commodity_guid: String,
+    commodity_scu: i64,
+    value: crate::Num,
+    quantity: crate::Num,
+    memo: String,
+    action: String,
+}
+
+/// Builds a balanced transaction and writes it to the book.
+///
+/// Created by [`Book::new_transaction`](crate::Book::new_transaction). Nothing is
+/// written until [`TransactionBuilder::commit`] succeeds.
+#[derive(Clone, Debug)]
+pub struct TransactionBuilder<Q>
+where
+    Q: Query + TransactionMutQ,
+{
+    query: Arc<Q>,
+
+    currency_guid: String,
+    currency_fraction: i64,
+    num: String,
+    description: String,
+    post_datetime: Option<NaiveDateTime>,
+    splits: Vec<SplitEntry>,
+}
+
+impl<Q> TransactionBuilder<Q>
+where
+    Q: Query + TransactionMutQ,
+{
+    pub(crate) fn new(query: Arc<Q>) -> Self {
+        Self {
+            query,
+
+            currency_guid: String::new(),
+            currency_fraction: 0,
+            num: String::new(),
+            description: String::new(),
+            post_datetime: None,
+            splits: Vec::new(),
+        }
+    }
+
+    /// The currency the split values are expressed in. Required.
+    #[must_use]
+    pub fn currency(mut self, currency: &Commodity<Q>) -> Self {
+        self.currency_guid.clone_from(&currency.guid);
+        self.currency_fraction = currency.fraction;
+        self
+    }
+
+    #[must_use]
+    pub fn num(mut self, num: &str) -> Self {
+        num.clone_into(&mut self.num);
+        self
+    }
+
+    #[must_use]
+    pub fn description(mut self, description: &str) -> Self {
+        description.clone_into(&mut self.description);
+        self
+    }
+
+    /// Defaults to the time of the commit.
+    #[must_use]
+    pub fn post_datetime(mut self, post_datetime: NaiveDateTime) -> Self {
+        self.post_datetime = Some(post_datetime);
+        self
+    }
+
+    /// Adds a split to `account`.
+    ///
+    /// `value` is in the transaction currency and `quantity` in the account commodity.
+    /// Both are rounded to the currency fraction and the account SCU respectively.
+    #[must_use]
+    pub fn split(
+        mut self,
+        account: &Account<Q>,
+        value: crate::Num,
+        quantity: crate::Num,
+        memo: &str,
+        action: &str,
+    ) -> Self {
+        self.splits.push(SplitEntry {
+            account_guid: account.guid.clone(),
+            commodity_guid: account.commodity_guid.clone(),
+            commodity_scu: account.commodity_scu,
+            value,
+            quantity,
+            memo: memo.to_string(),
+            action: action.to_string(),
+        });
+        self
+    }
+
+    fn invalid(reason: impl Into<String>) -> Error {
+        Error::Invalid {
+            model: "Transaction".to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Validates the transaction and converts it into the rows to be written.
+    pub(crate) fn build(&self) -> Result<(TransactionRecord, Vec<SplitRecord>), Error> {
+        if self.currency_guid.is_empty() || self.currency_fraction <= 0 {
+            return Err(Self::invalid("a currency is required"));
+        }
+        if self.splits.is_empty() {
+            return Err(Self::invalid("at least one split is required"));
+        }
+
+        let now = Utc::now().naive_utc().trunc_subsecs(0);
+        let transaction = TransactionRecord {
+            guid: new_guid(),
+            currency_guid: self.currency_guid.clone(),
+            num: self.num.clone(),
+            post_datetime: self.post_datetime.unwrap_or(now),
+            enter_datetime: now,
+            description: self.description.clone(),
+        };
+
+        let mut balance: i64 = 0;
+        let mut splits = Vec::with_capacity(self.splits.len());
+        for entry in &self.splits {
+            if entry.commodity_scu <= 0 {
+                return Err(Self::invalid(format!(
+                    "account {} has no commodity SCU",
+                    entry.account_guid
+                )));
+            }
+
+            let value_num = to_fraction(entry.value, self.currency_fraction)?;
+            let quantity_num = to_fraction(entry.quantity, entry.commodity_scu)?;
+
+            // GnuCash keeps amount and value identical when no conversion is involved
+            if entry.commodity_guid == self.currency_guid
+                && i128::from(value_num) * i128::from(entry.commodity_scu)
+                    != i128::from(quantity_num) * i128::from(self.currency_fraction)
+            {
+                return Err(Self::invalid(format!(
+                    "quantity must equal value for account {} in the transaction currency",
+                    entry.account_guid
+                )));
+            }
+
+            balance = balance
+                .checked_add(value_num)
+                .ok_or_else(|| Self::invalid("split values overflow"))?;
+
+            splits.push(SplitRecord {
+                guid: new_guid(),
+                tx_guid: transaction.guid.clone(),
+                account_guid: entry.account_guid.clone(),
+                memo: entry.memo.clone(),
+                action: entry.action.clone(),
+                reconcile_state: 'n',
+                reconcile_datetime: None,
+                value_num,
+                value_denom: self.currency_fraction,
+                quantity_num,
+                quantity_denom: entry.commodity_scu,
+                lot_guid: String::new(),
+            });
+        }
+
+        if balance != 0 {
+            return Err(Error::Unbalanced(from_fraction(
+                balance,
+                self.currency_fraction,
+            )));
+        }
+
+        Ok((transaction, splits))
+    }
+
+    /// Validates the transaction and writes it with all its splits atomically.
+    #[instrument(skip(self), fields(description = %self.description, split_count = self.splits.len()))]
+    pub async fn commit(self) -> Result<Transaction<Q>, Error> {
+        tracing::debug!("committing new transaction");
+
+        let (transaction, splits) = self
+            .build()
+            .inspect_err(|e| tracing::error!("invalid transaction: {e}"))?;
+
+        TransactionMutQ::insert(&*self.query, &transaction, &splits)
+            .await
+            .inspect_err(|e| tracing::error!("failed to insert transaction: {e}"))?;
+
+        let item = TransactionQ::guid(&*self.query, &transaction.guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch transaction: {e}"))?
+            .ok_or_else(|| Error::GuidNotFound {
+                model: "Transaction".to_string(),
+                guid: transaction.guid.clone(),
+            })?;
+
+        tracing::info!(transaction_guid = %transaction.guid, "transaction committed");
+        Ok(Transaction::from_with_query(&item, self.query.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::query::sqlite::tests::setup_writable;
+        use crate::{Book, SQLiteQuery};
+
+        use super::super::*;
+
+        async fn account(book: &Book<SQLiteQuery>, name: &str) -> Account<SQLiteQuery> {
+            book.accounts()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.name == name)
+                .unwrap()
+        }
+
+        async fn commodity(book: &Book<SQLiteQuery>, mnemonic: &str) -> Commodity<SQLiteQuery> {
+            book.commodities()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.mnemonic == mnemonic)
+                .unwrap()
+        }
+
+        #[cfg(not(feature = "decimal"))]
+        fn num(value: i64, scale: u32) -> f64 {
+            from_fraction(value, 10_i64.pow(scale))
+        }
+        #[cfg(feature = "decimal")]
+        fn num(value: i64, scale: u32) -> Decimal {
+            Decimal::new(value, scale)
+        }
+
+        #[test(tokio::test)]
+        async fn test_commit() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let eur = commodity(&book, "EUR").await;
+            let cash = account(&book, "Cash").await;
+            let expense = account(&book, "Expense").await;
+            let post_datetime =
+                NaiveDateTime::parse_from_str("2024-03-01 10:59:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+            let transaction = book
+                .new_transaction()
+                .currency(&eur)
+                .num("7")
+                .description("groceries")
+                .post_datetime(post_datetime)
+                .split(&cash, num(-1250, 2), num(-1250, 2), "", "")
+                .split(&expense, num(1250, 2), num(1250, 2), "food", "Buy")
+                .commit()
+                .await
+                .unwrap();
+
+            assert_eq!(transaction.guid.len(), 32);
+            assert_eq!(transaction.currency_guid, eur.guid);
+            assert_eq!(transaction.num, "7");
+            assert_eq!(transaction.description, "groceries");
+            assert_eq!(transaction.post_datetime, post_datetime);
+            assert_eq!(book.transactions().await.unwrap().len(), 12);
+
+            let splits = transaction.splits().await.unwrap();
+            assert_eq!(splits.len(), 2);
+            let split = splits.iter().find(|x| x.memo == "food").unwrap();
+            assert_eq!(split.account_guid, expense.guid);
+            assert_eq!(split.action, "Buy");
+            assert_eq!(
+                split.reconcile_status,
+                crate::model::ReconcileState::Unreconciled
+            );
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, split.value, 12.5);
+            #[cfg(feature = "decimal")]
+            assert_eq!(split.value, Decimal::new(125, 1));
+        }
+
+        #[test(tokio::test)]
+        async fn test_commit_different_commodity() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let eur = commodity(&book, "EUR").await;
+            let savings = account(&book, "Savings").await;
+            let stock = account(&book, "Foo stock").await;
+
+            let transaction = book
+                .new_transaction()
+                .currency(&eur)
+                .split(&savings, num(-100, 0), num(-100, 0), "", "")
+                .split(&stock, num(100, 0), num(3, 0), "", "Buy")
+                .commit()
+                .await
+                .unwrap();
+
+            let splits = transaction.splits().await.unwrap();
+            let split = splits.iter().find(|x| x.action == "Buy").unwrap();
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, split.quantity, 3.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(split.quantity, Decimal::new(3, 0));
+        }
+
+        #[test(tokio::test)]
+        async fn test_commit_unbalanced() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let eur = commodity(&book, "EUR").await;
+            let cash = account(&book, "Cash").await;
+            let expense = account(&book, "Expense").await;
+
+            let result = book
+                .new_transaction()
+                .currency(&eur)
+                .split(&cash, num(-10, 0), num(-10, 0), "", "")
+                .split(&expense, num(9, 0), num(9, 0), "", "")
+                .commit()
+                .await;
+
+            assert!(
+                matches!(result, Err(Error::Unbalanced(_))),
+                "Expected Unbalanced error, but got: {result:?}"
+            );
+            assert_eq!(book.transactions().await.unwrap().len(), 11);
+        }
+
+        #[test(tokio::test)]
+        async fn test_commit_without_currency() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let cash = account(&book, "Cash").await;
+
+            let result = book
+                .new_transaction()
+                .split(&cash, num(0, 0), num(0, 0), "", "")
+                .commit()
+                .await;
+
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+        }
+
+        #[test(tokio::test)]
+        async fn test_commit_quantity_mismatch() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let eur = commodity(&book, "EUR").await;
+            let cash = account(&book, "Cash").await;
+            let expense = account(&book, "Expense").await;
+
+            let result = book
+                .new_transaction()
+                .currency(&eur)
+                .split(&cash, num(-10, 0), num(-20, 0), "", "")
+                .split(&expense, num(10, 0), num(10, 0), "", "")
+                .commit()
+                .await;
+
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+        }
+
+        #[test(tokio::test)]
+        async fn test_commit_read_only() {
+            let uri: &str = &format!(
+                "{}/tests/db/sqlite/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let book = Book::new(SQLiteQuery::new(uri).unwrap()).await.unwrap();
+            let eur = commodity(&book, "EUR").await;
+            let cash = account(&book, "Cash").await;
+            let expense = account(&book, "Expense").await;
+
+            let result = book
+                .new_transaction()
+                .currency(&eur)
+                .split(&cash, num(-10, 0), num(-10, 0), "", "")
+                .split(&expense, num(10, 0), num(10, 0), "", "")
+                .commit()
+                .await;
+
+            assert!(matches!(result, Err(Error::ReadOnly)));
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! Writes books loaded through any [`Query`](crate::Query) backend into other `GnuCash` formats.
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod xml;
+
+#[cfg(feature = "sqlite")]
+pub use sqlite::to_sqlite;
+pub use xml::{to_xml, write_xml};
+
+/// `GnuCash` keeps scheduled transaction templates below a second root with this name.
+const TEMPLATE_ROOT: &str = "Template Root";
@@ -0,0 +1,805 @@
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use chrono::{DateTime, NaiveDate};
+use rusqlite::{Connection, params};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::TEMPLATE_ROOT;
+use crate::Book;
+use crate::builder::new_guid;
+use crate::error::Error;
+use crate::model::book_info::TABLE_VERSIONS;
+use crate::model::slot::{self, Slot};
+use crate::query::sqlite::{
+    account, budget, commodity, lot, price, recurrence, scheduled_transaction, split, transaction,
+};
+use crate::query::{
+    AccountRecord, AccountT, BudgetAmountQ, BudgetAmountT, BudgetT, CommodityRecord, CommodityT,
+    LotT, PriceRecord, PriceT, Query, RecurrenceQ, RecurrenceT, ScheduledTransactionT, SplitRecord,
+    SplitT, TransactionRecord, TransactionT,
+};
+
+/// The schema `GnuCash` 5 creates for a new `SQLite` book.
+const SCHEMA: &str = r"
+CREATE TABLE gnclock ( Hostname varchar(255), PID int );
+CREATE TABLE versions(table_name text(50) PRIMARY KEY NOT NULL, table_version integer NOT NULL);
+CREATE TABLE books(guid text(32) PRIMARY KEY NOT NULL, root_account_guid text(32) NOT NULL, root_template_guid text(32) NOT NULL);
+CREATE TABLE commodities(guid text(32) PRIMARY KEY NOT NULL, namespace text(2048) NOT NULL, mnemonic text(2048) NOT NULL, fullname text(2048), cusip text(2048), fraction integer NOT NULL, quote_flag integer NOT NULL, quote_source text(2048), quote_tz text(2048));
+CREATE TABLE accounts(guid text(32) PRIMARY KEY NOT NULL, name text(2048) NOT NULL, account_type text(2048) NOT NULL, commodity_guid text(32), commodity_scu integer NOT NULL, non_std_scu integer NOT NULL, parent_guid text(32), code text(2048), description text(2048), hidden integer, placeholder integer);
+CREATE TABLE budgets(guid text(32) PRIMARY KEY NOT NULL, name text(2048) NOT NULL, description text(2048), num_periods integer NOT NULL);
+CREATE TABLE budget_amounts(id integer PRIMARY KEY AUTOINCREMENT NOT NULL, budget_guid text(32) NOT NULL, account_guid text(32) NOT NULL, period_num integer NOT NULL, amount_num bigint NOT NULL, amount_denom bigint NOT NULL);
+CREATE TABLE prices(guid text(32) PRIMARY KEY NOT NULL, commodity_guid text(32) NOT NULL, currency_guid text(32) NOT NULL, date text(19) NOT NULL, source text(2048), type text(2048), value_num bigint NOT NULL, value_denom bigint NOT NULL);
+CREATE TABLE transactions(guid text(32) PRIMARY KEY NOT NULL, currency_guid text(32) NOT NULL, num text(2048) NOT NULL, post_date text(19), enter_date text(19), description text(2048));
+CREATE INDEX tx_post_date_index ON transactions(post_date);
+CREATE TABLE splits(guid text(32) PRIMARY KEY NOT NULL, tx_guid text(32) NOT NULL, account_guid text(32) NOT NULL, memo text(2048) NOT NULL, action text(2048) NOT NULL, reconcile_state text(1) NOT NULL, reconcile_date text(19), value_num bigint NOT NULL, value_denom bigint NOT NULL, quantity_num bigint NOT NULL, quantity_denom bigint NOT NULL, lot_guid text(32));
+CREATE INDEX splits_tx_guid_index ON splits(tx_guid);
+CREATE INDEX splits_account_guid_index ON splits(account_guid);
+CREATE TABLE slots(id integer PRIMARY KEY AUTOINCREMENT NOT NULL, obj_guid text(32) NOT NULL, name text(4096) NOT NULL, slot_type integer NOT NULL, int64_val bigint, string_val text(4096), double_val float8, timespec_val text(19), guid_val text(32), numeric_val_num bigint, numeric_val_denom bigint, gdate_val text(8));
+CREATE INDEX slots_guid_index ON slots(obj_guid);
+CREATE TABLE recurrences(id integer PRIMARY KEY AUTOINCREMENT NOT NULL, obj_guid text(32) NOT NULL, recurrence_mult integer NOT NULL, recurrence_period_type text(2048) NOT NULL, recurrence_period_start text(8) NOT NULL, recurrence_weekend_adjust text(2048) NOT NULL);
+CREATE TABLE schedxactions(guid text(32) PRIMARY KEY NOT NULL, name text(2048), enabled integer NOT NULL, start_date text(8), end_date text(8), last_occur text(8), num_occur integer NOT NULL, rem_occur integer NOT NULL, auto_create integer NOT NULL, auto_notify integer NOT NULL, adv_creation integer NOT NULL, adv_notify integer NOT NULL, instance_count integer NOT NULL, template_act_guid text(32) NOT NULL);
+CREATE TABLE lots(guid text(32) PRIMARY KEY NOT NULL, account_guid text(32), is_closed integer NOT NULL);
+CREATE TABLE billterms(guid text(32) PRIMARY KEY NOT NULL, name text(2048) NOT NULL, description text(2048) NOT NULL, refcount integer NOT NULL, invisible integer NOT NULL, parent text(32), type text(2048) NOT NULL, duedays integer, discountdays integer, discount_num bigint, discount_denom bigint, cutoff integer);
+CREATE TABLE customers(guid text(32) PRIMARY KEY NOT NULL, name text(2048) NOT NULL, id text(2048) NOT NULL, notes text(2048) NOT NULL, active integer NOT NULL, discount_num bigint NOT NULL, discount_denom bigint NOT NULL, credit_num bigint NOT NULL, credit_denom bigint NOT NULL, currency text(32) NOT NULL, tax_override integer NOT NULL, addr_name text(1024), addr_addr1 text(1024), addr_addr2 text(1024), addr_addr3 text(1024), addr_addr4 text(1024), addr_phone text(128), addr_fax text(128), addr_email text(256), shipaddr_name text(1024), shipaddr_addr1 text(1024), shipaddr_addr2 text(1024), shipaddr_addr3 text(1024), shipaddr_addr4 text(1024), shipaddr_phone text(128), shipaddr_fax text(128), shipaddr_email text(256), terms text(32), tax_included integer, taxtable text(32));
+CREATE TABLE employees(guid text(32) PRIMARY KEY NOT NULL, username text(2048) NOT NULL, id text(2048) NOT NULL, language text(2048) NOT NULL, acl text(2048) NOT NULL, active integer NOT NULL, currency text(32) NOT NULL, ccard_guid text(32), workday_num bigint NOT NULL, workday_denom bigint NOT NULL, rate_num bigint NOT NULL, rate_denom bigint NOT NULL, addr_name text(1024), addr_addr1 text(1024), addr_addr2 text(1024), addr_addr3 text(1024), addr_addr4 text(1024), addr_phone text(128), addr_fax text(128), addr_email text(256));
+CREATE TABLE entries(guid text(32) PRIMARY KEY NOT NULL, date text(19) NOT NULL, date_entered text(19), description text(2048), action text(2048), notes text(2048), quantity_num bigint, quantity_denom bigint, i_acct text(32), i_price_num bigint, i_price_denom bigint, i_discount_num bigint, i_discount_denom bigint, invoice text(32), i_disc_type text(2048), i_disc_how text(2048), i_taxable integer, i_taxincluded integer, i_taxtable text(32), b_acct text(32), b_price_num bigint, b_price_denom bigint, bill text(32), b_taxable integer, b_taxincluded integer, b_taxtable text(32), b_paytype integer, billable integer, billto_type integer, billto_guid text(32), order_guid text(32));
+CREATE TABLE invoices(guid text(32) PRIMARY KEY NOT NULL, id text(2048) NOT NULL, date_opened text(19), date_posted text(19), notes text(2048) NOT NULL, active integer NOT NULL, currency text(32) NOT NULL, owner_type integer, owner_guid text(32), terms text(32), billing_id text(2048), post_txn text(32), post_lot text(32), post_acc text(32), billto_type integer, billto_guid text(32), charge_amt_num bigint, charge_amt_denom bigint);
+CREATE TABLE jobs(guid text(32) PRIMARY KEY NOT NULL, id text(2048) NOT NULL, name text(2048) NOT NULL, reference text(2048) NOT NULL, active integer NOT NULL, owner_type integer, owner_guid text(32));
+CREATE TABLE orders(guid text(32) PRIMARY KEY NOT NULL, id text(2048) NOT NULL, notes text(2048) NOT NULL, reference text(2048) NOT NULL, active integer NOT NULL, date_opened text(19) NOT NULL, date_closed text(19) NOT NULL, owner_type integer NOT NULL, owner_guid text(32) NOT NULL);
+CREATE TABLE taxtables(guid text(32) PRIMARY KEY NOT NULL, name text(50) NOT NULL, refcount bigint NOT NULL, invisible integer NOT NULL, parent text(32));
+CREATE TABLE taxtable_entries(id integer PRIMARY KEY AUTOINCREMENT NOT NULL, taxtable text(32) NOT NULL, account text(32) NOT NULL, amount_num bigint NOT NULL, amount_denom bigint NOT NULL, type integer NOT NULL);
+CREATE TABLE vendors(guid text(32) PRIMARY KEY NOT NULL, name text(2048) NOT NULL, id text(2048) NOT NULL, notes text(2048) NOT NULL, currency text(32) NOT NULL, active integer NOT NULL, tax_override integer NOT NULL, addr_name text(1024), addr_addr1 text(1024), addr_addr2 text(1024), addr_addr3 text(1024), addr_addr4 text(1024), addr_phone text(128), addr_fax text(128), addr_email text(256), terms text(32), tax_inc text(2048), tax_table text(32));
+";
+
+/// The application versions recorded next to the table versions, `GnuCash` 5.4 as in
+/// [`crate::model::BookInfo::gnucash_version`].
+const GNUCASH_VERSIONS: [(&str, i64); 2] = [("Gnucash", 5_000_004), ("Gnucash-Resave", 19920)];
+
+const ISO_DATE_FEATURE: (&str, &str) = (
+    "ISO-8601 formatted date strings in SQLite3 databases.",
+    "Use ISO formatted date-time strings in SQLite3 databases (requires at least GnuCash 2.6.20)",
+);
+
+#[derive(Debug)]
+struct Rows {
+    book_guid: String,
+    root_guid: String,
+    template_guid: String,
+    commodities: Vec<CommodityRecord>,
+    accounts: Vec<AccountRecord>,
+    transactions: Vec<TransactionRecord>,
+    splits: Vec<SplitRecord>,
+    prices: Vec<PriceRecord>,
+    lots: Vec<lot::Lot>,
+    budgets: Vec<budget::Budget>,
+    budget_amounts: Vec<budget::BudgetAmount>,
+    scheduled_transactions: Vec<scheduled_transaction::ScheduledTransaction>,
+    recurrences: Vec<recurrence::Recurrence>,
+    slots: Vec<crate::query::sqlite::slot::Slot>,
+}
+
+fn is_guid(s: &str) -> bool {
+    s.len() == 32 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn split_record<T: SplitT>(s: &T) -> SplitRecord {
+    SplitRecord {
+        guid: s.guid().to_string(),
+        tx_guid: s.tx_guid().to_string(),
+        account_guid: s.account_guid().to_string(),
+        memo: s.memo().to_string(),
+        action: s.action().to_string(),
+        reconcile_state: s.reconcile_flag(),
+        reconcile_datetime: s.reconcile_datetime(),
+        value_num: s.value_num(),
+        value_denom: s.value_denom(),
+        quantity_num: s.quantity_num(),
+        quantity_denom: s.quantity_denom(),
+        lot_guid: s.lot_guid().to_string(),
+    }
+}
+
+/// Flattens a slot tree into `slots` rows below `prefix`.
+fn slot_rows(
+    obj_guid: &str,
+    prefix: &str,
+    tree: &BTreeMap<String, Slot>,
+    rows: &mut Vec<crate::query::sqlite::slot::Slot>,
+) -> Result<(), Error> {
+    for (key, value) in tree {
+        let name = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}/{key}")
+        };
+        slot_row(obj_guid, name, value, rows)?;
+    }
+    Ok(())
+}
+
+/// Adds the row of one slot, frames and lists get a new GUID their entries are attached to.
+fn slot_row(
+    obj_guid: &str,
+    name: String,
+    value: &Slot,
+    rows: &mut Vec<crate::query::sqlite::slot::Slot>,
+) -> Result<(), Error> {
+    // the defaults GnuCash writes into the columns a slot type does not use
+    let mut row = crate::query::sqlite::slot::Slot {
+        obj_guid: obj_guid.to_string(),
+        name,
+        slot_type: value.type_code(),
+        int64_val: Some(0),
+        string_val: None,
+        double_val: None,
+        timespec_val: Some(DateTime::UNIX_EPOCH.naive_utc()),
+        guid_val: None,
+        numeric_val_num: Some(0),
+        numeric_val_denom: Some(1),
+        gdate_val: None,
+    };
+    match value {
+        Slot::String(x) => row.string_val = Some(x.clone()),
+        Slot::Int64(x) => row.int64_val = Some(*x),
+        Slot::Double(x) => row.double_val = Some(*x),
+        Slot::Numeric { num, denom } => {
+            row.numeric_val_num = Some(*num);
+            row.numeric_val_denom = Some(*denom);
+        }
+        Slot::Timespec(x) => row.timespec_val = Some(*x),
+        Slot::Guid(x) => row.guid_val = Some(x.clone()),
+        Slot::GDate(x) => row.gdate_val = Some(x.format("%Y%m%d").to_string()),
+        Slot::Frame(frame) => {
+            let guid = new_guid();
+            let name = row.name.clone();
+            row.guid_val = Some(guid.clone());
+            rows.push(row);
+            return slot_rows(&guid, &name, frame, rows);
+        }
+        Slot::List(items) => {
+            let guid = new_guid();
+            let name = row.name.clone();
+            row.guid_val = Some(guid.clone());
+            rows.push(row);
+            for item in items {
+                slot_row(&guid, name.clone(), item, rows)?;
+            }
+            return Ok(());
+        }
+    }
+    rows.push(row);
+    Ok(())
+}
+
+fn date_text(date: Option<NaiveDate>) -> Option<String> {
+    date.map(|x| x.format("%Y%m%d").to_string())
+}
+
+impl Rows {
+    async fn commodities<Q>(
+        book: &Book<Q>,
+    ) -> Result<(Vec<CommodityRecord>, HashMap<String, String>), Error>
+    where
+        Q: Query,
+    {
+        // the XML backend identifies commodities by mnemonic, GnuCash SQL needs GUIDs
+        let mut commodity_guids = HashMap::new();
+        let commodities: Vec<_> = book
+            .query
+            .commodities()
+            .await?
+            .iter()
+            .map(|c| {
+                let guid = if is_guid(c.guid()) {
+                    c.guid().to_string()
+                } else {
+                    new_guid()
+                };
+                commodity_guids.insert(c.guid().to_string(), guid.clone());
+                CommodityRecord {
+                    guid,
+                    namespace: c.namespace().to_string(),
+                    mnemonic: c.mnemonic().to_string(),
+                    fullname: c.fullname().to_string(),
+                    cusip: c.cusip().to_string(),
+                    fraction: c.fraction(),
+                    quote_flag: c.quote_flag(),
+                    quote_source: c.quote_source().to_string(),
+                    quote_tz: c.quote_tz().to_string(),
+                }
+            })
+            .collect();
+
+        Ok((commodities, commodity_guids))
+    }
+
+    /// Returns the book root and the template root, adding the latter when missing.
+    fn roots(accounts: &mut Vec<AccountRecord>) -> Result<(String, String), Error> {
+        let roots: Vec<_> = accounts
+            .iter()
+            .filter(|a| a.account_type == "ROOT" && a.parent_guid.is_empty())
+            .collect();
+        let (templates, roots): (Vec<_>, Vec<_>) =
+            roots.into_iter().partition(|a| a.name == TEMPLATE_ROOT);
+        let root_guid = match roots.as_slice() {
+            [root] => root.guid.clone(),
+            _ => {
+                return Err(Error::Invalid {
+                    model: "Book".to_string(),
+                    reason: format!("expected one root account, found {}", roots.len()),
+                });
+            }
+        };
+        let template_guid = if let Some(template) = templates.first() {
+            template.guid.clone()
+        } else {
+            let template = AccountRecord {
+                guid: new_guid(),
+                name: TEMPLATE_ROOT.to_string(),
+                account_type: "ROOT".to_string(),
+                commodity_guid: String::new(),
+                commodity_scu: 0,
+                non_std_scu: false,
+                parent_guid: String::new(),
+                code: String::new(),
+                description: String::new(),
+                hidden: false,
+                placeholder: false,
+            };
+            let guid = template.guid.clone();
+            accounts.push(template);
+            guid
+        };
+
+        Ok((root_guid, template_guid))
+    }
+
+    async fn lots<Q>(book: &Book<Q>) -> Result<Vec<lot::Lot>, Error>
+    where
+        Q: Query,
+    {
+        Ok(book
+            .query
+            .lots()
+            .await?
+            .iter()
+            .map(|l| lot::Lot {
+                guid: l.guid().to_string(),
+                account_guid: (!l.account_guid().is_empty()).then(|| l.account_guid().to_string()),
+                // XML books do not record it, GnuCash recomputes it from the splits
+                is_closed: i64::from(l.is_closed().unwrap_or_default()),
+            })
+            .collect())
+    }
+
+    async fn budgets<Q>(
+        book: &Book<Q>,
+    ) -> Result<(Vec<budget::Budget>, Vec<budget::BudgetAmount>), Error>
+    where
+        Q: Query,
+    {
+        let budgets: Vec<_> = book
+            .query
+            .budgets()
+            .await?
+            .iter()
+            .map(|b| budget::Budget {
+                guid: b.guid().to_string(),
+                name: b.name().to_string(),
+                description: Some(b.description().to_string()),
+                num_periods: b.num_periods(),
+            })
+            .collect();
+
+        let mut budget_amounts = Vec::new();
+        for b in &budgets {
+            budget_amounts.extend(
+                BudgetAmountQ::budget(&*book.query, &b.guid)
+                    .await?
+                    .iter()
+                    .map(|a| budget::BudgetAmount {
+                        budget_guid: a.budget_guid().to_string(),
+                        account_guid: a.account_guid().to_string(),
+                        period_num: a.period_num(),
+                        amount_num: a.amount_num(),
+                        amount_denom: a.amount_denom(),
+                    }),
+            );
+        }
+
+        Ok((budgets, budget_amounts))
+    }
+
+    async fn scheduled_transactions<Q>(
+        book: &Book<Q>,
+    ) -> Result<Vec<scheduled_transaction::ScheduledTransaction>, Error>
+    where
+        Q: Query,
+    {
+        Ok(book
+            .query
+            .scheduled_transactions()
+            .await?
+            .iter()
+            .map(|x| scheduled_transaction::ScheduledTransaction {
+                guid: x.guid().to_string(),
+                name: Some(x.name().to_string()),
+                enabled: i64::from(x.enabled()),
+                start_date: date_text(x.start_date()),
+                end_date: date_text(x.end_date()),
+                last_occur: date_text(x.last_occur()),
+                num_occur: x.num_occur(),
+                rem_occur: x.rem_occur(),
+                auto_create: i64::from(x.auto_create()),
+                auto_notify: i64::from(x.auto_notify()),
+                adv_creation: x.adv_creation(),
+                adv_notify: x.adv_notify(),
+                instance_count: x.instance_count(),
+                template_act_guid: x.template_act_guid().to_string(),
+            })
+            .collect())
+    }
+
+    /// The recurrences of the budgets and scheduled transactions.
+    async fn recurrences<Q>(
+        book: &Book<Q>,
+        budgets: &[budget::Budget],
+        scheduled_transactions: &[scheduled_transaction::ScheduledTransaction],
+    ) -> Result<Vec<recurrence::Recurrence>, Error>
+    where
+        Q: Query,
+    {
+        let mut recurrences = Vec::new();
+        let guids = budgets
+            .iter()
+            .map(|b| &b.guid)
+            .chain(scheduled_transactions.iter().map(|x| &x.guid));
+        for guid in guids {
+            recurrences.extend(
+                RecurrenceQ::obj_guid(&*book.query, guid)
+                    .await?
+                    .iter()
+                    .map(|r| recurrence::Recurrence {
+                        obj_guid: r.obj_guid().to_string(),
+                        mult: r.mult(),
+                        period_type: r.period_type().to_string(),
+                        period_start: r.period_start(),
+                        weekend_adjust: r.weekend_adjust().to_string(),
+                    }),
+            );
+        }
+
+        Ok(recurrences)
+    }
+
+    async fn load<Q>(book: &Book<Q>) -> Result<Self, Error>
+    where
+        Q: Query,
+    {
+        let (commodities, commodity_guids) = Self::commodities(book).await?;
+        let commodity_guid = |guid: &str| -> Result<String, Error> {
+            if guid.is_empty() {
+                return Ok(String::new());
+            }
+            commodity_guids
+                .get(guid)
+                .cloned()
+                .ok_or_else(|| Error::GuidNotFound {
+                    model: "Commodity".to_string(),
+                    guid: guid.to_string(),
+                })
+        };
+
+        let mut accounts = book
+            .query
+            .accounts()
+            .await?
+            .iter()
+            .map(|a| {
+                Ok(AccountRecord {
+                    guid: a.guid().to_string(),
+                    name: a.name().to_string(),
+                    account_type: a.account_type().to_string(),
+                    commodity_guid: commodity_guid(a.commodity_guid())?,
+                    commodity_scu: a.commodity_scu(),
+                    non_std_scu: a.non_std_scu(),
+                    parent_guid: a.parent_guid().to_string(),
+                    code: a.code().to_string(),
+                    description: a.description().to_string(),
+                    hidden: a.hidden(),
+                    placeholder: a.placeholder(),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let (root_guid, template_guid) = Self::roots(&mut accounts)?;
+
+        let transactions = book
+            .query
+            .transactions()
+            .await?
+            .iter()
+            .map(|t| {
+                Ok(TransactionRecord {
+                    guid: t.guid().to_string(),
+                    currency_guid: commodity_guid(t.currency_guid())?,
+                    num: t.num().to_string(),
+                    post_datetime: t.post_datetime(),
+                    enter_datetime: t.enter_datetime(),
+                    description: t.description().to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let splits: Vec<_> = book
+            .query
+            .splits()
+            .await?
+            .iter()
+            .map(split_record)
+            .collect();
+
+        let lots = Self::lots(book).await?;
+        let (budgets, budget_amounts) = Self::budgets(book).await?;
+        let scheduled_transactions = Self::scheduled_transactions(book).await?;
+        let recurrences = Self::recurrences(book, &budgets, &scheduled_transactions).await?;
+
+        let prices = book
+            .query
+            .prices()
+            .await?
+            .iter()
+            .map(|p| {
+                Ok(PriceRecord {
+                    guid: p.guid().to_string(),
+                    commodity_guid: commodity_guid(p.commodity_guid())?,
+                    currency_guid: commodity_guid(p.currency_guid())?,
+                    datetime: p.datetime(),
+                    source: p.source().to_string(),
+                    r#type: p.r#type().to_string(),
+                    value_num: p.value_num(),
+                    value_denom: p.value_denom(),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut rows = Self {
+            book_guid: book.info().await?.guid,
+            root_guid,
+            template_guid,
+            commodities,
+            accounts,
+            transactions,
+            splits,
+            prices,
+            lots,
+            budgets,
+            budget_amounts,
+            scheduled_transactions,
+            recurrences,
+            slots: Vec::new(),
+        };
+        rows.slots = rows.slots(book, &commodity_guids).await?;
+
+        Ok(rows)
+    }
+
+    /// Flattens the slot trees of the book and of every copied object into rows.
+    async fn slots<Q>(
+        &self,
+        book: &Book<Q>,
+        commodity_guids: &HashMap<String, String>,
+    ) -> Result<Vec<crate::query::sqlite::slot::Slot>, Error>
+    where
+        Q: Query,
+    {
+        let mut rows = Vec::new();
+
+        // dates are written ISO formatted, which GnuCash only reads with this feature set
+        let book_guid = &self.book_guid;
+        let mut tree = slot::load(&*book.query, book_guid).await?;
+        let features = tree
+            .entry("features".to_string())
+            .or_insert_with(|| Slot::Frame(BTreeMap::new()));
+        if let Slot::Frame(features) = features {
+            features.insert(
+                ISO_DATE_FEATURE.0.to_string(),
+                Slot::String(ISO_DATE_FEATURE.1.to_string()),
+            );
+        }
+        slot_rows(book_guid, "", &tree, &mut rows)?;
+
+        for (source, guid) in commodity_guids {
+            slot_rows(
+                guid,
+                "",
+                &slot::load(&*book.query, source).await?,
+                &mut rows,
+            )?;
+        }
+        for t in &self.transactions {
+            let mut tree = slot::load(&*book.query, &t.guid).await?;
+            tree.entry("date-posted".to_string())
+                .or_insert_with(|| Slot::GDate(t.post_datetime.date()));
+            slot_rows(&t.guid, "", &tree, &mut rows)?;
+        }
+        let owners = self
+            .accounts
+            .iter()
+            .map(|a| &a.guid)
+            .chain(self.splits.iter().map(|s| &s.guid))
+            .chain(self.lots.iter().map(|l| &l.guid))
+            .chain(self.budgets.iter().map(|b| &b.guid))
+            .chain(self.scheduled_transactions.iter().map(|x| &x.guid));
+        for guid in owners {
+            let mut tree = slot::load(&*book.query, guid).await?;
+            // account::insert writes the placeholder slot from the account column
+            tree.remove("placeholder");
+            slot_rows(guid, "", &tree, &mut rows)?;
+        }
+
+        Ok(rows)
+    }
+
+    fn write(&self, conn: &mut Connection) -> Result<(), rusqlite::Error> {
+        conn.execute_batch(SCHEMA)
+            .inspect_err(|e| tracing::error!("failed to create schema: {e}"))?;
+
+        let tx = conn.transaction()?;
+        for (table, version) in GNUCASH_VERSIONS.into_iter().chain(TABLE_VERSIONS) {
+            tx.execute(
+                "INSERT INTO versions (table_name, table_version) VALUES (?1, ?2)",
+                params![table, version],
+            )?;
+        }
+        tx.execute(
+            "INSERT INTO books (guid, root_account_guid, root_template_guid) VALUES (?1, ?2, ?3)",
+            params![self.book_guid, self.root_guid, self.template_guid],
+        )?;
+
+        for c in &self.commodities {
+            commodity::insert(&tx, c)?;
+        }
+        for a in &self.accounts {
+            account::insert(&tx, a)?;
+        }
+        for t in &self.transactions {
+            transaction::insert(&tx, t)?;
+        }
+        for s in &self.splits {
+            split::insert(&tx, s)?;
+        }
+        for p in &self.prices {
+            price::insert(&tx, p)?;
+        }
+        for l in &self.lots {
+            lot::insert(&tx, l)?;
+        }
+        for b in &self.budgets {
+            budget::insert(&tx, b)?;
+        }
+        for a in &self.budget_amounts {
+            budget::insert_amount(&tx, a)?;
+        }
+        for x in &self.scheduled_transactions {
+            scheduled_transaction::insert(&tx, x)?;
+        }
+        for r in &self.recurrences {
+            recurrence::insert(&tx, r)?;
+        }
+        for s in &self.slots {
+            crate::query::sqlite::slot::insert(&tx, s)?;
+        }
+
+        tx.commit()
+    }
+}
+
+/// Writes the book into a new `GnuCash` `SQLite` database at `path`, which must not exist yet.
+///
+/// Commodities, accounts, transactions, splits, prices, lots, budgets, scheduled transactions
+/// and their recurrences are copied together with their slots, keeping the book GUID.
+/// Business objects (customers, vendors, employees, jobs, orders, invoices, entries, bill terms
+/// and tax tables) are not copied.
+#[instrument(skip(book, path), fields(path = %path.as_ref().display()))]
+pub async fn to_sqlite<Q>(book: &Book<Q>, path: impl AsRef<Path>) -> Result<(), Error>
+where
+    Q: Query,
+{
+    tracing::debug!("writing gnucash sqlite file");
+
+    let path = path.as_ref().to_path_buf();
+    if path.exists() {
+        tracing::error!("file already exists");
+        return Err(Error::IO(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} already exists", path.display()),
+        )));
+    }
+
+    let rows = Rows::load(book).await?;
+
+    spawn_blocking(move || {
+        let mut conn = Connection::open(&path)
+            .inspect_err(|e| tracing::error!("failed to create database: {e}"))?;
+        let result = rows.write(&mut conn);
+        drop(conn);
+        if let Err(e) = result {
+            tracing::error!("failed to write database: {e}");
+            std::fs::remove_file(&path)?;
+            return Err(e.into());
+        }
+
+        tracing::info!(
+            accounts = rows.accounts.len(),
+            transactions = rows.transactions.len(),
+            "gnucash sqlite file written"
+        );
+        Ok(())
+    })
+    .await
+    .map_err(|e| Error::Internal(format!("Join error in spawn_blocking: {e}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "decimal"))]
+    use float_cmp::assert_approx_eq;
+    use pretty_assertions::assert_eq;
+    #[cfg(feature = "decimal")]
+    use rust_decimal::Decimal;
+    use test_log::test;
+
+    use crate::SQLiteQuery;
+    use crate::query::sqlite::tests::setup_with;
+    use crate::query::tests::fixtures::{BUDGET, LOT, SCHEDULED_TRANSACTION};
+
+    use super::*;
+
+    async fn check(book: &Book<SQLiteQuery>, accounts: usize, commodities: usize) {
+        assert_eq!(book.accounts().await.unwrap().len(), accounts);
+        assert_eq!(book.transactions().await.unwrap().len(), 11);
+        assert_eq!(book.splits().await.unwrap().len(), 25);
+        assert_eq!(book.prices().await.unwrap().len(), 5);
+        assert_eq!(book.commodities().await.unwrap().len(), commodities);
+
+        let account = book
+            .account_contains_name_ignore_case("Current")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.placeholder, true);
+        #[cfg(not(feature = "decimal"))]
+        assert_approx_eq!(f64, account.balance(book).await.unwrap(), 4590.0);
+        #[cfg(feature = "decimal")]
+        assert_eq!(account.balance(book).await.unwrap(), Decimal::new(4590, 0));
+    }
+
+    #[test(tokio::test)]
+    async fn test_to_sqlite() {
+        let uri: &str = &format!(
+            "{}/tests/db/sqlite/complex_sample.gnucash",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let book = Book::new(SQLiteQuery::new(uri).unwrap()).await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("complex_sample.gnucash");
+        to_sqlite(&book, &path).await.unwrap();
+
+        let result = Book::new(SQLiteQuery::new(path.to_str().unwrap()).unwrap())
+            .await
+            .unwrap();
+        check(&result, 21, 5).await;
+        let info = result.info().await.unwrap();
+        assert_eq!(info.guid, "7d4ef4044fd30f41d08914a8174c2f5b");
+        assert_eq!(info.gnucash_version, Some(5_000_004));
+        assert!(info.has_feature(ISO_DATE_FEATURE.0));
+        assert_eq!(
+            result
+                .slot("options/Accounts/Use Trading Accounts")
+                .await
+                .unwrap(),
+            Some(Slot::String("t".to_string()))
+        );
+
+        let conn = Connection::open(&path).unwrap();
+        let (root, template): (String, String) = conn
+            .query_row(
+                "SELECT root_account_guid, root_template_guid FROM books",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(root, "00622dda21937b29e494179de5013f82");
+        assert_eq!(template, "f6c0cd00ec04169a44f170181882adab");
+        let versions: i64 = conn
+            .query_row("SELECT count(*) FROM versions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(versions, 24);
+
+        let result = to_sqlite(&book, &path).await;
+        assert!(matches!(result, Err(Error::IO(_))));
+    }
+
+    #[test(tokio::test)]
+    async fn test_to_sqlite_objects() {
+        let (_source, query) = setup_with(&[LOT, BUDGET, SCHEDULED_TRANSACTION]);
+        let book = Book::new(query).await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("objects.gnucash");
+        to_sqlite(&book, &path).await.unwrap();
+
+        let result = Book::new(SQLiteQuery::new(path.to_str().unwrap()).unwrap())
+            .await
+            .unwrap();
+
+        let lots = result.lots().await.unwrap();
+        assert_eq!(lots.len(), 1);
+        assert_eq!(lots[0].title().await.unwrap().as_deref(), Some("Lot 1"));
+        assert_eq!(lots[0].splits().await.unwrap().len(), 1);
+
+        let budgets = result.budgets().await.unwrap();
+        assert_eq!(budgets.len(), 1);
+        assert_eq!(
+            budgets[0].period(2).await.unwrap(),
+            (
+                NaiveDate::from_ymd_opt(2018, 3, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2018, 4, 1).unwrap()
+            )
+        );
+        let expense = result
+            .account_contains_name_ignore_case("Expense")
+            .await
+            .unwrap()
+            .unwrap();
+        #[cfg(not(feature = "decimal"))]
+        assert_approx_eq!(
+            f64,
+            budgets[0].amount(&expense, 1).await.unwrap().unwrap(),
+            50.0
+        );
+        #[cfg(feature = "decimal")]
+        assert_eq!(
+            budgets[0].amount(&expense, 1).await.unwrap(),
+            Some(Decimal::new(50, 0))
+        );
+
+        let scheduled = result.scheduled_transactions().await.unwrap();
+        assert_eq!(scheduled.len(), 1);
+        assert_eq!(scheduled[0].name, "Rent");
+        assert_eq!(scheduled[0].recurrences().await.unwrap().len(), 1);
+        let splits = scheduled[0].template_splits().await.unwrap();
+        assert_eq!(splits.len(), 2);
+        assert!(splits.iter().any(|x| x.debit_formula == "500"));
+    }
+
+    #[cfg(feature = "xml")]
+    #[test(tokio::test)]
+    async fn test_xml_to_sqlite() {
+        let uri: &str = &format!(
+            "{}/tests/db/xml/complex_sample.gnucash",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let book = Book::new(crate::XMLQuery::new(uri).unwrap()).await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("complex_sample.gnucash");
+        to_sqlite(&book, &path).await.unwrap();
+
+        let result = Book::new(SQLiteQuery::new(path.to_str().unwrap()).unwrap())
+            .await
+            .unwrap();
+        // a template root is added next to the 20 accounts of the xml book
+        check(&result, 21, 6).await;
+        assert!(
+            result
+                .commodities()
+                .await
+                .unwrap()
+                .iter()
+                .all(|c| is_guid(&c.guid))
+        );
+    }
+}
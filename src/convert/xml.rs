@@ -0,0 +1,1631 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use chrono::{NaiveDate, NaiveDateTime};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use tracing::instrument;
+
+use super::TEMPLATE_ROOT;
+use crate::Book;
+use crate::error::Error;
+use crate::model::slot::{self, Slot};
+use crate::model::{Account, AccountType, Address, Commodity, Transaction};
+use crate::query::{
+    BillTermQ, BillTermT, BudgetAmountQ, BudgetAmountT, BudgetQ, BudgetT, CustomerQ, CustomerT,
+    EmployeeQ, EmployeeT, EntryQ, EntryT, InvoiceQ, InvoiceT, JobQ, JobT, LotQ, LotT, OrderQ,
+    OrderT, PriceQ, PriceT, Query, RecurrenceQ, RecurrenceT, ScheduledTransactionQ,
+    ScheduledTransactionT, SplitQ, SplitT, TaxTableEntryQ, TaxTableEntryT, TaxTableQ, TaxTableT,
+    VendorQ, VendorT,
+};
+
+const NAMESPACES: [&str; 29] = [
+    "gnc",
+    "act",
+    "book",
+    "cd",
+    "cmdty",
+    "price",
+    "slot",
+    "split",
+    "sx",
+    "trn",
+    "ts",
+    "fs",
+    "bgt",
+    "recurrence",
+    "lot",
+    "addr",
+    "billterm",
+    "bt-days",
+    "bt-prox",
+    "cust",
+    "employee",
+    "entry",
+    "invoice",
+    "job",
+    "order",
+    "owner",
+    "taxtable",
+    "tte",
+    "vendor",
+];
+
+/// Writes the book as a gzip-compressed `GnuCash` v2 XML file, the format `GnuCash` saves by default.
+///
+/// Besides commodities, prices, accounts with their lots, and transactions, the file holds
+/// the scheduled transactions with their templates, the budgets, and the business objects.
+/// Entries are written for the invoices and bills they are lines of.
+#[instrument(skip(book, path), fields(path = %path.as_ref().display()))]
+pub async fn to_xml<Q>(book: &Book<Q>, path: impl AsRef<Path>) -> Result<(), Error>
+where
+    Q: Query,
+{
+    tracing::debug!("writing gnucash xml file");
+
+    let file = File::create(path.as_ref())
+        .inspect_err(|e| tracing::error!("failed to create file: {e}"))?;
+    let mut encoder = GzEncoder::new(BufWriter::new(file), Compression::default());
+    write_xml(book, &mut encoder).await?;
+    encoder
+        .finish()?
+        .flush()
+        .inspect_err(|e| tracing::error!("failed to flush file: {e}"))?;
+
+    tracing::info!("gnucash xml file written");
+    Ok(())
+}
+
+/// Writes the book as uncompressed `GnuCash` v2 XML.
+#[instrument(skip(book, writer))]
+pub async fn write_xml<Q, W>(book: &Book<Q>, writer: W) -> Result<(), Error>
+where
+    Q: Query,
+    W: Write,
+{
+    let data = BookData::load(book).await?;
+    let mut w = XmlWriter::new(writer);
+    data.write(&mut w)?;
+    w.into_inner().flush()?;
+    Ok(())
+}
+
+/// The accounts of the book and those of the template tree.
+type AccountTrees<Q> = (Vec<Account<Q>>, Vec<Account<Q>>);
+
+struct BookData<Q>
+where
+    Q: Query,
+{
+    guid: String,
+    commodities: Vec<Commodity<Q>>,
+    accounts: Vec<Account<Q>>,
+    /// The template root and the accounts below it, holding the splits of the templates.
+    template_accounts: Vec<Account<Q>>,
+    /// The lots of each account, by account GUID.
+    lots: HashMap<String, Vec<<Q as LotQ>::Item>>,
+    transactions: Vec<Transaction<Q>>,
+    /// The transactions of scheduled transaction templates.
+    templates: Vec<Transaction<Q>>,
+    splits: HashMap<String, Vec<<Q as SplitQ>::Item>>,
+    prices: Vec<<Q as PriceQ>::Item>,
+    scheduled_transactions: Vec<<Q as ScheduledTransactionQ>::Item>,
+    budgets: Vec<<Q as BudgetQ>::Item>,
+    /// The amounts of each budget, by budget GUID.
+    budget_amounts: HashMap<String, Vec<<Q as BudgetAmountQ>::Item>>,
+    /// The recurrences of the budgets and scheduled transactions, by their GUID.
+    recurrences: HashMap<String, Vec<<Q as RecurrenceQ>::Item>>,
+    business: Business<Q>,
+    /// The slot trees of the book and of the objects written with their slots, by GUID.
+    slots: HashMap<String, BTreeMap<String, Slot>>,
+}
+
+/// The business objects of the book, each sorted by GUID.
+struct Business<Q>
+where
+    Q: Query,
+{
+    bill_terms: Vec<<Q as BillTermQ>::Item>,
+    customers: Vec<<Q as CustomerQ>::Item>,
+    employees: Vec<<Q as EmployeeQ>::Item>,
+    entries: Vec<<Q as EntryQ>::Item>,
+    invoices: Vec<<Q as InvoiceQ>::Item>,
+    jobs: Vec<<Q as JobQ>::Item>,
+    orders: Vec<<Q as OrderQ>::Item>,
+    tax_tables: Vec<<Q as TaxTableQ>::Item>,
+    /// The entries of each tax table, by tax table GUID.
+    tax_table_entries: HashMap<String, Vec<<Q as TaxTableEntryQ>::Item>>,
+    vendors: Vec<<Q as VendorQ>::Item>,
+}
+
+impl<Q> Business<Q>
+where
+    Q: Query,
+{
+    async fn load(query: &Q) -> Result<Self, Error> {
+        let mut bill_terms = query.bill_terms().await?;
+        bill_terms.sort_by(|a, b| a.guid().cmp(b.guid()));
+        let mut customers = query.customers().await?;
+        customers.sort_by(|a, b| a.guid().cmp(b.guid()));
+        let mut employees = query.employees().await?;
+        employees.sort_by(|a, b| a.guid().cmp(b.guid()));
+        let mut invoices = query.invoices().await?;
+        invoices.sort_by(|a, b| a.guid().cmp(b.guid()));
+        let mut jobs = query.jobs().await?;
+        jobs.sort_by(|a, b| a.guid().cmp(b.guid()));
+        let mut orders = query.orders().await?;
+        orders.sort_by(|a, b| a.guid().cmp(b.guid()));
+        let mut tax_tables = query.tax_tables().await?;
+        tax_tables.sort_by(|a, b| a.guid().cmp(b.guid()));
+        let mut vendors = query.vendors().await?;
+        vendors.sort_by(|a, b| a.guid().cmp(b.guid()));
+
+        let mut tax_table_entries = HashMap::new();
+        for t in &tax_tables {
+            tax_table_entries.insert(
+                t.guid().to_string(),
+                TaxTableEntryQ::taxtable(query, t.guid()).await?,
+            );
+        }
+
+        // entries are only reachable through the invoice or bill they are a line of
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        for i in &invoices {
+            for e in EntryQ::invoice(query, i.guid()).await? {
+                if seen.insert(e.guid().to_string()) {
+                    entries.push(e);
+                }
+            }
+        }
+        entries.sort_by(|a, b| a.guid().cmp(b.guid()));
+
+        Ok(Self {
+            bill_terms,
+            customers,
+            employees,
+            entries,
+            invoices,
+            jobs,
+            orders,
+            tax_tables,
+            tax_table_entries,
+            vendors,
+        })
+    }
+}
+
+impl<Q> BookData<Q>
+where
+    Q: Query,
+{
+    async fn load(book: &Book<Q>) -> Result<Self, Error> {
+        let mut commodities = book.commodities().await?;
+        commodities.sort_by(|a, b| (&a.namespace, &a.mnemonic).cmp(&(&b.namespace, &b.mnemonic)));
+
+        let (accounts, template_accounts) = Self::account_trees(book.accounts().await?)?;
+
+        let lots = Self::lots(&*book.query).await?;
+        let splits = Self::splits(&*book.query).await?;
+
+        // transactions of scheduled templates only touch accounts below the template root
+        let guids: HashSet<&str> = accounts.iter().map(|a| a.guid.as_str()).collect();
+        let mut transactions = Vec::new();
+        let mut templates = Vec::new();
+        for t in book.transactions().await? {
+            let Some(s) = splits.get(&t.guid) else {
+                continue;
+            };
+            if s.iter().any(|s| guids.contains(s.account_guid())) {
+                transactions.push(t);
+            } else {
+                templates.push(t);
+            }
+        }
+        transactions.sort_by(|a, b| (a.post_datetime, &a.guid).cmp(&(b.post_datetime, &b.guid)));
+        templates.sort_by(|a, b| a.guid.cmp(&b.guid));
+
+        let mut prices = book.query.prices().await?;
+        prices.sort_by(|a, b| (a.datetime(), a.guid()).cmp(&(b.datetime(), b.guid())));
+
+        let mut scheduled_transactions = book.query.scheduled_transactions().await?;
+        scheduled_transactions.sort_by(|a, b| a.guid().cmp(b.guid()));
+        let mut budgets = book.query.budgets().await?;
+        budgets.sort_by(|a, b| a.guid().cmp(b.guid()));
+
+        let mut budget_amounts = HashMap::new();
+        for b in &budgets {
+            budget_amounts.insert(
+                b.guid().to_string(),
+                BudgetAmountQ::budget(&*book.query, b.guid()).await?,
+            );
+        }
+        let recurrences = Self::recurrences(
+            &*book.query,
+            budgets.iter().map(BudgetT::guid).chain(
+                scheduled_transactions
+                    .iter()
+                    .map(ScheduledTransactionT::guid),
+            ),
+        )
+        .await?;
+
+        let business = Business::load(&*book.query).await?;
+
+        let guid = book.info().await?.guid;
+        let mut slots = HashMap::new();
+        let owners = std::iter::once(guid.as_str())
+            .chain(
+                accounts
+                    .iter()
+                    .chain(&template_accounts)
+                    .map(|a| a.guid.as_str()),
+            )
+            .chain(lots.values().flatten().map(LotT::guid))
+            .chain(
+                transactions
+                    .iter()
+                    .chain(&templates)
+                    .map(|t| t.guid.as_str()),
+            )
+            .chain(
+                transactions
+                    .iter()
+                    .chain(&templates)
+                    .filter_map(|t| splits.get(&t.guid))
+                    .flatten()
+                    .map(SplitT::guid),
+            )
+            .chain(
+                scheduled_transactions
+                    .iter()
+                    .map(ScheduledTransactionT::guid),
+            )
+            .chain(budgets.iter().map(BudgetT::guid));
+        for owner in owners {
+            let tree = slot::load(&*book.query, owner).await?;
+            if !tree.is_empty() {
+                slots.insert(owner.to_string(), tree);
+            }
+        }
+
+        Ok(Self {
+            guid,
+            commodities,
+            accounts,
+            template_accounts,
+            lots,
+            transactions,
+            templates,
+            splits,
+            prices,
+            scheduled_transactions,
+            budgets,
+            budget_amounts,
+            recurrences,
+            business,
+            slots,
+        })
+    }
+
+    /// The splits of the book by transaction GUID, each sorted by GUID.
+    async fn splits(query: &Q) -> Result<HashMap<String, Vec<<Q as SplitQ>::Item>>, Error> {
+        // amounts are read from the backend to keep their exact fractions
+        let mut splits: HashMap<String, Vec<_>> = HashMap::new();
+        for split in query.splits().await? {
+            splits
+                .entry(split.tx_guid().to_string())
+                .or_default()
+                .push(split);
+        }
+        for v in splits.values_mut() {
+            v.sort_by(|a, b| a.guid().cmp(b.guid()));
+        }
+        Ok(splits)
+    }
+
+    /// The lots of the book by account GUID, each sorted by GUID.
+    async fn lots(query: &Q) -> Result<HashMap<String, Vec<<Q as LotQ>::Item>>, Error> {
+        let mut lots: HashMap<String, Vec<_>> = HashMap::new();
+        for lot in query.lots().await? {
+            lots.entry(lot.account_guid().to_string())
+                .or_default()
+                .push(lot);
+        }
+        for v in lots.values_mut() {
+            v.sort_by(|a, b| a.guid().cmp(b.guid()));
+        }
+        Ok(lots)
+    }
+
+    /// The recurrences of the objects with the given GUIDs, by GUID.
+    async fn recurrences<'a>(
+        query: &Q,
+        guids: impl Iterator<Item = &'a str>,
+    ) -> Result<HashMap<String, Vec<<Q as RecurrenceQ>::Item>>, Error> {
+        let mut recurrences = HashMap::new();
+        for guid in guids {
+            recurrences.insert(guid.to_string(), RecurrenceQ::obj_guid(query, guid).await?);
+        }
+        Ok(recurrences)
+    }
+
+    /// Orders the accounts of the book and those of the template tree root parents first.
+    fn account_trees(accounts: Vec<Account<Q>>) -> Result<AccountTrees<Q>, Error> {
+        let mut children: HashMap<String, Vec<Account<Q>>> = HashMap::new();
+        let mut roots = Vec::new();
+        let mut template_roots = Vec::new();
+        for account in accounts {
+            if account.parent_guid.is_empty() {
+                if account.r#type != AccountType::Root {
+                    continue;
+                }
+                if account.name == TEMPLATE_ROOT {
+                    template_roots.push(account);
+                } else {
+                    roots.push(account);
+                }
+            } else {
+                children
+                    .entry(account.parent_guid.clone())
+                    .or_default()
+                    .push(account);
+            }
+        }
+
+        let root = match roots.len() {
+            1 => roots.remove(0),
+            n => {
+                return Err(Error::Invalid {
+                    model: "Book".to_string(),
+                    reason: format!("expected one root account, found {n}"),
+                });
+            }
+        };
+
+        let accounts = Self::tree(root, &mut children);
+        let templates = template_roots
+            .into_iter()
+            .flat_map(|root| Self::tree(root, &mut children))
+            .collect();
+        Ok((accounts, templates))
+    }
+
+    /// The account `root` followed by its descendants, taken out of `children`.
+    fn tree(root: Account<Q>, children: &mut HashMap<String, Vec<Account<Q>>>) -> Vec<Account<Q>> {
+        let mut result = vec![root];
+        let mut i = 0;
+        while i < result.len() {
+            if let Some(mut c) = children.remove(&result[i].guid) {
+                c.sort_by(|a, b| a.name.cmp(&b.name));
+                result.extend(c);
+            }
+            i += 1;
+        }
+        result
+    }
+
+    fn commodity(&self, guid: &str) -> Result<&Commodity<Q>, Error> {
+        self.commodities
+            .iter()
+            .find(|c| c.guid == guid)
+            .ok_or_else(|| Error::GuidNotFound {
+                model: "Commodity".to_string(),
+                guid: guid.to_string(),
+            })
+    }
+
+    fn slots(&self, guid: &str) -> BTreeMap<String, Slot> {
+        self.slots.get(guid).cloned().unwrap_or_default()
+    }
+
+    /// The SCU of an account, falling back to the fraction of its commodity.
+    fn scu(&self, account: &Account<Q>) -> Result<i64, Error> {
+        if account.commodity_scu > 0 {
+            return Ok(account.commodity_scu);
+        }
+        Ok(self.commodity(&account.commodity_guid)?.fraction.max(1))
+    }
+
+    fn write<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        w.raw("<?xml version=\"1.0\" encoding=\"utf-8\" ?>\n")?;
+        w.raw("<gnc-v2")?;
+        for ns in NAMESPACES {
+            w.raw(&format!(
+                "\n     xmlns:{ns}=\"http://www.gnucash.org/XML/{ns}\""
+            ))?;
+        }
+        w.raw(">\n")?;
+        w.count("book", 1)?;
+
+        w.open("gnc:book", &[("version", "2.0.0")])?;
+        w.guid("book:id", &self.guid)?;
+        w.slots("book:slots", &self.slots(&self.guid))?;
+        w.count("commodity", self.commodities.len())?;
+        w.count("account", self.accounts.len())?;
+        w.count("transaction", self.transactions.len())?;
+        let b = &self.business;
+        for (kind, count) in [
+            ("schedxaction", self.scheduled_transactions.len()),
+            ("budget", self.budgets.len()),
+            ("price", self.prices.len()),
+            ("gnc:GncBillTerm", b.bill_terms.len()),
+            ("gnc:GncCustomer", b.customers.len()),
+            ("gnc:GncEmployee", b.employees.len()),
+            ("gnc:GncEntry", b.entries.len()),
+            ("gnc:GncInvoice", b.invoices.len()),
+            ("gnc:GncJob", b.jobs.len()),
+            ("gnc:GncOrder", b.orders.len()),
+            ("gnc:GncTaxTable", b.tax_tables.len()),
+            ("gnc:GncVendor", b.vendors.len()),
+        ] {
+            if count > 0 {
+                w.count(kind, count)?;
+            }
+        }
+
+        self.write_commodities(w)?;
+        self.write_prices(w)?;
+        for a in &self.accounts {
+            self.write_account(w, a)?;
+        }
+        for t in &self.transactions {
+            self.write_transaction(w, t)?;
+        }
+        self.write_templates(w)?;
+        self.write_scheduled_transactions(w)?;
+        self.write_budgets(w)?;
+        self.write_business(w)?;
+
+        w.close("gnc:book")?;
+        w.raw("</gnc-v2>\n")?;
+        Ok(())
+    }
+
+    fn write_commodities<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        for c in &self.commodities {
+            w.open("gnc:commodity", &[("version", "2.0.0")])?;
+            w.text("cmdty:space", &c.namespace)?;
+            w.text("cmdty:id", &c.mnemonic)?;
+            w.text_non_empty("cmdty:name", &c.fullname)?;
+            w.text_non_empty("cmdty:xcode", &c.cusip)?;
+            w.text("cmdty:fraction", &c.fraction.to_string())?;
+            if c.quote_flag {
+                w.empty("cmdty:get_quotes")?;
+            }
+            w.text_non_empty("cmdty:quote_source", &c.quote_source)?;
+            w.text_non_empty("cmdty:quote_tz", &c.quote_tz)?;
+            w.close("gnc:commodity")?;
+        }
+        Ok(())
+    }
+
+    fn write_prices<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        if self.prices.is_empty() {
+            return Ok(());
+        }
+
+        w.open("gnc:pricedb", &[("version", "1")])?;
+        for p in &self.prices {
+            w.open("price", &[])?;
+            w.guid("price:id", p.guid())?;
+            w.commodity("price:commodity", self.commodity(p.commodity_guid())?)?;
+            w.commodity("price:currency", self.commodity(p.currency_guid())?)?;
+            w.date("price:time", p.datetime())?;
+            w.text_non_empty("price:source", p.source())?;
+            w.text_non_empty("price:type", p.r#type())?;
+            w.fraction("price:value", p.value_num(), p.value_denom())?;
+            w.close("price")?;
+        }
+        w.close("gnc:pricedb")
+    }
+
+    fn write_account<W: Write>(&self, w: &mut XmlWriter<W>, a: &Account<Q>) -> Result<(), Error> {
+        w.open("gnc:account", &[("version", "2.0.0")])?;
+        w.text("act:name", &a.name)?;
+        w.guid("act:id", &a.guid)?;
+        w.text("act:type", a.r#type.as_str())?;
+        if !a.commodity_guid.is_empty() {
+            w.commodity("act:commodity", self.commodity(&a.commodity_guid)?)?;
+            w.text("act:commodity-scu", &self.scu(a)?.to_string())?;
+            if a.non_std_scu {
+                w.empty("act:non-standard-scu")?;
+            }
+        }
+        w.text_non_empty("act:code", &a.code)?;
+        w.text_non_empty("act:description", &a.description)?;
+        // SQL files keep these flags in columns, XML files only in slots
+        let mut slots = self.slots(&a.guid);
+        if a.placeholder {
+            slots.insert("placeholder".to_string(), Slot::String("true".to_string()));
+        }
+        if a.hidden {
+            slots.insert("hidden".to_string(), Slot::String("true".to_string()));
+        }
+        w.slots("act:slots", &slots)?;
+        if !a.parent_guid.is_empty() {
+            w.guid("act:parent", &a.parent_guid)?;
+        }
+        if let Some(lots) = self.lots.get(&a.guid) {
+            w.open("act:lots", &[])?;
+            for l in lots {
+                w.open("gnc:lot", &[("version", "2.0.0")])?;
+                w.guid("lot:id", l.guid())?;
+                // the title and notes of a lot are slots
+                w.slots("lot:slots", &self.slots(l.guid()))?;
+                w.close("gnc:lot")?;
+            }
+            w.close("act:lots")?;
+        }
+        w.close("gnc:account")
+    }
+
+    fn write_transaction<W: Write>(
+        &self,
+        w: &mut XmlWriter<W>,
+        t: &Transaction<Q>,
+    ) -> Result<(), Error> {
+        let currency = self.commodity(&t.currency_guid)?;
+        w.open("gnc:transaction", &[("version", "2.0.0")])?;
+        w.guid("trn:id", &t.guid)?;
+        w.commodity("trn:currency", currency)?;
+        w.text_non_empty("trn:num", &t.num)?;
+        w.date("trn:date-posted", t.post_datetime)?;
+        w.date("trn:date-entered", t.enter_datetime)?;
+        w.text("trn:description", &t.description)?;
+        w.slots("trn:slots", &self.slots(&t.guid))?;
+        w.open("trn:splits", &[])?;
+        for s in self.splits.get(&t.guid).into_iter().flatten() {
+            w.open("trn:split", &[])?;
+            w.guid("split:id", s.guid())?;
+            w.text_non_empty("split:memo", s.memo())?;
+            w.text_non_empty("split:action", s.action())?;
+            w.text("split:reconciled-state", &s.reconcile_flag().to_string())?;
+            if let Some(datetime) = s.reconcile_datetime() {
+                w.date("split:reconcile-date", datetime)?;
+            }
+            w.fraction("split:value", s.value_num(), s.value_denom())?;
+            w.fraction("split:quantity", s.quantity_num(), s.quantity_denom())?;
+            w.guid("split:account", s.account_guid())?;
+            if !s.lot_guid().is_empty() {
+                w.guid("split:lot", s.lot_guid())?;
+            }
+            w.slots("split:slots", &self.slots(s.guid()))?;
+            w.close("trn:split")?;
+        }
+        w.close("trn:splits")?;
+        w.close("gnc:transaction")
+    }
+
+    /// Writes the template tree and the transactions of the scheduled transaction templates.
+    fn write_templates<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        // a template root without templates below it is not written
+        if self.templates.is_empty() && self.template_accounts.len() <= 1 {
+            return Ok(());
+        }
+
+        w.open("gnc:template-transactions", &[])?;
+        for a in &self.template_accounts {
+            self.write_account(w, a)?;
+        }
+        for t in &self.templates {
+            self.write_transaction(w, t)?;
+        }
+        w.close("gnc:template-transactions")
+    }
+
+    fn write_scheduled_transactions<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        let yes_no = |x: bool| if x { "y" } else { "n" };
+        for x in &self.scheduled_transactions {
+            w.open("gnc:schedxaction", &[("version", "2.0.0")])?;
+            w.guid("sx:id", x.guid())?;
+            w.text("sx:name", x.name())?;
+            w.text("sx:enabled", yes_no(x.enabled()))?;
+            w.text("sx:autoCreate", yes_no(x.auto_create()))?;
+            w.text("sx:autoCreateNotify", yes_no(x.auto_notify()))?;
+            w.text("sx:advanceCreateDays", &x.adv_creation().to_string())?;
+            w.text("sx:advanceRemindDays", &x.adv_notify().to_string())?;
+            w.text("sx:instanceCount", &x.instance_count().to_string())?;
+            if let Some(date) = x.start_date() {
+                w.gdate("sx:start", date)?;
+            }
+            if let Some(date) = x.last_occur() {
+                w.gdate("sx:last", date)?;
+            }
+            // both counts are only kept for schedules with a limited number of occurrences
+            if x.num_occur() > 0 {
+                w.text("sx:num-occur", &x.num_occur().to_string())?;
+                w.text("sx:rem-occur", &x.rem_occur().to_string())?;
+            }
+            if let Some(date) = x.end_date() {
+                w.gdate("sx:end", date)?;
+            }
+            w.guid("sx:templ-acct", x.template_act_guid())?;
+            w.open("sx:schedule", &[])?;
+            for r in self.recurrences.get(x.guid()).into_iter().flatten() {
+                w.recurrence("gnc:recurrence", r)?;
+            }
+            w.close("sx:schedule")?;
+            w.slots("sx:slots", &self.slots(x.guid()))?;
+            w.close("gnc:schedxaction")?;
+        }
+        Ok(())
+    }
+
+    fn write_budgets<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        for b in &self.budgets {
+            w.open("gnc:budget", &[("version", "2.0.0")])?;
+            w.guid("bgt:id", b.guid())?;
+            w.text("bgt:name", b.name())?;
+            w.text_non_empty("bgt:description", b.description())?;
+            w.text("bgt:num-periods", &b.num_periods().to_string())?;
+            for r in self.recurrences.get(b.guid()).into_iter().flatten() {
+                w.recurrence("bgt:recurrence", r)?;
+            }
+            // XML files keep the amounts in a frame per account, keyed by period number
+            let mut slots = self.slots(b.guid());
+            for a in self.budget_amounts.get(b.guid()).into_iter().flatten() {
+                let frame = slots
+                    .entry(a.account_guid().to_string())
+                    .or_insert_with(|| Slot::Frame(BTreeMap::new()));
+                if let Slot::Frame(frame) = frame {
+                    frame.insert(
+                        a.period_num().to_string(),
+                        Slot::Numeric {
+                            num: a.amount_num(),
+                            denom: a.amount_denom(),
+                        },
+                    );
+                }
+            }
+            w.slots("bgt:slots", &slots)?;
+            w.close("gnc:budget")?;
+        }
+        Ok(())
+    }
+
+    fn write_business<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        self.write_bill_terms(w)?;
+        self.write_customers(w)?;
+        self.write_employees(w)?;
+        self.write_entries(w)?;
+        self.write_invoices(w)?;
+        self.write_jobs(w)?;
+        self.write_orders(w)?;
+        self.write_tax_tables(w)?;
+        self.write_vendors(w)?;
+        Ok(())
+    }
+
+    fn write_bill_terms<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        for x in &self.business.bill_terms {
+            w.open("gnc:GncBillTerm", &[("version", "2.0.0")])?;
+            w.guid("billterm:guid", x.guid())?;
+            w.text("billterm:name", x.name())?;
+            w.text("billterm:desc", x.description())?;
+            w.text("billterm:refcount", &x.refcount().to_string())?;
+            w.flag("billterm:invisible", x.invisible())?;
+            w.guid_non_empty("billterm:parent", x.parent_guid())?;
+            if x.r#type() == "GNC_TERM_TYPE_PROXIMO" {
+                w.open("billterm:proximo", &[])?;
+                w.text("bt-prox:due-day", &x.due_days().to_string())?;
+                w.text("bt-prox:disc-day", &x.discount_days().to_string())?;
+                w.fraction("bt-prox:discount", x.discount_num(), x.discount_denom())?;
+                w.text("bt-prox:cutoff-day", &x.cutoff().to_string())?;
+                w.close("billterm:proximo")?;
+            } else {
+                w.open("billterm:days", &[])?;
+                w.text("bt-days:due-days", &x.due_days().to_string())?;
+                w.text("bt-days:disc-days", &x.discount_days().to_string())?;
+                w.fraction("bt-days:discount", x.discount_num(), x.discount_denom())?;
+                w.close("billterm:days")?;
+            }
+            w.close("gnc:GncBillTerm")?;
+        }
+        Ok(())
+    }
+
+    fn write_customers<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        for x in &self.business.customers {
+            w.open("gnc:GncCustomer", &[("version", "2.0.0")])?;
+            w.guid("cust:guid", x.guid())?;
+            w.text("cust:name", x.name())?;
+            w.text("cust:id", x.id())?;
+            w.address("cust:addr", &x.addr())?;
+            w.address("cust:shipaddr", &x.shipaddr())?;
+            w.text_non_empty("cust:notes", x.notes())?;
+            w.guid_non_empty("cust:terms", x.terms_guid())?;
+            w.text("cust:taxincluded", x.tax_included())?;
+            w.flag("cust:active", x.active())?;
+            w.fraction("cust:discount", x.discount_num(), x.discount_denom())?;
+            w.fraction("cust:credit", x.credit_num(), x.credit_denom())?;
+            self.write_currency(w, "cust:currency", x.currency_guid())?;
+            w.flag("cust:use-tt", x.tax_override())?;
+            w.guid_non_empty("cust:taxtable", x.taxtable_guid())?;
+            w.close("gnc:GncCustomer")?;
+        }
+        Ok(())
+    }
+
+    fn write_employees<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        for x in &self.business.employees {
+            w.open("gnc:GncEmployee", &[("version", "2.0.0")])?;
+            w.guid("employee:guid", x.guid())?;
+            w.text("employee:username", x.username())?;
+            w.text("employee:id", x.id())?;
+            w.address("employee:addr", &x.addr())?;
+            w.text_non_empty("employee:language", x.language())?;
+            w.text_non_empty("employee:acl", x.acl())?;
+            w.flag("employee:active", x.active())?;
+            w.fraction("employee:workday", x.workday_num(), x.workday_denom())?;
+            w.fraction("employee:rate", x.rate_num(), x.rate_denom())?;
+            self.write_currency(w, "employee:currency", x.currency_guid())?;
+            w.guid_non_empty("employee:ccard", x.ccard_guid())?;
+            w.close("gnc:GncEmployee")?;
+        }
+        Ok(())
+    }
+
+    fn write_entries<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        for x in &self.business.entries {
+            w.open("gnc:GncEntry", &[("version", "2.0.0")])?;
+            w.guid("entry:guid", x.guid())?;
+            w.date("entry:date", x.date())?;
+            if let Some(datetime) = x.date_entered() {
+                w.date("entry:entered", datetime)?;
+            }
+            w.text_non_empty("entry:description", x.description())?;
+            w.text_non_empty("entry:action", x.action())?;
+            w.text_non_empty("entry:notes", x.notes())?;
+            w.fraction("entry:qty", x.quantity_num(), x.quantity_denom())?;
+            w.guid_non_empty("entry:i-acct", x.i_acct_guid())?;
+            w.fraction("entry:i-price", x.i_price_num(), x.i_price_denom())?;
+            w.fraction("entry:i-discount", x.i_discount_num(), x.i_discount_denom())?;
+            w.guid_non_empty("entry:invoice", x.invoice_guid())?;
+            w.text_non_empty("entry:i-disc-type", x.i_disc_type())?;
+            w.text_non_empty("entry:i-disc-how", x.i_disc_how())?;
+            w.flag("entry:i-taxable", x.i_taxable())?;
+            w.flag("entry:i-taxincluded", x.i_taxincluded())?;
+            w.guid_non_empty("entry:i-taxtable", x.i_taxtable_guid())?;
+            w.guid_non_empty("entry:b-acct", x.b_acct_guid())?;
+            w.fraction("entry:b-price", x.b_price_num(), x.b_price_denom())?;
+            w.guid_non_empty("entry:bill", x.bill_guid())?;
+            w.flag("entry:b-taxable", x.b_taxable())?;
+            w.flag("entry:b-taxincluded", x.b_taxincluded())?;
+            w.guid_non_empty("entry:b-taxtable", x.b_taxtable_guid())?;
+            w.text(
+                "entry:b-pay",
+                if x.b_paytype() == 2 { "CARD" } else { "CASH" },
+            )?;
+            w.flag("entry:billable", x.billable())?;
+            w.guid_non_empty("entry:order", x.order_guid())?;
+            w.close("gnc:GncEntry")?;
+        }
+        Ok(())
+    }
+
+    fn write_invoices<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        for x in &self.business.invoices {
+            w.open("gnc:GncInvoice", &[("version", "2.0.0")])?;
+            w.guid("invoice:guid", x.guid())?;
+            w.text("invoice:id", x.id())?;
+            w.owner("invoice:owner", x.owner_type(), x.owner_guid())?;
+            if let Some(datetime) = x.date_opened() {
+                w.date("invoice:opened", datetime)?;
+            }
+            if let Some(datetime) = x.date_posted() {
+                w.date("invoice:posted", datetime)?;
+            }
+            w.guid_non_empty("invoice:terms", x.terms_guid())?;
+            w.text_non_empty("invoice:billing_id", x.billing_id())?;
+            w.text_non_empty("invoice:notes", x.notes())?;
+            w.flag("invoice:active", x.active())?;
+            w.guid_non_empty("invoice:posttxn", x.post_txn_guid())?;
+            w.guid_non_empty("invoice:postlot", x.post_lot_guid())?;
+            w.guid_non_empty("invoice:postacc", x.post_acc_guid())?;
+            self.write_currency(w, "invoice:currency", x.currency_guid())?;
+            w.fraction(
+                "invoice:charge-amt",
+                x.charge_amt_num(),
+                x.charge_amt_denom(),
+            )?;
+            w.close("gnc:GncInvoice")?;
+        }
+        Ok(())
+    }
+
+    fn write_jobs<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        for x in &self.business.jobs {
+            w.open("gnc:GncJob", &[("version", "2.0.0")])?;
+            w.guid("job:guid", x.guid())?;
+            w.text("job:id", x.id())?;
+            w.text("job:name", x.name())?;
+            w.text_non_empty("job:reference", x.reference())?;
+            w.owner("job:owner", x.owner_type(), x.owner_guid())?;
+            w.flag("job:active", x.active())?;
+            w.close("gnc:GncJob")?;
+        }
+        Ok(())
+    }
+
+    fn write_orders<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        for x in &self.business.orders {
+            w.open("gnc:GncOrder", &[("version", "2.0.0")])?;
+            w.guid("order:guid", x.guid())?;
+            w.text("order:id", x.id())?;
+            w.owner("order:owner", x.owner_type(), x.owner_guid())?;
+            w.date("order:opened", x.date_opened())?;
+            if let Some(datetime) = x.date_closed() {
+                w.date("order:closed", datetime)?;
+            }
+            w.text_non_empty("order:notes", x.notes())?;
+            w.text_non_empty("order:reference", x.reference())?;
+            w.flag("order:active", x.active())?;
+            w.close("gnc:GncOrder")?;
+        }
+        Ok(())
+    }
+
+    fn write_tax_tables<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        for x in &self.business.tax_tables {
+            w.open("gnc:GncTaxTable", &[("version", "2.0.0")])?;
+            w.guid("taxtable:guid", x.guid())?;
+            w.text("taxtable:name", x.name())?;
+            w.text("taxtable:refcount", &x.refcount().to_string())?;
+            w.flag("taxtable:invisible", x.invisible())?;
+            w.guid_non_empty("taxtable:parent", x.parent_guid())?;
+            w.open("taxtable:entries", &[])?;
+            for e in self
+                .business
+                .tax_table_entries
+                .get(x.guid())
+                .into_iter()
+                .flatten()
+            {
+                w.open("gnc:GncTaxTableEntry", &[])?;
+                w.guid("tte:acct", e.account_guid())?;
+                w.fraction("tte:amount", e.amount_num(), e.amount_denom())?;
+                w.text(
+                    "tte:type",
+                    if e.r#type() == 1 { "VALUE" } else { "PERCENT" },
+                )?;
+                w.close("gnc:GncTaxTableEntry")?;
+            }
+            w.close("taxtable:entries")?;
+            w.close("gnc:GncTaxTable")?;
+        }
+        Ok(())
+    }
+
+    fn write_vendors<W: Write>(&self, w: &mut XmlWriter<W>) -> Result<(), Error> {
+        for x in &self.business.vendors {
+            w.open("gnc:GncVendor", &[("version", "2.0.0")])?;
+            w.guid("vendor:guid", x.guid())?;
+            w.text("vendor:name", x.name())?;
+            w.text("vendor:id", x.id())?;
+            w.address("vendor:addr", &x.addr())?;
+            w.text_non_empty("vendor:notes", x.notes())?;
+            w.guid_non_empty("vendor:terms", x.terms_guid())?;
+            w.text("vendor:taxincluded", x.tax_included())?;
+            w.flag("vendor:active", x.active())?;
+            self.write_currency(w, "vendor:currency", x.currency_guid())?;
+            w.flag("vendor:use-tt", x.tax_override())?;
+            w.guid_non_empty("vendor:taxtable", x.taxtable_guid())?;
+            w.close("gnc:GncVendor")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the currency of a business object, which may have none.
+    fn write_currency<W: Write>(
+        &self,
+        w: &mut XmlWriter<W>,
+        tag: &str,
+        guid: &str,
+    ) -> Result<(), Error> {
+        if guid.is_empty() {
+            return Ok(());
+        }
+        w.commodity(tag, self.commodity(guid)?)
+    }
+}
+
+/// Minimal indenting XML emitter, laid out like the files `GnuCash` writes.
+struct XmlWriter<W: Write> {
+    inner: W,
+    depth: usize,
+}
+
+impl<W: Write> XmlWriter<W> {
+    fn new(inner: W) -> Self {
+        Self { inner, depth: 0 }
+    }
+
+    fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn raw(&mut self, s: &str) -> Result<(), Error> {
+        self.inner.write_all(s.as_bytes())?;
+        Ok(())
+    }
+
+    fn indent(&mut self) -> Result<(), Error> {
+        // top level elements of the book are not indented
+        let width = self.depth.saturating_sub(1) * 2;
+        write!(self.inner, "{:width$}", "")?;
+        Ok(())
+    }
+
+    fn start_tag(&mut self, tag: &str, attrs: &[(&str, &str)]) -> Result<(), Error> {
+        self.indent()?;
+        write!(self.inner, "<{tag}")?;
+        for (k, v) in attrs {
+            write!(self.inner, " {k}=\"{}\"", escape(v))?;
+        }
+        Ok(())
+    }
+
+    fn open(&mut self, tag: &str, attrs: &[(&str, &str)]) -> Result<(), Error> {
+        self.start_tag(tag, attrs)?;
+        self.raw(">\n")?;
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn close(&mut self, tag: &str) -> Result<(), Error> {
+        self.depth -= 1;
+        self.indent()?;
+        writeln!(self.inner, "</{tag}>")?;
+        Ok(())
+    }
+
+    fn empty(&mut self, tag: &str) -> Result<(), Error> {
+        self.start_tag(tag, &[])?;
+        self.raw("/>\n")
+    }
+
+    fn element(&mut self, tag: &str, attrs: &[(&str, &str)], text: &str) -> Result<(), Error> {
+        self.start_tag(tag, attrs)?;
+        writeln!(self.inner, ">{}</{tag}>", escape(text))?;
+        Ok(())
+    }
+
+    fn text(&mut self, tag: &str, text: &str) -> Result<(), Error> {
+        self.element(tag, &[], text)
+    }
+
+    fn text_non_empty(&mut self, tag: &str, text: &str) -> Result<(), Error> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        self.text(tag, text)
+    }
+
+    fn fraction(&mut self, tag: &str, num: i64, denom: i64) -> Result<(), Error> {
+        self.text(tag, &format!("{num}/{denom}"))
+    }
+
+    fn guid(&mut self, tag: &str, guid: &str) -> Result<(), Error> {
+        self.element(tag, &[("type", "guid")], guid)
+    }
+
+    fn guid_non_empty(&mut self, tag: &str, guid: &str) -> Result<(), Error> {
+        if guid.is_empty() {
+            return Ok(());
+        }
+        self.guid(tag, guid)
+    }
+
+    /// Business objects write their flags as `1` and `0`.
+    fn flag(&mut self, tag: &str, value: bool) -> Result<(), Error> {
+        self.text(tag, if value { "1" } else { "0" })
+    }
+
+    fn count(&mut self, kind: &str, count: usize) -> Result<(), Error> {
+        self.element("gnc:count-data", &[("cd:type", kind)], &count.to_string())
+    }
+
+    fn date(&mut self, tag: &str, datetime: NaiveDateTime) -> Result<(), Error> {
+        self.open(tag, &[])?;
+        self.text(
+            "ts:date",
+            &datetime.format("%Y-%m-%d %H:%M:%S +0000").to_string(),
+        )?;
+        self.close(tag)
+    }
+
+    fn gdate(&mut self, tag: &str, date: NaiveDate) -> Result<(), Error> {
+        self.open(tag, &[])?;
+        self.text("gdate", &date.format("%Y-%m-%d").to_string())?;
+        self.close(tag)
+    }
+
+    fn recurrence<R: RecurrenceT>(&mut self, tag: &str, recurrence: &R) -> Result<(), Error> {
+        self.open(tag, &[("version", "1.0.0")])?;
+        self.text("recurrence:mult", &recurrence.mult().to_string())?;
+        self.text("recurrence:period_type", recurrence.period_type())?;
+        self.gdate("recurrence:start", recurrence.period_start())?;
+        // omitted when no adjustment is made
+        let adjust = recurrence.weekend_adjust();
+        if !adjust.is_empty() && adjust != "none" {
+            self.text("recurrence:weekend_adj", adjust)?;
+        }
+        self.close(tag)
+    }
+
+    fn address(&mut self, tag: &str, address: &Address) -> Result<(), Error> {
+        self.open(tag, &[("version", "2.0.0")])?;
+        self.text_non_empty("addr:name", &address.name)?;
+        self.text_non_empty("addr:addr1", &address.addr1)?;
+        self.text_non_empty("addr:addr2", &address.addr2)?;
+        self.text_non_empty("addr:addr3", &address.addr3)?;
+        self.text_non_empty("addr:addr4", &address.addr4)?;
+        self.text_non_empty("addr:phone", &address.phone)?;
+        self.text_non_empty("addr:fax", &address.fax)?;
+        self.text_non_empty("addr:email", &address.email)?;
+        self.close(tag)
+    }
+
+    /// Writes an owner given as its `GncOwnerType` and GUID.
+    fn owner(&mut self, tag: &str, owner_type: i64, guid: &str) -> Result<(), Error> {
+        if guid.is_empty() {
+            return Ok(());
+        }
+        let owner_type = match owner_type {
+            2 => "gncCustomer",
+            3 => "gncJob",
+            4 => "gncVendor",
+            5 => "gncEmployee",
+            n => {
+                return Err(Error::Invalid {
+                    model: "Owner".to_string(),
+                    reason: format!("unknown owner type {n} of {guid}"),
+                });
+            }
+        };
+        self.open(tag, &[("version", "2.0.0")])?;
+        self.text("owner:type", owner_type)?;
+        self.guid("owner:id", guid)?;
+        self.close(tag)
+    }
+
+    fn commodity<Q: Query>(&mut self, tag: &str, commodity: &Commodity<Q>) -> Result<(), Error> {
+        self.open(tag, &[])?;
+        self.text("cmdty:space", &commodity.namespace)?;
+        self.text("cmdty:id", &commodity.mnemonic)?;
+        self.close(tag)
+    }
+
+    fn slots(&mut self, tag: &str, slots: &BTreeMap<String, Slot>) -> Result<(), Error> {
+        if slots.is_empty() {
+            return Ok(());
+        }
+        self.open(tag, &[])?;
+        for (key, value) in slots {
+            self.slot(key, value)?;
+        }
+        self.close(tag)
+    }
+
+    fn slot(&mut self, key: &str, value: &Slot) -> Result<(), Error> {
+        self.open("slot", &[])?;
+        self.text("slot:key", key)?;
+        self.slot_value(value)?;
+        self.close("slot")
+    }
+
+    fn slot_value(&mut self, value: &Slot) -> Result<(), Error> {
+        const TAG: &str = "slot:value";
+        match value {
+            Slot::String(x) => self.element(TAG, &[("type", "string")], x),
+            Slot::Int64(x) => self.element(TAG, &[("type", "integer")], &x.to_string()),
+            Slot::Double(x) => self.element(TAG, &[("type", "double")], &x.to_string()),
+            Slot::Numeric { num, denom } => {
+                self.element(TAG, &[("type", "numeric")], &format!("{num}/{denom}"))
+            }
+            Slot::Guid(x) => self.element(TAG, &[("type", "guid")], x),
+            Slot::Timespec(x) => {
+                self.open(TAG, &[("type", "timespec")])?;
+                self.text("ts:date", &x.format("%Y-%m-%d %H:%M:%S +0000").to_string())?;
+                self.close(TAG)
+            }
+            Slot::GDate(x) => {
+                self.open(TAG, &[("type", "gdate")])?;
+                self.text("gdate", &x.format("%Y-%m-%d").to_string())?;
+                self.close(TAG)
+            }
+            Slot::Frame(frame) => {
+                self.open(TAG, &[("type", "frame")])?;
+                for (key, value) in frame {
+                    self.slot(key, value)?;
+                }
+                self.close(TAG)
+            }
+            Slot::List(items) => {
+                self.open(TAG, &[("type", "list")])?;
+                for item in items {
+                    self.slot_value(item)?;
+                }
+                self.close(TAG)
+            }
+        }
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape(r#"a<b>&"c"'"#), "a&lt;b&gt;&amp;&quot;c&quot;&apos;");
+    }
+
+    #[test]
+    fn test_slot_numeric() {
+        let mut writer = XmlWriter::new(Vec::new());
+        writer
+            .slot_value(&Slot::Numeric { num: 1, denom: 3 })
+            .unwrap();
+        let data = String::from_utf8(writer.into_inner()).unwrap();
+        assert_eq!(data, "<slot:value type=\"numeric\">1/3</slot:value>\n");
+    }
+
+    #[cfg(all(feature = "sqlite", feature = "xml"))]
+    mod sqlite {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        use test_log::test;
+
+        use crate::model::{Owner, ReconcileState};
+        use crate::query::sqlite::tests::{setup_with, setup_writable};
+        use crate::query::tests::fixtures::{
+            BUDGET, BUSINESS, INVOICES, LOT, SCHEDULED_TRANSACTION, TAX, TERMS,
+        };
+        use crate::{SQLiteQuery, XMLQuery};
+
+        use super::super::*;
+
+        async fn setup() -> Book<SQLiteQuery> {
+            let uri: &str = &format!(
+                "{}/tests/db/sqlite/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            Book::new(SQLiteQuery::new(uri).unwrap()).await.unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_write_xml() {
+            let book = setup().await;
+            let mut data = Vec::new();
+            write_xml(&book, &mut data).await.unwrap();
+            let data = String::from_utf8(data).unwrap();
+
+            assert!(data.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\" ?>\n<gnc-v2\n"));
+            assert!(data.contains("<gnc:count-data cd:type=\"book\">1</gnc:count-data>"));
+            assert!(data.contains("<gnc:count-data cd:type=\"account\">20</gnc:count-data>"));
+            assert!(data.contains("<gnc:count-data cd:type=\"transaction\">11</gnc:count-data>"));
+            assert!(data.contains("<gnc:count-data cd:type=\"price\">5</gnc:count-data>"));
+            assert!(!data.contains(TEMPLATE_ROOT));
+            assert!(data.ends_with("</gnc:book>\n</gnc-v2>\n"));
+        }
+
+        #[test(tokio::test)]
+        async fn test_to_xml() {
+            let book = setup().await;
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("complex_sample.gnucash");
+            to_xml(&book, &path).await.unwrap();
+
+            let xml = Book::new(XMLQuery::new(path.to_str().unwrap()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(xml.accounts().await.unwrap().len(), 20);
+            assert_eq!(xml.transactions().await.unwrap().len(), 11);
+            assert_eq!(xml.splits().await.unwrap().len(), 25);
+            assert_eq!(xml.prices().await.unwrap().len(), 5);
+            assert_eq!(xml.commodities().await.unwrap().len(), 5);
+
+            // the book keeps its guid and its slots
+            assert_eq!(
+                xml.info().await.unwrap().guid,
+                "7d4ef4044fd30f41d08914a8174c2f5b"
+            );
+            assert_eq!(xml.slots().await.unwrap(), book.slots().await.unwrap());
+
+            let account = xml
+                .account_contains_name_ignore_case("Asset")
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(account.placeholder, true);
+
+            let split = xml
+                .splits()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "de832fe97e37811a7fff7e28b3a43425")
+                .unwrap();
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, split.value, 150.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(split.value, rust_decimal::Decimal::new(150, 0));
+
+            let account = xml
+                .account_contains_name_ignore_case("Current")
+                .await
+                .unwrap()
+                .unwrap();
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, account.balance(&xml).await.unwrap(), 4590.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(
+                account.balance(&xml).await.unwrap(),
+                rust_decimal::Decimal::new(4590, 0)
+            );
+        }
+
+        /// Asserts that two objects read through different backends agree on `fields`.
+        macro_rules! assert_fields {
+            ($left:expr, $right:expr, $($field:ident),+ $(,)?) => {
+                // amounts are written as exact fractions, so they read back unchanged
+                $(#[allow(clippy::float_cmp)]
+                {
+                    assert_eq!($left.$field, $right.$field, stringify!($field));
+                })+
+            };
+        }
+
+        /// A copy of the sample with every kind of object, and the XML file it is written to.
+        async fn setup_objects() -> (tempfile::TempDir, Book<SQLiteQuery>, Book<XMLQuery>) {
+            let (dir, query) = setup_with(&[
+                LOT,
+                BUDGET,
+                SCHEDULED_TRANSACTION,
+                BUSINESS,
+                INVOICES,
+                TERMS,
+                TAX,
+            ]);
+            let book = Book::new(query).await.unwrap();
+            let path = dir.path().join("objects.gnucash");
+            to_xml(&book, &path).await.unwrap();
+            let xml = Book::new(XMLQuery::new(path.to_str().unwrap()).unwrap())
+                .await
+                .unwrap();
+            (dir, book, xml)
+        }
+
+        #[test(tokio::test)]
+        async fn test_to_xml_objects() {
+            let (_dir, book, xml) = setup_objects().await;
+
+            let mut lots = book.lots().await.unwrap();
+            lots.sort_by(|a, b| a.guid.cmp(&b.guid));
+            let mut result = xml.lots().await.unwrap();
+            result.sort_by(|a, b| a.guid.cmp(&b.guid));
+            assert_eq!(result.len(), 3);
+            for (l, r) in lots.iter().zip(&result) {
+                assert_fields!(l, r, guid, account_guid);
+                assert_eq!(r.title().await.unwrap(), l.title().await.unwrap());
+                assert_eq!(r.notes().await.unwrap(), l.notes().await.unwrap());
+                assert_eq!(
+                    r.splits().await.unwrap().len(),
+                    l.splits().await.unwrap().len()
+                );
+            }
+
+            let budgets = book.budgets().await.unwrap();
+            let result = xml.budgets().await.unwrap();
+            assert_eq!(result.len(), 1);
+            assert_fields!(budgets[0], result[0], guid, name, description, num_periods);
+            assert_eq!(
+                result[0].recurrence().await.unwrap(),
+                budgets[0].recurrence().await.unwrap()
+            );
+            // the accounts of the template tree are not read back as accounts of the book
+            for xml_account in xml.accounts().await.unwrap() {
+                let account = book
+                    .accounts()
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .find(|a| a.guid == xml_account.guid)
+                    .unwrap();
+                for period in 0..12 {
+                    assert_eq!(
+                        result[0].amount(&xml_account, period).await.unwrap(),
+                        budgets[0].amount(&account, period).await.unwrap()
+                    );
+                }
+            }
+
+            let scheduled = book.scheduled_transactions().await.unwrap();
+            let result = xml.scheduled_transactions().await.unwrap();
+            assert_eq!(result.len(), 1);
+            assert_fields!(
+                scheduled[0],
+                result[0],
+                guid,
+                name,
+                enabled,
+                start_date,
+                end_date,
+                last_occur,
+                num_occur,
+                rem_occur,
+                auto_create,
+                auto_notify,
+                advance_create_days,
+                advance_notify_days,
+                instance_count,
+                template_account_guid,
+            );
+            assert_eq!(
+                result[0].recurrences().await.unwrap(),
+                scheduled[0].recurrences().await.unwrap()
+            );
+            assert_eq!(
+                result[0].template_splits().await.unwrap(),
+                scheduled[0].template_splits().await.unwrap()
+            );
+            // the template transaction is not one of the book
+            assert_eq!(
+                xml.transactions().await.unwrap().len(),
+                book.transactions().await.unwrap().len()
+            );
+        }
+
+        #[test(tokio::test)]
+        async fn test_to_xml_business() {
+            let (_dir, book, xml) = setup_objects().await;
+
+            let customers = book.customers().await.unwrap();
+            let result = xml.customers().await.unwrap();
+            assert_eq!(result.len(), 1);
+            assert_fields!(
+                customers[0],
+                result[0],
+                guid,
+                name,
+                id,
+                notes,
+                active,
+                discount,
+                credit_limit,
+                address,
+                ship_address,
+                terms_guid,
+                tax_included,
+                tax_table_override,
+                tax_table_guid,
+            );
+            // XML files name currencies by their mnemonic
+            assert_eq!(result[0].currency_guid, "EUR");
+
+            let vendors = book.vendors().await.unwrap();
+            let result = xml.vendors().await.unwrap();
+            assert_eq!(result.len(), 1);
+            assert_fields!(
+                vendors[0],
+                result[0],
+                guid,
+                name,
+                id,
+                notes,
+                active,
+                address,
+                terms_guid,
+                tax_included,
+                tax_table_override,
+                tax_table_guid,
+            );
+
+            let employees = book.employees().await.unwrap();
+            let result = xml.employees().await.unwrap();
+            assert_eq!(result.len(), 1);
+            assert_fields!(
+                employees[0],
+                result[0],
+                guid,
+                username,
+                id,
+                language,
+                acl,
+                active,
+                credit_card_guid,
+                workday,
+                rate,
+                address,
+            );
+
+            let jobs = book.jobs().await.unwrap();
+            let result = xml.jobs().await.unwrap();
+            assert_eq!(result.len(), 1);
+            assert_fields!(
+                jobs[0], result[0], guid, id, name, reference, active, owner_guid
+            );
+            assert!(matches!(
+                result[0].owner().await.unwrap(),
+                Owner::Customer(_)
+            ));
+
+            let orders = book.orders().await.unwrap();
+            let result = xml.orders().await.unwrap();
+            assert_eq!(result.len(), 1);
+            assert_fields!(
+                orders[0],
+                result[0],
+                guid,
+                id,
+                notes,
+                reference,
+                active,
+                date_opened,
+                date_closed,
+                owner_guid,
+            );
+            assert!(matches!(result[0].owner().await.unwrap(), Owner::Job(_)));
+        }
+
+        #[test(tokio::test)]
+        async fn test_to_xml_invoices() {
+            let (_dir, book, xml) = setup_objects().await;
+
+            let mut invoices = book.invoices().await.unwrap();
+            invoices.sort_by(|a, b| a.guid.cmp(&b.guid));
+            let mut result = xml.invoices().await.unwrap();
+            result.sort_by(|a, b| a.guid.cmp(&b.guid));
+            assert_eq!(result.len(), 3);
+            for (i, r) in invoices.iter().zip(&result) {
+                assert_fields!(
+                    i,
+                    r,
+                    guid,
+                    id,
+                    date_opened,
+                    date_posted,
+                    notes,
+                    active,
+                    owner_guid,
+                    terms_guid,
+                    billing_id,
+                    post_transaction_guid,
+                    post_lot_guid,
+                    post_account_guid,
+                    charge_amount,
+                );
+                assert_eq!(
+                    r.owner().await.unwrap().guid(),
+                    i.owner().await.unwrap().guid()
+                );
+                let (due, expected) =
+                    (r.amount_due().await.unwrap(), i.amount_due().await.unwrap());
+                #[cfg(not(feature = "decimal"))]
+                assert_approx_eq!(f64, due, expected);
+                #[cfg(feature = "decimal")]
+                assert_eq!(due, expected);
+
+                let entries = i.entries().await.unwrap();
+                let entries_result = r.entries().await.unwrap();
+                assert_eq!(entries_result.len(), entries.len());
+                for (e, r) in entries.iter().zip(&entries_result) {
+                    assert_fields!(
+                        e,
+                        r,
+                        guid,
+                        date,
+                        date_entered,
+                        description,
+                        action,
+                        notes,
+                        quantity,
+                        invoice_guid,
+                        i_account_guid,
+                        i_price,
+                        i_discount,
+                        i_discount_type,
+                        i_discount_how,
+                        i_taxable,
+                        i_tax_included,
+                        i_tax_table_guid,
+                        bill_guid,
+                        b_account_guid,
+                        b_price,
+                        b_taxable,
+                        b_tax_included,
+                        b_tax_table_guid,
+                        b_paid_by_card,
+                        billable,
+                        order_guid,
+                    );
+                }
+            }
+
+            let mut terms = book.bill_terms().await.unwrap();
+            terms.sort_by(|a, b| a.guid.cmp(&b.guid));
+            let mut result = xml.bill_terms().await.unwrap();
+            result.sort_by(|a, b| a.guid.cmp(&b.guid));
+            assert_eq!(result, terms);
+
+            let mut tables = book.tax_tables().await.unwrap();
+            tables.sort_by(|a, b| a.guid.cmp(&b.guid));
+            let mut result = xml.tax_tables().await.unwrap();
+            result.sort_by(|a, b| a.guid.cmp(&b.guid));
+            assert_eq!(result.len(), 2);
+            for (t, r) in tables.iter().zip(&result) {
+                assert_fields!(t, r, guid, name, refcount, invisible, parent_guid);
+                let mut entries = t.entries().await.unwrap();
+                entries.sort_by(|a, b| a.account_guid.cmp(&b.account_guid));
+                let mut entries_result = r.entries().await.unwrap();
+                entries_result.sort_by(|a, b| a.account_guid.cmp(&b.account_guid));
+                assert_eq!(entries_result.len(), entries.len());
+                for (e, r) in entries.iter().zip(&entries_result) {
+                    assert_fields!(e, r, taxtable_guid, account_guid, amount, r#type);
+                }
+            }
+        }
+
+        async fn find<Q: Query>(book: &Book<Q>, guid: &str) -> Transaction<Q> {
+            book.transactions()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == guid)
+                .unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_to_xml_voided() {
+            let (dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let guid = "6c8876003c4a6026e38e3afb67d6f2b1";
+            let transaction = find(&book, guid).await;
+            transaction.void("Entered twice").await.unwrap();
+
+            let path = dir.path().join("voided.gnucash");
+            to_xml(&book, &path).await.unwrap();
+            let xml = Book::new(XMLQuery::new(path.to_str().unwrap()).unwrap())
+                .await
+                .unwrap();
+
+            let result = find(&xml, guid).await;
+            assert_eq!(
+                result.slots().await.unwrap(),
+                transaction.slots().await.unwrap()
+            );
+            assert_eq!(
+                result.void_reason().await.unwrap().as_deref(),
+                Some("Entered twice")
+            );
+            for split in result.splits().await.unwrap() {
+                assert_eq!(split.reconcile_status, ReconcileState::Voided);
+                // the former amounts are kept in split slots
+                assert_eq!(split.slots().await.unwrap().len(), 2);
+            }
+        }
+    }
+}
@@ -8,6 +8,20 @@ pub enum Error {
     NameMultipleFound { model: String, name: String },
     #[error("Exchange graph not available")]
     NoExchangeGraph,
+    #[error("Book is opened read-only")]
+    ReadOnly,
+    #[error("Book is locked by {hostname} (PID {pid})")]
+    Locked { hostname: String, pid: i64 },
+    #[error("Transaction does not balance: splits sum to {0}")]
+    Unbalanced(crate::Num),
+    #[error("Invalid {model}: {reason}")]
+    Invalid { model: String, reason: String },
+    #[error("Unsupported {table} table version {version}, expected at most {supported}")]
+    UnsupportedVersion {
+        table: String,
+        version: i64,
+        supported: i64,
+    },
     #[error("Internal system error: {0}")]
     Internal(String),
 
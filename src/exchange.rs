@@ -11,8 +11,9 @@ use crate::model::{Commodity, Price};
 use crate::query::{CommodityQ, PriceQ, Query};
 
 /// Adjacency list representing the exchange graph.
-/// The structure is: Map<From, Map<To, (Rate, Timestamp)>>
-type Graph = HashMap<String, HashMap<String, (crate::Num, NaiveDateTime)>>;
+/// The structure is: Map<From, Map<To, Vec<(Rate, Timestamp)>>>, each edge's
+/// history sorted by timestamp.
+type Graph = HashMap<String, HashMap<String, Vec<(crate::Num, NaiveDateTime)>>>;
 
 /// The `Exchange` struct manages currency conversions by maintaining a
 /// directed graph of commodity prices and their historical timestamps.
@@ -43,9 +44,8 @@ impl Exchange {
     where
         Q: Query,
     {
-        /// Helper function to update or insert an edge in the graph.
-        /// It only updates the edge if the new price entry has a more recent timestamp.
-        fn upsert_edge(
+        /// Helper function to add a price entry to the history of an edge in the graph.
+        fn push_edge(
             graph: &mut Graph,
             from: &str,
             to: &str,
@@ -56,21 +56,8 @@ impl Exchange {
                 .entry(from.to_string())
                 .or_default()
                 .entry(to.to_string())
-                .and_modify(|e| {
-                    if e.1 < date {
-                        tracing::debug!(
-                            from = from,
-                            to = to,
-                            old_date = %e.1,
-                            new_date = %date,
-                            old_rate = ?e.0,
-                            new_rate = ?rate,
-                            "updating edge to newer entry"
-                        );
-                        *e = (rate, date);
-                    }
-                })
-                .or_insert((rate, date));
+                .or_default()
+                .push((rate, date));
         }
 
         tracing::debug!("building exchange graph from prices and commodities");
@@ -129,10 +116,10 @@ impl Exchange {
             }
 
             // Insert forward edge: commodity -> currency
-            upsert_edge(&mut graph, commodity, currency, p.value, p.datetime);
+            push_edge(&mut graph, commodity, currency, p.value, p.datetime);
 
             // Insert reverse edge: currency -> commodity (reciprocal rate)
-            upsert_edge(
+            push_edge(
                 &mut graph,
                 currency,
                 commodity,
@@ -141,6 +128,13 @@ impl Exchange {
             );
         }
 
+        // The sort is stable, so of two prices with the same timestamp the first
+        // one fetched stays, as it did when an edge only kept its latest price.
+        for history in graph.values_mut().flat_map(HashMap::values_mut) {
+            history.sort_by_key(|(_, date)| *date);
+            history.dedup_by_key(|(_, date)| *date);
+        }
+
         tracing::info!(
             graph_nodes = graph.len(),
             total_edges = graph.values().map(HashMap::len).sum::<usize>(),
@@ -162,6 +156,24 @@ impl Exchange {
         commodity: &Commodity<Q>,
         currency: &Commodity<Q>,
     ) -> Option<crate::Num>
+    where
+        Q: Query,
+    {
+        self.calculate_at(commodity, currency, None)
+    }
+
+    /// Calculates the exchange rate from the prices dated before `until`,
+    /// or from the latest ones when it is `None`.
+    #[instrument(skip(self, commodity, currency), fields(
+        commodity = %commodity.mnemonic,
+        currency = %currency.mnemonic
+    ))]
+    pub(crate) fn calculate_at<Q>(
+        &self,
+        commodity: &Commodity<Q>,
+        currency: &Commodity<Q>,
+        until: Option<NaiveDateTime>,
+    ) -> Option<crate::Num>
     where
         Q: Query,
     {
@@ -212,12 +224,20 @@ impl Exchange {
 
             // Explore neighbors
             if let Some(neighbors) = self.graph.get(&node) {
-                for (next, (edge_rate, edge_date)) in neighbors {
+                for (next, history) in neighbors {
+                    // The latest entry of the edge's history known by `until`
+                    let known = until.map_or(history.len(), |until| {
+                        history.partition_point(|(_, date)| *date < until)
+                    });
+                    let Some((edge_rate, priced_at)) = known.checked_sub(1).map(|i| &history[i])
+                    else {
+                        continue;
+                    };
                     if !visited.contains(next) {
                         heap.push(ExchangePath {
                             node: next.clone(),
                             rate: rate * edge_rate,
-                            oldest_edge_date: oldest_edge_date.min(*edge_date),
+                            oldest_edge_date: oldest_edge_date.min(*priced_at),
                             hop_count: hop_count + 1,
                         });
                     }
@@ -492,6 +512,38 @@ mod tests {
                 exchange.calculate(&from, &to).unwrap()
             );
         }
+
+        #[test(tokio::test)]
+        async fn test_exchange_at() {
+            let query = setup().await;
+            let book = Book::new(query.clone()).await.unwrap();
+            let until = Some(
+                NaiveDateTime::parse_from_str("2018-01-01 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            );
+            let exchange = Exchange::new(Arc::new(query)).await.unwrap();
+
+            let commodities = book.commodities().await.unwrap();
+            let find = |x| commodities.iter().find(|c| c.mnemonic == x).unwrap();
+
+            // only the 2017 price of AED in EUR is known by then
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(
+                f64,
+                2.0,
+                exchange
+                    .calculate_at(find("EUR"), find("AED"), until)
+                    .unwrap()
+            );
+            #[cfg(feature = "decimal")]
+            assert_eq!(
+                Decimal::new(2, 0),
+                exchange
+                    .calculate_at(find("EUR"), find("AED"), until)
+                    .unwrap()
+            );
+            assert_eq!(exchange.calculate_at(find("FOO"), find("EUR"), until), None);
+            assert!(exchange.calculate(find("FOO"), find("EUR")).is_some());
+        }
     }
 
     #[cfg(feature = "mysql")]
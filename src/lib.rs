@@ -29,7 +29,10 @@ mod error;
 mod exchange;
 mod query;
 
+pub mod builder;
+pub mod convert;
 pub mod model;
+pub mod reports;
 
 #[cfg(not(feature = "decimal"))]
 pub type Num = f64;
@@ -1,11 +1,56 @@
 mod account;
+mod account_tree;
+mod account_type;
+mod address;
+pub(crate) mod aging;
+mod billterm;
+pub(crate) mod book_info;
+mod budget;
 mod commodity;
+mod customer;
+mod employee;
+mod entry;
+mod invoice;
+mod job;
+mod lock;
+mod lot;
+mod order;
+mod owner;
 mod price;
+mod reconciliation;
+mod recurrence;
+mod scheduled_transaction;
+pub(crate) mod slot;
 mod split;
+mod taxtable;
 mod transaction;
+mod vendor;
 
 pub use account::Account;
+pub(crate) use account::{check_name, check_scu, day_after, types_compatible};
+pub use account_tree::AccountTree;
+pub use account_type::AccountType;
+pub use address::Address;
+pub use aging::{AgingBuckets, AgingKind, AgingLine};
+pub use billterm::{BillTerm, BillTermType};
+pub use book_info::BookInfo;
+pub use budget::{Budget, BudgetLine};
 pub use commodity::Commodity;
+pub use customer::{Customer, TaxIncluded};
+pub use employee::Employee;
+pub use entry::{DiscountHow, DiscountType, Entry};
+pub use invoice::Invoice;
+pub use job::Job;
+pub use lock::Lock;
+pub use lot::Lot;
+pub use order::Order;
+pub use owner::Owner;
 pub use price::Price;
-pub use split::Split;
-pub use transaction::Transaction;
+pub use reconciliation::Reconciliation;
+pub use recurrence::{PeriodType, Recurrence, WeekendAdjust};
+pub use scheduled_transaction::{Occurrence, ScheduledTransaction, TemplateSplit};
+pub use slot::Slot;
+pub use split::{ReconcileState, Split};
+pub use taxtable::{AmountType, TaxTable, TaxTableEntry};
+pub use transaction::{OriginalAmount, Transaction};
+pub use vendor::Vendor;
@@ -1,11 +1,16 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use tracing::instrument;
 
 use crate::Book;
 use crate::error::Error;
-use crate::model::{Commodity, Split};
-use crate::query::{AccountQ, AccountT, CommodityQ, Query, SplitQ};
+use crate::model::slot::{self, Slot};
+use crate::model::{AccountType, Commodity, Lot, Reconciliation, Split};
+use crate::query::{
+    AccountMutQ, AccountQ, AccountRecord, AccountT, CommodityQ, LotQ, Query, SplitQ,
+};
 
 #[derive(Clone, Debug)]
 pub struct Account<Q>
@@ -16,7 +21,7 @@ where
 
     pub guid: String,
     pub name: String,
-    pub r#type: String,
+    pub r#type: AccountType,
     pub commodity_guid: String,
     pub commodity_scu: i64,
     pub non_std_scu: bool,
@@ -37,7 +42,7 @@ where
 
             guid: item.guid().to_string(),
             name: item.name().to_string(),
-            r#type: item.account_type().to_string(),
+            r#type: item.account_type(),
             commodity_guid: item.commodity_guid().to_string(),
             commodity_scu: item.commodity_scu(),
             non_std_scu: item.non_std_scu(),
@@ -63,6 +68,20 @@ where
         Ok(result)
     }
 
+    #[instrument(skip(self), fields(account_guid = %self.guid))]
+    pub async fn lots(&self) -> Result<Vec<Lot<Q>>, Error> {
+        tracing::debug!("fetching lots for account");
+        let lots = LotQ::account(&*self.query, &self.guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch lots: {e}"))?;
+        let result: Vec<_> = lots
+            .into_iter()
+            .map(|x| Lot::from_with_query(&x, self.query.clone()))
+            .collect();
+        tracing::debug!(count = result.len(), "lots fetched for account");
+        Ok(result)
+    }
+
     #[instrument(skip(self), fields(account_guid = %self.guid, parent_guid = %self.parent_guid))]
     pub async fn parent(&self) -> Result<Option<Account<Q>>, Error> {
         if self.parent_guid.is_empty() {
@@ -124,22 +143,77 @@ where
         }
     }
 
+    /// All key-value slots of the account, e.g. `placeholder` or `color`.
+    #[instrument(skip(self), fields(account_guid = %self.guid))]
+    pub async fn slots(&self) -> Result<BTreeMap<String, Slot>, Error> {
+        slot::load(&*self.query, &self.guid).await
+    }
+
+    /// The slot at a `/`-separated `path`, `None` if it is not set.
+    #[instrument(skip(self), fields(account_guid = %self.guid))]
+    pub async fn slot(&self, path: &str) -> Result<Option<Slot>, Error> {
+        slot::find(&*self.query, &self.guid, path).await
+    }
+
+    /// The splits of the account whose transaction is posted in `[from, to)`.
+    pub(crate) async fn splits_between(
+        &self,
+        from: Option<NaiveDateTime>,
+        to: Option<NaiveDateTime>,
+    ) -> Result<Vec<Split<Q>>, Error> {
+        Ok(self
+            .dated_splits_between(from, to)
+            .await?
+            .into_iter()
+            .map(|(_, x)| x)
+            .collect())
+    }
+
+    /// As [`Account::splits_between`], with the post date of each split's
+    /// transaction, oldest first.
+    #[instrument(skip(self), fields(account_guid = %self.guid))]
+    pub(crate) async fn dated_splits_between(
+        &self,
+        from: Option<NaiveDateTime>,
+        to: Option<NaiveDateTime>,
+    ) -> Result<Vec<(NaiveDateTime, Split<Q>)>, Error> {
+        tracing::debug!("fetching splits for account by post date");
+        let splits = SplitQ::account_between(&*self.query, &self.guid, from, to)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch splits: {e}"))?;
+        Ok(splits
+            .into_iter()
+            .map(|(date, x)| (date, Split::from_with_query(&x, self.query.clone())))
+            .collect())
+    }
+
+    async fn balance_into_currency<'a>(
+        &'a self,
+        currency: &'a Commodity<Q>,
+        book: &'a Book<Q>,
+    ) -> Result<crate::Num, Error> {
+        self.balance_into_currency_between(currency, book, None, None, None)
+            .await
+    }
+
+    /// The net of the splits posted in `[from, to)`, sub-accounts included, converted
+    /// with the prices dated before `rate_until`, or the latest ones when it is `None`.
     #[instrument(skip(self, currency, book), fields(
         account_guid = %self.guid,
         account_name = %self.name,
         currency_mnemonic = %currency.mnemonic
     ))]
-    async fn balance_into_currency<'a>(
+    async fn balance_into_currency_between<'a>(
         &'a self,
         currency: &'a Commodity<Q>,
         book: &'a Book<Q>,
+        from: Option<NaiveDateTime>,
+        to: Option<NaiveDateTime>,
+        rate_until: Option<NaiveDateTime>,
     ) -> Result<crate::Num, Error> {
         tracing::debug!("calculating balance into currency");
 
-        let splits = self
-            .splits()
-            .await
-            .inspect_err(|e| tracing::error!("failed to fetch splits: {e}"))?;
+        let splits = self.splits_between(from, to).await?;
         let mut net: crate::Num = splits.iter().map(|s| s.quantity).sum();
 
         tracing::debug!(
@@ -163,20 +237,25 @@ where
         );
 
         for child in children {
-            let child_net = Box::pin(child.balance_into_currency(&commodity, book))
+            let child_net = Box::pin(child.balance_into_currency_between(&commodity, book, from, to, rate_until))
                 .await
                 .inspect_err(|e| tracing::error!(child_account = %child.name, "failed to calculate child balance: {e}"))?;
             net += child_net;
         }
 
-        let rate = commodity.sell(currency, book).await.ok_or_else(|| {
-            tracing::error!(
-                from = %commodity.mnemonic,
-                to = %currency.mnemonic,
-                "No exchange rate available for currency conversion"
-            );
-            Error::NoExchangeGraph
-        })?;
+        let rate = book
+            .exchange_graph
+            .lock()
+            .await
+            .calculate_at(&commodity, currency, rate_until)
+            .ok_or_else(|| {
+                tracing::error!(
+                    from = %commodity.mnemonic,
+                    to = %currency.mnemonic,
+                    "No exchange rate available for currency conversion"
+                );
+                Error::NoExchangeGraph
+            })?;
 
         let result = net * rate;
         tracing::debug!(?result, ?rate, "balance calculated in currency");
@@ -188,6 +267,318 @@ where
         let commodity = self.commodity().await?;
         self.balance_into_currency(&commodity, book).await
     }
+
+    /// The balance at the end of `date`, sub-accounts included, in the commodity of the account.
+    ///
+    /// Only transactions posted on or before `date` count; sub-accounts in other
+    /// commodities are converted at the latest rate known at the end of `date`.
+    #[instrument(skip(self, book), fields(account_guid = %self.guid, account_name = %self.name))]
+    pub async fn balance_at(&self, book: &Book<Q>, date: NaiveDate) -> Result<crate::Num, Error> {
+        let commodity = self.commodity().await?;
+        let until = Some(day_after(date)?);
+        self.balance_into_currency_between(&commodity, book, None, until, until)
+            .await
+    }
+
+    /// The change in balance over `from` to `to`, both days included, sub-accounts included.
+    ///
+    /// Sub-accounts in other commodities are converted at the latest rate known at the end of `to`.
+    #[instrument(skip(self, book), fields(account_guid = %self.guid, account_name = %self.name))]
+    pub async fn balance_between(
+        &self,
+        book: &Book<Q>,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<crate::Num, Error> {
+        let commodity = self.commodity().await?;
+        let until = Some(day_after(to)?);
+        self.balance_into_currency_between(
+            &commodity,
+            book,
+            Some(from.and_time(NaiveTime::MIN)),
+            until,
+            until,
+        )
+        .await
+    }
+
+    /// The balance of the account's own splits at the end of `date`, leaving out sub-accounts.
+    #[instrument(skip(self), fields(account_guid = %self.guid, account_name = %self.name))]
+    pub async fn own_balance_at(&self, date: NaiveDate) -> Result<crate::Num, Error> {
+        let splits = self.splits_between(None, Some(day_after(date)?)).await?;
+        Ok(splits.iter().map(|s| s.quantity).sum())
+    }
+
+    /// The change in the account's own splits over `from` to `to`, both days included.
+    #[instrument(skip(self), fields(account_guid = %self.guid, account_name = %self.name))]
+    pub async fn own_balance_between(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<crate::Num, Error> {
+        let splits = self
+            .splits_between(Some(from.and_time(NaiveTime::MIN)), Some(day_after(to)?))
+            .await?;
+        Ok(splits.iter().map(|s| s.quantity).sum())
+    }
+
+    /// Starts reconciling the account against a statement ending on `statement_date`
+    /// with `ending_balance`, in the commodity of the account.
+    #[instrument(skip(self), fields(account_guid = %self.guid, account_name = %self.name))]
+    pub async fn reconcile(
+        &self,
+        statement_date: chrono::NaiveDate,
+        ending_balance: crate::Num,
+    ) -> Result<Reconciliation<Q>, Error> {
+        Reconciliation::new(self, self.query.clone(), statement_date, ending_balance).await
+    }
+
+    /// Checks that an account of `account_type` holding `commodity` may live below `self`.
+    ///
+    /// Apart from `STOCK`, `MUTUAL` and `TRADING` accounts, an account holding a
+    /// non-currency commodity must share the commodity of its parent.
+    pub(crate) fn check_child(
+        &self,
+        account_type: &AccountType,
+        commodity: &Commodity<Q>,
+    ) -> Result<(), Error> {
+        if !types_compatible(&self.r#type, account_type) {
+            return Err(invalid(format!(
+                "{account_type} account cannot be placed under {} account {}",
+                self.r#type, self.name
+            )));
+        }
+        if !(account_type.is_investment() || *account_type == AccountType::Trading)
+            && commodity.namespace != "CURRENCY"
+            && commodity.guid != self.commodity_guid
+        {
+            return Err(invalid(format!(
+                "{account_type} account holding {} must share the commodity of {}",
+                commodity.mnemonic, self.name
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Account types which may only hang below a parent of a compatible type,
+/// following the grouping `GnuCash` uses in its account dialog.
+pub(crate) fn types_compatible(parent: &AccountType, child: &AccountType) -> bool {
+    let balance = |x: &AccountType| x.is_asset_like() || x.is_liability_like();
+
+    match parent {
+        AccountType::Root => *child != AccountType::Root,
+        p if balance(p) => balance(child),
+        p if p.is_income_statement() => child.is_income_statement(),
+        AccountType::Equity => *child == AccountType::Equity,
+        AccountType::Trading => *child == AccountType::Trading,
+        _ => false,
+    }
+}
+
+/// Checks the name of an account placed below `parent_name`, whose other children
+/// are named `siblings`.
+pub(crate) fn check_name<'a>(
+    name: &str,
+    parent_name: &str,
+    mut siblings: impl Iterator<Item = &'a str>,
+) -> Result<(), Error> {
+    if name.is_empty() {
+        return Err(invalid("name must not be empty"));
+    }
+    if name.contains(':') {
+        return Err(invalid(format!(
+            "name {name} must not contain the separator ':'"
+        )));
+    }
+    if siblings.any(|x| x == name) {
+        return Err(invalid(format!(
+            "{parent_name} already has a child named {name}"
+        )));
+    }
+    Ok(())
+}
+
+/// Checks the smallest fraction of its commodity an account records.
+pub(crate) fn check_scu(commodity_scu: i64) -> Result<(), Error> {
+    if commodity_scu <= 0 {
+        return Err(invalid(format!("SCU {commodity_scu} must be positive")));
+    }
+    Ok(())
+}
+
+/// The first moment after `date`, which bounds a range ending with that day.
+pub(crate) fn day_after(date: NaiveDate) -> Result<NaiveDateTime, Error> {
+    date.succ_opt()
+        .map(|x| x.and_time(NaiveTime::MIN))
+        .ok_or_else(|| invalid(format!("no day follows {date}")))
+}
+
+fn invalid(reason: impl Into<String>) -> Error {
+    Error::Invalid {
+        model: "Account".to_string(),
+        reason: reason.into(),
+    }
+}
+
+impl<Q> Account<Q>
+where
+    Q: Query + AccountMutQ,
+{
+    pub(crate) fn to_record(&self) -> AccountRecord {
+        AccountRecord {
+            guid: self.guid.clone(),
+            name: self.name.clone(),
+            account_type: self.r#type.to_string(),
+            commodity_guid: self.commodity_guid.clone(),
+            commodity_scu: self.commodity_scu,
+            non_std_scu: self.non_std_scu,
+            parent_guid: self.parent_guid.clone(),
+            code: self.code.clone(),
+            description: self.description.clone(),
+            hidden: self.hidden,
+            placeholder: self.placeholder,
+        }
+    }
+
+    async fn check_child_name(&self, name: &str, exclude_guid: &str) -> Result<(), Error> {
+        let children = AccountQ::parent(&*self.query, &self.guid).await?;
+        check_name(
+            name,
+            &self.name,
+            children
+                .iter()
+                .filter(|x| x.guid() != exclude_guid)
+                .map(AccountT::name),
+        )
+    }
+
+    async fn update(&self) -> Result<(), Error> {
+        AccountMutQ::update(&*self.query, &self.to_record())
+            .await
+            .inspect_err(|e| tracing::error!("failed to update account: {e}"))
+    }
+
+    /// Creates a new account below `self`.
+    ///
+    /// `commodity_scu` is the smallest fraction of `commodity` the account records,
+    /// usually `commodity.fraction`.
+    #[instrument(skip(self, commodity), fields(parent_guid = %self.guid, commodity_guid = %commodity.guid))]
+    pub async fn create_child(
+        &self,
+        name: &str,
+        account_type: &AccountType,
+        commodity: &Commodity<Q>,
+        commodity_scu: i64,
+    ) -> Result<Account<Q>, Error> {
+        tracing::debug!("creating child account");
+
+        check_scu(commodity_scu)?;
+        self.check_child_name(name, "").await?;
+        self.check_child(account_type, commodity)?;
+
+        let record = AccountRecord {
+            guid: crate::builder::new_guid(),
+            name: name.to_string(),
+            account_type: account_type.as_str().to_string(),
+            commodity_guid: commodity.guid.clone(),
+            commodity_scu,
+            non_std_scu: commodity_scu != commodity.fraction,
+            parent_guid: self.guid.clone(),
+            code: String::new(),
+            description: String::new(),
+            hidden: false,
+            placeholder: false,
+        };
+        AccountMutQ::insert(&*self.query, &record)
+            .await
+            .inspect_err(|e| tracing::error!("failed to insert account: {e}"))?;
+
+        let item = AccountQ::guid(&*self.query, &record.guid)
+            .await?
+            .ok_or_else(|| Error::GuidNotFound {
+                model: "Account".to_string(),
+                guid: record.guid.clone(),
+            })?;
+
+        tracing::info!(account_guid = %record.guid, "account created");
+        Ok(Account::from_with_query(&item, self.query.clone()))
+    }
+
+    #[instrument(skip(self), fields(account_guid = %self.guid, account_name = %self.name))]
+    pub async fn rename(&mut self, name: &str) -> Result<(), Error> {
+        if self.r#type == AccountType::Root {
+            return Err(invalid("the root account cannot be renamed"));
+        }
+        if let Some(parent) = self.parent().await? {
+            parent.check_child_name(name, &self.guid).await?;
+        }
+
+        let old = std::mem::replace(&mut self.name, name.to_string());
+        self.update().await.inspect_err(|_| self.name = old)?;
+
+        tracing::info!("account renamed");
+        Ok(())
+    }
+
+    /// Moves the account, with all its descendants, below `parent`.
+    #[instrument(skip(self, parent), fields(account_guid = %self.guid, parent_guid = %parent.guid))]
+    pub async fn reparent(&mut self, parent: &Account<Q>) -> Result<(), Error> {
+        if self.r#type == AccountType::Root {
+            return Err(invalid("the root account cannot be moved"));
+        }
+
+        let mut ancestor = Some(parent.clone());
+        while let Some(a) = ancestor {
+            if a.guid == self.guid {
+                return Err(invalid(format!(
+                    "{} cannot be moved below itself or its descendant {}",
+                    self.name, parent.name
+                )));
+            }
+            ancestor = a.parent().await?;
+        }
+
+        parent.check_child_name(&self.name, &self.guid).await?;
+        let commodity = self.commodity().await?;
+        parent.check_child(&self.r#type, &commodity)?;
+
+        let old = std::mem::replace(&mut self.parent_guid, parent.guid.clone());
+        self.update()
+            .await
+            .inspect_err(|_| self.parent_guid = old)?;
+
+        tracing::info!("account moved");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(account_guid = %self.guid))]
+    pub async fn set_hidden(&mut self, hidden: bool) -> Result<(), Error> {
+        let old = std::mem::replace(&mut self.hidden, hidden);
+        self.update().await.inspect_err(|_| self.hidden = old)
+    }
+
+    #[instrument(skip(self), fields(account_guid = %self.guid))]
+    pub async fn set_placeholder(&mut self, placeholder: bool) -> Result<(), Error> {
+        let old = std::mem::replace(&mut self.placeholder, placeholder);
+        self.update().await.inspect_err(|_| self.placeholder = old)
+    }
+
+    /// Deletes the account. Only accounts without splits, children, lots and budget amounts
+    /// can be deleted, and never the root account.
+    #[instrument(skip(self), fields(account_guid = %self.guid, account_name = %self.name))]
+    pub async fn delete(self) -> Result<(), Error> {
+        if self.r#type == AccountType::Root || self.parent_guid.is_empty() {
+            return Err(invalid("the root account cannot be deleted"));
+        }
+
+        AccountMutQ::delete(&*self.query, &self.guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to delete account: {e}"))?;
+
+        tracing::info!("account deleted");
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -206,6 +597,7 @@ mod tests {
 
         use crate::SQLiteQuery;
         use crate::query::sqlite::account::Account as AccountBase;
+        use crate::query::sqlite::tests::{setup_with, setup_writable};
 
         use super::*;
 
@@ -241,7 +633,10 @@ mod tests {
 
             assert_eq!(result.guid, "guid");
             assert_eq!(result.name, "name");
-            assert_eq!(result.r#type, "account_type");
+            assert_eq!(
+                result.r#type,
+                AccountType::Other("account_type".to_string())
+            );
             assert_eq!(result.commodity_guid, "commodity_guid");
             assert_eq!(result.commodity_scu, 100);
             assert_eq!(result.non_std_scu, false);
@@ -350,6 +745,27 @@ mod tests {
             assert_eq!(commodity.mnemonic, "EUR");
         }
 
+        #[test(tokio::test)]
+        async fn test_slots() {
+            let query = setup().await;
+            let book = Book::new(query).await.unwrap();
+            let account = book
+                .account_contains_name_ignore_case("Asset")
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                account.slot("placeholder").await.unwrap(),
+                Some(Slot::String("true".to_string()))
+            );
+            let account = book
+                .account_contains_name_ignore_case("Cash")
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(account.slots().await.unwrap().is_empty());
+        }
+
         #[test(tokio::test)]
         async fn test_balance_into_currency() {
             let query = setup().await;
@@ -395,6 +811,244 @@ mod tests {
             #[cfg(feature = "decimal")]
             assert_eq!(account.balance(&book).await.unwrap(), Decimal::new(4590, 0));
         }
+
+        #[test(tokio::test)]
+        async fn test_balance_at() {
+            let query = setup().await;
+            let book = Book::new(query).await.unwrap();
+            let account = book
+                .account_contains_name_ignore_case("Current")
+                .await
+                .unwrap()
+                .unwrap();
+            let checking = book
+                .account_contains_name_ignore_case("Checking")
+                .await
+                .unwrap()
+                .unwrap();
+            let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+            let at = account.balance_at(&book, date(2014, 12, 31)).await.unwrap();
+            let day = account
+                .balance_between(&book, date(2018, 2, 21), date(2018, 2, 21))
+                .await
+                .unwrap();
+            let own_at = checking.own_balance_at(date(2014, 12, 24)).await.unwrap();
+            let own_between = checking
+                .own_balance_between(date(2015, 1, 1), date(2018, 12, 31))
+                .await
+                .unwrap();
+            let parent_own = account.own_balance_at(date(2018, 12, 31)).await.unwrap();
+
+            #[cfg(not(feature = "decimal"))]
+            {
+                assert_approx_eq!(f64, at, 820.0);
+                assert_approx_eq!(f64, day, 1300.0);
+                assert_approx_eq!(f64, own_at, 670.0);
+                assert_approx_eq!(f64, own_between, 150.0);
+                assert_approx_eq!(f64, parent_own, 0.0);
+            }
+            #[cfg(feature = "decimal")]
+            {
+                assert_eq!(at, Decimal::new(820, 0));
+                assert_eq!(day, Decimal::new(1300, 0));
+                assert_eq!(own_at, Decimal::new(670, 0));
+                assert_eq!(own_between, Decimal::new(150, 0));
+                assert_eq!(parent_own, Decimal::ZERO);
+            }
+        }
+
+        #[test(tokio::test)]
+        async fn test_balance_at_dated_rate() {
+            // FOO is worth 0.9 AED from 2018-02-20 and 2 AED from 2018-03-01
+            let (_dir, query) = setup_with(&["
+                INSERT INTO prices (guid, commodity_guid, currency_guid, date, source, type,
+                                    value_num, value_denom)
+                VALUES ('5d1e2f3a4b5c6d7e8f9011a2b3c4d5e6', '069410ffec45a41a218bba474093d466',
+                        '5f586908098232e67edb1371408bfaa8', '2018-03-01 10:59:00',
+                        'user:price-editor', 'unknown', 2, 1);
+            "]);
+            let book = Book::new(query).await.unwrap();
+            let broker = account(&book, "Broker").await;
+            let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+            // 130 FOO bought on 2018-02-21
+            let at = broker.balance_at(&book, date(2018, 2, 28)).await.unwrap();
+            let between = broker
+                .balance_between(&book, date(2018, 2, 1), date(2018, 2, 28))
+                .await
+                .unwrap();
+            let latest = broker.balance(&book).await.unwrap();
+
+            #[cfg(not(feature = "decimal"))]
+            {
+                assert_approx_eq!(f64, at, 117.0);
+                assert_approx_eq!(f64, between, 117.0);
+                assert_approx_eq!(f64, latest, 260.0);
+            }
+            #[cfg(feature = "decimal")]
+            {
+                assert_eq!(at, Decimal::new(117, 0));
+                assert_eq!(between, Decimal::new(117, 0));
+                assert_eq!(latest, Decimal::new(260, 0));
+            }
+        }
+
+        async fn account(book: &Book<SQLiteQuery>, name: &str) -> Account<SQLiteQuery> {
+            book.accounts()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.name == name)
+                .unwrap()
+        }
+
+        async fn commodity(book: &Book<SQLiteQuery>, mnemonic: &str) -> Commodity<SQLiteQuery> {
+            book.commodities()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.mnemonic == mnemonic)
+                .unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_create_child() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let current = account(&book, "Current").await;
+            let eur = commodity(&book, "EUR").await;
+
+            let wallet = current
+                .create_child("Wallet", &AccountType::Cash, &eur, 100)
+                .await
+                .unwrap();
+            assert_eq!(wallet.name, "Wallet");
+            assert_eq!(wallet.r#type, AccountType::Cash);
+            assert_eq!(wallet.commodity_guid, eur.guid);
+            assert_eq!(wallet.commodity_scu, 100);
+            assert_eq!(wallet.non_std_scu, false);
+            assert_eq!(wallet.parent_guid, current.guid);
+            assert_eq!(current.children().await.unwrap().len(), 4);
+            assert_eq!(book.accounts().await.unwrap().len(), 22);
+        }
+
+        #[test(tokio::test)]
+        async fn test_create_child_invalid() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let current = account(&book, "Current").await;
+            let eur = commodity(&book, "EUR").await;
+            let foo = commodity(&book, "FOO").await;
+
+            for (name, account_type, commodity) in [
+                ("Cash", AccountType::Cash, &eur),
+                ("", AccountType::Cash, &eur),
+                ("A:B", AccountType::Cash, &eur),
+                ("Salary", AccountType::Income, &eur),
+                ("Shares", AccountType::Asset, &foo),
+                ("Everything", AccountType::Root, &eur),
+            ] {
+                let result = current
+                    .create_child(name, &account_type, commodity, 100)
+                    .await;
+                assert!(
+                    matches!(result, Err(Error::Invalid { .. })),
+                    "Expected Invalid error for {name}, but got: {result:?}"
+                );
+            }
+            assert_eq!(book.accounts().await.unwrap().len(), 21);
+
+            let broker = account(&book, "Broker").await;
+            broker
+                .create_child("Bar stock", &AccountType::Stock, &foo, 1000)
+                .await
+                .unwrap();
+        }
+
+        #[test(tokio::test)]
+        async fn test_rename() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let mut cash = account(&book, "Cash").await;
+
+            cash.rename("Wallet").await.unwrap();
+            assert_eq!(account(&book, "Wallet").await.guid, cash.guid);
+
+            let result = cash.rename("Savings").await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+            assert_eq!(cash.name, "Wallet");
+        }
+
+        #[test(tokio::test)]
+        async fn test_reparent() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let mut cash = account(&book, "Cash").await;
+            let mut current = account(&book, "Current").await;
+            let fixed = account(&book, "Fixed").await;
+            let expense = account(&book, "Expense").await;
+
+            let result = current.reparent(&cash).await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+            let result = current.clone().reparent(&current).await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+            let result = cash.reparent(&expense).await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+
+            cash.reparent(&fixed).await.unwrap();
+            assert_eq!(cash.parent_guid, fixed.guid);
+            assert_eq!(account(&book, "Cash").await.parent_guid, fixed.guid);
+        }
+
+        #[test(tokio::test)]
+        async fn test_set_hidden_and_placeholder() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let mut cash = account(&book, "Cash").await;
+
+            cash.set_hidden(true).await.unwrap();
+            cash.set_placeholder(true).await.unwrap();
+
+            let cash = account(&book, "Cash").await;
+            assert_eq!(cash.hidden, true);
+            assert_eq!(cash.placeholder, true);
+        }
+
+        #[test(tokio::test)]
+        async fn test_delete() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let root = account(&book, "Root Account").await;
+            let current = account(&book, "Current").await;
+            let cash = account(&book, "Cash").await;
+
+            let result = root.delete().await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+            let result = current.clone().delete().await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+            let result = cash.delete().await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+
+            let eur = commodity(&book, "EUR").await;
+            let wallet = current
+                .create_child("Wallet", &AccountType::Cash, &eur, 100)
+                .await
+                .unwrap();
+            wallet.delete().await.unwrap();
+            assert_eq!(book.accounts().await.unwrap().len(), 21);
+        }
+
+        #[test(tokio::test)]
+        async fn test_mutation_read_only() {
+            let query = setup().await;
+            let book = Book::new(query).await.unwrap();
+            let mut cash = account(&book, "Cash").await;
+
+            let result = cash.rename("Wallet").await;
+            assert!(matches!(result, Err(Error::ReadOnly)));
+            assert_eq!(cash.name, "Cash");
+        }
     }
 
     #[cfg(feature = "mysql")]
@@ -433,7 +1087,10 @@ mod tests {
 
             assert_eq!(result.guid, "guid");
             assert_eq!(result.name, "name");
-            assert_eq!(result.r#type, "account_type");
+            assert_eq!(
+                result.r#type,
+                AccountType::Other("account_type".to_string())
+            );
             assert_eq!(result.commodity_guid, "commodity_guid");
             assert_eq!(result.commodity_scu, 100);
             assert_eq!(result.non_std_scu, false);
@@ -542,6 +1199,27 @@ mod tests {
             assert_eq!(commodity.mnemonic, "EUR");
         }
 
+        #[test(tokio::test)]
+        async fn test_slots() {
+            let query = setup().await;
+            let book = Book::new(query).await.unwrap();
+            let account = book
+                .account_contains_name_ignore_case("Asset")
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                account.slot("placeholder").await.unwrap(),
+                Some(Slot::String("true".to_string()))
+            );
+            let account = book
+                .account_contains_name_ignore_case("Cash")
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(account.slots().await.unwrap().is_empty());
+        }
+
         #[test(tokio::test)]
         async fn test_balance_into_currency() {
             let query = setup().await;
@@ -587,6 +1265,52 @@ mod tests {
             #[cfg(feature = "decimal")]
             assert_eq!(account.balance(&book).await.unwrap(), Decimal::new(4590, 0));
         }
+
+        #[test(tokio::test)]
+        async fn test_balance_at() {
+            let query = setup().await;
+            let book = Book::new(query).await.unwrap();
+            let account = book
+                .account_contains_name_ignore_case("Current")
+                .await
+                .unwrap()
+                .unwrap();
+            let checking = book
+                .account_contains_name_ignore_case("Checking")
+                .await
+                .unwrap()
+                .unwrap();
+            let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+            let at = account.balance_at(&book, date(2014, 12, 31)).await.unwrap();
+            let day = account
+                .balance_between(&book, date(2018, 2, 21), date(2018, 2, 21))
+                .await
+                .unwrap();
+            let own_at = checking.own_balance_at(date(2014, 12, 24)).await.unwrap();
+            let own_between = checking
+                .own_balance_between(date(2015, 1, 1), date(2018, 12, 31))
+                .await
+                .unwrap();
+            let parent_own = account.own_balance_at(date(2018, 12, 31)).await.unwrap();
+
+            #[cfg(not(feature = "decimal"))]
+            {
+                assert_approx_eq!(f64, at, 820.0);
+                assert_approx_eq!(f64, day, 1300.0);
+                assert_approx_eq!(f64, own_at, 670.0);
+                assert_approx_eq!(f64, own_between, 150.0);
+                assert_approx_eq!(f64, parent_own, 0.0);
+            }
+            #[cfg(feature = "decimal")]
+            {
+                assert_eq!(at, Decimal::new(820, 0));
+                assert_eq!(day, Decimal::new(1300, 0));
+                assert_eq!(own_at, Decimal::new(670, 0));
+                assert_eq!(own_between, Decimal::new(150, 0));
+                assert_eq!(parent_own, Decimal::ZERO);
+            }
+        }
     }
 
     #[cfg(feature = "postgresql")]
@@ -625,7 +1349,10 @@ mod tests {
 
             assert_eq!(result.guid, "guid");
             assert_eq!(result.name, "name");
-            assert_eq!(result.r#type, "account_type");
+            assert_eq!(
+                result.r#type,
+                AccountType::Other("account_type".to_string())
+            );
             assert_eq!(result.commodity_guid, "commodity_guid");
             assert_eq!(result.commodity_scu, 100);
             assert_eq!(result.non_std_scu, false);
@@ -734,6 +1461,27 @@ mod tests {
             assert_eq!(commodity.mnemonic, "EUR");
         }
 
+        #[test(tokio::test)]
+        async fn test_slots() {
+            let query = setup().await;
+            let book = Book::new(query).await.unwrap();
+            let account = book
+                .account_contains_name_ignore_case("Asset")
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                account.slot("placeholder").await.unwrap(),
+                Some(Slot::String("true".to_string()))
+            );
+            let account = book
+                .account_contains_name_ignore_case("Cash")
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(account.slots().await.unwrap().is_empty());
+        }
+
         #[test(tokio::test)]
         async fn test_balance_into_currency() {
             let query = setup().await;
@@ -779,6 +1527,52 @@ mod tests {
             #[cfg(feature = "decimal")]
             assert_eq!(account.balance(&book).await.unwrap(), Decimal::new(4590, 0));
         }
+
+        #[test(tokio::test)]
+        async fn test_balance_at() {
+            let query = setup().await;
+            let book = Book::new(query).await.unwrap();
+            let account = book
+                .account_contains_name_ignore_case("Current")
+                .await
+                .unwrap()
+                .unwrap();
+            let checking = book
+                .account_contains_name_ignore_case("Checking")
+                .await
+                .unwrap()
+                .unwrap();
+            let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+            let at = account.balance_at(&book, date(2014, 12, 31)).await.unwrap();
+            let day = account
+                .balance_between(&book, date(2018, 2, 21), date(2018, 2, 21))
+                .await
+                .unwrap();
+            let own_at = checking.own_balance_at(date(2014, 12, 24)).await.unwrap();
+            let own_between = checking
+                .own_balance_between(date(2015, 1, 1), date(2018, 12, 31))
+                .await
+                .unwrap();
+            let parent_own = account.own_balance_at(date(2018, 12, 31)).await.unwrap();
+
+            #[cfg(not(feature = "decimal"))]
+            {
+                assert_approx_eq!(f64, at, 820.0);
+                assert_approx_eq!(f64, day, 1300.0);
+                assert_approx_eq!(f64, own_at, 670.0);
+                assert_approx_eq!(f64, own_between, 150.0);
+                assert_approx_eq!(f64, parent_own, 0.0);
+            }
+            #[cfg(feature = "decimal")]
+            {
+                assert_eq!(at, Decimal::new(820, 0));
+                assert_eq!(day, Decimal::new(1300, 0));
+                assert_eq!(own_at, Decimal::new(670, 0));
+                assert_eq!(own_between, Decimal::new(150, 0));
+                assert_eq!(parent_own, Decimal::ZERO);
+            }
+        }
     }
 
     #[cfg(feature = "xml")]
@@ -822,7 +1616,10 @@ mod tests {
 
             assert_eq!(result.guid, "guid");
             assert_eq!(result.name, "name");
-            assert_eq!(result.r#type, "account_type");
+            assert_eq!(
+                result.r#type,
+                AccountType::Other("account_type".to_string())
+            );
             assert_eq!(result.commodity_guid, "commodity_guid");
             assert_eq!(result.commodity_scu, 100);
             assert_eq!(result.non_std_scu, false);
@@ -931,6 +1728,27 @@ mod tests {
             assert_eq!(commodity.mnemonic, "EUR");
         }
 
+        #[test(tokio::test)]
+        async fn test_slots() {
+            let query = setup();
+            let book = Book::new(query).await.unwrap();
+            let account = book
+                .account_contains_name_ignore_case("Asset")
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                account.slot("placeholder").await.unwrap(),
+                Some(Slot::String("true".to_string()))
+            );
+            let account = book
+                .account_contains_name_ignore_case("Cash")
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(account.slots().await.unwrap().is_empty());
+        }
+
         #[test(tokio::test)]
         async fn test_balance_into_currency() {
             let query = setup();
@@ -976,5 +1794,51 @@ mod tests {
             #[cfg(feature = "decimal")]
             assert_eq!(account.balance(&book).await.unwrap(), Decimal::new(4590, 0));
         }
+
+        #[test(tokio::test)]
+        async fn test_balance_at() {
+            let query = setup();
+            let book = Book::new(query).await.unwrap();
+            let account = book
+                .account_contains_name_ignore_case("Current")
+                .await
+                .unwrap()
+                .unwrap();
+            let checking = book
+                .account_contains_name_ignore_case("Checking")
+                .await
+                .unwrap()
+                .unwrap();
+            let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+            let at = account.balance_at(&book, date(2014, 12, 31)).await.unwrap();
+            let day = account
+                .balance_between(&book, date(2018, 2, 21), date(2018, 2, 21))
+                .await
+                .unwrap();
+            let own_at = checking.own_balance_at(date(2014, 12, 24)).await.unwrap();
+            let own_between = checking
+                .own_balance_between(date(2015, 1, 1), date(2018, 12, 31))
+                .await
+                .unwrap();
+            let parent_own = account.own_balance_at(date(2018, 12, 31)).await.unwrap();
+
+            #[cfg(not(feature = "decimal"))]
+            {
+                assert_approx_eq!(f64, at, 820.0);
+                assert_approx_eq!(f64, day, 1300.0);
+                assert_approx_eq!(f64, own_at, 670.0);
+                assert_approx_eq!(f64, own_between, 150.0);
+                assert_approx_eq!(f64, parent_own, 0.0);
+            }
+            #[cfg(feature = "decimal")]
+            {
+                assert_eq!(at, Decimal::new(820, 0));
+                assert_eq!(day, Decimal::new(1300, 0));
+                assert_eq!(own_at, Decimal::new(670, 0));
+                assert_eq!(own_between, Decimal::new(150, 0));
+                assert_eq!(parent_own, Decimal::ZERO);
+            }
+        }
     }
 }
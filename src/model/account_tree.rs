@@ -0,0 +1,352 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::model::{Account, AccountType};
+use crate::query::{AccountQ, BookQ, BookT, Query};
+
+/// What `GnuCash` puts between account names in a full name.
+const SEPARATOR: char = ':';
+
+/// Every account of a book, linked to its parent and children.
+///
+/// The tree is read in one query and answers the structural questions without
+/// going back to the file. Accounts are kept in depth-first order, children
+/// sorted by name, so the descendants of an account follow it contiguously.
+#[derive(Clone, Debug)]
+pub struct AccountTree<Q>
+where
+    Q: Query,
+{
+    accounts: Vec<Account<Q>>,
+    index: HashMap<String, usize>,
+    parent: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    /// One past the last descendant of each account.
+    subtree_end: Vec<usize>,
+    depth: Vec<usize>,
+    full_name: Vec<String>,
+    by_path: HashMap<String, usize>,
+    root: usize,
+}
+
+impl<Q> AccountTree<Q>
+where
+    Q: Query,
+{
+    #[instrument(skip(query))]
+    pub(crate) async fn load(query: Arc<Q>) -> Result<Self, Error> {
+        tracing::debug!("building account tree");
+        let book = BookQ::book(&*query)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch book: {e}"))?;
+        let accounts = AccountQ::all(&*query)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch accounts: {e}"))?;
+        let accounts: Vec<_> = accounts
+            .into_iter()
+            .map(|x| Account::from_with_query(&x, query.clone()))
+            .collect();
+
+        let tree = Self::build(accounts, book.root_account_guid())?;
+        tracing::debug!(count = tree.len(), "account tree built");
+        Ok(tree)
+    }
+
+    fn build(mut accounts: Vec<Account<Q>>, root_guid: &str) -> Result<Self, Error> {
+        accounts.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.guid.cmp(&b.guid)));
+        let position: HashMap<&str, usize> = accounts
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (x.guid.as_str(), i))
+            .collect();
+
+        // Accounts whose parent is missing are kept as extra tops rather than dropped.
+        let mut tops = Vec::new();
+        let mut kids = vec![Vec::new(); accounts.len()];
+        for (i, account) in accounts.iter().enumerate() {
+            match position.get(account.parent_guid.as_str()) {
+                Some(&p) if p != i => kids[p].push(i),
+                _ => tops.push(i),
+            }
+        }
+
+        let mut order = Vec::with_capacity(accounts.len());
+        let mut parent = vec![None; accounts.len()];
+        let mut depth = vec![0; accounts.len()];
+        let mut full_name = vec![String::new(); accounts.len()];
+        let mut stack: Vec<usize> = tops.into_iter().rev().collect();
+        while let Some(i) = stack.pop() {
+            order.push(i);
+            for &k in kids[i].iter().rev() {
+                parent[k] = Some(i);
+                depth[k] = depth[i] + 1;
+                full_name[k] = if accounts[i].r#type == AccountType::Root {
+                    accounts[k].name.clone()
+                } else {
+                    format!("{}{SEPARATOR}{}", full_name[i], accounts[k].name)
+                };
+                stack.push(k);
+            }
+        }
+
+        // A parent cycle leaves its accounts unreached.
+        if order.len() != accounts.len() {
+            let account = accounts
+                .iter()
+                .enumerate()
+                .find(|(i, _)| !order.contains(i))
+                .map(|(_, x)| x.guid.clone())
+                .unwrap_or_default();
+            tracing::error!("account parents form a cycle");
+            return Err(Error::Internal(format!(
+                "account {account} is its own ancestor"
+            )));
+        }
+
+        // Renumber everything by depth-first position.
+        let mut new_of = vec![0; accounts.len()];
+        for (new, &old) in order.iter().enumerate() {
+            new_of[old] = new;
+        }
+        let mut slots: Vec<Option<Account<Q>>> = accounts.into_iter().map(Some).collect();
+        let accounts: Vec<Account<Q>> = order
+            .iter()
+            .map(|&old| slots[old].take().expect("each account is visited once"))
+            .collect();
+        let parent: Vec<_> = order
+            .iter()
+            .map(|&old| parent[old].map(|p| new_of[p]))
+            .collect();
+        let children: Vec<Vec<usize>> = order
+            .iter()
+            .map(|&old| kids[old].iter().map(|&k| new_of[k]).collect())
+            .collect();
+        let depth: Vec<_> = order.iter().map(|&old| depth[old]).collect();
+        let full_name: Vec<_> = order
+            .iter()
+            .map(|&old| std::mem::take(&mut full_name[old]))
+            .collect();
+
+        let mut subtree_end: Vec<usize> = (1..=accounts.len()).collect();
+        for i in (0..accounts.len()).rev() {
+            if let Some(p) = parent[i] {
+                subtree_end[p] = subtree_end[p].max(subtree_end[i]);
+            }
+        }
+
+        let index: HashMap<_, _> = accounts
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (x.guid.clone(), i))
+            .collect();
+        let Some(&root) = index.get(root_guid) else {
+            tracing::error!("root account not found");
+            return Err(Error::GuidNotFound {
+                model: "Account".to_string(),
+                guid: root_guid.to_string(),
+            });
+        };
+
+        // Only the book's own accounts are reachable by path, not the templates'.
+        let by_path = (root + 1..subtree_end[root])
+            .map(|i| (full_name[i].clone(), i))
+            .collect();
+
+        Ok(Self {
+            accounts,
+            index,
+            parent,
+            children,
+            subtree_end,
+            depth,
+            full_name,
+            by_path,
+            root,
+        })
+    }
+
+    /// The number of accounts, including the roots.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    /// The top of the book's account tree.
+    #[must_use]
+    pub fn root(&self) -> &Account<Q> {
+        &self.accounts[self.root]
+    }
+
+    /// All accounts in depth-first order, the template accounts included.
+    pub fn accounts(&self) -> impl Iterator<Item = &Account<Q>> {
+        self.accounts.iter()
+    }
+
+    #[must_use]
+    pub fn get(&self, guid: &str) -> Option<&Account<Q>> {
+        self.index.get(guid).map(|&i| &self.accounts[i])
+    }
+
+    /// The account at a colon-joined path below the root, e.g. `Asset:Broker:Foo stock`.
+    #[must_use]
+    pub fn by_path(&self, path: &str) -> Option<&Account<Q>> {
+        self.by_path.get(path).map(|&i| &self.accounts[i])
+    }
+
+    #[must_use]
+    pub fn parent(&self, guid: &str) -> Option<&Account<Q>> {
+        let i = *self.index.get(guid)?;
+        self.parent[i].map(|p| &self.accounts[p])
+    }
+
+    /// The direct children of an account, sorted by name.
+    pub fn children(&self, guid: &str) -> impl Iterator<Item = &Account<Q>> {
+        self.index
+            .get(guid)
+            .into_iter()
+            .flat_map(|&i| self.children[i].iter().map(|&k| &self.accounts[k]))
+    }
+
+    /// Every account below an account, in depth-first order.
+    pub fn descendants(&self, guid: &str) -> impl Iterator<Item = &Account<Q>> {
+        let range = self
+            .index
+            .get(guid)
+            .map_or(0..0, |&i| i + 1..self.subtree_end[i]);
+        self.accounts[range].iter()
+    }
+
+    /// The accounts above an account, from its parent up to the root.
+    pub fn ancestors(&self, guid: &str) -> impl Iterator<Item = &Account<Q>> {
+        let mut next = self.index.get(guid).and_then(|&i| self.parent[i]);
+        std::iter::from_fn(move || {
+            let i = next?;
+            next = self.parent[i];
+            Some(&self.accounts[i])
+        })
+    }
+
+    /// How far below its root an account is; the root is at depth zero.
+    #[must_use]
+    pub fn depth(&self, guid: &str) -> Option<usize> {
+        self.index.get(guid).map(|&i| self.depth[i])
+    }
+
+    /// The colon-joined names from the top of the tree, empty for the roots.
+    #[must_use]
+    pub fn full_name(&self, guid: &str) -> Option<&str> {
+        self.index.get(guid).map(|&i| self.full_name[i].as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        use pretty_assertions::assert_eq;
+        use test_log::test;
+
+        use crate::{Book, SQLiteQuery};
+
+        async fn setup() -> Book<SQLiteQuery> {
+            let uri: &str = &format!(
+                "{}/tests/db/sqlite/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let query = SQLiteQuery::new(uri).unwrap();
+            Book::new(query).await.unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_tree() {
+            let book = setup().await;
+            let tree = book.account_tree().await.unwrap();
+
+            assert_eq!(tree.len(), 21);
+            assert_eq!(tree.root().name, "Root Account");
+            assert_eq!(tree.depth(&tree.root().guid), Some(0));
+            assert_eq!(tree.full_name(&tree.root().guid), Some(""));
+
+            let cash = tree.by_path("Asset:Current:Cash").unwrap();
+            assert_eq!(cash.guid, "93fc043c3062aaa1297b30e543d2cd0d");
+            assert_eq!(tree.depth(&cash.guid), Some(3));
+            assert_eq!(tree.full_name(&cash.guid), Some("Asset:Current:Cash"));
+            assert_eq!(tree.parent(&cash.guid).unwrap().name, "Current");
+
+            let ancestors: Vec<_> = tree.ancestors(&cash.guid).map(|x| &x.name).collect();
+            assert_eq!(ancestors, vec!["Current", "Asset", "Root Account"]);
+
+            let current = tree.parent(&cash.guid).unwrap();
+            let children: Vec<_> = tree.children(&current.guid).map(|x| &x.name).collect();
+            assert_eq!(children, vec!["Cash", "Checking", "Savings"]);
+
+            let asset = tree.by_path("Asset").unwrap();
+            let descendants: Vec<_> = tree.descendants(&asset.guid).map(|x| &x.name).collect();
+            assert_eq!(
+                descendants,
+                vec![
+                    "Broker",
+                    "Foo stock",
+                    "Current",
+                    "Cash",
+                    "Checking",
+                    "Savings",
+                    "Fixed",
+                    "House"
+                ]
+            );
+            assert_eq!(tree.descendants(&tree.root().guid).count(), 19);
+
+            // the template root is kept, but not reachable by path
+            let template = tree.get("f6c0cd00ec04169a44f170181882adab").unwrap();
+            assert_eq!(tree.depth(&template.guid), Some(0));
+            assert!(tree.by_path("Template Root").is_none());
+            assert!(tree.by_path("Asset:Cash").is_none());
+            assert!(tree.get("not-a-guid").is_none());
+            assert_eq!(tree.children("not-a-guid").count(), 0);
+        }
+
+        #[test(tokio::test)]
+        async fn test_account_by_path() {
+            let book = setup().await;
+
+            let account = book
+                .account_by_path("Mouvements:NASDAQ:FOO")
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(account.guid, "0ccab772d0d16a3e1eaf42cd53f891e5");
+            assert!(book.account_by_path("FOO").await.unwrap().is_none());
+        }
+    }
+
+    #[cfg(feature = "xml")]
+    mod xml {
+        use pretty_assertions::assert_eq;
+        use test_log::test;
+
+        use crate::{Book, XMLQuery};
+
+        #[test(tokio::test)]
+        async fn test_tree() {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let query = XMLQuery::new(path).unwrap();
+            let book = Book::new(query).await.unwrap();
+            let tree = book.account_tree().await.unwrap();
+
+            let stock = tree.by_path("Asset:Broker:Foo stock").unwrap();
+            assert_eq!(stock.guid, "1c089803052e85f5c6d8e786057dbaee");
+            assert_eq!(tree.depth(&stock.guid), Some(3));
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use std::fmt;
+
+/// The type of an account, as stored in `GnuCash` files.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum AccountType {
+    Bank,
+    Cash,
+    Credit,
+    Asset,
+    Liability,
+    Stock,
+    Mutual,
+    Currency,
+    Income,
+    Expense,
+    Equity,
+    Receivable,
+    Payable,
+    /// The top of the account tree, and of the scheduled transaction templates.
+    Root,
+    /// Holds the currency exchange legs of multi-currency transactions.
+    Trading,
+    Checking,
+    Savings,
+    MoneyMarket,
+    CreditLine,
+    /// A type unknown to this crate, kept as stored.
+    Other(String),
+}
+
+impl AccountType {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Bank => "BANK",
+            Self::Cash => "CASH",
+            Self::Credit => "CREDIT",
+            Self::Asset => "ASSET",
+            Self::Liability => "LIABILITY",
+            Self::Stock => "STOCK",
+            Self::Mutual => "MUTUAL",
+            Self::Currency => "CURRENCY",
+            Self::Income => "INCOME",
+            Self::Expense => "EXPENSE",
+            Self::Equity => "EQUITY",
+            Self::Receivable => "RECEIVABLE",
+            Self::Payable => "PAYABLE",
+            Self::Root => "ROOT",
+            Self::Trading => "TRADING",
+            Self::Checking => "CHECKING",
+            Self::Savings => "SAVINGS",
+            Self::MoneyMarket => "MONEYMRKT",
+            Self::CreditLine => "CREDITLINE",
+            Self::Other(x) => x,
+        }
+    }
+
+    /// Whether the account holds something the book owns.
+    #[must_use]
+    pub fn is_asset_like(&self) -> bool {
+        matches!(
+            self,
+            Self::Bank
+                | Self::Cash
+                | Self::Asset
+                | Self::Stock
+                | Self::Mutual
+                | Self::Currency
+                | Self::Receivable
+                | Self::Checking
+                | Self::Savings
+                | Self::MoneyMarket
+        )
+    }
+
+    /// Whether the account holds something the book owes.
+    #[must_use]
+    pub fn is_liability_like(&self) -> bool {
+        matches!(
+            self,
+            Self::Credit | Self::Liability | Self::Payable | Self::CreditLine
+        )
+    }
+
+    /// Whether the account belongs on the balance sheet: assets, liabilities and equity.
+    #[must_use]
+    pub fn is_balance_sheet(&self) -> bool {
+        self.is_asset_like() || self.is_liability_like() || *self == Self::Equity
+    }
+
+    /// Whether the account belongs on the income statement: income and expenses.
+    #[must_use]
+    pub fn is_income_statement(&self) -> bool {
+        matches!(self, Self::Income | Self::Expense)
+    }
+
+    /// Whether the account holds stocks or mutual funds.
+    #[must_use]
+    pub fn is_investment(&self) -> bool {
+        matches!(self, Self::Stock | Self::Mutual)
+    }
+
+    /// One when a balance of the type normally shows as a debit, minus one when as a credit.
+    ///
+    /// Multiplying a balance by it gives the amount as reports show it, positive
+    /// for a liability the book owes or income it earned.
+    #[must_use]
+    pub fn normal_balance_sign(&self) -> crate::Num {
+        if self.is_liability_like() || matches!(self, Self::Equity | Self::Income) {
+            -crate::Num::from(1)
+        } else {
+            crate::Num::from(1)
+        }
+    }
+}
+
+impl From<&str> for AccountType {
+    fn from(value: &str) -> Self {
+        match value {
+            "BANK" => Self::Bank,
+            "CASH" => Self::Cash,
+            "CREDIT" => Self::Credit,
+            "ASSET" => Self::Asset,
+            "LIABILITY" => Self::Liability,
+            "STOCK" => Self::Stock,
+            "MUTUAL" => Self::Mutual,
+            "CURRENCY" => Self::Currency,
+            "INCOME" => Self::Income,
+            "EXPENSE" => Self::Expense,
+            "EQUITY" => Self::Equity,
+            "RECEIVABLE" => Self::Receivable,
+            "PAYABLE" => Self::Payable,
+            "ROOT" => Self::Root,
+            "TRADING" => Self::Trading,
+            "CHECKING" => Self::Checking,
+            "SAVINGS" => Self::Savings,
+            "MONEYMRKT" => Self::MoneyMarket,
+            "CREDITLINE" => Self::CreditLine,
+            x => Self::Other(x.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for AccountType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for name in [
+            "BANK",
+            "CASH",
+            "CREDIT",
+            "ASSET",
+            "LIABILITY",
+            "STOCK",
+            "MUTUAL",
+            "CURRENCY",
+            "INCOME",
+            "EXPENSE",
+            "EQUITY",
+            "RECEIVABLE",
+            "PAYABLE",
+            "ROOT",
+            "TRADING",
+            "CHECKING",
+            "SAVINGS",
+            "MONEYMRKT",
+            "CREDITLINE",
+        ] {
+            let account_type = AccountType::from(name);
+            assert!(!matches!(account_type, AccountType::Other(_)), "{name}");
+            assert_eq!(account_type.to_string(), name);
+        }
+
+        assert_eq!(
+            AccountType::from("account_type"),
+            AccountType::Other("account_type".to_string())
+        );
+        assert_eq!(AccountType::from("account_type").as_str(), "account_type");
+    }
+
+    #[test]
+    fn test_classification() {
+        assert!(AccountType::Receivable.is_asset_like());
+        assert!(AccountType::Stock.is_investment());
+        assert!(AccountType::Payable.is_liability_like());
+        assert!(AccountType::Equity.is_balance_sheet());
+        assert!(!AccountType::Equity.is_asset_like());
+        assert!(!AccountType::Equity.is_liability_like());
+        assert!(AccountType::Expense.is_income_statement());
+        assert!(!AccountType::Trading.is_balance_sheet());
+        assert!(!AccountType::Root.is_income_statement());
+
+        let zero = crate::Num::default();
+        assert!(AccountType::Bank.normal_balance_sign() > zero);
+        assert!(AccountType::Expense.normal_balance_sign() > zero);
+        assert!(AccountType::Credit.normal_balance_sign() < zero);
+        assert!(AccountType::Equity.normal_balance_sign() < zero);
+        assert!(AccountType::Income.normal_balance_sign() < zero);
+    }
+}
@@ -0,0 +1,41 @@
+/// A postal address with contact details, as kept for customers, vendors and employees.
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Address {
+    pub name: String,
+    pub addr1: String,
+    pub addr2: String,
+    pub addr3: String,
+    pub addr4: String,
+    pub phone: String,
+    pub fax: String,
+    pub email: String,
+}
+
+impl Address {
+    /// The non-empty address lines, in order.
+    #[must_use]
+    pub fn lines(&self) -> Vec<&str> {
+        [&self.addr1, &self.addr2, &self.addr3, &self.addr4]
+            .into_iter()
+            .map(String::as_str)
+            .filter(|x| !x.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let address = Address {
+            addr1: "Main Street 1".to_string(),
+            addr3: "Springfield".to_string(),
+            ..Address::default()
+        };
+        assert_eq!(address.lines(), vec!["Main Street 1", "Springfield"]);
+    }
+}
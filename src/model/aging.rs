@@ -0,0 +1,241 @@
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::model::{Commodity, Invoice, Owner};
+use crate::query::{InvoiceQ, Query};
+
+/// Which side of the business ledger an aging report covers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum AgingKind {
+    /// What customers owe on their invoices.
+    Receivable,
+    /// What the book owes vendors on their bills.
+    Payable,
+}
+
+/// Open amounts by how many days they are past due.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AgingBuckets {
+    /// Not yet due, or due on the report date.
+    pub current: crate::Num,
+    pub days_0_30: crate::Num,
+    pub days_31_60: crate::Num,
+    pub days_61_90: crate::Num,
+    pub days_over_90: crate::Num,
+}
+
+impl AgingBuckets {
+    #[must_use]
+    pub fn total(&self) -> crate::Num {
+        self.current + self.days_0_30 + self.days_31_60 + self.days_61_90 + self.days_over_90
+    }
+
+    fn add(&mut self, days_overdue: i64, amount: crate::Num) {
+        let bucket = match days_overdue {
+            ..=0 => &mut self.current,
+            1..=30 => &mut self.days_0_30,
+            31..=60 => &mut self.days_31_60,
+            61..=90 => &mut self.days_61_90,
+            _ => &mut self.days_over_90,
+        };
+        *bucket += amount;
+    }
+}
+
+/// The open balance of one customer or vendor, in their currency.
+#[derive(Clone, Debug)]
+pub struct AgingLine<Q>
+where
+    Q: Query,
+{
+    pub owner: Owner<Q>,
+    pub currency: Commodity<Q>,
+    pub buckets: AgingBuckets,
+}
+
+/// Buckets the amounts due on posted invoices or bills as of a date.
+///
+/// Invoices for a job count towards the job's customer or vendor; employee
+/// expense vouchers are left out. Payments not applied to an invoice, which
+/// stay outside its lot, do not reduce the balance.
+#[instrument(skip(query))]
+pub(crate) async fn report<Q: Query>(
+    query: Arc<Q>,
+    kind: AgingKind,
+    as_of: NaiveDate,
+) -> Result<Vec<AgingLine<Q>>, Error> {
+    tracing::debug!("building aging report");
+    let invoices = InvoiceQ::all(&*query)
+        .await
+        .inspect_err(|e| tracing::error!("failed to fetch invoices: {e}"))?;
+
+    let mut lines: BTreeMap<String, AgingLine<Q>> = BTreeMap::new();
+    for invoice in invoices {
+        let invoice = Invoice::from_with_query(&invoice, query.clone());
+        if invoice.date_posted.is_none_or(|x| x.date() > as_of) {
+            continue;
+        }
+
+        let mut owner = invoice.owner().await?;
+        if let Owner::Job(job) = owner {
+            owner = job.owner().await?;
+        }
+        let currency = match (&owner, kind) {
+            (Owner::Customer(x), AgingKind::Receivable) => x.currency().await?,
+            (Owner::Vendor(x), AgingKind::Payable) => x.currency().await?,
+            _ => continue,
+        };
+
+        let amount = invoice.amount_due_at(as_of).await?;
+        #[cfg(not(feature = "decimal"))]
+        let is_zero = amount.abs() < f64::EPSILON;
+        #[cfg(feature = "decimal")]
+        let is_zero = amount.is_zero();
+        if is_zero {
+            continue;
+        }
+
+        let Some(due) = invoice.due_date().await? else {
+            continue;
+        };
+        let days_overdue = (as_of - due.date()).num_days();
+
+        lines
+            .entry(owner.guid().to_string())
+            .or_insert_with(|| AgingLine {
+                owner,
+                currency,
+                buckets: AgingBuckets::default(),
+            })
+            .buckets
+            .add(days_overdue, amount);
+    }
+
+    let mut result: Vec<_> = lines.into_values().collect();
+    result.sort_by(|a, b| a.owner.name().cmp(b.owner.name()));
+
+    tracing::debug!(count = result.len(), "aging report built");
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::Book;
+        use crate::query::sqlite::tests::setup_with;
+        use crate::query::tests::fixtures::{BUSINESS, INVOICES};
+
+        use super::super::*;
+
+        fn date(s: &str) -> NaiveDate {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_receivable() {
+            let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+            let book = Book::new(query).await.unwrap();
+
+            // before the payment and the due date
+            let lines = book
+                .aging(AgingKind::Receivable, date("2018-03-15"))
+                .await
+                .unwrap();
+            assert_eq!(lines.len(), 1);
+            assert_eq!(lines[0].owner.name(), "Acme Corp");
+            assert_eq!(lines[0].currency.mnemonic, "EUR");
+            let buckets = lines[0].buckets;
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, buckets.current, 590.0);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, buckets.total(), 590.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(buckets.current, Decimal::new(590, 0));
+            #[cfg(feature = "decimal")]
+            assert_eq!(buckets.total(), Decimal::new(590, 0));
+
+            // six days past due, after the payment
+            let buckets = book
+                .aging(AgingKind::Receivable, date("2018-04-10"))
+                .await
+                .unwrap()[0]
+                .buckets;
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, buckets.current, 0.0);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, buckets.days_0_30, 390.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(buckets.current, Decimal::ZERO);
+            #[cfg(feature = "decimal")]
+            assert_eq!(buckets.days_0_30, Decimal::new(390, 0));
+
+            let buckets = book
+                .aging(AgingKind::Receivable, date("2018-07-10"))
+                .await
+                .unwrap()[0]
+                .buckets;
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, buckets.days_over_90, 390.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(buckets.days_over_90, Decimal::new(390, 0));
+
+            // not yet posted
+            let lines = book
+                .aging(AgingKind::Receivable, date("2018-03-01"))
+                .await
+                .unwrap();
+            assert_eq!(lines.len(), 0);
+        }
+
+        #[test(tokio::test)]
+        async fn test_payable() {
+            let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+            let book = Book::new(query).await.unwrap();
+
+            let lines = book
+                .aging(AgingKind::Payable, date("2018-04-10"))
+                .await
+                .unwrap();
+            assert_eq!(lines.len(), 1);
+            assert_eq!(lines[0].owner.name(), "Paper Supplies Ltd");
+            let buckets = lines[0].buckets;
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, buckets.days_31_60, 120.0);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, buckets.total(), 120.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(buckets.days_31_60, Decimal::new(120, 0));
+            #[cfg(feature = "decimal")]
+            assert_eq!(buckets.total(), Decimal::new(120, 0));
+        }
+
+        #[test]
+        fn test_buckets() {
+            let mut buckets = AgingBuckets::default();
+            for days in [-1, 0, 1, 30, 31, 60, 61, 90, 91] {
+                buckets.add(days, crate::Num::from(1));
+            }
+            assert_eq!(
+                buckets,
+                AgingBuckets {
+                    current: crate::Num::from(2),
+                    days_0_30: crate::Num::from(2),
+                    days_31_60: crate::Num::from(2),
+                    days_61_90: crate::Num::from(2),
+                    days_over_90: crate::Num::from(1),
+                }
+            );
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use crate::builder::from_fraction;
+use crate::error::Error;
+use crate::model::recurrence::last_day_of_month;
+use crate::query::{BillTermQ, BillTermT, Query};
+
+/// How a billing term counts its due and discount days.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum BillTermType {
+    /// A number of days after the post date.
+    #[default]
+    Days,
+    /// A day of the next month, or of the month after when posted past the cutoff day.
+    Proximo,
+}
+
+impl From<&str> for BillTermType {
+    fn from(value: &str) -> Self {
+        match value {
+            "GNC_TERM_TYPE_PROXIMO" => Self::Proximo,
+            _ => Self::Days,
+        }
+    }
+}
+
+/// When an invoice falls due and how much is taken off for paying early.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BillTerm {
+    pub guid: String,
+    pub name: String,
+    pub description: String,
+    pub refcount: i64,
+    pub invisible: bool,
+    /// The term this one was copied from, empty when there is none.
+    pub parent_guid: String,
+    pub r#type: BillTermType,
+    /// Days after posting, or the day of the month for [`BillTermType::Proximo`].
+    pub due_days: i64,
+    /// Same as `due_days`, for the early payment discount.
+    pub discount_days: i64,
+    /// The early payment discount, in percent.
+    pub discount: crate::Num,
+    /// The last day of the month still counted as this month for
+    /// [`BillTermType::Proximo`]; zero or less counts back from the month's end.
+    pub cutoff: i64,
+}
+
+impl BillTerm {
+    pub(crate) fn from_item<T: BillTermT>(item: &T) -> Self {
+        Self {
+            guid: item.guid().to_string(),
+            name: item.name().to_string(),
+            description: item.description().to_string(),
+            refcount: item.refcount(),
+            invisible: item.invisible(),
+            parent_guid: item.parent_guid().to_string(),
+            r#type: item.r#type().into(),
+            due_days: item.due_days(),
+            discount_days: item.discount_days(),
+            discount: from_fraction(item.discount_num(), item.discount_denom()),
+            cutoff: item.cutoff(),
+        }
+    }
+
+    /// Looks up a billing term referenced by guid, `None` when the guid is empty.
+    pub(crate) async fn fetch<Q: Query>(query: &Q, guid: &str) -> Result<Option<Self>, Error> {
+        if guid.is_empty() {
+            return Ok(None);
+        }
+
+        let billterm = BillTermQ::guid(query, guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch bill term: {e}"))?;
+
+        if let Some(b) = billterm {
+            Ok(Some(Self::from_item(&b)))
+        } else {
+            tracing::error!("bill term not found");
+            Err(Error::GuidNotFound {
+                model: "BillTerm".to_string(),
+                guid: guid.to_string(),
+            })
+        }
+    }
+
+    /// The date an invoice posted on `posted` falls due.
+    #[must_use]
+    pub fn due_date(&self, posted: NaiveDate) -> Option<NaiveDate> {
+        self.compute_date(posted, self.due_days)
+    }
+
+    /// The last date the early payment discount applies, `None` when there is no discount.
+    #[must_use]
+    pub fn discount_date(&self, posted: NaiveDate) -> Option<NaiveDate> {
+        if self.discount_days <= 0 {
+            return None;
+        }
+        self.compute_date(posted, self.discount_days)
+    }
+
+    fn compute_date(&self, posted: NaiveDate, days: i64) -> Option<NaiveDate> {
+        match self.r#type {
+            BillTermType::Days => {
+                posted.checked_add_days(Days::new(u64::try_from(days.max(0)).ok()?))
+            }
+            BillTermType::Proximo => {
+                let first = posted.with_day(1)?;
+                let month_end = i64::from(last_day_of_month(first)?.day());
+                let cutoff = if self.cutoff <= 0 {
+                    self.cutoff + month_end
+                } else {
+                    self.cutoff
+                };
+                let months = if i64::from(posted.day()) <= cutoff {
+                    1
+                } else {
+                    2
+                };
+
+                let first = first.checked_add_months(Months::new(months))?;
+                let last = last_day_of_month(first)?.day();
+                let day = u32::try_from(days.max(1)).ok()?.min(last);
+                first.with_day(day)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::Book;
+        use crate::query::sqlite::tests::setup_with;
+        use crate::query::tests::fixtures::{BUSINESS, INVOICES, TERMS};
+
+        use super::super::*;
+
+        fn date(s: &str) -> NaiveDate {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_days() {
+            let (_dir, query) = setup_with(&[BUSINESS, INVOICES, TERMS]);
+            let book = Book::new(query).await.unwrap();
+            let invoice = book
+                .invoices()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "3d4e5f60718293a4b5c6d7e8f90a1b01")
+                .unwrap();
+
+            let term = invoice.terms().await.unwrap().unwrap();
+            assert_eq!(term.name, "Net 30");
+            assert_eq!(term.r#type, BillTermType::Days);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, term.discount, 2.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(term.discount, Decimal::new(2, 0));
+
+            let crate::model::Owner::Customer(customer) = invoice.owner().await.unwrap() else {
+                panic!("not a customer");
+            };
+            assert_eq!(customer.terms().await.unwrap(), Some(term.clone()));
+
+            let posted = invoice.date_posted.unwrap().date();
+            assert_eq!(term.due_date(posted), Some(date("2018-04-04")));
+            assert_eq!(term.discount_date(posted), Some(date("2018-03-15")));
+        }
+
+        #[test(tokio::test)]
+        async fn test_proximo() {
+            let (_dir, query) = setup_with(&[BUSINESS, INVOICES, TERMS]);
+            let book = Book::new(query).await.unwrap();
+            let term = book
+                .bill_terms()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.name == "Proximo 31")
+                .unwrap();
+            assert_eq!(term.r#type, BillTermType::Proximo);
+
+            // on or before the cutoff, due next month, clamped to its last day
+            assert_eq!(term.due_date(date("2018-01-25")), Some(date("2018-02-28")));
+            // past the cutoff, due the month after
+            assert_eq!(term.due_date(date("2018-01-26")), Some(date("2018-03-31")));
+            assert_eq!(term.due_date(date("2018-11-30")), Some(date("2019-01-31")));
+            assert_eq!(term.discount_date(date("2018-01-26")), None);
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::BTreeMap;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::model::slot::{self, Slot};
+use crate::query::{BookQ, BookT, Query, VersionQ, VersionT};
+
+/// The newest schema of each table this crate reads, as written since `GnuCash` 2.6.
+pub(crate) const TABLE_VERSIONS: [(&str, i64); 22] = [
+    ("books", 1),
+    ("commodities", 1),
+    ("accounts", 1),
+    ("budgets", 1),
+    ("budget_amounts", 1),
+    ("prices", 3),
+    ("transactions", 4),
+    ("splits", 5),
+    ("slots", 4),
+    ("recurrences", 2),
+    ("schedxactions", 1),
+    ("lots", 2),
+    ("billterms", 2),
+    ("customers", 2),
+    ("employees", 2),
+    ("entries", 4),
+    ("invoices", 4),
+    ("jobs", 1),
+    ("orders", 1),
+    ("taxtables", 2),
+    ("taxtable_entries", 3),
+    ("vendors", 1),
+];
+
+/// What a `GnuCash` file records about the book itself.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BookInfo {
+    pub guid: String,
+    pub root_account_guid: String,
+    /// The root of the accounts used by scheduled transaction templates, empty when there is none.
+    pub root_template_guid: String,
+    /// The `GnuCash` release that last wrote the file, e.g. `3_000_005` for 3.5 or
+    /// `5_000_004` for 5.4, the release `convert::to_sqlite` records.
+    ///
+    /// Only SQL files record it.
+    pub gnucash_version: Option<i64>,
+    /// The oldest release whose files `GnuCash` reads without saving them again.
+    pub resave_version: Option<i64>,
+    /// The schema version of each table, empty for XML files.
+    pub table_versions: BTreeMap<String, i64>,
+    /// The features the file relies on, by name, with their descriptions.
+    pub features: BTreeMap<String, String>,
+}
+
+impl BookInfo {
+    /// Whether the file relies on a feature, e.g. `Use a Book Currency`.
+    #[must_use]
+    pub fn has_feature(&self, name: &str) -> bool {
+        self.features.contains_key(name)
+    }
+
+    /// Fails with [`Error::UnsupportedVersion`] when a table has a newer schema than this crate reads.
+    ///
+    /// Tables this crate does not read are not checked.
+    pub fn check_versions(&self) -> Result<(), Error> {
+        for (table, supported) in TABLE_VERSIONS {
+            if let Some(&version) = self.table_versions.get(table)
+                && version > supported
+            {
+                return Err(Error::UnsupportedVersion {
+                    table: table.to_string(),
+                    version,
+                    supported,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[instrument(skip(query))]
+pub(crate) async fn load<Q: Query>(query: &Q) -> Result<BookInfo, Error> {
+    tracing::debug!("loading book info");
+    let book = BookQ::book(query)
+        .await
+        .inspect_err(|e| tracing::error!("failed to fetch book: {e}"))?;
+    let versions = VersionQ::all(query)
+        .await
+        .inspect_err(|e| tracing::error!("failed to fetch versions: {e}"))?;
+
+    let mut info = BookInfo {
+        guid: book.guid().to_string(),
+        root_account_guid: book.root_account_guid().to_string(),
+        root_template_guid: book.root_template_guid().to_string(),
+        ..BookInfo::default()
+    };
+    for version in versions {
+        match version.table_name() {
+            "Gnucash" => info.gnucash_version = Some(version.table_version()),
+            "Gnucash-Resave" => info.resave_version = Some(version.table_version()),
+            table => {
+                info.table_versions
+                    .insert(table.to_string(), version.table_version());
+            }
+        }
+    }
+
+    if let Some(Slot::Frame(features)) = slot::load(query, book.guid()).await?.remove("features") {
+        info.features = features
+            .into_iter()
+            .map(|(name, description)| {
+                let description = description.as_str().unwrap_or_default().to_string();
+                (name, description)
+            })
+            .collect();
+    }
+
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_check_versions() {
+        let mut info = BookInfo {
+            table_versions: BTreeMap::from([("splits".to_string(), 5), ("gncjobs".to_string(), 9)]),
+            ..BookInfo::default()
+        };
+        assert!(info.check_versions().is_ok());
+
+        info.table_versions.insert("splits".to_string(), 6);
+        let Err(Error::UnsupportedVersion {
+            table,
+            version,
+            supported,
+        }) = info.check_versions()
+        else {
+            panic!("expected an unsupported version");
+        };
+        assert_eq!(table, "splits");
+        assert_eq!(version, 6);
+        assert_eq!(supported, 5);
+    }
+
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        use pretty_assertions::assert_eq;
+        use test_log::test;
+
+        use crate::{Book, SQLiteQuery};
+
+        #[test(tokio::test)]
+        async fn test_info() {
+            let uri: &str = &format!(
+                "{}/tests/db/sqlite/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let query = SQLiteQuery::new(uri).unwrap();
+            let book = Book::new(query).await.unwrap();
+            let info = book.info().await.unwrap();
+
+            assert_eq!(info.guid, "7d4ef4044fd30f41d08914a8174c2f5b");
+            assert_eq!(info.root_account_guid, "00622dda21937b29e494179de5013f82");
+            assert_eq!(info.root_template_guid, "f6c0cd00ec04169a44f170181882adab");
+            assert_eq!(info.gnucash_version, Some(3_000_005));
+            assert_eq!(info.resave_version, Some(19920));
+            assert_eq!(info.table_versions.len(), 22);
+            assert_eq!(info.table_versions["transactions"], 4);
+            assert!(info.has_feature("ISO-8601 formatted date strings in SQLite3 databases."));
+            assert!(!info.has_feature("Use a Book Currency"));
+            assert!(info.check_versions().is_ok());
+
+            let root = book.root_account().await.unwrap();
+            assert_eq!(root.name, "Root Account");
+        }
+    }
+
+    #[cfg(feature = "xml")]
+    mod xml {
+        use pretty_assertions::assert_eq;
+        use test_log::test;
+
+        use crate::{Book, XMLQuery};
+
+        #[test(tokio::test)]
+        async fn test_info() {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let query = XMLQuery::new(path).unwrap();
+            let book = Book::new(query).await.unwrap();
+            let info = book.info().await.unwrap();
+
+            assert_eq!(info.root_account_guid, "00622dda21937b29e494179de5013f82");
+            assert_eq!(info.gnucash_version, None);
+            assert!(info.table_versions.is_empty());
+            assert!(info.check_versions().is_ok());
+        }
+    }
+}
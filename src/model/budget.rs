@@ -0,0 +1,490 @@
+use chrono::{NaiveDate, NaiveTime};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::builder::from_fraction;
+use crate::error::Error;
+use crate::model::{Account, Recurrence};
+use crate::query::{AccountQ, BudgetAmountQ, BudgetAmountT, BudgetT, Query, RecurrenceQ};
+
+/// A plan of amounts per account for a number of consecutive periods.
+#[derive(Clone, Debug)]
+pub struct Budget<Q>
+where
+    Q: Query,
+{
+    query: Arc<Q>,
+
+    pub guid: String,
+    pub name: String,
+    pub description: String,
+    pub num_periods: i64,
+}
+
+/// The budgeted and the actual amount of an account for one period.
+#[derive(Clone, Debug)]
+pub struct BudgetLine<Q>
+where
+    Q: Query,
+{
+    pub account: Account<Q>,
+    pub period: i64,
+    /// `None` when nothing was budgeted for the period.
+    pub budgeted: Option<crate::Num>,
+    /// The sum of the split quantities of the account and its descendants posted within the period.
+    pub actual: crate::Num,
+}
+
+impl<Q> Budget<Q>
+where
+    Q: Query,
+{
+    pub(crate) fn from_with_query<T: BudgetT>(item: &T, query: Arc<Q>) -> Self {
+        Self {
+            query,
+
+            guid: item.guid().to_string(),
+            name: item.name().to_string(),
+            description: item.description().to_string(),
+            num_periods: item.num_periods(),
+        }
+    }
+
+    /// The schedule the periods follow, usually monthly from the first of a month.
+    #[instrument(skip(self), fields(budget_guid = %self.guid))]
+    pub async fn recurrence(&self) -> Result<Recurrence, Error> {
+        tracing::debug!("fetching recurrence for budget");
+        let recurrences = RecurrenceQ::obj_guid(&*self.query, &self.guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch recurrences: {e}"))?;
+
+        let Some(recurrence) = recurrences.first() else {
+            tracing::error!("recurrence not found");
+            return Err(Error::GuidNotFound {
+                model: "Recurrence".to_string(),
+                guid: self.guid.clone(),
+            });
+        };
+        Recurrence::from_item(recurrence)
+    }
+
+    /// The first day of `period` and the first day of the period after it.
+    #[instrument(skip(self), fields(budget_guid = %self.guid))]
+    pub async fn period(&self, period: i64) -> Result<(NaiveDate, NaiveDate), Error> {
+        let recurrence = self.recurrence().await?;
+        self.period_of(&recurrence, period)
+    }
+
+    /// The amount budgeted for `account` in `period`, `None` if there is none.
+    #[instrument(skip(self, account), fields(budget_guid = %self.guid, account_guid = %account.guid))]
+    pub async fn amount(
+        &self,
+        account: &Account<Q>,
+        period: i64,
+    ) -> Result<Option<crate::Num>, Error> {
+        tracing::debug!("fetching amount for budget");
+        let amounts = BudgetAmountQ::budget(&*self.query, &self.guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch budget amounts: {e}"))?;
+
+        Ok(amounts
+            .iter()
+            .find(|x| x.account_guid() == account.guid && x.period_num() == period)
+            .map(|x| from_fraction(x.amount_num(), x.amount_denom())))
+    }
+
+    /// What was actually booked to `account` and its descendants in `period`.
+    ///
+    /// Fails with [`Error::Invalid`] if a descendant holds another commodity than `account`.
+    #[instrument(skip(self, account), fields(budget_guid = %self.guid, account_guid = %account.guid))]
+    pub async fn actual(&self, account: &Account<Q>, period: i64) -> Result<crate::Num, Error> {
+        let range = self.period(period).await?;
+        let actuals = self.actuals(account, &[range]).await?;
+        Ok(actuals[0])
+    }
+
+    /// Budgeted against actual amounts for every period of every account with a budgeted amount.
+    ///
+    /// Fails with [`Error::Invalid`] if a descendant of such an account holds another
+    /// commodity than it.
+    #[instrument(skip(self), fields(budget_guid = %self.guid))]
+    pub async fn vs_actual(&self) -> Result<Vec<BudgetLine<Q>>, Error> {
+        tracing::debug!("comparing budget with actual amounts");
+        let recurrence = self.recurrence().await?;
+        let periods = (0..self.num_periods)
+            .map(|x| self.period_of(&recurrence, x))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let amounts = BudgetAmountQ::budget(&*self.query, &self.guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch budget amounts: {e}"))?;
+        let mut budgeted: HashMap<&str, HashMap<i64, crate::Num>> = HashMap::new();
+        for x in &amounts {
+            budgeted.entry(x.account_guid()).or_default().insert(
+                x.period_num(),
+                from_fraction(x.amount_num(), x.amount_denom()),
+            );
+        }
+        let mut account_guids: Vec<_> = budgeted.keys().copied().collect();
+        account_guids.sort_unstable();
+
+        let mut result = Vec::new();
+        for guid in account_guids {
+            let account = AccountQ::guid(&*self.query, guid)
+                .await
+                .inspect_err(|e| tracing::error!("failed to fetch account: {e}"))?
+                .ok_or_else(|| Error::GuidNotFound {
+                    model: "Account".to_string(),
+                    guid: guid.to_string(),
+                })?;
+            let account = Account::from_with_query(&account, self.query.clone());
+            let actuals = self.actuals(&account, &periods).await?;
+
+            for (period, actual) in (0..self.num_periods).zip(actuals) {
+                result.push(BudgetLine {
+                    account: account.clone(),
+                    period,
+                    budgeted: budgeted[guid].get(&period).copied(),
+                    actual,
+                });
+            }
+        }
+
+        tracing::debug!(count = result.len(), "budget lines computed");
+        Ok(result)
+    }
+
+    fn period_of(
+        &self,
+        recurrence: &Recurrence,
+        period: i64,
+    ) -> Result<(NaiveDate, NaiveDate), Error> {
+        if !(0..self.num_periods).contains(&period) {
+            return Err(Error::Invalid {
+                model: "Budget".to_string(),
+                reason: format!("period {period} is not in 0..{}", self.num_periods),
+            });
+        }
+
+        recurrence
+            .nth(period)
+            .zip(recurrence.nth(period + 1))
+            .ok_or_else(|| Error::Invalid {
+                model: "Budget".to_string(),
+                reason: format!("period {period} has no dates"),
+            })
+    }
+
+    /// Sums the quantities of the splits of `account` and its descendants per period.
+    ///
+    /// The amounts are only added up when all accounts hold the same commodity.
+    async fn actuals(
+        &self,
+        account: &Account<Q>,
+        periods: &[(NaiveDate, NaiveDate)],
+    ) -> Result<Vec<crate::Num>, Error> {
+        let mut result = vec![crate::Num::default(); periods.len()];
+        let (Some((from, _)), Some((_, to))) = (periods.first(), periods.last()) else {
+            return Ok(result);
+        };
+        let (from, to) = (from.and_time(NaiveTime::MIN), to.and_time(NaiveTime::MIN));
+
+        let mut pending = vec![account.clone()];
+        while let Some(x) = pending.pop() {
+            if x.commodity_guid != account.commodity_guid {
+                tracing::error!(account_guid = %x.guid, "account holds another commodity");
+                return Err(Error::Invalid {
+                    model: "Budget".to_string(),
+                    reason: format!(
+                        "{} holds another commodity than {}, amounts cannot be added up",
+                        x.name, account.name
+                    ),
+                });
+            }
+
+            for (date, split) in x.dated_splits_between(Some(from), Some(to)).await? {
+                let date = date.date();
+                if let Some(i) = periods
+                    .iter()
+                    .position(|(start, end)| *start <= date && date < *end)
+                {
+                    result[i] += split.quantity;
+                }
+            }
+            pending.extend(x.children().await?);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::query::sqlite::tests::setup_with;
+        use crate::query::tests::fixtures::BUDGET;
+        use crate::{Book, SQLiteQuery};
+
+        use super::super::*;
+
+        async fn setup() -> (tempfile::TempDir, Book<SQLiteQuery>) {
+            let (dir, query) = setup_with(&[BUDGET]);
+            (dir, Book::new(query).await.unwrap())
+        }
+
+        async fn expense(book: &Book<SQLiteQuery>) -> Account<SQLiteQuery> {
+            book.accounts()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "af88d386d44b14acf244362b85ccaf4c")
+                .unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_budgets() {
+            let (_dir, book) = setup().await;
+            let budgets = book.budgets().await.unwrap();
+            assert_eq!(budgets.len(), 1);
+            assert_eq!(budgets[0].name, "Household");
+            assert_eq!(budgets[0].description, "monthly spending");
+            assert_eq!(budgets[0].num_periods, 12);
+        }
+
+        #[test(tokio::test)]
+        async fn test_period() {
+            let (_dir, book) = setup().await;
+            let budget = book.budgets().await.unwrap().remove(0);
+            assert_eq!(
+                budget.period(1).await.unwrap(),
+                (
+                    NaiveDate::from_ymd_opt(2018, 2, 1).unwrap(),
+                    NaiveDate::from_ymd_opt(2018, 3, 1).unwrap()
+                )
+            );
+            assert!(matches!(
+                budget.period(12).await,
+                Err(Error::Invalid { .. })
+            ));
+        }
+
+        #[test(tokio::test)]
+        async fn test_amount() {
+            let (_dir, book) = setup().await;
+            let budget = book.budgets().await.unwrap().remove(0);
+            let account = expense(&book).await;
+
+            let amount = budget.amount(&account, 1).await.unwrap().unwrap();
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, amount, 50.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(amount, Decimal::new(50, 0));
+            assert_eq!(budget.amount(&account, 0).await.unwrap(), None);
+        }
+
+        #[test(tokio::test)]
+        async fn test_actual() {
+            let (_dir, book) = setup().await;
+            let budget = book.budgets().await.unwrap().remove(0);
+            let account = expense(&book).await;
+
+            let actual = budget.actual(&account, 1).await.unwrap();
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, actual, 30.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(actual, Decimal::new(30, 0));
+        }
+
+        #[test(tokio::test)]
+        async fn test_actual_descendants() {
+            let (_dir, query) = setup_with(&[
+                BUDGET,
+                "
+                INSERT INTO accounts (guid, name, account_type, commodity_guid, commodity_scu,
+                                      non_std_scu, parent_guid, code, description, hidden, placeholder)
+                VALUES ('0d2c4e6f8a1b3c5d7e9f0a2b4c6d8e0f', 'Groceries', 'EXPENSE',
+                        '346629655191dcf59a7e2c2a85b70f69', 100, 0, 'af88d386d44b14acf244362b85ccaf4c',
+                        '', '', 0, 0);
+                UPDATE splits SET account_guid = '0d2c4e6f8a1b3c5d7e9f0a2b4c6d8e0f'
+                WHERE guid = 'eb832677cb2a013fcc4140316ccb3323';
+                ",
+            ]);
+            let book = Book::new(query).await.unwrap();
+            let budget = book.budgets().await.unwrap().remove(0);
+            let account = expense(&book).await;
+
+            // the February split now sits on a child of Expense
+            let actual = budget.actual(&account, 1).await.unwrap();
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, actual, 30.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(actual, Decimal::new(30, 0));
+
+            let lines = budget.vs_actual().await.unwrap();
+            let line = lines
+                .iter()
+                .find(|x| x.account.name == "Expense" && x.period == 1)
+                .unwrap();
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, line.actual, 30.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(line.actual, Decimal::new(30, 0));
+        }
+
+        #[test(tokio::test)]
+        async fn test_actual_mixed_commodities() {
+            let (_dir, query) = setup_with(&[
+                BUDGET,
+                "
+                INSERT INTO accounts (guid, name, account_type, commodity_guid, commodity_scu,
+                                      non_std_scu, parent_guid, code, description, hidden, placeholder)
+                VALUES ('0d2c4e6f8a1b3c5d7e9f0a2b4c6d8e0f', 'Travel', 'EXPENSE',
+                        '1e5d65e2726a5d4595741cb204992991', 100, 0, 'af88d386d44b14acf244362b85ccaf4c',
+                        '', '', 0, 0);
+                ",
+            ]);
+            let book = Book::new(query).await.unwrap();
+            let budget = book.budgets().await.unwrap().remove(0);
+            let account = expense(&book).await;
+
+            // USD amounts cannot be added to the EUR ones of Expense
+            assert!(matches!(
+                budget.actual(&account, 1).await,
+                Err(Error::Invalid { .. })
+            ));
+            assert!(matches!(
+                budget.vs_actual().await,
+                Err(Error::Invalid { .. })
+            ));
+        }
+
+        #[test(tokio::test)]
+        async fn test_vs_actual() {
+            let (_dir, book) = setup().await;
+            let budget = book.budgets().await.unwrap().remove(0);
+            let lines = budget.vs_actual().await.unwrap();
+
+            // income and expense, 12 periods each
+            assert_eq!(lines.len(), 24);
+            let line = lines
+                .iter()
+                .find(|x| x.account.name == "Expense" && x.period == 1)
+                .unwrap();
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, line.budgeted.unwrap(), 50.0);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, line.actual, 30.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(line.budgeted, Some(Decimal::new(50, 0)));
+            #[cfg(feature = "decimal")]
+            assert_eq!(line.actual, Decimal::new(30, 0));
+
+            let line = lines
+                .iter()
+                .find(|x| x.account.name == "Income" && x.period == 0)
+                .unwrap();
+            assert_eq!(line.budgeted, None);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, line.actual, 0.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(line.actual, Decimal::ZERO);
+        }
+    }
+
+    #[cfg(feature = "xml")]
+    mod xml {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use std::io::{Read, Write};
+
+        use flate2::Compression;
+        use flate2::read::GzDecoder;
+        use flate2::write::GzEncoder;
+        use pretty_assertions::assert_eq;
+        use test_log::test;
+
+        use crate::{Book, XMLQuery};
+
+        /// Writes a copy of the sample book with a monthly budget for 2018, as the fixture has none.
+        async fn setup() -> (tempfile::TempDir, Book<XMLQuery>) {
+            let path = format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let mut data = String::new();
+            GzDecoder::new(std::fs::File::open(path).unwrap())
+                .read_to_string(&mut data)
+                .unwrap();
+
+            let data = data.replace(
+                "</gnc:book>",
+                r#"<gnc:budget version="2.0.0">
+                  <bgt:id type="guid">5f1a0c7d1e9b4b6f8a2d3c4e5f607182</bgt:id>
+                  <bgt:name>Household</bgt:name>
+                  <bgt:num-periods>12</bgt:num-periods>
+                  <bgt:recurrence version="1.0.0">
+                    <recurrence:mult>1</recurrence:mult>
+                    <recurrence:period_type>month</recurrence:period_type>
+                    <recurrence:start>
+                      <gdate>2018-01-01</gdate>
+                    </recurrence:start>
+                  </bgt:recurrence>
+                  <bgt:slots>
+                    <slot>
+                      <slot:key>af88d386d44b14acf244362b85ccaf4c</slot:key>
+                      <slot:value type="frame">
+                        <slot>
+                          <slot:key>1</slot:key>
+                          <slot:value type="numeric">5000/100</slot:value>
+                        </slot>
+                      </slot:value>
+                    </slot>
+                  </bgt:slots>
+                </gnc:budget>
+                </gnc:book>"#,
+            );
+
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("complex_sample.gnucash");
+            let mut encoder = GzEncoder::new(
+                std::fs::File::create(&path).unwrap(),
+                Compression::default(),
+            );
+            encoder.write_all(data.as_bytes()).unwrap();
+            encoder.finish().unwrap();
+
+            let query = XMLQuery::new(path.to_str().unwrap()).unwrap();
+            (dir, Book::new(query).await.unwrap())
+        }
+
+        #[test(tokio::test)]
+        async fn test_vs_actual() {
+            let (_dir, book) = setup().await;
+            let budget = book.budgets().await.unwrap().remove(0);
+            assert_eq!(budget.name, "Household");
+            assert_eq!(budget.description, "");
+
+            let lines = budget.vs_actual().await.unwrap();
+            assert_eq!(lines.len(), 12);
+            assert_eq!(lines[1].account.name, "Expense");
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, lines[1].budgeted.unwrap(), 50.0);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, lines[1].actual, 30.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(lines[1].budgeted, Some(Decimal::new(50, 0)));
+            #[cfg(feature = "decimal")]
+            assert_eq!(lines[1].actual, Decimal::new(30, 0));
+        }
+    }
+}
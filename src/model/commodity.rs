@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use tracing::instrument;
 
 use crate::book::Book;
 use crate::error::Error;
+use crate::model::slot::{self, Slot};
 use crate::model::{Account, Price, Transaction};
 use crate::query::{AccountQ, CommodityT, PriceQ, Query, TransactionQ};
 
@@ -73,6 +75,17 @@ where
         Ok(result)
     }
 
+    #[instrument(skip(self), fields(commodity_guid = %self.guid))]
+    pub async fn slots(&self) -> Result<BTreeMap<String, Slot>, Error> {
+        slot::load(&*self.query, &self.guid).await
+    }
+
+    /// The slot at `path`, e.g. `user_symbol`.
+    #[instrument(skip(self), fields(commodity_guid = %self.guid))]
+    pub async fn slot(&self, path: &str) -> Result<Option<Slot>, Error> {
+        slot::find(&*self.query, &self.guid, path).await
+    }
+
     #[instrument(skip(self), fields(commodity_guid = %self.guid))]
     pub async fn as_commodity_prices(&self) -> Result<Vec<Price<Q>>, Error> {
         tracing::debug!("fetching prices where this is the commodity");
@@ -0,0 +1,161 @@
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::builder::from_fraction;
+use crate::error::Error;
+use crate::model::{Address, BillTerm, Commodity, TaxTable};
+use crate::query::{CommodityQ, CustomerT, Query};
+
+/// Whether the prices on a customer's or vendor's invoices already include tax.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum TaxIncluded {
+    Yes,
+    No,
+    /// As set in the book's options.
+    #[default]
+    UseGlobal,
+}
+
+impl From<&str> for TaxIncluded {
+    fn from(value: &str) -> Self {
+        match value {
+            "YES" => Self::Yes,
+            "NO" => Self::No,
+            _ => Self::UseGlobal,
+        }
+    }
+}
+
+/// Someone the book invoices.
+#[derive(Clone, Debug)]
+pub struct Customer<Q>
+where
+    Q: Query,
+{
+    query: Arc<Q>,
+
+    pub guid: String,
+    pub name: String,
+    /// The customer number, e.g. `000001`.
+    pub id: String,
+    pub notes: String,
+    pub active: bool,
+    /// The discount in percent granted on invoices.
+    pub discount: crate::Num,
+    pub credit_limit: crate::Num,
+    pub currency_guid: String,
+    pub address: Address,
+    pub ship_address: Address,
+    /// The billing terms, empty when there are none.
+    pub terms_guid: String,
+    pub tax_included: TaxIncluded,
+    /// Whether `tax_table_guid` replaces the tax tables of the invoice entries.
+    pub tax_table_override: bool,
+    pub tax_table_guid: String,
+}
+
+impl<Q> Customer<Q>
+where
+    Q: Query,
+{
+    pub(crate) fn from_with_query<T: CustomerT>(item: &T, query: Arc<Q>) -> Self {
+        Self {
+            query,
+
+            guid: item.guid().to_string(),
+            name: item.name().to_string(),
+            id: item.id().to_string(),
+            notes: item.notes().to_string(),
+            active: item.active(),
+            discount: from_fraction(item.discount_num(), item.discount_denom()),
+            credit_limit: from_fraction(item.credit_num(), item.credit_denom()),
+            currency_guid: item.currency_guid().to_string(),
+            address: item.addr(),
+            ship_address: item.shipaddr(),
+            terms_guid: item.terms_guid().to_string(),
+            tax_included: item.tax_included().into(),
+            tax_table_override: item.tax_override(),
+            tax_table_guid: item.taxtable_guid().to_string(),
+        }
+    }
+
+    #[instrument(skip(self), fields(customer_guid = %self.guid, currency_guid = %self.currency_guid))]
+    pub async fn currency(&self) -> Result<Commodity<Q>, Error> {
+        tracing::debug!("fetching currency for customer");
+        let currency = CommodityQ::guid(&*self.query, &self.currency_guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch currency: {e}"))?;
+
+        if let Some(c) = currency {
+            Ok(Commodity::from_with_query(&c, self.query.clone()))
+        } else {
+            tracing::error!("currency not found");
+            Err(Error::GuidNotFound {
+                model: "Commodity".to_string(),
+                guid: self.currency_guid.clone(),
+            })
+        }
+    }
+
+    /// The billing terms, `None` when there are none.
+    #[instrument(skip(self), fields(customer_guid = %self.guid, terms_guid = %self.terms_guid))]
+    pub async fn terms(&self) -> Result<Option<BillTerm>, Error> {
+        tracing::debug!("fetching terms for customer");
+        BillTerm::fetch(&*self.query, &self.terms_guid).await
+    }
+
+    #[instrument(skip(self), fields(customer_guid = %self.guid, taxtable_guid = %self.tax_table_guid))]
+    pub async fn tax_table(&self) -> Result<Option<TaxTable<Q>>, Error> {
+        tracing::debug!("fetching tax table for customer");
+        TaxTable::fetch(&self.query, &self.tax_table_guid).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::Book;
+        use crate::query::sqlite::tests::setup_with;
+        use crate::query::tests::fixtures::BUSINESS;
+
+        use super::super::*;
+
+        #[test(tokio::test)]
+        async fn test_customers() {
+            let (_dir, query) = setup_with(&[BUSINESS]);
+            let book = Book::new(query).await.unwrap();
+            let customers = book.customers().await.unwrap();
+            assert_eq!(customers.len(), 1);
+
+            let customer = &customers[0];
+            assert_eq!(customer.name, "Acme Corp");
+            assert_eq!(customer.active, true);
+            assert_eq!(
+                customer.address.lines(),
+                vec!["Main Street 1", "Springfield"]
+            );
+            assert_eq!(customer.ship_address.name, "Acme Warehouse");
+            assert_eq!(customer.tax_included, TaxIncluded::UseGlobal);
+            assert_eq!(customer.tax_table_override, false);
+            assert_eq!(customer.terms_guid, "");
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, customer.discount, 5.0);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, customer.credit_limit, 1000.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(customer.discount, Decimal::new(5, 0));
+            #[cfg(feature = "decimal")]
+            assert_eq!(customer.credit_limit, Decimal::new(1000, 0));
+
+            assert_eq!(customer.currency().await.unwrap().mnemonic, "EUR");
+        }
+    }
+}
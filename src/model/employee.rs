@@ -0,0 +1,112 @@
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::builder::from_fraction;
+use crate::error::Error;
+use crate::model::{Address, Commodity};
+use crate::query::{CommodityQ, EmployeeT, Query};
+
+/// Someone whose expense vouchers the book pays.
+#[derive(Clone, Debug)]
+pub struct Employee<Q>
+where
+    Q: Query,
+{
+    query: Arc<Q>,
+
+    pub guid: String,
+    pub username: String,
+    pub id: String,
+    pub language: String,
+    pub acl: String,
+    pub active: bool,
+    pub currency_guid: String,
+    /// The credit card account expenses are charged to, empty when there is none.
+    pub credit_card_guid: String,
+    /// The hours of a working day.
+    pub workday: crate::Num,
+    /// The hourly rate.
+    pub rate: crate::Num,
+    pub address: Address,
+}
+
+impl<Q> Employee<Q>
+where
+    Q: Query,
+{
+    pub(crate) fn from_with_query<T: EmployeeT>(item: &T, query: Arc<Q>) -> Self {
+        Self {
+            query,
+
+            guid: item.guid().to_string(),
+            username: item.username().to_string(),
+            id: item.id().to_string(),
+            language: item.language().to_string(),
+            acl: item.acl().to_string(),
+            active: item.active(),
+            currency_guid: item.currency_guid().to_string(),
+            credit_card_guid: item.ccard_guid().to_string(),
+            workday: from_fraction(item.workday_num(), item.workday_denom()),
+            rate: from_fraction(item.rate_num(), item.rate_denom()),
+            address: item.addr(),
+        }
+    }
+
+    #[instrument(skip(self), fields(employee_guid = %self.guid, currency_guid = %self.currency_guid))]
+    pub async fn currency(&self) -> Result<Commodity<Q>, Error> {
+        tracing::debug!("fetching currency for employee");
+        let currency = CommodityQ::guid(&*self.query, &self.currency_guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch currency: {e}"))?;
+
+        if let Some(c) = currency {
+            Ok(Commodity::from_with_query(&c, self.query.clone()))
+        } else {
+            tracing::error!("currency not found");
+            Err(Error::GuidNotFound {
+                model: "Commodity".to_string(),
+                guid: self.currency_guid.clone(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::Book;
+        use crate::query::sqlite::tests::setup_with;
+        use crate::query::tests::fixtures::BUSINESS;
+
+        #[test(tokio::test)]
+        async fn test_employees() {
+            let (_dir, query) = setup_with(&[BUSINESS]);
+            let book = Book::new(query).await.unwrap();
+            let employees = book.employees().await.unwrap();
+            assert_eq!(employees.len(), 1);
+
+            let employee = &employees[0];
+            assert_eq!(employee.username, "jdoe");
+            assert_eq!(employee.active, true);
+            assert_eq!(employee.credit_card_guid, "");
+            assert_eq!(employee.address.email, "jane@example.com");
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, employee.workday, 8.0);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, employee.rate, 25.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(employee.workday, Decimal::new(8, 0));
+            #[cfg(feature = "decimal")]
+            assert_eq!(employee.rate, Decimal::new(25, 0));
+            assert_eq!(employee.currency().await.unwrap().mnemonic, "EUR");
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use chrono::NaiveDateTime;
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::builder::from_fraction;
+use crate::error::Error;
+use crate::model::{Order, TaxTable};
+use crate::query::{EntryT, OrderQ, Query};
+
+/// How an invoice entry's discount is given.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum DiscountType {
+    /// A percentage of the line amount.
+    #[default]
+    Percent,
+    /// A fixed amount.
+    Value,
+}
+
+impl From<&str> for DiscountType {
+    fn from(value: &str) -> Self {
+        match value {
+            "VALUE" => Self::Value,
+            _ => Self::Percent,
+        }
+    }
+}
+
+/// When an invoice entry's discount applies relative to tax.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum DiscountHow {
+    /// Tax is computed on the discounted amount.
+    #[default]
+    PreTax,
+    /// Tax and discount are both computed on the undiscounted amount.
+    SameTime,
+    /// The discount applies to the amount including tax.
+    PostTax,
+}
+
+impl From<&str> for DiscountHow {
+    fn from(value: &str) -> Self {
+        match value {
+            "SAMETIME" => Self::SameTime,
+            "POSTTAX" => Self::PostTax,
+            _ => Self::PreTax,
+        }
+    }
+}
+
+/// A line of an invoice, a bill or an expense voucher.
+///
+/// The `i_` fields describe the line as invoiced to a customer, the `b_`
+/// fields as billed by a vendor or claimed by an employee. A billable bill
+/// line carries both once it is charged on to a customer.
+#[derive(Clone, Debug)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Entry<Q>
+where
+    Q: Query,
+{
+    query: Arc<Q>,
+
+    pub guid: String,
+    pub date: NaiveDateTime,
+    pub date_entered: Option<NaiveDateTime>,
+    pub description: String,
+    pub action: String,
+    pub notes: String,
+    pub quantity: crate::Num,
+
+    pub invoice_guid: String,
+    pub i_account_guid: String,
+    pub i_price: crate::Num,
+    pub i_discount: crate::Num,
+    pub i_discount_type: DiscountType,
+    pub i_discount_how: DiscountHow,
+    pub i_taxable: bool,
+    pub i_tax_included: bool,
+    pub i_tax_table_guid: String,
+
+    pub bill_guid: String,
+    pub b_account_guid: String,
+    pub b_price: crate::Num,
+    pub b_taxable: bool,
+    pub b_tax_included: bool,
+    pub b_tax_table_guid: String,
+    /// Whether an employee paid with the company credit card rather than cash.
+    pub b_paid_by_card: bool,
+    /// Whether the bill line may be charged on to a customer.
+    pub billable: bool,
+
+    pub order_guid: String,
+}
+
+impl<Q> Entry<Q>
+where
+    Q: Query,
+{
+    pub(crate) fn from_with_query<T: EntryT>(item: &T, query: Arc<Q>) -> Self {
+        Self {
+            query,
+
+            guid: item.guid().to_string(),
+            date: item.date(),
+            date_entered: item.date_entered(),
+            description: item.description().to_string(),
+            action: item.action().to_string(),
+            notes: item.notes().to_string(),
+            quantity: from_fraction(item.quantity_num(), item.quantity_denom()),
+
+            invoice_guid: item.invoice_guid().to_string(),
+            i_account_guid: item.i_acct_guid().to_string(),
+            i_price: from_fraction(item.i_price_num(), item.i_price_denom()),
+            i_discount: from_fraction(item.i_discount_num(), item.i_discount_denom()),
+            i_discount_type: item.i_disc_type().into(),
+            i_discount_how: item.i_disc_how().into(),
+            i_taxable: item.i_taxable(),
+            i_tax_included: item.i_taxincluded(),
+            i_tax_table_guid: item.i_taxtable_guid().to_string(),
+
+            bill_guid: item.bill_guid().to_string(),
+            b_account_guid: item.b_acct_guid().to_string(),
+            b_price: from_fraction(item.b_price_num(), item.b_price_denom()),
+            b_taxable: item.b_taxable(),
+            b_tax_included: item.b_taxincluded(),
+            b_tax_table_guid: item.b_taxtable_guid().to_string(),
+            b_paid_by_card: item.b_paytype() == 2,
+            billable: item.billable(),
+
+            order_guid: item.order_guid().to_string(),
+        }
+    }
+
+    /// The invoiced amount: quantity times price, less the discount.
+    ///
+    /// It includes tax when `i_tax_included` is set. A percentage discount
+    /// is taken from the undiscounted amount whatever `i_discount_how` says.
+    #[must_use]
+    pub fn invoice_amount(&self) -> crate::Num {
+        let gross = self.quantity * self.i_price;
+        let discount = match self.i_discount_type {
+            DiscountType::Percent => gross * self.i_discount / crate::Num::from(100),
+            DiscountType::Value => self.i_discount,
+        };
+        gross - discount
+    }
+
+    /// The billed amount: quantity times price, tax included when `b_tax_included` is set.
+    #[must_use]
+    pub fn bill_amount(&self) -> crate::Num {
+        self.quantity * self.b_price
+    }
+
+    /// The tax on the invoiced amount, zero when the line is not taxable.
+    #[instrument(skip(self), fields(entry_guid = %self.guid, taxtable_guid = %self.i_tax_table_guid))]
+    pub async fn invoice_tax(&self) -> Result<crate::Num, Error> {
+        if !self.i_taxable {
+            return Ok(crate::Num::default());
+        }
+        match TaxTable::fetch(&self.query, &self.i_tax_table_guid).await? {
+            Some(taxtable) => {
+                taxtable
+                    .tax(self.invoice_amount(), self.i_tax_included)
+                    .await
+            }
+            None => Ok(crate::Num::default()),
+        }
+    }
+
+    /// The tax on the billed amount, zero when the line is not taxable.
+    #[instrument(skip(self), fields(entry_guid = %self.guid, taxtable_guid = %self.b_tax_table_guid))]
+    pub async fn bill_tax(&self) -> Result<crate::Num, Error> {
+        if !self.b_taxable {
+            return Ok(crate::Num::default());
+        }
+        match TaxTable::fetch(&self.query, &self.b_tax_table_guid).await? {
+            Some(taxtable) => taxtable.tax(self.bill_amount(), self.b_tax_included).await,
+            None => Ok(crate::Num::default()),
+        }
+    }
+
+    /// The order the entry was filed under, `None` when there is none.
+    #[instrument(skip(self), fields(entry_guid = %self.guid, order_guid = %self.order_guid))]
+    pub async fn order(&self) -> Result<Option<Order<Q>>, Error> {
+        if self.order_guid.is_empty() {
+            return Ok(None);
+        }
+
+        tracing::debug!("fetching order for entry");
+        let order = OrderQ::guid(&*self.query, &self.order_guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch order: {e}"))?;
+
+        Ok(order.map(|x| Order::from_with_query(&x, self.query.clone())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::Book;
+        use crate::query::sqlite::tests::setup_with;
+        use crate::query::tests::fixtures::{BUSINESS, INVOICES};
+
+        use super::super::*;
+
+        #[test(tokio::test)]
+        async fn test_invoice_amount() {
+            let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+            let book = Book::new(query).await.unwrap();
+            let invoice = book
+                .invoices()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "3d4e5f60718293a4b5c6d7e8f90a1b01")
+                .unwrap();
+            let entries = invoice.entries().await.unwrap();
+
+            let hosting = &entries[1];
+            assert_eq!(hosting.i_discount_type, DiscountType::Percent);
+            assert_eq!(hosting.i_discount_how, DiscountHow::PreTax);
+            assert_eq!(hosting.i_taxable, true);
+            assert_eq!(hosting.i_tax_included, false);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, entries[0].invoice_amount(), 500.0);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, hosting.invoice_amount(), 90.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(entries[0].invoice_amount(), Decimal::new(500, 0));
+            #[cfg(feature = "decimal")]
+            assert_eq!(hosting.invoice_amount(), Decimal::new(90, 0));
+
+            let order = entries[0].order().await.unwrap().unwrap();
+            assert_eq!(order.reference, "PO-17");
+            assert_eq!(order.date_closed, None);
+            assert_eq!(order.owner().await.unwrap().name(), "Website");
+            assert!(hosting.order().await.unwrap().is_none());
+        }
+
+        #[test(tokio::test)]
+        async fn test_bill_amount() {
+            let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+            let book = Book::new(query).await.unwrap();
+            let bill = book
+                .invoices()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "3d4e5f60718293a4b5c6d7e8f90a1b02")
+                .unwrap();
+            let entries = bill.entries().await.unwrap();
+            assert_eq!(entries.len(), 1);
+
+            let paper = &entries[0];
+            assert_eq!(paper.b_paid_by_card, true);
+            assert_eq!(paper.b_tax_included, true);
+            assert_eq!(paper.billable, false);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, paper.bill_amount(), 120.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(paper.bill_amount(), Decimal::new(120, 0));
+        }
+    }
+}
@@ -0,0 +1,388 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::builder::from_fraction;
+use crate::error::Error;
+use crate::model::{Account, AccountType, BillTerm, Commodity, Entry, Lot, Owner, Transaction};
+use crate::query::{AccountQ, CommodityQ, EntryQ, InvoiceT, LotQ, Query, TransactionQ};
+
+/// A customer invoice, a vendor bill or an employee expense voucher.
+#[derive(Clone, Debug)]
+pub struct Invoice<Q>
+where
+    Q: Query,
+{
+    query: Arc<Q>,
+
+    pub guid: String,
+    /// The invoice number, e.g. `000001`.
+    pub id: String,
+    pub date_opened: Option<NaiveDateTime>,
+    /// `None` until the invoice is posted.
+    pub date_posted: Option<NaiveDateTime>,
+    pub notes: String,
+    pub active: bool,
+    pub currency_guid: String,
+    owner_type: i64,
+    pub owner_guid: String,
+    /// The billing terms, empty when there are none.
+    pub terms_guid: String,
+    pub billing_id: String,
+    pub post_transaction_guid: String,
+    pub post_lot_guid: String,
+    pub post_account_guid: String,
+    /// The amount charged on to a customer for a billable expense voucher.
+    pub charge_amount: crate::Num,
+}
+
+impl<Q> Invoice<Q>
+where
+    Q: Query,
+{
+    pub(crate) fn from_with_query<T: InvoiceT>(item: &T, query: Arc<Q>) -> Self {
+        Self {
+            query,
+
+            guid: item.guid().to_string(),
+            id: item.id().to_string(),
+            date_opened: item.date_opened(),
+            date_posted: item.date_posted(),
+            notes: item.notes().to_string(),
+            active: item.active(),
+            currency_guid: item.currency_guid().to_string(),
+            owner_type: item.owner_type(),
+            owner_guid: item.owner_guid().to_string(),
+            terms_guid: item.terms_guid().to_string(),
+            billing_id: item.billing_id().to_string(),
+            post_transaction_guid: item.post_txn_guid().to_string(),
+            post_lot_guid: item.post_lot_guid().to_string(),
+            post_account_guid: item.post_acc_guid().to_string(),
+            charge_amount: from_fraction(item.charge_amt_num(), item.charge_amt_denom()),
+        }
+    }
+
+    #[must_use]
+    pub fn is_posted(&self) -> bool {
+        !self.post_transaction_guid.is_empty()
+    }
+
+    /// The customer, job, vendor or employee the invoice is addressed to or from.
+    #[instrument(skip(self), fields(invoice_guid = %self.guid, owner_guid = %self.owner_guid))]
+    pub async fn owner(&self) -> Result<Owner<Q>, Error> {
+        tracing::debug!("fetching owner for invoice");
+        Owner::fetch(&self.query, self.owner_type, &self.owner_guid).await
+    }
+
+    /// The billing terms, `None` when there are none.
+    #[instrument(skip(self), fields(invoice_guid = %self.guid, terms_guid = %self.terms_guid))]
+    pub async fn terms(&self) -> Result<Option<BillTerm>, Error> {
+        tracing::debug!("fetching terms for invoice");
+        BillTerm::fetch(&*self.query, &self.terms_guid).await
+    }
+
+    #[instrument(skip(self), fields(invoice_guid = %self.guid, currency_guid = %self.currency_guid))]
+    pub async fn currency(&self) -> Result<Commodity<Q>, Error> {
+        tracing::debug!("fetching currency for invoice");
+        let currency = CommodityQ::guid(&*self.query, &self.currency_guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch currency: {e}"))?;
+
+        if let Some(c) = currency {
+            Ok(Commodity::from_with_query(&c, self.query.clone()))
+        } else {
+            tracing::error!("currency not found");
+            Err(Error::GuidNotFound {
+                model: "Commodity".to_string(),
+                guid: self.currency_guid.clone(),
+            })
+        }
+    }
+
+    /// The lines of the invoice in date order.
+    #[instrument(skip(self), fields(invoice_guid = %self.guid))]
+    pub async fn entries(&self) -> Result<Vec<Entry<Q>>, Error> {
+        tracing::debug!("fetching entries for invoice");
+        let entries = EntryQ::invoice(&*self.query, &self.guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch entries: {e}"))?;
+
+        let result: Vec<_> = entries
+            .into_iter()
+            .map(|x| Entry::from_with_query(&x, self.query.clone()))
+            .collect();
+
+        tracing::debug!(count = result.len(), "entries fetched for invoice");
+        Ok(result)
+    }
+
+    /// The transaction that posted the invoice, `None` while it is not posted.
+    #[instrument(skip(self), fields(invoice_guid = %self.guid, transaction_guid = %self.post_transaction_guid))]
+    pub async fn posted_transaction(&self) -> Result<Option<Transaction<Q>>, Error> {
+        if !self.is_posted() {
+            return Ok(None);
+        }
+
+        tracing::debug!("fetching posted transaction for invoice");
+        let transaction = TransactionQ::guid(&*self.query, &self.post_transaction_guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch transaction: {e}"))?;
+
+        if let Some(t) = transaction {
+            Ok(Some(Transaction::from_with_query(&t, self.query.clone())))
+        } else {
+            tracing::error!("transaction not found");
+            Err(Error::GuidNotFound {
+                model: "Transaction".to_string(),
+                guid: self.post_transaction_guid.clone(),
+            })
+        }
+    }
+
+    /// The A/R or A/P account the invoice is posted to, `None` while it is not posted.
+    #[instrument(skip(self), fields(invoice_guid = %self.guid, account_guid = %self.post_account_guid))]
+    pub async fn posted_account(&self) -> Result<Option<Account<Q>>, Error> {
+        if self.post_account_guid.is_empty() {
+            return Ok(None);
+        }
+
+        tracing::debug!("fetching posted account for invoice");
+        let account = AccountQ::guid(&*self.query, &self.post_account_guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch account: {e}"))?;
+
+        if let Some(a) = account {
+            Ok(Some(Account::from_with_query(&a, self.query.clone())))
+        } else {
+            tracing::error!("account not found");
+            Err(Error::GuidNotFound {
+                model: "Account".to_string(),
+                guid: self.post_account_guid.clone(),
+            })
+        }
+    }
+
+    /// The lot holding the posting split and the payments against it,
+    /// `None` while the invoice is not posted.
+    #[instrument(skip(self), fields(invoice_guid = %self.guid, lot_guid = %self.post_lot_guid))]
+    pub async fn lot(&self) -> Result<Option<Lot<Q>>, Error> {
+        if self.post_lot_guid.is_empty() {
+            return Ok(None);
+        }
+
+        tracing::debug!("fetching lot for invoice");
+        let lot = LotQ::guid(&*self.query, &self.post_lot_guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch lot: {e}"))?;
+
+        if let Some(l) = lot {
+            Ok(Some(Lot::from_with_query(&l, self.query.clone())))
+        } else {
+            tracing::error!("lot not found");
+            Err(Error::GuidNotFound {
+                model: "Lot".to_string(),
+                guid: self.post_lot_guid.clone(),
+            })
+        }
+    }
+
+    /// The due date worked out from the billing terms when the invoice was posted,
+    /// or the posting date if it had none. `None` while it is not posted.
+    #[instrument(skip(self), fields(invoice_guid = %self.guid))]
+    pub async fn due_date(&self) -> Result<Option<NaiveDateTime>, Error> {
+        let Some(transaction) = self.posted_transaction().await? else {
+            return Ok(None);
+        };
+
+        let due = transaction.slot("trans-date-due").await?;
+        Ok(due
+            .and_then(|x| x.as_datetime())
+            .or(Some(transaction.post_datetime)))
+    }
+
+    /// What is still owed on the invoice: the balance of its posting lot,
+    /// positive for both invoices and bills. Zero while it is not posted.
+    #[instrument(skip(self), fields(invoice_guid = %self.guid))]
+    pub async fn amount_due(&self) -> Result<crate::Num, Error> {
+        self.amount_due_until(None).await
+    }
+
+    /// What was still owed on the invoice at the end of `as_of`, counting only the
+    /// splits of its posting lot posted by then.
+    #[instrument(skip(self), fields(invoice_guid = %self.guid))]
+    pub async fn amount_due_at(&self, as_of: NaiveDate) -> Result<crate::Num, Error> {
+        self.amount_due_until(Some(as_of)).await
+    }
+
+    async fn amount_due_until(&self, as_of: Option<NaiveDate>) -> Result<crate::Num, Error> {
+        let Some(lot) = self.lot().await? else {
+            return Ok(crate::Num::default());
+        };
+
+        let mut balance = crate::Num::default();
+        for split in lot.splits().await? {
+            if let Some(as_of) = as_of
+                && split.transaction().await?.post_datetime.date() > as_of
+            {
+                continue;
+            }
+            balance += split.quantity;
+        }
+        let account = lot.account().await?;
+
+        tracing::debug!(%balance, account_type = %account.r#type, "posting lot balance");
+        if account.r#type == AccountType::Payable {
+            Ok(-balance)
+        } else {
+            Ok(balance)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        use chrono::NaiveDateTime;
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::Book;
+        use crate::model::Owner;
+        use crate::query::sqlite::tests::setup_with;
+        use crate::query::tests::fixtures::{BUSINESS, INVOICES};
+
+        fn date(s: &str) -> NaiveDateTime {
+            NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_invoice() {
+            let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+            let book = Book::new(query).await.unwrap();
+            let invoices = book.invoices().await.unwrap();
+            assert_eq!(invoices.len(), 3);
+
+            let invoice = invoices
+                .into_iter()
+                .find(|x| x.guid == "3d4e5f60718293a4b5c6d7e8f90a1b01")
+                .unwrap();
+            assert_eq!(invoice.id, "000001");
+            assert_eq!(invoice.is_posted(), true);
+            assert_eq!(invoice.date_posted, Some(date("2018-03-05 10:59:00")));
+            assert_eq!(invoice.currency().await.unwrap().mnemonic, "EUR");
+
+            let Owner::Customer(customer) = invoice.owner().await.unwrap() else {
+                panic!("invoice should be owned by a customer");
+            };
+            assert_eq!(customer.name, "Acme Corp");
+
+            let transaction = invoice.posted_transaction().await.unwrap().unwrap();
+            assert_eq!(transaction.description, "Acme Corp");
+            let account = invoice.posted_account().await.unwrap().unwrap();
+            assert_eq!(account.name, "Accounts Receivable");
+            let lot = invoice.lot().await.unwrap().unwrap();
+            assert_eq!(lot.splits().await.unwrap().len(), 2);
+            assert_eq!(
+                invoice.due_date().await.unwrap(),
+                Some(date("2018-04-04 10:59:00"))
+            );
+
+            let entries = invoice.entries().await.unwrap();
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].description, "Consulting");
+            assert_eq!(entries[1].description, "Hosting");
+
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, invoice.amount_due().await.unwrap(), 390.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(invoice.amount_due().await.unwrap(), Decimal::new(390, 0));
+
+            // the payment is posted on 2018-03-20
+            let before_payment = date("2018-03-19 10:59:00").date();
+            let before_posting = date("2018-03-04 10:59:00").date();
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(
+                f64,
+                invoice.amount_due_at(before_payment).await.unwrap(),
+                590.0
+            );
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(
+                f64,
+                invoice.amount_due_at(before_posting).await.unwrap(),
+                0.0
+            );
+            #[cfg(feature = "decimal")]
+            assert_eq!(
+                invoice.amount_due_at(before_payment).await.unwrap(),
+                Decimal::new(590, 0)
+            );
+            #[cfg(feature = "decimal")]
+            assert_eq!(
+                invoice.amount_due_at(before_posting).await.unwrap(),
+                Decimal::ZERO
+            );
+        }
+
+        #[test(tokio::test)]
+        async fn test_bill() {
+            let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+            let book = Book::new(query).await.unwrap();
+            let bill = book
+                .invoices()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "3d4e5f60718293a4b5c6d7e8f90a1b02")
+                .unwrap();
+
+            let owner = bill.owner().await.unwrap();
+            assert!(matches!(owner, Owner::Vendor(_)));
+            assert_eq!(owner.name(), "Paper Supplies Ltd");
+            // no terms, so due on posting
+            assert_eq!(
+                bill.due_date().await.unwrap(),
+                Some(date("2018-03-10 10:59:00"))
+            );
+
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, bill.amount_due().await.unwrap(), 120.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(bill.amount_due().await.unwrap(), Decimal::new(120, 0));
+        }
+
+        #[test(tokio::test)]
+        async fn test_draft() {
+            let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+            let book = Book::new(query).await.unwrap();
+            let draft = book
+                .invoices()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "3d4e5f60718293a4b5c6d7e8f90a1b03")
+                .unwrap();
+
+            assert_eq!(draft.is_posted(), false);
+            assert!(draft.posted_transaction().await.unwrap().is_none());
+            assert!(draft.lot().await.unwrap().is_none());
+            assert_eq!(draft.due_date().await.unwrap(), None);
+
+            let Owner::Job(job) = draft.owner().await.unwrap() else {
+                panic!("draft should be owned by a job");
+            };
+            assert_eq!(job.name, "Website");
+            assert_eq!(job.owner().await.unwrap().name(), "Acme Corp");
+
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, draft.amount_due().await.unwrap(), 0.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(draft.amount_due().await.unwrap(), Decimal::ZERO);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::model::Owner;
+use crate::query::{JobT, Query};
+
+/// A project done for a customer or by a vendor, invoiced on its own.
+#[derive(Clone, Debug)]
+pub struct Job<Q>
+where
+    Q: Query,
+{
+    query: Arc<Q>,
+
+    pub guid: String,
+    pub id: String,
+    pub name: String,
+    pub reference: String,
+    pub active: bool,
+    owner_type: i64,
+    pub owner_guid: String,
+}
+
+impl<Q> Job<Q>
+where
+    Q: Query,
+{
+    pub(crate) fn from_with_query<T: JobT>(item: &T, query: Arc<Q>) -> Self {
+        Self {
+            query,
+
+            guid: item.guid().to_string(),
+            id: item.id().to_string(),
+            name: item.name().to_string(),
+            reference: item.reference().to_string(),
+            active: item.active(),
+            owner_type: item.owner_type(),
+            owner_guid: item.owner_guid().to_string(),
+        }
+    }
+
+    /// The customer or vendor the job is for.
+    #[instrument(skip(self), fields(job_guid = %self.guid, owner_guid = %self.owner_guid))]
+    pub async fn owner(&self) -> Result<Owner<Q>, Error> {
+        tracing::debug!("fetching owner for job");
+        Owner::fetch(&self.query, self.owner_type, &self.owner_guid).await
+    }
+}
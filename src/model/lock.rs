@@ -0,0 +1,6 @@
+/// An entry of the `gnclock` table, written by the process which has the book open.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Lock {
+    pub hostname: String,
+    pub pid: i64,
+}
@@ -0,0 +1,302 @@
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::model::{Account, Split};
+use crate::query::{AccountQ, LotT, Query, SlotQ, SlotT, SplitQ, SplitT};
+
+/// A group of splits in one account whose quantities offset each other,
+/// e.g. the purchase and later sales of the same shares.
+#[derive(Clone, Debug)]
+pub struct Lot<Q>
+where
+    Q: Query,
+{
+    query: Arc<Q>,
+
+    pub guid: String,
+    pub account_guid: String,
+    is_closed: Option<bool>,
+}
+
+impl<Q> Lot<Q>
+where
+    Q: Query,
+{
+    pub(crate) fn from_with_query<T: LotT>(item: &T, query: Arc<Q>) -> Self {
+        Self {
+            query,
+
+            guid: item.guid().to_string(),
+            account_guid: item.account_guid().to_string(),
+            is_closed: item.is_closed(),
+        }
+    }
+
+    #[instrument(skip(self), fields(lot_guid = %self.guid, account_guid = %self.account_guid))]
+    pub async fn account(&self) -> Result<Account<Q>, Error> {
+        if self.account_guid.is_empty() {
+            tracing::error!("account guid is empty");
+            return Err(Error::GuidNotFound {
+                model: "Account".to_string(),
+                guid: self.account_guid.clone(),
+            });
+        }
+
+        tracing::debug!("fetching account for lot");
+        let account = AccountQ::guid(&*self.query, &self.account_guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch account: {e}"))?;
+
+        if let Some(a) = account {
+            tracing::debug!("account found for lot");
+            Ok(Account::from_with_query(&a, self.query.clone()))
+        } else {
+            tracing::error!("account not found");
+            Err(Error::GuidNotFound {
+                model: "Account".to_string(),
+                guid: self.account_guid.clone(),
+            })
+        }
+    }
+
+    /// The splits assigned to the lot, all of which belong to its account.
+    #[instrument(skip(self), fields(lot_guid = %self.guid, account_guid = %self.account_guid))]
+    pub async fn splits(&self) -> Result<Vec<Split<Q>>, Error> {
+        tracing::debug!("fetching splits for lot");
+        let splits = SplitQ::account(&*self.query, &self.account_guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch splits: {e}"))?;
+
+        let result: Vec<_> = splits
+            .into_iter()
+            .filter(|x| x.lot_guid() == self.guid)
+            .map(|x| Split::from_with_query(&x, self.query.clone()))
+            .collect();
+
+        tracing::debug!(count = result.len(), "splits fetched for lot");
+        Ok(result)
+    }
+
+    /// Whether the quantities of the lot's splits sum to zero.
+    ///
+    /// Uses the state cached by the SQL backends; XML books do not store it,
+    /// so it is computed from the splits as `GnuCash` does.
+    #[instrument(skip(self), fields(lot_guid = %self.guid))]
+    pub async fn is_closed(&self) -> Result<bool, Error> {
+        if let Some(is_closed) = self.is_closed {
+            return Ok(is_closed);
+        }
+
+        let splits = self.splits().await?;
+        let balance: crate::Num = splits.iter().map(|x| x.quantity).sum();
+
+        #[cfg(not(feature = "decimal"))]
+        let is_zero = balance.abs() < f64::EPSILON;
+        #[cfg(feature = "decimal")]
+        let is_zero = balance.is_zero();
+
+        Ok(!splits.is_empty() && is_zero)
+    }
+
+    /// The title shown in the lot viewer, `None` if it was never set.
+    #[instrument(skip(self), fields(lot_guid = %self.guid))]
+    pub async fn title(&self) -> Result<Option<String>, Error> {
+        self.string_slot("title").await
+    }
+
+    /// The notes entered in the lot viewer, `None` if there are none.
+    #[instrument(skip(self), fields(lot_guid = %self.guid))]
+    pub async fn notes(&self) -> Result<Option<String>, Error> {
+        self.string_slot("notes").await
+    }
+
+    async fn string_slot(&self, name: &str) -> Result<Option<String>, Error> {
+        tracing::debug!(name, "fetching slot for lot");
+        let slots = SlotQ::obj_guid(&*self.query, &self.guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch slots: {e}"))?;
+
+        Ok(slots
+            .into_iter()
+            .find(|x| x.name() == name)
+            .map(|x| x.string_val().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        use pretty_assertions::assert_eq;
+        use test_log::test;
+
+        use crate::query::sqlite::tests::setup_with;
+        use crate::query::tests::fixtures::LOT;
+        use crate::{Book, SQLiteQuery};
+
+        use super::super::*;
+
+        async fn setup() -> (tempfile::TempDir, Book<SQLiteQuery>) {
+            let (dir, query) = setup_with(&[LOT]);
+            (dir, Book::new(query).await.unwrap())
+        }
+
+        #[test(tokio::test)]
+        async fn test_from_with_query() {
+            let (_dir, query) = setup_with(&[LOT]);
+            let item = crate::query::sqlite::lot::Lot {
+                guid: "guid".to_string(),
+                account_guid: Some("account_guid".to_string()),
+                is_closed: 1,
+            };
+
+            let result = Lot::from_with_query(&item, Arc::new(query));
+
+            assert_eq!(result.guid, "guid");
+            assert_eq!(result.account_guid, "account_guid");
+            assert_eq!(result.is_closed().await.unwrap(), true);
+        }
+
+        #[test(tokio::test)]
+        async fn test_account() {
+            let (_dir, book) = setup().await;
+            let lot = book.lots().await.unwrap().remove(0);
+            let account = lot.account().await.unwrap();
+            assert_eq!(account.name, "Foo stock");
+        }
+
+        #[test(tokio::test)]
+        async fn test_splits() {
+            let (_dir, book) = setup().await;
+            let lot = book.lots().await.unwrap().remove(0);
+            let splits = lot.splits().await.unwrap();
+            assert_eq!(splits.len(), 1);
+            assert_eq!(splits[0].guid, "e07539176a70222b16369bd246b174b9");
+            assert_eq!(lot.is_closed().await.unwrap(), false);
+        }
+
+        #[test(tokio::test)]
+        async fn test_slots() {
+            let (_dir, book) = setup().await;
+            let lot = book.lots().await.unwrap().remove(0);
+            assert_eq!(lot.title().await.unwrap(), Some("Lot 1".to_string()));
+            assert_eq!(
+                lot.notes().await.unwrap(),
+                Some("bought on margin".to_string())
+            );
+        }
+
+        #[test(tokio::test)]
+        async fn test_split_lot() {
+            let (_dir, book) = setup().await;
+            let splits = book.splits().await.unwrap();
+            let split = splits
+                .iter()
+                .find(|x| x.guid == "e07539176a70222b16369bd246b174b9")
+                .unwrap();
+            let lot = split.lot().await.unwrap().unwrap();
+            assert_eq!(lot.guid, "a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01");
+
+            let split = splits
+                .iter()
+                .find(|x| x.guid == "de832fe97e37811a7fff7e28b3a43425")
+                .unwrap();
+            assert!(split.lot().await.unwrap().is_none());
+        }
+    }
+
+    #[cfg(feature = "xml")]
+    mod xml {
+        use std::io::{Read, Write};
+
+        use flate2::Compression;
+        use flate2::read::GzDecoder;
+        use flate2::write::GzEncoder;
+        use pretty_assertions::assert_eq;
+        use test_log::test;
+
+        use crate::{Book, XMLQuery};
+
+        /// Writes a copy of the sample book with a lot of `Foo stock`, as the fixture has none.
+        async fn setup() -> (tempfile::TempDir, Book<XMLQuery>) {
+            let path = format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let mut data = String::new();
+            GzDecoder::new(std::fs::File::open(path).unwrap())
+                .read_to_string(&mut data)
+                .unwrap();
+
+            let account = r#"<act:id type="guid">1c089803052e85f5c6d8e786057dbaee</act:id>"#;
+            let split =
+                r#"<split:account type="guid">1c089803052e85f5c6d8e786057dbaee</split:account>"#;
+            let data = data
+                .replace(
+                    account,
+                    &format!(
+                        r#"{account}
+                        <act:lots>
+                          <gnc:lot version="2.0.0">
+                            <lot:id type="guid">a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01</lot:id>
+                            <lot:slots>
+                              <slot>
+                                <slot:key>title</slot:key>
+                                <slot:value type="string">Lot 1</slot:value>
+                              </slot>
+                            </lot:slots>
+                          </gnc:lot>
+                        </act:lots>"#
+                    ),
+                )
+                .replace(
+                    split,
+                    &format!(
+                        r#"{split}
+                        <split:lot type="guid">a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01</split:lot>"#
+                    ),
+                );
+
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("complex_sample.gnucash");
+            let mut encoder = GzEncoder::new(
+                std::fs::File::create(&path).unwrap(),
+                Compression::default(),
+            );
+            encoder.write_all(data.as_bytes()).unwrap();
+            encoder.finish().unwrap();
+
+            let query = XMLQuery::new(path.to_str().unwrap()).unwrap();
+            (dir, Book::new(query).await.unwrap())
+        }
+
+        #[test(tokio::test)]
+        async fn test_lot() {
+            let (_dir, book) = setup().await;
+            let lots = book.lots().await.unwrap();
+            assert_eq!(lots.len(), 1);
+
+            let lot = &lots[0];
+            assert_eq!(lot.account().await.unwrap().name, "Foo stock");
+            assert_eq!(lot.splits().await.unwrap().len(), 1);
+            assert_eq!(lot.is_closed().await.unwrap(), false);
+            assert_eq!(lot.title().await.unwrap(), Some("Lot 1".to_string()));
+            assert_eq!(lot.notes().await.unwrap(), None);
+        }
+
+        #[test(tokio::test)]
+        async fn test_split_lot() {
+            let (_dir, book) = setup().await;
+            let split = book
+                .splits()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "e07539176a70222b16369bd246b174b9")
+                .unwrap();
+            let lot = split.lot().await.unwrap().unwrap();
+            assert_eq!(lot.guid, "a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01");
+        }
+    }
+}
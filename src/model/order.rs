@@ -0,0 +1,54 @@
+use chrono::NaiveDateTime;
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::model::Owner;
+use crate::query::{OrderT, Query};
+
+/// A purchase or sales order the entries of invoices and bills can be filed under.
+#[derive(Clone, Debug)]
+pub struct Order<Q>
+where
+    Q: Query,
+{
+    query: Arc<Q>,
+
+    pub guid: String,
+    pub id: String,
+    pub notes: String,
+    pub reference: String,
+    pub active: bool,
+    pub date_opened: NaiveDateTime,
+    /// `None` while the order is open.
+    pub date_closed: Option<NaiveDateTime>,
+    owner_type: i64,
+    pub owner_guid: String,
+}
+
+impl<Q> Order<Q>
+where
+    Q: Query,
+{
+    pub(crate) fn from_with_query<T: OrderT>(item: &T, query: Arc<Q>) -> Self {
+        Self {
+            query,
+
+            guid: item.guid().to_string(),
+            id: item.id().to_string(),
+            notes: item.notes().to_string(),
+            reference: item.reference().to_string(),
+            active: item.active(),
+            date_opened: item.date_opened(),
+            date_closed: item.date_closed(),
+            owner_type: item.owner_type(),
+            owner_guid: item.owner_guid().to_string(),
+        }
+    }
+
+    #[instrument(skip(self), fields(order_guid = %self.guid, owner_guid = %self.owner_guid))]
+    pub async fn owner(&self) -> Result<Owner<Q>, Error> {
+        tracing::debug!("fetching owner for order");
+        Owner::fetch(&self.query, self.owner_type, &self.owner_guid).await
+    }
+}
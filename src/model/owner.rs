@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use crate::error::Error;
+use crate::model::{Customer, Employee, Job, Vendor};
+use crate::query::{CustomerQ, EmployeeQ, JobQ, Query, VendorQ};
+
+/// Who an invoice, a job or an order belongs to.
+#[derive(Clone, Debug)]
+pub enum Owner<Q>
+where
+    Q: Query,
+{
+    Customer(Customer<Q>),
+    Job(Job<Q>),
+    Vendor(Vendor<Q>),
+    Employee(Employee<Q>),
+}
+
+impl<Q> Owner<Q>
+where
+    Q: Query,
+{
+    /// Looks up the owner stored as a `GncOwnerType` and a guid.
+    pub(crate) async fn fetch(query: &Arc<Q>, owner_type: i64, guid: &str) -> Result<Self, Error> {
+        let owner = match owner_type {
+            2 => CustomerQ::guid(&**query, guid)
+                .await?
+                .map(|x| Self::Customer(Customer::from_with_query(&x, query.clone()))),
+            3 => JobQ::guid(&**query, guid)
+                .await?
+                .map(|x| Self::Job(Job::from_with_query(&x, query.clone()))),
+            4 => VendorQ::guid(&**query, guid)
+                .await?
+                .map(|x| Self::Vendor(Vendor::from_with_query(&x, query.clone()))),
+            5 => EmployeeQ::guid(&**query, guid)
+                .await?
+                .map(|x| Self::Employee(Employee::from_with_query(&x, query.clone()))),
+            _ => None,
+        };
+
+        owner.ok_or_else(|| {
+            tracing::error!(owner_type, "owner not found");
+            Error::GuidNotFound {
+                model: "Owner".to_string(),
+                guid: guid.to_string(),
+            }
+        })
+    }
+
+    #[must_use]
+    pub fn guid(&self) -> &str {
+        match self {
+            Self::Customer(x) => &x.guid,
+            Self::Job(x) => &x.guid,
+            Self::Vendor(x) => &x.guid,
+            Self::Employee(x) => &x.guid,
+        }
+    }
+
+    /// The display name; an employee's is the name on their address.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Customer(x) => &x.name,
+            Self::Job(x) => &x.name,
+            Self::Vendor(x) => &x.name,
+            Self::Employee(x) => &x.address.name,
+        }
+    }
+}
@@ -0,0 +1,409 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::builder::to_fraction;
+use crate::error::Error;
+use crate::model::{Account, ReconcileState, Split, day_after};
+use crate::query::{Query, ReconcileRecord, SplitMutQ};
+
+/// Reconciles an account against a bank statement, like the `GnuCash` reconcile window.
+///
+/// Candidates are the unreconciled and cleared splits posted up to the statement date;
+/// the cleared ones start out marked. Once the marked splits account for the statement's
+/// ending balance, [`Reconciliation::finish`] flags them as reconciled.
+#[derive(Clone, Debug)]
+pub struct Reconciliation<Q>
+where
+    Q: Query,
+{
+    query: Arc<Q>,
+
+    pub account_guid: String,
+    pub statement_date: NaiveDate,
+    pub ending_balance: crate::Num,
+    /// Balance of the splits reconciled on earlier statements.
+    pub starting_balance: crate::Num,
+    commodity_scu: i64,
+    candidates: Vec<Split<Q>>,
+    marked: HashSet<String>,
+}
+
+impl<Q> Reconciliation<Q>
+where
+    Q: Query,
+{
+    #[instrument(skip(account, query), fields(account_guid = %account.guid))]
+    pub(crate) async fn new(
+        account: &Account<Q>,
+        query: Arc<Q>,
+        statement_date: NaiveDate,
+        ending_balance: crate::Num,
+    ) -> Result<Self, Error> {
+        tracing::debug!("starting reconciliation");
+
+        let starting_balance = account
+            .splits()
+            .await?
+            .iter()
+            .filter(|x| {
+                matches!(
+                    x.reconcile_status,
+                    ReconcileState::Reconciled | ReconcileState::Frozen
+                )
+            })
+            .map(|x| x.quantity)
+            .sum();
+        let candidates: Vec<_> = account
+            .dated_splits_between(None, Some(day_after(statement_date)?))
+            .await?
+            .into_iter()
+            .map(|(_, x)| x)
+            .filter(|x| {
+                matches!(
+                    x.reconcile_status,
+                    ReconcileState::Unreconciled | ReconcileState::Cleared
+                )
+            })
+            .collect();
+
+        let marked = candidates
+            .iter()
+            .filter(|x| x.reconcile_status == ReconcileState::Cleared)
+            .map(|x| x.guid.clone())
+            .collect();
+
+        tracing::debug!(count = candidates.len(), "reconciliation candidates found");
+        Ok(Self {
+            query,
+
+            account_guid: account.guid.clone(),
+            statement_date,
+            ending_balance,
+            starting_balance,
+            commodity_scu: account.commodity_scu,
+            candidates,
+            marked,
+        })
+    }
+
+    /// The splits which may appear on the statement, oldest first.
+    #[must_use]
+    pub fn candidates(&self) -> &[Split<Q>] {
+        &self.candidates
+    }
+
+    #[must_use]
+    pub fn is_marked(&self, guid: &str) -> bool {
+        self.marked.contains(guid)
+    }
+
+    /// Marks a candidate split as appearing on the statement.
+    pub fn mark(&mut self, guid: &str) -> Result<(), Error> {
+        if !self.candidates.iter().any(|x| x.guid == guid) {
+            return Err(Error::GuidNotFound {
+                model: "Split".to_string(),
+                guid: guid.to_string(),
+            });
+        }
+        self.marked.insert(guid.to_string());
+        Ok(())
+    }
+
+    pub fn unmark(&mut self, guid: &str) {
+        self.marked.remove(guid);
+    }
+
+    pub fn mark_all(&mut self) {
+        self.marked = self.candidates.iter().map(|x| x.guid.clone()).collect();
+    }
+
+    /// The starting balance plus the marked splits.
+    #[must_use]
+    pub fn cleared_balance(&self) -> crate::Num {
+        self.candidates
+            .iter()
+            .filter(|x| self.marked.contains(&x.guid))
+            .fold(self.starting_balance, |acc, x| acc + x.quantity)
+    }
+
+    /// What is left to explain: the ending balance minus the cleared balance.
+    #[must_use]
+    pub fn difference(&self) -> crate::Num {
+        self.ending_balance - self.cleared_balance()
+    }
+
+    /// Whether the difference is zero in the smallest unit of the account.
+    pub fn is_balanced(&self) -> Result<bool, Error> {
+        Ok(to_fraction(self.difference(), self.commodity_scu)? == 0)
+    }
+
+    fn records(
+        &self,
+        state: ReconcileState,
+        datetime: Option<NaiveDateTime>,
+    ) -> Vec<ReconcileRecord> {
+        self.candidates
+            .iter()
+            .filter(|x| self.marked.contains(&x.guid))
+            .map(|x| ReconcileRecord {
+                guid: x.guid.clone(),
+                reconcile_state: state.into(),
+                reconcile_datetime: datetime,
+            })
+            .collect()
+    }
+}
+
+impl<Q> Reconciliation<Q>
+where
+    Q: Query + SplitMutQ,
+{
+    /// Saves the marks for later: marked splits become cleared, unmarked cleared
+    /// splits go back to unreconciled.
+    #[instrument(skip(self), fields(account_guid = %self.account_guid))]
+    pub async fn postpone(&mut self) -> Result<(), Error> {
+        let mut records = self.records(ReconcileState::Cleared, None);
+        records.extend(
+            self.candidates
+                .iter()
+                .filter(|x| {
+                    x.reconcile_status == ReconcileState::Cleared && !self.marked.contains(&x.guid)
+                })
+                .map(|x| ReconcileRecord {
+                    guid: x.guid.clone(),
+                    reconcile_state: ReconcileState::Unreconciled.into(),
+                    reconcile_datetime: None,
+                }),
+        );
+
+        SplitMutQ::reconcile(&*self.query, &records)
+            .await
+            .inspect_err(|e| tracing::error!("failed to postpone reconciliation: {e}"))?;
+
+        for split in &mut self.candidates {
+            split.reconcile_status = if self.marked.contains(&split.guid) {
+                ReconcileState::Cleared
+            } else {
+                ReconcileState::Unreconciled
+            };
+            // only reconciled splits count as such in the writable SQL backends
+            split.reconcile_state = false;
+        }
+        tracing::debug!("reconciliation postponed");
+        Ok(())
+    }
+
+    /// Flags the marked splits as reconciled on the statement date.
+    ///
+    /// Fails unless the marked splits account for the ending balance.
+    #[instrument(skip(self), fields(account_guid = %self.account_guid))]
+    pub async fn finish(self) -> Result<(), Error> {
+        if !self.is_balanced()? {
+            tracing::error!("reconciliation does not balance");
+            return Err(Error::Invalid {
+                model: "Reconciliation".to_string(),
+                reason: format!("difference of {} is left", self.difference()),
+            });
+        }
+
+        let datetime = self.statement_date.and_hms_opt(23, 59, 59);
+        let records = self.records(ReconcileState::Reconciled, datetime);
+        SplitMutQ::reconcile(&*self.query, &records)
+            .await
+            .inspect_err(|e| tracing::error!("failed to finish reconciliation: {e}"))?;
+
+        tracing::debug!(count = records.len(), "reconciliation finished");
+        Ok(())
+    }
+}
+
+#[cfg(all(test, any(feature = "sqlite", feature = "xml")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::query::sqlite::tests::setup_writable;
+        use crate::{Book, SQLiteQuery};
+
+        use super::*;
+
+        async fn cash(book: &Book<SQLiteQuery>) -> Account<SQLiteQuery> {
+            book.accounts()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.name == "Cash")
+                .unwrap()
+        }
+
+        fn date(s: &str) -> NaiveDate {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_finish() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let account = cash(&book).await;
+
+            let mut session = account
+                .reconcile(date("2015-01-01"), crate::Num::from(150))
+                .await
+                .unwrap();
+            assert_eq!(session.candidates().len(), 1);
+            assert!(!session.is_balanced().unwrap());
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, session.difference(), 150.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(session.difference(), Decimal::new(150, 0));
+
+            session.mark("de832fe97e37811a7fff7e28b3a43425").unwrap();
+            assert!(session.is_balanced().unwrap());
+            session.finish().await.unwrap();
+
+            let split = account
+                .splits()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "de832fe97e37811a7fff7e28b3a43425")
+                .unwrap();
+            assert_eq!(split.reconcile_status, ReconcileState::Reconciled);
+            assert_eq!(split.reconcile_state, true);
+            assert_eq!(
+                split.reconcile_datetime,
+                date("2015-01-01").and_hms_opt(23, 59, 59)
+            );
+
+            let mut session = account
+                .reconcile(date("2018-12-31"), crate::Num::from(220))
+                .await
+                .unwrap();
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, session.starting_balance, 150.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(session.starting_balance, Decimal::new(150, 0));
+            assert_eq!(session.candidates().len(), 2);
+            session.mark_all();
+            assert!(session.is_balanced().unwrap());
+        }
+
+        #[test(tokio::test)]
+        async fn test_finish_unbalanced() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let account = cash(&book).await;
+
+            let mut session = account
+                .reconcile(date("2018-12-31"), crate::Num::from(100))
+                .await
+                .unwrap();
+            assert!(matches!(
+                session.mark("unknown"),
+                Err(Error::GuidNotFound { .. })
+            ));
+            session.mark_all();
+            assert!(matches!(session.finish().await, Err(Error::Invalid { .. })));
+            assert!(
+                account
+                    .splits()
+                    .await
+                    .unwrap()
+                    .iter()
+                    .all(|x| x.reconcile_status == ReconcileState::Unreconciled)
+            );
+        }
+
+        #[test(tokio::test)]
+        async fn test_postpone() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let account = cash(&book).await;
+
+            let mut session = account
+                .reconcile(date("2018-12-31"), crate::Num::from(220))
+                .await
+                .unwrap();
+            session.mark("42336a5732d54b22fa080be3b50ff5b7").unwrap();
+            session.postpone().await.unwrap();
+
+            let stored = account.splits().await.unwrap();
+            for split in session.candidates() {
+                let x = stored.iter().find(|x| x.guid == split.guid).unwrap();
+                assert_eq!(split.reconcile_state, x.reconcile_state);
+                assert_eq!(split.reconcile_status, x.reconcile_status);
+            }
+
+            let mut session = account
+                .reconcile(date("2018-12-31"), crate::Num::from(220))
+                .await
+                .unwrap();
+            assert!(session.is_marked("42336a5732d54b22fa080be3b50ff5b7"));
+            assert!(!session.is_marked("de832fe97e37811a7fff7e28b3a43425"));
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, session.cleared_balance(), 100.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(session.cleared_balance(), Decimal::new(100, 0));
+
+            session.unmark("42336a5732d54b22fa080be3b50ff5b7");
+            session.postpone().await.unwrap();
+            assert!(
+                account
+                    .splits()
+                    .await
+                    .unwrap()
+                    .iter()
+                    .all(|x| x.reconcile_status == ReconcileState::Unreconciled)
+            );
+        }
+    }
+
+    #[cfg(feature = "xml")]
+    mod xml {
+        use pretty_assertions::assert_eq;
+        use test_log::test;
+
+        use crate::{Book, XMLQuery};
+
+        use super::*;
+
+        #[test(tokio::test)]
+        async fn test_candidates() {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let book = Book::new(XMLQuery::new(path).unwrap()).await.unwrap();
+            let account = book
+                .accounts()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.name == "Cash")
+                .unwrap();
+
+            let date = NaiveDate::from_ymd_opt(2018, 12, 31).unwrap();
+            let mut session = account
+                .reconcile(date, crate::Num::from(220))
+                .await
+                .unwrap();
+            assert_eq!(session.candidates().len(), 3);
+            assert_eq!(
+                session.candidates()[0].guid,
+                "de832fe97e37811a7fff7e28b3a43425"
+            );
+            session.mark_all();
+            assert!(session.is_balanced().unwrap());
+        }
+    }
+}
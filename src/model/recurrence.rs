@@ -0,0 +1,288 @@
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+use crate::error::Error;
+use crate::query::RecurrenceT;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum PeriodType {
+    Once,
+    Day,
+    Week,
+    Month,
+    EndOfMonth,
+    /// The same weekday in the same week of the month, e.g. the second Tuesday.
+    NthWeekday,
+    /// The last occurrence of the weekday in the month.
+    LastWeekday,
+    Year,
+}
+
+impl TryFrom<&str> for PeriodType {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "once" => Ok(Self::Once),
+            "day" => Ok(Self::Day),
+            "week" => Ok(Self::Week),
+            "month" => Ok(Self::Month),
+            "end of month" => Ok(Self::EndOfMonth),
+            "nth weekday" => Ok(Self::NthWeekday),
+            "last weekday" => Ok(Self::LastWeekday),
+            "year" => Ok(Self::Year),
+            _ => Err(Error::Invalid {
+                model: "Recurrence".to_string(),
+                reason: format!("unknown period type {value:?}"),
+            }),
+        }
+    }
+}
+
+/// How a monthly or yearly date falling on a weekend is moved.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum WeekendAdjust {
+    #[default]
+    None,
+    /// To the preceding Friday.
+    Back,
+    /// To the following Monday.
+    Forward,
+}
+
+impl From<&str> for WeekendAdjust {
+    fn from(value: &str) -> Self {
+        match value {
+            "back" => Self::Back,
+            "forward" => Self::Forward,
+            _ => Self::None,
+        }
+    }
+}
+
+/// A schedule of dates, as used for budget periods and scheduled transactions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Recurrence {
+    pub multiplier: i64,
+    pub period_type: PeriodType,
+    pub start: NaiveDate,
+    pub weekend_adjust: WeekendAdjust,
+}
+
+impl Recurrence {
+    pub(crate) fn from_item<T: RecurrenceT>(item: &T) -> Result<Self, Error> {
+        if item.mult() < 1 {
+            return Err(Error::Invalid {
+                model: "Recurrence".to_string(),
+                reason: format!("multiplier {} is not positive", item.mult()),
+            });
+        }
+
+        Ok(Self {
+            multiplier: item.mult(),
+            period_type: item.period_type().try_into()?,
+            start: item.period_start(),
+            weekend_adjust: item.weekend_adjust().into(),
+        })
+    }
+
+    /// The date of the `n`-th occurrence, the start being the 0-th.
+    ///
+    /// `None` past the single occurrence of [`PeriodType::Once`] or when the date overflows.
+    #[must_use]
+    pub fn nth(&self, n: i64) -> Option<NaiveDate> {
+        let steps = u32::try_from(n.checked_mul(self.multiplier)?).ok()?;
+        let start = self.start;
+
+        let date = match self.period_type {
+            PeriodType::Once => (steps == 0).then_some(start)?,
+            PeriodType::Day => start.checked_add_days(Days::new(steps.into()))?,
+            PeriodType::Week => start.checked_add_days(Days::new(u64::from(steps) * 7))?,
+            PeriodType::Month => start.checked_add_months(Months::new(steps))?,
+            PeriodType::EndOfMonth => last_day_of_month(month_after(start, steps)?)?,
+            PeriodType::NthWeekday => {
+                let month = month_after(start, steps)?;
+                let week = u8::try_from(start.day0() / 7 + 1).ok()?;
+                NaiveDate::from_weekday_of_month_opt(
+                    month.year(),
+                    month.month(),
+                    start.weekday(),
+                    week,
+                )?
+            }
+            PeriodType::LastWeekday => {
+                let last = last_day_of_month(month_after(start, steps)?)?;
+                let back = (7 + last.weekday().num_days_from_monday()
+                    - start.weekday().num_days_from_monday())
+                    % 7;
+                last.checked_sub_days(Days::new(back.into()))?
+            }
+            PeriodType::Year => start.checked_add_months(Months::new(steps.checked_mul(12)?))?,
+        };
+
+        match self.period_type {
+            PeriodType::Month | PeriodType::EndOfMonth | PeriodType::Year => {
+                self.adjust_for_weekend(date)
+            }
+            _ => Some(date),
+        }
+    }
+
+    /// The occurrences in order, from the start on.
+    ///
+    /// Unlike [`Recurrence::nth`], months lacking the weekday of a
+    /// [`PeriodType::NthWeekday`] recurrence, such as a fifth Monday, are skipped
+    /// rather than ending the dates.
+    pub fn dates(self) -> impl Iterator<Item = NaiveDate> {
+        (0..)
+            .map_while(move |n| match self.nth(n) {
+                Some(date) => Some(Some(date)),
+                None if self.lacks_weekday(n) => Some(None),
+                None => None,
+            })
+            .flatten()
+    }
+
+    /// Whether the month of the `n`-th occurrence exists but has no matching weekday.
+    fn lacks_weekday(&self, n: i64) -> bool {
+        self.period_type == PeriodType::NthWeekday
+            && n.checked_mul(self.multiplier)
+                .and_then(|x| u32::try_from(x).ok())
+                .and_then(|steps| month_after(self.start, steps))
+                .is_some()
+    }
+
+    fn adjust_for_weekend(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let days: i64 = match (self.weekend_adjust, date.weekday()) {
+            (WeekendAdjust::Back, Weekday::Sat) => -1,
+            (WeekendAdjust::Back, Weekday::Sun) => -2,
+            (WeekendAdjust::Forward, Weekday::Sat) => 2,
+            (WeekendAdjust::Forward, Weekday::Sun) => 1,
+            _ => 0,
+        };
+        if days < 0 {
+            date.checked_sub_days(Days::new(days.unsigned_abs()))
+        } else {
+            date.checked_add_days(Days::new(days.unsigned_abs()))
+        }
+    }
+}
+
+/// The first day of the month `months` after the one of `date`.
+fn month_after(date: NaiveDate, months: u32) -> Option<NaiveDate> {
+    date.with_day(1)?.checked_add_months(Months::new(months))
+}
+
+/// The last day of the month that starts on `first`.
+pub(crate) fn last_day_of_month(first: NaiveDate) -> Option<NaiveDate> {
+    first
+        .checked_add_months(Months::new(1))?
+        .checked_sub_days(Days::new(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn recurrence(period_type: PeriodType, multiplier: i64, start: NaiveDate) -> Recurrence {
+        Recurrence {
+            multiplier,
+            period_type,
+            start,
+            weekend_adjust: WeekendAdjust::None,
+        }
+    }
+
+    #[test]
+    fn test_nth() {
+        let r = recurrence(PeriodType::Once, 1, date(2018, 1, 31));
+        assert_eq!(r.nth(0), Some(date(2018, 1, 31)));
+        assert_eq!(r.nth(1), None);
+
+        let r = recurrence(PeriodType::Day, 3, date(2018, 1, 31));
+        assert_eq!(r.nth(1), Some(date(2018, 2, 3)));
+
+        let r = recurrence(PeriodType::Week, 2, date(2018, 1, 31));
+        assert_eq!(r.nth(1), Some(date(2018, 2, 14)));
+
+        let r = recurrence(PeriodType::Month, 1, date(2018, 1, 31));
+        assert_eq!(r.nth(1), Some(date(2018, 2, 28)));
+        assert_eq!(r.nth(-1), None);
+
+        let r = recurrence(PeriodType::EndOfMonth, 1, date(2018, 2, 28));
+        assert_eq!(r.nth(1), Some(date(2018, 3, 31)));
+
+        let r = recurrence(PeriodType::Year, 1, date(2018, 2, 20));
+        assert_eq!(r.nth(2), Some(date(2020, 2, 20)));
+    }
+
+    #[test]
+    fn test_nth_weekday() {
+        // second Tuesday
+        let r = recurrence(PeriodType::NthWeekday, 1, date(2018, 2, 13));
+        assert_eq!(r.nth(1), Some(date(2018, 3, 13)));
+        assert_eq!(r.nth(2), Some(date(2018, 4, 10)));
+
+        // fifth Thursday, which 2018-04 and 2018-06 lack
+        let r = recurrence(PeriodType::NthWeekday, 1, date(2018, 3, 29));
+        assert_eq!(r.nth(1), None);
+        assert_eq!(
+            r.dates().take(3).collect::<Vec<_>>(),
+            [date(2018, 3, 29), date(2018, 5, 31), date(2018, 8, 30)]
+        );
+
+        // last Wednesday
+        let r = recurrence(PeriodType::LastWeekday, 1, date(2018, 2, 28));
+        assert_eq!(r.nth(1), Some(date(2018, 3, 28)));
+        assert_eq!(r.nth(2), Some(date(2018, 4, 25)));
+    }
+
+    #[test]
+    fn test_weekend_adjust() {
+        // 2018-03-31 is a Saturday
+        let mut r = recurrence(PeriodType::EndOfMonth, 1, date(2018, 2, 28));
+        r.weekend_adjust = WeekendAdjust::Back;
+        assert_eq!(r.nth(1), Some(date(2018, 3, 30)));
+        r.weekend_adjust = WeekendAdjust::Forward;
+        assert_eq!(r.nth(1), Some(date(2018, 4, 2)));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_from_item() {
+        let mut item = crate::query::sqlite::recurrence::Recurrence {
+            obj_guid: "obj_guid".to_string(),
+            mult: 2,
+            period_type: "week".to_string(),
+            period_start: date(2018, 1, 31),
+            weekend_adjust: "none".to_string(),
+        };
+        assert_eq!(
+            Recurrence::from_item(&item).unwrap(),
+            recurrence(PeriodType::Week, 2, date(2018, 1, 31))
+        );
+
+        item.mult = 0;
+        assert!(matches!(
+            Recurrence::from_item(&item),
+            Err(Error::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_period_type() {
+        assert_eq!(
+            PeriodType::try_from("end of month").unwrap(),
+            PeriodType::EndOfMonth
+        );
+        assert!(matches!(
+            PeriodType::try_from("fortnight"),
+            Err(Error::Invalid { .. })
+        ));
+    }
+}
@@ -0,0 +1,514 @@
+use chrono::NaiveDate;
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::model::Recurrence;
+use crate::model::slot::{self, Slot};
+use crate::query::{Query, RecurrenceQ, ScheduledTransactionT, SplitQ, SplitT};
+
+/// A transaction entered repeatedly on the dates of its recurrences.
+///
+/// Its splits live in a template account of their own and carry the real
+/// account and amounts in their `sched-xaction` slots.
+#[derive(Clone, Debug)]
+pub struct ScheduledTransaction<Q>
+where
+    Q: Query,
+{
+    query: Arc<Q>,
+
+    pub guid: String,
+    pub name: String,
+    pub enabled: bool,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub last_occur: Option<NaiveDate>,
+    /// The total number of occurrences, 0 when unlimited.
+    pub num_occur: i64,
+    /// The occurrences left after `last_occur`, meaningful only when `num_occur` is set.
+    pub rem_occur: i64,
+    pub auto_create: bool,
+    pub auto_notify: bool,
+    pub advance_create_days: i64,
+    pub advance_notify_days: i64,
+    pub instance_count: i64,
+    pub template_account_guid: String,
+}
+
+/// A split of the template transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemplateSplit {
+    pub account_guid: String,
+    pub memo: String,
+    pub debit_formula: String,
+    pub credit_formula: String,
+    /// Debit minus credit, as last computed from the formulas.
+    pub amount: crate::Num,
+}
+
+/// A date the transaction is due, with the splits to enter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Occurrence {
+    pub date: NaiveDate,
+    pub splits: Vec<TemplateSplit>,
+}
+
+impl<Q> ScheduledTransaction<Q>
+where
+    Q: Query,
+{
+    pub(crate) fn from_with_query<T: ScheduledTransactionT>(item: &T, query: Arc<Q>) -> Self {
+        Self {
+            query,
+
+            guid: item.guid().to_string(),
+            name: item.name().to_string(),
+            enabled: item.enabled(),
+            start_date: item.start_date(),
+            end_date: item.end_date(),
+            last_occur: item.last_occur(),
+            num_occur: item.num_occur(),
+            rem_occur: item.rem_occur(),
+            auto_create: item.auto_create(),
+            auto_notify: item.auto_notify(),
+            advance_create_days: item.adv_creation(),
+            advance_notify_days: item.adv_notify(),
+            instance_count: item.instance_count(),
+            template_account_guid: item.template_act_guid().to_string(),
+        }
+    }
+
+    /// The schedules the transaction follows, most often a single one.
+    #[instrument(skip(self), fields(sx_guid = %self.guid))]
+    pub async fn recurrences(&self) -> Result<Vec<Recurrence>, Error> {
+        tracing::debug!("fetching recurrences for scheduled transaction");
+        let recurrences = RecurrenceQ::obj_guid(&*self.query, &self.guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch recurrences: {e}"))?;
+
+        recurrences.iter().map(Recurrence::from_item).collect()
+    }
+
+    #[instrument(skip(self), fields(sx_guid = %self.guid))]
+    pub async fn template_splits(&self) -> Result<Vec<TemplateSplit>, Error> {
+        tracing::debug!("fetching template splits for scheduled transaction");
+        let splits = SplitQ::account(&*self.query, &self.template_account_guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch splits: {e}"))?;
+
+        let mut result = Vec::with_capacity(splits.len());
+        for split in &splits {
+            let slots = slot::load(&*self.query, split.guid()).await?;
+            let Some(Slot::Frame(frame)) = slots.get("sched-xaction") else {
+                tracing::warn!(
+                    split_guid = split.guid(),
+                    "template split without sched-xaction"
+                );
+                continue;
+            };
+
+            let text = |key: &str| {
+                frame
+                    .get(key)
+                    .and_then(Slot::as_str)
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            let num = |key: &str| frame.get(key).and_then(Slot::as_num).unwrap_or_default();
+
+            result.push(TemplateSplit {
+                account_guid: frame
+                    .get("account")
+                    .and_then(Slot::as_guid)
+                    .unwrap_or_default()
+                    .to_string(),
+                memo: split.memo().to_string(),
+                debit_formula: text("debit-formula"),
+                credit_formula: text("credit-formula"),
+                amount: num("debit-numeric") - num("credit-numeric"),
+            });
+        }
+
+        tracing::debug!(count = result.len(), "template splits fetched");
+        Ok(result)
+    }
+
+    /// The occurrences still due within `from..=to`.
+    ///
+    /// Dates already entered, up to `last_occur`, are skipped and, when the number of
+    /// occurrences is limited, only the `rem_occur` following ones are expanded.
+    #[instrument(skip(self), fields(sx_guid = %self.guid))]
+    pub async fn occurrences_between(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<Occurrence>, Error> {
+        if !self.enabled {
+            return Ok(Vec::new());
+        }
+
+        let to = self.end_date.map_or(to, |end| end.min(to));
+        let mut dates = Vec::new();
+        for recurrence in self.recurrences().await? {
+            dates.extend(recurrence.dates().take_while(|date| *date <= to));
+        }
+        dates.sort_unstable();
+        dates.dedup();
+
+        let due = dates.into_iter().filter(|date| {
+            self.start_date.is_none_or(|start| start <= *date)
+                && self.last_occur.is_none_or(|last| last < *date)
+        });
+        let limit = if self.num_occur > 0 {
+            usize::try_from(self.rem_occur).unwrap_or_default()
+        } else {
+            usize::MAX
+        };
+        let dates: Vec<_> = due.take(limit).filter(|date| from <= *date).collect();
+        if dates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let splits = self.template_splits().await?;
+        let result: Vec<_> = dates
+            .into_iter()
+            .map(|date| Occurrence {
+                date,
+                splits: splits.clone(),
+            })
+            .collect();
+
+        tracing::debug!(count = result.len(), "occurrences expanded");
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::model::PeriodType;
+        use crate::query::sqlite::tests::setup_with;
+        use crate::query::tests::fixtures::SCHEDULED_TRANSACTION;
+        use crate::{Book, SQLiteQuery};
+
+        use super::super::*;
+
+        async fn setup() -> (tempfile::TempDir, ScheduledTransaction<SQLiteQuery>) {
+            let (dir, query) = setup_with(&[SCHEDULED_TRANSACTION]);
+            let book = Book::new(query).await.unwrap();
+            let sx = book.scheduled_transactions().await.unwrap().remove(0);
+            (dir, sx)
+        }
+
+        fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+            NaiveDate::from_ymd_opt(year, month, day).unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_scheduled_transactions() {
+            let (_dir, sx) = setup().await;
+            assert_eq!(sx.name, "Rent");
+            assert_eq!(sx.enabled, true);
+            assert_eq!(sx.start_date, Some(date(2018, 3, 1)));
+            assert_eq!(sx.end_date, None);
+            assert_eq!(sx.rem_occur, 5);
+        }
+
+        #[test(tokio::test)]
+        async fn test_recurrences() {
+            let (_dir, sx) = setup().await;
+            let recurrences = sx.recurrences().await.unwrap();
+            assert_eq!(recurrences.len(), 1);
+            assert_eq!(recurrences[0].period_type, PeriodType::Month);
+            assert_eq!(recurrences[0].multiplier, 1);
+            assert_eq!(recurrences[0].start, date(2018, 3, 1));
+        }
+
+        #[test(tokio::test)]
+        async fn test_template_splits() {
+            let (_dir, sx) = setup().await;
+            let mut splits = sx.template_splits().await.unwrap();
+            splits.sort_by(|a, b| a.account_guid.cmp(&b.account_guid));
+
+            assert_eq!(splits.len(), 2);
+            assert_eq!(splits[0].account_guid, "adc619f0ac7fa27d5768bfd73ecbc01e");
+            assert_eq!(splits[0].credit_formula, "500");
+            assert_eq!(splits[1].account_guid, "af88d386d44b14acf244362b85ccaf4c");
+            assert_eq!(splits[1].debit_formula, "500");
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, splits[0].amount, -500.0);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, splits[1].amount, 500.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(splits[0].amount, Decimal::new(-500, 0));
+            #[cfg(feature = "decimal")]
+            assert_eq!(splits[1].amount, Decimal::new(500, 0));
+        }
+
+        #[test(tokio::test)]
+        async fn test_occurrences_between() {
+            let (_dir, mut sx) = setup().await;
+
+            // five left after the one of March
+            let occurrences = sx
+                .occurrences_between(date(2018, 1, 1), date(2018, 12, 31))
+                .await
+                .unwrap();
+            let dates: Vec<_> = occurrences.iter().map(|x| x.date).collect();
+            assert_eq!(
+                dates,
+                vec![
+                    date(2018, 4, 1),
+                    date(2018, 5, 1),
+                    date(2018, 6, 1),
+                    date(2018, 7, 1),
+                    date(2018, 8, 1)
+                ]
+            );
+            assert_eq!(occurrences[0].splits.len(), 2);
+
+            let occurrences = sx
+                .occurrences_between(date(2018, 5, 15), date(2018, 6, 30))
+                .await
+                .unwrap();
+            assert_eq!(occurrences.len(), 1);
+            assert_eq!(occurrences[0].date, date(2018, 6, 1));
+
+            // unlimited, up to the end date
+            sx.num_occur = 0;
+            sx.end_date = Some(date(2019, 1, 1));
+            let occurrences = sx
+                .occurrences_between(date(2018, 1, 1), date(2020, 1, 1))
+                .await
+                .unwrap();
+            assert_eq!(occurrences.len(), 10);
+
+            sx.enabled = false;
+            let occurrences = sx
+                .occurrences_between(date(2018, 1, 1), date(2020, 1, 1))
+                .await
+                .unwrap();
+            assert!(occurrences.is_empty());
+        }
+
+        #[test(tokio::test)]
+        async fn test_occurrences_between_fifth_week() {
+            let (_dir, query) = setup_with(&[
+                SCHEDULED_TRANSACTION,
+                "
+                UPDATE recurrences SET recurrence_period_type = 'nth weekday',
+                                       recurrence_period_start = '20180329'
+                WHERE obj_guid = '3b4c5d6e7f8091a2b3c4d5e6f7081920';
+                UPDATE schedxactions SET start_date = '20180329', last_occur = NULL, num_occur = 0
+                WHERE guid = '3b4c5d6e7f8091a2b3c4d5e6f7081920';
+                ",
+            ]);
+            let book = Book::new(query).await.unwrap();
+            let sx = book.scheduled_transactions().await.unwrap().remove(0);
+
+            // months without a fifth Thursday are skipped
+            let occurrences = sx
+                .occurrences_between(date(2018, 1, 1), date(2018, 12, 31))
+                .await
+                .unwrap();
+            let dates: Vec<_> = occurrences.iter().map(|x| x.date).collect();
+            assert_eq!(
+                dates,
+                vec![
+                    date(2018, 3, 29),
+                    date(2018, 5, 31),
+                    date(2018, 8, 30),
+                    date(2018, 11, 29)
+                ]
+            );
+        }
+    }
+
+    #[cfg(feature = "xml")]
+    mod xml {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use std::io::{Read, Write};
+
+        use flate2::Compression;
+        use flate2::read::GzDecoder;
+        use flate2::write::GzEncoder;
+        use pretty_assertions::assert_eq;
+        use test_log::test;
+
+        use crate::{Book, XMLQuery};
+
+        use super::super::*;
+
+        const SCHEDULED: &str = r#"<gnc:template-transactions>
+                  <gnc:transaction version="2.0.0">
+                    <trn:id type="guid">b1c2d3e4f5a60718293a4b5c6d7e8f90</trn:id>
+                    <trn:currency>
+                      <cmdty:space>CURRENCY</cmdty:space>
+                      <cmdty:id>EUR</cmdty:id>
+                    </trn:currency>
+                    <trn:date-posted>
+                      <ts:date>2018-03-01 10:59:00 +0000</ts:date>
+                    </trn:date-posted>
+                    <trn:date-entered>
+                      <ts:date>2018-03-01 10:59:00 +0000</ts:date>
+                    </trn:date-entered>
+                    <trn:description>Rent</trn:description>
+                    <trn:splits>
+                      <trn:split>
+                        <split:id type="guid">c1d2e3f4a5b60718293a4b5c6d7e8f91</split:id>
+                        <split:reconciled-state>n</split:reconciled-state>
+                        <split:value>0/1</split:value>
+                        <split:quantity>0/1</split:quantity>
+                        <split:account type="guid">9a8b7c6d5e4f30211203f4e5d6c7b8a9</split:account>
+                        <split:slots>
+                          <slot>
+                            <slot:key>sched-xaction</slot:key>
+                            <slot:value type="frame">
+                              <slot>
+                                <slot:key>account</slot:key>
+                                <slot:value type="guid">af88d386d44b14acf244362b85ccaf4c</slot:value>
+                              </slot>
+                              <slot>
+                                <slot:key>debit-formula</slot:key>
+                                <slot:value type="string">500</slot:value>
+                              </slot>
+                              <slot>
+                                <slot:key>debit-numeric</slot:key>
+                                <slot:value type="numeric">500/1</slot:value>
+                              </slot>
+                            </slot:value>
+                          </slot>
+                        </split:slots>
+                      </trn:split>
+                      <trn:split>
+                        <split:id type="guid">c1d2e3f4a5b60718293a4b5c6d7e8f92</split:id>
+                        <split:reconciled-state>n</split:reconciled-state>
+                        <split:value>0/1</split:value>
+                        <split:quantity>0/1</split:quantity>
+                        <split:account type="guid">9a8b7c6d5e4f30211203f4e5d6c7b8a9</split:account>
+                        <split:slots>
+                          <slot>
+                            <slot:key>sched-xaction</slot:key>
+                            <slot:value type="frame">
+                              <slot>
+                                <slot:key>account</slot:key>
+                                <slot:value type="guid">adc619f0ac7fa27d5768bfd73ecbc01e</slot:value>
+                              </slot>
+                              <slot>
+                                <slot:key>credit-formula</slot:key>
+                                <slot:value type="string">500</slot:value>
+                              </slot>
+                              <slot>
+                                <slot:key>credit-numeric</slot:key>
+                                <slot:value type="numeric">500/1</slot:value>
+                              </slot>
+                            </slot:value>
+                          </slot>
+                        </split:slots>
+                      </trn:split>
+                    </trn:splits>
+                  </gnc:transaction>
+                </gnc:template-transactions>
+                <gnc:schedxaction version="2.0.0">
+                  <sx:id type="guid">3b4c5d6e7f8091a2b3c4d5e6f7081920</sx:id>
+                  <sx:name>Rent</sx:name>
+                  <sx:enabled>y</sx:enabled>
+                  <sx:autoCreate>n</sx:autoCreate>
+                  <sx:autoCreateNotify>n</sx:autoCreateNotify>
+                  <sx:advanceCreateDays>2</sx:advanceCreateDays>
+                  <sx:advanceRemindDays>0</sx:advanceRemindDays>
+                  <sx:instanceCount>1</sx:instanceCount>
+                  <sx:start>
+                    <gdate>2018-03-01</gdate>
+                  </sx:start>
+                  <sx:last>
+                    <gdate>2018-03-01</gdate>
+                  </sx:last>
+                  <sx:num-occur>6</sx:num-occur>
+                  <sx:rem-occur>5</sx:rem-occur>
+                  <sx:templ-acct type="guid">9a8b7c6d5e4f30211203f4e5d6c7b8a9</sx:templ-acct>
+                  <sx:schedule>
+                    <gnc:recurrence version="1.0.0">
+                      <recurrence:mult>1</recurrence:mult>
+                      <recurrence:period_type>month</recurrence:period_type>
+                      <recurrence:start>
+                        <gdate>2018-03-01</gdate>
+                      </recurrence:start>
+                    </gnc:recurrence>
+                  </sx:schedule>
+                </gnc:schedxaction>
+                </gnc:book>"#;
+
+        /// Writes a copy of the sample book with a monthly rent payment scheduled from March 2018,
+        /// as the fixture has none.
+        async fn setup() -> (tempfile::TempDir, Book<XMLQuery>) {
+            let path = format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let mut data = String::new();
+            GzDecoder::new(std::fs::File::open(path).unwrap())
+                .read_to_string(&mut data)
+                .unwrap();
+
+            let data = data.replace("</gnc:book>", SCHEDULED);
+
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("complex_sample.gnucash");
+            let mut encoder = GzEncoder::new(
+                std::fs::File::create(&path).unwrap(),
+                Compression::default(),
+            );
+            encoder.write_all(data.as_bytes()).unwrap();
+            encoder.finish().unwrap();
+
+            let query = XMLQuery::new(path.to_str().unwrap()).unwrap();
+            (dir, Book::new(query).await.unwrap())
+        }
+
+        #[test(tokio::test)]
+        async fn test_occurrences_between() {
+            let (_dir, book) = setup().await;
+            let sx = book.scheduled_transactions().await.unwrap().remove(0);
+            assert_eq!(sx.name, "Rent");
+
+            let occurrences = sx
+                .occurrences_between(
+                    NaiveDate::from_ymd_opt(2018, 5, 15).unwrap(),
+                    NaiveDate::from_ymd_opt(2018, 12, 31).unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(occurrences.len(), 3);
+            assert_eq!(
+                occurrences[0].date,
+                NaiveDate::from_ymd_opt(2018, 6, 1).unwrap()
+            );
+
+            let mut splits = occurrences[0].splits.clone();
+            splits.sort_by(|a, b| a.account_guid.cmp(&b.account_guid));
+            assert_eq!(splits.len(), 2);
+            assert_eq!(splits[0].account_guid, "adc619f0ac7fa27d5768bfd73ecbc01e");
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, splits[0].amount, -500.0);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, splits[1].amount, 500.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(splits[0].amount, Decimal::new(-500, 0));
+            #[cfg(feature = "decimal")]
+            assert_eq!(splits[1].amount, Decimal::new(500, 0));
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::builder::from_fraction;
+use crate::error::Error;
+use crate::query::{SlotQ, SlotT};
+
+// `slot_type` values of the `slots` table
+const INT64: i64 = 1;
+const DOUBLE: i64 = 2;
+const NUMERIC: i64 = 3;
+const STRING: i64 = 4;
+const GUID: i64 = 5;
+const TIMESPEC: i64 = 6;
+const GLIST: i64 = 8;
+const FRAME: i64 = 9;
+const GDATE: i64 = 10;
+
+/// A value of the key-value store `GnuCash` attaches to books, accounts, transactions and more.
+///
+/// Frames nest further slots by key; their entries are addressed with `/`-separated paths
+/// such as `options/Accounts/Use Trading Accounts`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Slot {
+    String(String),
+    Int64(i64),
+    Double(f64),
+    /// A fraction, kept as stored so that it is written back unchanged.
+    Numeric {
+        num: i64,
+        denom: i64,
+    },
+    Timespec(NaiveDateTime),
+    Guid(String),
+    Frame(BTreeMap<String, Slot>),
+    List(Vec<Slot>),
+    GDate(NaiveDate),
+}
+
+impl Slot {
+    /// The `slot_type` the `slots` table stores this value with.
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn type_code(&self) -> i64 {
+        match self {
+            Self::String(_) => STRING,
+            Self::Int64(_) => INT64,
+            Self::Double(_) => DOUBLE,
+            Self::Numeric { .. } => NUMERIC,
+            Self::Timespec(_) => TIMESPEC,
+            Self::Guid(_) => GUID,
+            Self::Frame(_) => FRAME,
+            Self::List(_) => GLIST,
+            Self::GDate(_) => GDATE,
+        }
+    }
+
+    /// Looks up the slot at `path` below this frame.
+    #[must_use]
+    pub fn get(&self, path: &str) -> Option<&Slot> {
+        path.split('/').try_fold(self, |slot, key| match slot {
+            Self::Frame(frame) => frame.get(key),
+            _ => None,
+        })
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Int64(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Double(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    /// The value of a numeric slot, or `None` for other slots and a zero denominator.
+    #[must_use]
+    pub fn as_num(&self) -> Option<crate::Num> {
+        match self {
+            Self::Numeric { num, denom } if *denom != 0 => Some(from_fraction(*num, *denom)),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_datetime(&self) -> Option<NaiveDateTime> {
+        match self {
+            Self::Timespec(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_guid(&self) -> Option<&str> {
+        match self {
+            Self::Guid(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_frame(&self) -> Option<&BTreeMap<String, Slot>> {
+        match self {
+            Self::Frame(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_list(&self) -> Option<&[Slot]> {
+        match self {
+            Self::List(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_date(&self) -> Option<NaiveDate> {
+        match self {
+            Self::GDate(x) => Some(*x),
+            _ => None,
+        }
+    }
+}
+
+/// A row converted to a value, or the `guid_val` whose rows still have to be fetched.
+enum Pending {
+    Value(Slot),
+    Frame(String),
+    List(String),
+}
+
+impl Pending {
+    fn from_item<T: SlotT>(item: &T) -> Option<Self> {
+        let value = match item.slot_type() {
+            INT64 => Slot::Int64(item.int64_val()),
+            DOUBLE => Slot::Double(item.double_val()),
+            NUMERIC => Slot::Numeric {
+                num: item.numeric_val_num(),
+                denom: item.numeric_val_denom(),
+            },
+            STRING => Slot::String(item.string_val().to_string()),
+            GUID => Slot::Guid(item.guid_val().to_string()),
+            TIMESPEC => Slot::Timespec(item.timespec_val()?),
+            GDATE => Slot::GDate(item.gdate_val()?),
+            GLIST => return Some(Self::List(item.guid_val().to_string())),
+            FRAME => return Some(Self::Frame(item.guid_val().to_string())),
+            _ => return None,
+        };
+        Some(Self::Value(value))
+    }
+
+    fn resolve<Q: SlotQ + Sync>(
+        self,
+        query: &Q,
+    ) -> Pin<Box<dyn Future<Output = Result<Slot, Error>> + Send + '_>> {
+        Box::pin(async move {
+            match self {
+                Self::Value(x) => Ok(x),
+                Self::Frame(guid) => Ok(Slot::Frame(load(query, &guid).await?)),
+                Self::List(guid) => {
+                    let mut result = Vec::new();
+                    for (_, item) in pending(query, &guid).await? {
+                        result.push(item.resolve(query).await?);
+                    }
+                    Ok(Slot::List(result))
+                }
+            }
+        })
+    }
+}
+
+/// Fetches the rows attached to `guid`, keyed by the last component of their name.
+async fn pending<Q: SlotQ>(query: &Q, guid: &str) -> Result<Vec<(String, Pending)>, Error> {
+    let slots = SlotQ::obj_guid(query, guid)
+        .await
+        .inspect_err(|e| tracing::error!("failed to fetch slots: {e}"))?;
+
+    Ok(slots
+        .iter()
+        .filter_map(|x| {
+            let key = x.name().rsplit('/').next().unwrap_or_default().to_string();
+            Some((key, Pending::from_item(x)?))
+        })
+        .collect())
+}
+
+/// Builds the slot tree of the object `guid`, following frames and lists.
+pub(crate) async fn load<Q: SlotQ + Sync>(
+    query: &Q,
+    guid: &str,
+) -> Result<BTreeMap<String, Slot>, Error> {
+    let mut result = BTreeMap::new();
+    for (key, item) in pending(query, guid).await? {
+        result.insert(key, item.resolve(query).await?);
+    }
+    Ok(result)
+}
+
+/// Returns the slot at `path` of the object `guid`.
+pub(crate) async fn find<Q: SlotQ + Sync>(
+    query: &Q,
+    guid: &str,
+    path: &str,
+) -> Result<Option<Slot>, Error> {
+    let root = Slot::Frame(load(query, guid).await?);
+    Ok(root.get(path).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        let slot = Slot::Frame(BTreeMap::from([(
+            "options".to_string(),
+            Slot::Frame(BTreeMap::from([(
+                "Accounts".to_string(),
+                Slot::String("t".to_string()),
+            )])),
+        )]));
+
+        assert_eq!(slot.get("options/Accounts").unwrap().as_str(), Some("t"));
+        assert_eq!(slot.get("options/Budgeting"), None);
+        assert_eq!(slot.get("options/Accounts/Trading"), None);
+        assert!(slot.get("options").unwrap().as_frame().is_some());
+    }
+
+    #[test]
+    fn test_as_num() {
+        let slot = Slot::Numeric {
+            num: 5000,
+            denom: 100,
+        };
+        #[cfg(not(feature = "decimal"))]
+        assert_eq!(slot.as_num(), Some(50.0));
+        #[cfg(feature = "decimal")]
+        assert_eq!(slot.as_num(), Some(rust_decimal::Decimal::new(50, 0)));
+        assert_eq!(Slot::Numeric { num: 1, denom: 0 }.as_num(), None);
+        assert_eq!(Slot::Int64(1).as_num(), None);
+    }
+
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        use pretty_assertions::assert_eq;
+        use test_log::test;
+
+        use crate::SQLiteQuery;
+        use crate::query::sqlite::tests::setup_with;
+
+        use super::*;
+
+        fn setup() -> SQLiteQuery {
+            let uri: &str = &format!(
+                "{}/tests/db/sqlite/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            SQLiteQuery::new(uri).unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_load() {
+            let query = setup();
+            let result = load(&query, "7d4ef4044fd30f41d08914a8174c2f5b")
+                .await
+                .unwrap();
+
+            assert_eq!(result.len(), 3);
+            assert_eq!(
+                result["remove-color-not-set-slots"],
+                Slot::String("true".to_string())
+            );
+            assert_eq!(
+                result["options"].get("Accounts/Use Trading Accounts"),
+                Some(&Slot::String("t".to_string()))
+            );
+            assert_eq!(
+                result["options"].get("Budgeting"),
+                Some(&Slot::Frame(BTreeMap::new()))
+            );
+        }
+
+        #[test(tokio::test)]
+        async fn test_load_list() {
+            let (_dir, query) = setup_with(&["
+                INSERT INTO slots (obj_guid, name, slot_type, guid_val)
+                VALUES ('93fc043c3062aaa1297b30e543d2cd0d', 'ids', 8, 'list');
+                INSERT INTO slots (obj_guid, name, slot_type, int64_val, double_val) VALUES
+                ('list', 'ids', 1, 7, NULL),
+                ('list', 'ids', 2, 0, 1.5);
+                "]);
+
+            let result = find(&query, "93fc043c3062aaa1297b30e543d2cd0d", "ids")
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                result.as_list().unwrap(),
+                [Slot::Int64(7), Slot::Double(1.5)]
+            );
+        }
+
+        #[test(tokio::test)]
+        async fn test_find() {
+            let query = setup();
+            let result = find(&query, "0fac88d8412f46d5e16beb87aee0fd22", "date-posted")
+                .await
+                .unwrap();
+            assert_eq!(
+                result,
+                Some(Slot::GDate(NaiveDate::from_ymd_opt(2018, 2, 20).unwrap()))
+            );
+
+            let result = find(&query, "0fac88d8412f46d5e16beb87aee0fd22", "notes")
+                .await
+                .unwrap();
+            assert_eq!(result, None);
+        }
+    }
+}
@@ -1,10 +1,47 @@
 use chrono::NaiveDateTime;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tracing::instrument;
 
 use crate::error::Error;
-use crate::model::{Account, Transaction};
-use crate::query::{AccountQ, Query, SplitT, TransactionQ};
+use crate::model::slot::{self, Slot};
+use crate::model::{Account, Lot, Transaction};
+use crate::query::{AccountQ, LotQ, Query, SplitT, TransactionQ};
+
+/// The reconciliation state `GnuCash` keeps for every split.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum ReconcileState {
+    #[default]
+    Unreconciled,
+    Cleared,
+    Reconciled,
+    Frozen,
+    Voided,
+}
+
+impl From<char> for ReconcileState {
+    fn from(flag: char) -> Self {
+        match flag.to_ascii_lowercase() {
+            'c' => Self::Cleared,
+            'y' => Self::Reconciled,
+            'f' => Self::Frozen,
+            'v' => Self::Voided,
+            _ => Self::Unreconciled,
+        }
+    }
+}
+
+impl From<ReconcileState> for char {
+    fn from(state: ReconcileState) -> Self {
+        match state {
+            ReconcileState::Unreconciled => 'n',
+            ReconcileState::Cleared => 'c',
+            ReconcileState::Reconciled => 'y',
+            ReconcileState::Frozen => 'f',
+            ReconcileState::Voided => 'v',
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Split<Q>
@@ -19,6 +56,7 @@ where
     pub memo: String,
     pub action: String,
     pub reconcile_state: bool,
+    pub reconcile_status: ReconcileState,
     pub reconcile_datetime: Option<NaiveDateTime>,
     pub value: crate::Num,
     pub quantity: crate::Num,
@@ -39,6 +77,7 @@ where
             memo: item.memo().to_string(),
             action: item.action().to_string(),
             reconcile_state: item.reconcile_state(),
+            reconcile_status: item.reconcile_flag().into(),
             reconcile_datetime: item.reconcile_datetime(),
             lot_guid: item.lot_guid().to_string(),
             value: item.value(),
@@ -99,6 +138,42 @@ where
             })
         }
     }
+
+    /// All key-value slots of the split; voided splits keep their former amounts here.
+    #[instrument(skip(self), fields(split_guid = %self.guid))]
+    pub async fn slots(&self) -> Result<BTreeMap<String, Slot>, Error> {
+        slot::load(&*self.query, &self.guid).await
+    }
+
+    #[instrument(skip(self), fields(split_guid = %self.guid))]
+    pub async fn slot(&self, path: &str) -> Result<Option<Slot>, Error> {
+        slot::find(&*self.query, &self.guid, path).await
+    }
+
+    /// The lot the split is assigned to, `None` if it is not in a lot.
+    #[instrument(skip(self), fields(split_guid = %self.guid, lot_guid = %self.lot_guid))]
+    pub async fn lot(&self) -> Result<Option<Lot<Q>>, Error> {
+        if self.lot_guid.is_empty() {
+            tracing::debug!("no lot guid, returning None");
+            return Ok(None);
+        }
+
+        tracing::debug!("fetching lot for split");
+        let lot = LotQ::guid(&*self.query, &self.lot_guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch lot: {e}"))?;
+
+        if let Some(l) = lot {
+            tracing::debug!("lot found for split");
+            Ok(Some(Lot::from_with_query(&l, self.query.clone())))
+        } else {
+            tracing::error!("lot not found");
+            Err(Error::GuidNotFound {
+                model: "Lot".to_string(),
+                guid: self.lot_guid.clone(),
+            })
+        }
+    }
 }
 
 #[cfg(test)]
@@ -164,6 +239,7 @@ mod tests {
             assert_eq!(result.memo, "memo");
             assert_eq!(result.action, "action");
             assert_eq!(result.reconcile_state, false);
+            assert_eq!(result.reconcile_status, ReconcileState::Unreconciled);
             assert_eq!(
                 result.reconcile_datetime,
                 NaiveDateTime::parse_from_str("2014-12-24 10:59:00", "%Y-%m-%d %H:%M:%S").ok()
@@ -256,6 +332,7 @@ mod tests {
             assert_eq!(result.memo, "memo");
             assert_eq!(result.action, "action");
             assert_eq!(result.reconcile_state, false);
+            assert_eq!(result.reconcile_status, ReconcileState::Unreconciled);
             assert_eq!(
                 result.reconcile_datetime,
                 NaiveDateTime::parse_from_str("2014-12-24 10:59:00", "%Y-%m-%d %H:%M:%S").ok()
@@ -348,6 +425,7 @@ mod tests {
             assert_eq!(result.memo, "memo");
             assert_eq!(result.action, "action");
             assert_eq!(result.reconcile_state, false);
+            assert_eq!(result.reconcile_status, ReconcileState::Unreconciled);
             assert_eq!(
                 result.reconcile_datetime,
                 NaiveDateTime::parse_from_str("2014-12-24 10:59:00", "%Y-%m-%d %H:%M:%S").ok()
@@ -423,7 +501,7 @@ mod tests {
                 account_guid: "account_guid".to_string(),
                 memo: "memo".to_string(),
                 action: "action".to_string(),
-                reconcile_state: false,
+                reconcile_state: "n".to_string(),
                 reconcile_date: NaiveDateTime::parse_from_str(
                     "2014-12-24 10:59:00",
                     "%Y-%m-%d %H:%M:%S",
@@ -445,6 +523,7 @@ mod tests {
             assert_eq!(result.memo, "memo");
             assert_eq!(result.action, "action");
             assert_eq!(result.reconcile_state, false);
+            assert_eq!(result.reconcile_status, ReconcileState::Unreconciled);
             assert_eq!(
                 result.reconcile_datetime,
                 NaiveDateTime::parse_from_str("2014-12-24 10:59:00", "%Y-%m-%d %H:%M:%S").ok()
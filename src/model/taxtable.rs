@@ -0,0 +1,320 @@
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::builder::from_fraction;
+use crate::error::Error;
+use crate::model::Account;
+use crate::query::{AccountQ, Query, TaxTableEntryQ, TaxTableEntryT, TaxTableQ, TaxTableT};
+
+/// How a tax table entry's amount is applied.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum AmountType {
+    /// A fixed amount per line.
+    Value,
+    /// A percentage of the net line amount.
+    #[default]
+    Percent,
+}
+
+impl From<i64> for AmountType {
+    fn from(value: i64) -> Self {
+        match value {
+            1 => Self::Value,
+            _ => Self::Percent,
+        }
+    }
+}
+
+/// A named set of taxes applied to invoice and bill entries.
+#[derive(Clone, Debug)]
+pub struct TaxTable<Q>
+where
+    Q: Query,
+{
+    query: Arc<Q>,
+
+    pub guid: String,
+    pub name: String,
+    pub refcount: i64,
+    pub invisible: bool,
+    /// The table this one was copied from, empty when there is none.
+    pub parent_guid: String,
+}
+
+impl<Q> TaxTable<Q>
+where
+    Q: Query,
+{
+    pub(crate) fn from_with_query<T: TaxTableT>(item: &T, query: Arc<Q>) -> Self {
+        Self {
+            query,
+
+            guid: item.guid().to_string(),
+            name: item.name().to_string(),
+            refcount: item.refcount(),
+            invisible: item.invisible(),
+            parent_guid: item.parent_guid().to_string(),
+        }
+    }
+
+    /// Looks up a tax table referenced by guid, `None` when the guid is empty.
+    pub(crate) async fn fetch(query: &Arc<Q>, guid: &str) -> Result<Option<Self>, Error> {
+        if guid.is_empty() {
+            return Ok(None);
+        }
+
+        let taxtable = TaxTableQ::guid(&**query, guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch tax table: {e}"))?;
+
+        if let Some(t) = taxtable {
+            Ok(Some(Self::from_with_query(&t, query.clone())))
+        } else {
+            tracing::error!("tax table not found");
+            Err(Error::GuidNotFound {
+                model: "TaxTable".to_string(),
+                guid: guid.to_string(),
+            })
+        }
+    }
+
+    #[instrument(skip(self), fields(taxtable_guid = %self.guid))]
+    pub async fn entries(&self) -> Result<Vec<TaxTableEntry<Q>>, Error> {
+        tracing::debug!("fetching entries for tax table");
+        let entries = TaxTableEntryQ::taxtable(&*self.query, &self.guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch entries: {e}"))?;
+
+        let result: Vec<_> = entries
+            .into_iter()
+            .map(|x| TaxTableEntry::from_with_query(&x, self.query.clone()))
+            .collect();
+
+        tracing::debug!(count = result.len(), "entries fetched for tax table");
+        Ok(result)
+    }
+
+    /// The total tax on an entry amount.
+    ///
+    /// When `tax_included` is set the amount is gross: the fixed values are
+    /// taken off first and the rest is split into net and percentage taxes,
+    /// the way `GnuCash` computes entry values. Percentages adding up to -100%
+    /// leave no net amount and fail with [`Error::Invalid`].
+    #[instrument(skip(self), fields(taxtable_guid = %self.guid))]
+    pub async fn tax(&self, amount: crate::Num, tax_included: bool) -> Result<crate::Num, Error> {
+        let entries = self.entries().await?;
+        compute_tax(&entries, amount, tax_included)
+    }
+}
+
+fn compute_tax<Q: Query>(
+    entries: &[TaxTableEntry<Q>],
+    amount: crate::Num,
+    tax_included: bool,
+) -> Result<crate::Num, Error> {
+    let net = if tax_included {
+        let mut values = crate::Num::default();
+        let mut percent = crate::Num::default();
+        for entry in entries {
+            match entry.r#type {
+                AmountType::Value => values += entry.amount,
+                AmountType::Percent => percent += entry.amount,
+            }
+        }
+        let rate = crate::Num::from(1) + percent / crate::Num::from(100);
+        #[cfg(not(feature = "decimal"))]
+        let is_zero = rate.abs() < f64::EPSILON;
+        #[cfg(feature = "decimal")]
+        let is_zero = rate.is_zero();
+        if is_zero {
+            tracing::error!(%percent, "tax percentages cancel the net amount");
+            return Err(Error::Invalid {
+                model: "TaxTable".to_string(),
+                reason: format!("tax percentages of {percent}% leave no net amount"),
+            });
+        }
+        (amount - values) / rate
+    } else {
+        amount
+    };
+
+    Ok(entries.iter().map(|x| x.tax(net)).sum())
+}
+
+/// One tax of a tax table and the account it is posted to.
+#[derive(Clone, Debug)]
+pub struct TaxTableEntry<Q>
+where
+    Q: Query,
+{
+    query: Arc<Q>,
+
+    pub taxtable_guid: String,
+    pub account_guid: String,
+    pub amount: crate::Num,
+    pub r#type: AmountType,
+}
+
+impl<Q> TaxTableEntry<Q>
+where
+    Q: Query,
+{
+    pub(crate) fn from_with_query<T: TaxTableEntryT>(item: &T, query: Arc<Q>) -> Self {
+        Self {
+            query,
+
+            taxtable_guid: item.taxtable_guid().to_string(),
+            account_guid: item.account_guid().to_string(),
+            amount: from_fraction(item.amount_num(), item.amount_denom()),
+            r#type: item.r#type().into(),
+        }
+    }
+
+    /// The tax on a net amount.
+    #[must_use]
+    pub fn tax(&self, net: crate::Num) -> crate::Num {
+        match self.r#type {
+            AmountType::Value => self.amount,
+            AmountType::Percent => net * self.amount / crate::Num::from(100),
+        }
+    }
+
+    /// The account the tax is posted to.
+    #[instrument(skip(self), fields(taxtable_guid = %self.taxtable_guid, account_guid = %self.account_guid))]
+    pub async fn account(&self) -> Result<Account<Q>, Error> {
+        tracing::debug!("fetching account for tax table entry");
+        let account = AccountQ::guid(&*self.query, &self.account_guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch account: {e}"))?;
+
+        if let Some(a) = account {
+            Ok(Account::from_with_query(&a, self.query.clone()))
+        } else {
+            tracing::error!("account not found");
+            Err(Error::GuidNotFound {
+                model: "Account".to_string(),
+                guid: self.account_guid.clone(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::Book;
+        use crate::query::sqlite::tests::setup_with;
+        use crate::query::tests::fixtures::{BUSINESS, INVOICES, TAX};
+
+        use super::super::*;
+
+        #[test(tokio::test)]
+        async fn test_tax() {
+            let (_dir, query) = setup_with(&[BUSINESS, INVOICES, TAX]);
+            let book = Book::new(query).await.unwrap();
+            let levy = book
+                .tax_tables()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.name == "Levy")
+                .unwrap();
+
+            let entries = levy.entries().await.unwrap();
+            assert_eq!(entries.len(), 2);
+            assert_eq!(entries[0].r#type, AmountType::Percent);
+            assert_eq!(entries[0].account().await.unwrap().name, "Liability");
+            assert_eq!(entries[1].r#type, AmountType::Value);
+            assert_eq!(entries[1].account().await.unwrap().name, "Expense");
+
+            // 10% of 100 plus a levy of 5
+            let excluded = levy.tax(crate::Num::from(100), false).await.unwrap();
+            // 115 less the levy is 110, which is 100 plus 10%
+            let included = levy.tax(crate::Num::from(115), true).await.unwrap();
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, excluded, 15.0);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, included, 15.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(excluded, Decimal::new(15, 0));
+            #[cfg(feature = "decimal")]
+            assert_eq!(included, Decimal::new(15, 0));
+        }
+
+        #[test(tokio::test)]
+        async fn test_tax_no_net() {
+            let (_dir, query) = setup_with(&[
+                BUSINESS,
+                INVOICES,
+                TAX,
+                "
+                INSERT INTO taxtables (guid, name, refcount, invisible, parent)
+                VALUES ('8a9b0c1d2e3f405162738495a6b7c803', 'Rebate', 0, 0, NULL);
+                INSERT INTO taxtable_entries (taxtable, account, amount_num, amount_denom, type)
+                VALUES ('8a9b0c1d2e3f405162738495a6b7c803', '96ed7a45459fb5fe570e48fcd46f05d0', -100, 1, 2);
+                ",
+            ]);
+            let book = Book::new(query).await.unwrap();
+            let rebate = book
+                .tax_tables()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.name == "Rebate")
+                .unwrap();
+
+            assert!(rebate.tax(crate::Num::from(100), false).await.is_ok());
+            assert!(matches!(
+                rebate.tax(crate::Num::from(100), true).await,
+                Err(Error::Invalid { .. })
+            ));
+        }
+
+        #[test(tokio::test)]
+        async fn test_entry_tax() {
+            let (_dir, query) = setup_with(&[BUSINESS, INVOICES, TAX]);
+            let book = Book::new(query).await.unwrap();
+            let invoices = book.invoices().await.unwrap();
+
+            let invoice = invoices
+                .iter()
+                .find(|x| x.guid == "3d4e5f60718293a4b5c6d7e8f90a1b01")
+                .unwrap();
+            let crate::model::Owner::Customer(customer) = invoice.owner().await.unwrap() else {
+                panic!("not a customer");
+            };
+            assert_eq!(customer.tax_table().await.unwrap().unwrap().name, "VAT");
+            let entries = invoice.entries().await.unwrap();
+            let consulting = entries[0].invoice_tax().await.unwrap();
+            let hosting = entries[1].invoice_tax().await.unwrap();
+
+            let bill = invoices
+                .iter()
+                .find(|x| x.guid == "3d4e5f60718293a4b5c6d7e8f90a1b02")
+                .unwrap();
+            // 120 with tax included is 100 plus 20% VAT
+            let paper = bill.entries().await.unwrap()[0].bill_tax().await.unwrap();
+
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, consulting, 100.0);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, hosting, 18.0);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, paper, 20.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(consulting, Decimal::new(100, 0));
+            #[cfg(feature = "decimal")]
+            assert_eq!(hosting, Decimal::new(18, 0));
+            #[cfg(feature = "decimal")]
+            assert_eq!(paper, Decimal::new(20, 0));
+        }
+    }
+}
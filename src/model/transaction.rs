@@ -1,10 +1,15 @@
 use chrono::NaiveDateTime;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tracing::instrument;
 
+use crate::builder::from_fraction;
 use crate::error::Error;
+use crate::model::slot::{self, Slot};
 use crate::model::{Commodity, Split};
-use crate::query::{CommodityQ, Query, SplitQ, TransactionT};
+use crate::query::{
+    CommodityQ, Query, SlotQ, SlotT, SplitQ, SplitT, TransactionMutQ, TransactionT,
+};
 
 #[derive(Clone, Debug)]
 pub struct Transaction<Q>
@@ -21,6 +26,17 @@ where
     pub description: String,
 }
 
+/// The amounts a split of a voided transaction had before it was zeroed.
+#[derive(Clone, Debug)]
+pub struct OriginalAmount<Q>
+where
+    Q: Query,
+{
+    pub split: Split<Q>,
+    pub quantity: crate::Num,
+    pub value: crate::Num,
+}
+
 impl<Q> Transaction<Q>
 where
     Q: Query,
@@ -78,6 +94,109 @@ where
         tracing::debug!(count = result.len(), "splits fetched for transaction");
         Ok(result)
     }
+
+    #[instrument(skip(self), fields(transaction_guid = %self.guid))]
+    pub async fn is_voided(&self) -> Result<bool, Error> {
+        Ok(self.void_reason().await?.is_some())
+    }
+
+    /// The reason given when the transaction was voided, `None` if it is not voided.
+    #[instrument(skip(self), fields(transaction_guid = %self.guid))]
+    pub async fn void_reason(&self) -> Result<Option<String>, Error> {
+        tracing::debug!("fetching void reason for transaction");
+        let slots = SlotQ::obj_guid(&*self.query, &self.guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch slots: {e}"))?;
+
+        Ok(slots
+            .into_iter()
+            .find(|x| x.name() == "void-reason")
+            .map(|x| x.string_val().to_string()))
+    }
+
+    /// All key-value slots of the transaction, such as `notes` or `date-posted`.
+    #[instrument(skip(self), fields(transaction_guid = %self.guid))]
+    pub async fn slots(&self) -> Result<BTreeMap<String, Slot>, Error> {
+        slot::load(&*self.query, &self.guid).await
+    }
+
+    #[instrument(skip(self), fields(transaction_guid = %self.guid))]
+    pub async fn slot(&self, path: &str) -> Result<Option<Slot>, Error> {
+        slot::find(&*self.query, &self.guid, path).await
+    }
+
+    /// The quantity and value of every split before the transaction was voided,
+    /// empty if it is not voided.
+    #[instrument(skip(self), fields(transaction_guid = %self.guid))]
+    pub async fn original_amounts(&self) -> Result<Vec<OriginalAmount<Q>>, Error> {
+        tracing::debug!("fetching original amounts for transaction");
+        let splits = SplitQ::transaction(&*self.query, &self.guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch splits: {e}"))?;
+
+        let mut result = Vec::new();
+        for split in splits {
+            let slots = SlotQ::obj_guid(&*self.query, split.guid())
+                .await
+                .inspect_err(|e| tracing::error!("failed to fetch slots: {e}"))?;
+            let former = |name: &str| {
+                slots
+                    .iter()
+                    .find(|x| x.name() == name)
+                    .map(|x| match x.numeric_val_denom() {
+                        0 => Err(Error::Invalid {
+                            model: "Transaction".to_string(),
+                            reason: format!(
+                                "{name} of split {} has a zero denominator",
+                                x.obj_guid()
+                            ),
+                        }),
+                        denom => Ok(from_fraction(x.numeric_val_num(), denom)),
+                    })
+                    .transpose()
+                    .inspect_err(|e| tracing::error!("invalid original amount: {e}"))
+            };
+
+            if let (Some(quantity), Some(value)) =
+                (former("void-former-amount")?, former("void-former-value")?)
+            {
+                result.push(OriginalAmount {
+                    split: Split::from_with_query(&split, self.query.clone()),
+                    quantity,
+                    value,
+                });
+            }
+        }
+
+        tracing::debug!(
+            count = result.len(),
+            "original amounts fetched for transaction"
+        );
+        Ok(result)
+    }
+}
+
+impl<Q> Transaction<Q>
+where
+    Q: Query + TransactionMutQ,
+{
+    /// Voids the transaction as `GnuCash` does: every split is zeroed and its amounts are
+    /// kept, together with `reason`, so that [`Transaction::unvoid`] can restore them.
+    #[instrument(skip(self), fields(transaction_guid = %self.guid))]
+    pub async fn void(&self, reason: &str) -> Result<(), Error> {
+        let now = chrono::Utc::now().naive_utc();
+        TransactionMutQ::void(&*self.query, &self.guid, reason, now)
+            .await
+            .inspect_err(|e| tracing::error!("failed to void transaction: {e}"))
+    }
+
+    /// Restores the amounts of a voided transaction.
+    #[instrument(skip(self), fields(transaction_guid = %self.guid))]
+    pub async fn unvoid(&self) -> Result<(), Error> {
+        TransactionMutQ::unvoid(&*self.query, &self.guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to unvoid transaction: {e}"))
+    }
 }
 
 #[cfg(test)]
@@ -88,10 +207,15 @@ mod tests {
 
     #[cfg(feature = "sqlite")]
     mod sqlite {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
         use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
         use test_log::test;
 
         use crate::SQLiteQuery;
+        use crate::query::sqlite::tests::{setup_with, setup_writable};
         use crate::query::sqlite::transaction::Transaction as TransactionBase;
 
         use super::*;
@@ -172,6 +296,129 @@ mod tests {
             let splits = transaction.splits().await.unwrap();
             assert_eq!(splits.len(), 2);
         }
+
+        #[test(tokio::test)]
+        async fn slots() {
+            let query = setup().await;
+            let book = Book::new(query).await.unwrap();
+            let transaction = book
+                .transactions()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "0fac88d8412f46d5e16beb87aee0fd22")
+                .unwrap();
+            assert_eq!(transaction.slots().await.unwrap().len(), 1);
+            assert_eq!(
+                transaction.slot("date-posted").await.unwrap(),
+                Some(Slot::GDate(
+                    chrono::NaiveDate::from_ymd_opt(2018, 2, 20).unwrap()
+                ))
+            );
+        }
+
+        #[test(tokio::test)]
+        async fn void_and_unvoid() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+            let transaction = book
+                .transactions()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "6c8876003c4a6026e38e3afb67d6f2b1")
+                .unwrap();
+            assert!(!transaction.is_voided().await.unwrap());
+            assert!(transaction.original_amounts().await.unwrap().is_empty());
+
+            transaction.void("entered twice").await.unwrap();
+            assert!(transaction.is_voided().await.unwrap());
+            assert_eq!(
+                transaction.void_reason().await.unwrap().as_deref(),
+                Some("entered twice")
+            );
+            let slots = transaction.slots().await.unwrap();
+            let void_time = slots["void-time"].as_str().unwrap();
+            assert!(chrono::DateTime::parse_from_str(void_time, "%Y-%m-%d %H:%M:%S %z").is_ok());
+            for split in transaction.splits().await.unwrap() {
+                #[cfg(not(feature = "decimal"))]
+                assert_approx_eq!(f64, split.value, 0.0);
+                #[cfg(not(feature = "decimal"))]
+                assert_approx_eq!(f64, split.quantity, 0.0);
+                #[cfg(feature = "decimal")]
+                assert_eq!(split.value, Decimal::ZERO);
+                #[cfg(feature = "decimal")]
+                assert_eq!(split.quantity, Decimal::ZERO);
+            }
+            let mut original = transaction.original_amounts().await.unwrap();
+            original.sort_by_key(|x| x.split.guid.clone());
+            assert_eq!(original.len(), 2);
+            assert_eq!(original[0].split.guid, "1e612f650eb598d9e803902b6aca73e3");
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, original[0].quantity, -150.0);
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, original[1].value, 150.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(original[0].quantity, Decimal::new(-150, 0));
+            #[cfg(feature = "decimal")]
+            assert_eq!(original[1].value, Decimal::new(150, 0));
+            assert!(matches!(
+                transaction.void("again").await,
+                Err(Error::Invalid { .. })
+            ));
+
+            transaction.unvoid().await.unwrap();
+            assert!(!transaction.is_voided().await.unwrap());
+            assert!(transaction.original_amounts().await.unwrap().is_empty());
+            let split = transaction
+                .splits()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "de832fe97e37811a7fff7e28b3a43425")
+                .unwrap();
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, split.value, 150.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(split.value, Decimal::new(150, 0));
+            assert!(matches!(
+                transaction.unvoid().await,
+                Err(Error::Invalid { .. })
+            ));
+        }
+
+        #[test(tokio::test)]
+        async fn test_original_amounts_zero_denom() {
+            let (_dir, query) = setup_with(&[r"
+                INSERT INTO slots (obj_guid, name, slot_type, numeric_val_num, numeric_val_denom)
+                VALUES
+                    ('de832fe97e37811a7fff7e28b3a43425', 'void-former-amount', 3, 150, 0),
+                    ('de832fe97e37811a7fff7e28b3a43425', 'void-former-value', 3, 150, 1);
+            "]);
+            let book = Book::new(query).await.unwrap();
+            let transaction = book
+                .transactions()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "6c8876003c4a6026e38e3afb67d6f2b1")
+                .unwrap();
+            assert!(matches!(
+                transaction.original_amounts().await,
+                Err(Error::Invalid { .. })
+            ));
+        }
+
+        #[test(tokio::test)]
+        async fn void_read_only() {
+            let query = setup().await;
+            let book = Book::new(query).await.unwrap();
+            let transaction = book.transactions().await.unwrap().remove(0);
+            assert!(matches!(
+                transaction.void("entered twice").await,
+                Err(Error::ReadOnly)
+            ));
+        }
     }
 
     #[cfg(feature = "mysql")]
@@ -254,6 +501,26 @@ mod tests {
             let splits = transaction.splits().await.unwrap();
             assert_eq!(splits.len(), 2);
         }
+
+        #[test(tokio::test)]
+        async fn slots() {
+            let query = setup().await;
+            let book = Book::new(query).await.unwrap();
+            let transaction = book
+                .transactions()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "0fac88d8412f46d5e16beb87aee0fd22")
+                .unwrap();
+            assert_eq!(transaction.slots().await.unwrap().len(), 1);
+            assert_eq!(
+                transaction.slot("date-posted").await.unwrap(),
+                Some(Slot::GDate(
+                    chrono::NaiveDate::from_ymd_opt(2018, 2, 20).unwrap()
+                ))
+            );
+        }
     }
 
     #[cfg(feature = "postgresql")]
@@ -336,6 +603,26 @@ mod tests {
             let splits = transaction.splits().await.unwrap();
             assert_eq!(splits.len(), 2);
         }
+
+        #[test(tokio::test)]
+        async fn slots() {
+            let query = setup().await;
+            let book = Book::new(query).await.unwrap();
+            let transaction = book
+                .transactions()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "0fac88d8412f46d5e16beb87aee0fd22")
+                .unwrap();
+            assert_eq!(transaction.slots().await.unwrap().len(), 1);
+            assert_eq!(
+                transaction.slot("date-posted").await.unwrap(),
+                Some(Slot::GDate(
+                    chrono::NaiveDate::from_ymd_opt(2018, 2, 20).unwrap()
+                ))
+            );
+        }
     }
 
     #[cfg(feature = "xml")]
@@ -423,5 +710,41 @@ mod tests {
             let splits = transaction.splits().await.unwrap();
             assert_eq!(splits.len(), 2);
         }
+
+        #[test(tokio::test)]
+        async fn slots() {
+            let query = setup();
+            let book = Book::new(query).await.unwrap();
+            let transaction = book
+                .transactions()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "0fac88d8412f46d5e16beb87aee0fd22")
+                .unwrap();
+            assert_eq!(transaction.slots().await.unwrap().len(), 1);
+            assert_eq!(
+                transaction.slot("date-posted").await.unwrap(),
+                Some(Slot::GDate(
+                    chrono::NaiveDate::from_ymd_opt(2018, 2, 20).unwrap()
+                ))
+            );
+        }
+
+        #[test(tokio::test)]
+        async fn void_reason() {
+            let query = setup();
+            let book = Book::new(query).await.unwrap();
+            let transaction = book
+                .transactions()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.guid == "6c8876003c4a6026e38e3afb67d6f2b1")
+                .unwrap();
+            assert!(!transaction.is_voided().await.unwrap());
+            assert_eq!(transaction.void_reason().await.unwrap(), None);
+            assert!(transaction.original_amounts().await.unwrap().is_empty());
+        }
     }
 }
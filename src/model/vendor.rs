@@ -0,0 +1,112 @@
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::model::{Address, BillTerm, Commodity, TaxIncluded, TaxTable};
+use crate::query::{CommodityQ, Query, VendorT};
+
+/// Someone who bills the book.
+#[derive(Clone, Debug)]
+pub struct Vendor<Q>
+where
+    Q: Query,
+{
+    query: Arc<Q>,
+
+    pub guid: String,
+    pub name: String,
+    pub id: String,
+    pub notes: String,
+    pub active: bool,
+    pub currency_guid: String,
+    pub address: Address,
+    pub terms_guid: String,
+    pub tax_included: TaxIncluded,
+    pub tax_table_override: bool,
+    pub tax_table_guid: String,
+}
+
+impl<Q> Vendor<Q>
+where
+    Q: Query,
+{
+    pub(crate) fn from_with_query<T: VendorT>(item: &T, query: Arc<Q>) -> Self {
+        Self {
+            query,
+
+            guid: item.guid().to_string(),
+            name: item.name().to_string(),
+            id: item.id().to_string(),
+            notes: item.notes().to_string(),
+            active: item.active(),
+            currency_guid: item.currency_guid().to_string(),
+            address: item.addr(),
+            terms_guid: item.terms_guid().to_string(),
+            tax_included: item.tax_included().into(),
+            tax_table_override: item.tax_override(),
+            tax_table_guid: item.taxtable_guid().to_string(),
+        }
+    }
+
+    #[instrument(skip(self), fields(vendor_guid = %self.guid, currency_guid = %self.currency_guid))]
+    pub async fn currency(&self) -> Result<Commodity<Q>, Error> {
+        tracing::debug!("fetching currency for vendor");
+        let currency = CommodityQ::guid(&*self.query, &self.currency_guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch currency: {e}"))?;
+
+        if let Some(c) = currency {
+            Ok(Commodity::from_with_query(&c, self.query.clone()))
+        } else {
+            tracing::error!("currency not found");
+            Err(Error::GuidNotFound {
+                model: "Commodity".to_string(),
+                guid: self.currency_guid.clone(),
+            })
+        }
+    }
+
+    /// The billing terms, `None` when there are none.
+    #[instrument(skip(self), fields(vendor_guid = %self.guid, terms_guid = %self.terms_guid))]
+    pub async fn terms(&self) -> Result<Option<BillTerm>, Error> {
+        tracing::debug!("fetching terms for vendor");
+        BillTerm::fetch(&*self.query, &self.terms_guid).await
+    }
+
+    #[instrument(skip(self), fields(vendor_guid = %self.guid, taxtable_guid = %self.tax_table_guid))]
+    pub async fn tax_table(&self) -> Result<Option<TaxTable<Q>>, Error> {
+        tracing::debug!("fetching tax table for vendor");
+        TaxTable::fetch(&self.query, &self.tax_table_guid).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        use pretty_assertions::assert_eq;
+        use test_log::test;
+
+        use crate::Book;
+        use crate::query::sqlite::tests::setup_with;
+        use crate::query::tests::fixtures::BUSINESS;
+
+        use super::super::*;
+
+        #[test(tokio::test)]
+        async fn test_vendors() {
+            let (_dir, query) = setup_with(&[BUSINESS]);
+            let book = Book::new(query).await.unwrap();
+            let vendors = book.vendors().await.unwrap();
+            assert_eq!(vendors.len(), 1);
+
+            let vendor = &vendors[0];
+            assert_eq!(vendor.name, "Paper Supplies Ltd");
+            assert_eq!(vendor.active, true);
+            assert_eq!(vendor.address.lines(), vec!["Mill Lane 3"]);
+            assert_eq!(vendor.tax_included, TaxIncluded::Yes);
+            assert_eq!(vendor.tax_table_guid, "");
+            assert_eq!(vendor.currency().await.unwrap().mnemonic, "EUR");
+        }
+    }
+}
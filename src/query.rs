@@ -7,12 +7,39 @@ pub(crate) mod sqlite;
 #[cfg(feature = "xml")]
 pub(crate) mod xml;
 
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
+use std::collections::HashSet;
 
 use crate::error::Error;
+use crate::model::{AccountType, Address, Lock};
 
 pub trait Query:
-    Clone + Sync + Send + AccountQ + CommodityQ + PriceQ + SplitQ + TransactionQ
+    Clone
+    + Sync
+    + Send
+    + AccountQ
+    + BillTermQ
+    + BookQ
+    + BudgetQ
+    + BudgetAmountQ
+    + CommodityQ
+    + CustomerQ
+    + EmployeeQ
+    + EntryQ
+    + InvoiceQ
+    + JobQ
+    + LotQ
+    + OrderQ
+    + PriceQ
+    + RecurrenceQ
+    + ScheduledTransactionQ
+    + SlotQ
+    + SplitQ
+    + TaxTableQ
+    + TaxTableEntryQ
+    + TransactionQ
+    + VendorQ
+    + VersionQ
 {
     fn accounts(
         &self,
@@ -27,6 +54,67 @@ pub trait Query:
     {
         async { AccountQ::contains_name_ignore_case(self, name).await }
     }
+    fn bill_terms(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<<Self as BillTermQ>::Item>, Error>> + Send
+    {
+        async { BillTermQ::all(self).await }
+    }
+    fn budgets(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<<Self as BudgetQ>::Item>, Error>> + Send {
+        async { BudgetQ::all(self).await }
+    }
+    fn customers(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<<Self as CustomerQ>::Item>, Error>> + Send
+    {
+        async { CustomerQ::all(self).await }
+    }
+    fn employees(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<<Self as EmployeeQ>::Item>, Error>> + Send
+    {
+        async { EmployeeQ::all(self).await }
+    }
+    fn invoices(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<<Self as InvoiceQ>::Item>, Error>> + Send
+    {
+        async { InvoiceQ::all(self).await }
+    }
+    fn jobs(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<<Self as JobQ>::Item>, Error>> + Send {
+        async { JobQ::all(self).await }
+    }
+    fn orders(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<<Self as OrderQ>::Item>, Error>> + Send {
+        async { OrderQ::all(self).await }
+    }
+    fn tax_tables(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<<Self as TaxTableQ>::Item>, Error>> + Send
+    {
+        async { TaxTableQ::all(self).await }
+    }
+    fn vendors(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<<Self as VendorQ>::Item>, Error>> + Send {
+        async { VendorQ::all(self).await }
+    }
+    fn lots(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<<Self as LotQ>::Item>, Error>> + Send {
+        async { LotQ::all(self).await }
+    }
+    fn scheduled_transactions(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<<Self as ScheduledTransactionQ>::Item>, Error>>
+    + Send {
+        async { ScheduledTransactionQ::all(self).await }
+    }
     fn splits(
         &self,
     ) -> impl std::future::Future<Output = Result<Vec<<Self as SplitQ>::Item>, Error>> + Send {
@@ -83,6 +171,42 @@ pub trait AccountQ {
     ) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
 }
 
+pub trait BookQ {
+    type Item: BookT;
+
+    /// Returns the book stored in the file, `GnuCash` keeps exactly one.
+    fn book(&self) -> impl std::future::Future<Output = Result<Self::Item, Error>> + Send;
+}
+
+pub trait BillTermQ {
+    type Item: BillTermT;
+
+    fn all(&self) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+    fn guid(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Option<Self::Item>, Error>> + Send;
+}
+
+pub trait BudgetQ {
+    type Item: BudgetT;
+
+    fn all(&self) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+    fn guid(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Option<Self::Item>, Error>> + Send;
+}
+
+pub trait BudgetAmountQ {
+    type Item: BudgetAmountT;
+
+    fn budget(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+}
+
 pub trait CommodityQ {
     type Item: CommodityT;
 
@@ -97,6 +221,80 @@ pub trait CommodityQ {
     ) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
 }
 
+pub trait CustomerQ {
+    type Item: CustomerT;
+
+    fn all(&self) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+    fn guid(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Option<Self::Item>, Error>> + Send;
+}
+
+pub trait EmployeeQ {
+    type Item: EmployeeT;
+
+    fn all(&self) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+    fn guid(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Option<Self::Item>, Error>> + Send;
+}
+
+pub trait EntryQ {
+    type Item: EntryT;
+
+    /// The lines of an invoice, a bill or an expense voucher, in date order.
+    fn invoice(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+}
+
+pub trait InvoiceQ {
+    type Item: InvoiceT;
+
+    fn all(&self) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+    fn guid(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Option<Self::Item>, Error>> + Send;
+}
+
+pub trait JobQ {
+    type Item: JobT;
+
+    fn all(&self) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+    fn guid(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Option<Self::Item>, Error>> + Send;
+}
+
+pub trait LotQ {
+    type Item: LotT;
+
+    fn all(&self) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+    fn guid(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Option<Self::Item>, Error>> + Send;
+    fn account(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+}
+
+pub trait OrderQ {
+    type Item: OrderT;
+
+    fn all(&self) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+    fn guid(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Option<Self::Item>, Error>> + Send;
+}
+
 pub trait PriceQ {
     type Item: PriceT;
 
@@ -131,12 +329,40 @@ pub trait SplitQ {
         &self,
         guid: &str,
     ) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+    /// Returns the splits of an account whose transaction is posted at or after `from`
+    /// and before `to`, each with the post date of its transaction, oldest first.
+    /// A `None` bound leaves that side open.
+    fn account_between(
+        &self,
+        guid: &str,
+        from: Option<NaiveDateTime>,
+        to: Option<NaiveDateTime>,
+    ) -> impl std::future::Future<Output = Result<Vec<(NaiveDateTime, Self::Item)>, Error>> + Send;
     fn transaction(
         &self,
         guid: &str,
     ) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
 }
 
+pub trait TaxTableQ {
+    type Item: TaxTableT;
+
+    fn all(&self) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+    fn guid(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Option<Self::Item>, Error>> + Send;
+}
+
+pub trait TaxTableEntryQ {
+    type Item: TaxTableEntryT;
+
+    fn taxtable(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+}
+
 pub trait TransactionQ {
     type Item: TransactionT;
 
@@ -151,10 +377,199 @@ pub trait TransactionQ {
     ) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
 }
 
+pub trait RecurrenceQ {
+    type Item: RecurrenceT;
+
+    fn obj_guid(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+}
+
+pub trait ScheduledTransactionQ {
+    type Item: ScheduledTransactionT;
+
+    fn all(&self) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+    fn guid(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Option<Self::Item>, Error>> + Send;
+}
+
+pub trait SlotQ {
+    type Item: SlotT;
+
+    /// Returns the slots directly attached to `guid`.
+    ///
+    /// The entries of a frame slot are attached to the frame's `guid_val`.
+    fn obj_guid(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+}
+
+pub trait VendorQ {
+    type Item: VendorT;
+
+    fn all(&self) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+    fn guid(
+        &self,
+        guid: &str,
+    ) -> impl std::future::Future<Output = Result<Option<Self::Item>, Error>> + Send;
+}
+
+pub trait VersionQ {
+    type Item: VersionT;
+
+    /// Returns the rows of the `versions` table; files without one yield none.
+    fn all(&self) -> impl std::future::Future<Output = Result<Vec<Self::Item>, Error>> + Send;
+}
+
+pub trait AccountMutQ {
+    fn insert(
+        &self,
+        account: &AccountRecord,
+    ) -> impl std::future::Future<Output = Result<(), Error>> + Send;
+    /// Overwrites every column of the account identified by `account.guid`.
+    fn update(
+        &self,
+        account: &AccountRecord,
+    ) -> impl std::future::Future<Output = Result<(), Error>> + Send;
+    /// Removes the account and its slots. Refuses with [`Error::Invalid`] while splits, child
+    /// accounts, lots or budget amounts still refer to it.
+    fn delete(&self, guid: &str) -> impl std::future::Future<Output = Result<(), Error>> + Send;
+}
+
+pub trait PriceMutQ {
+    /// Inserts the prices in a single database transaction, skipping every price which
+    /// shares commodity, currency and calendar date with a stored or earlier one.
+    /// Returns the inserted prices.
+    fn insert(
+        &self,
+        prices: &[PriceRecord],
+    ) -> impl std::future::Future<Output = Result<Vec<PriceRecord>, Error>> + Send;
+}
+
+pub trait TransactionMutQ {
+    /// Inserts a transaction together with its splits in a single database transaction.
+    fn insert(
+        &self,
+        transaction: &TransactionRecord,
+        splits: &[SplitRecord],
+    ) -> impl std::future::Future<Output = Result<(), Error>> + Send;
+    /// Zeroes the splits of a transaction, keeping their former amounts, `reason` and
+    /// `datetime` in slots the way `GnuCash` voids a transaction.
+    fn void(
+        &self,
+        guid: &str,
+        reason: &str,
+        datetime: NaiveDateTime,
+    ) -> impl std::future::Future<Output = Result<(), Error>> + Send;
+    /// Restores the amounts kept by [`TransactionMutQ::void`] and removes the void slots.
+    fn unvoid(&self, guid: &str) -> impl std::future::Future<Output = Result<(), Error>> + Send;
+}
+
+pub trait SplitMutQ {
+    /// Updates the reconcile flag of the splits in a single database transaction.
+    /// A `None` date keeps the stored reconcile date.
+    fn reconcile(
+        &self,
+        splits: &[ReconcileRecord],
+    ) -> impl std::future::Future<Output = Result<(), Error>> + Send;
+}
+
+/// A buffered mutation applied by a [`ChangeSetQ`] backend.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Change {
+    InsertAccount(AccountRecord),
+    UpdateAccount(AccountRecord),
+    DeleteAccount(String),
+    InsertTransaction(TransactionRecord, Vec<SplitRecord>),
+    InsertPrice(PriceRecord),
+}
+
+/// The stored rows a change set is validated against, read by a [`ChangeSetQ`] backend
+/// inside the database transaction that applies the changes.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StoredState {
+    pub accounts: Vec<AccountRecord>,
+    /// Accounts referenced by at least one split.
+    pub used_accounts: HashSet<String>,
+    /// Commodity, currency and day of the stored prices.
+    pub prices: HashSet<(String, String, NaiveDate)>,
+}
+
+pub trait ChangeSetQ: AccountMutQ + PriceMutQ + TransactionMutQ {
+    /// Applies the changes in order in a single database transaction, once `check` has
+    /// accepted them against the [`StoredState`] read in that transaction.
+    ///
+    /// Other writers are kept out from the read to the commit.
+    /// Nothing is written if `check` or any change fails.
+    fn apply(
+        &self,
+        changes: &[Change],
+        check: impl FnOnce(&StoredState) -> Result<(), Error> + Send + 'static,
+    ) -> impl std::future::Future<Output = Result<(), Error>> + Send;
+}
+
+pub trait LockQ {
+    /// Returns the `gnclock` entries of the processes which currently have the book open.
+    fn locks(&self) -> impl std::future::Future<Output = Result<Vec<Lock>, Error>> + Send;
+}
+
+/// Name of this host as recorded in the `gnclock` table.
+pub(crate) fn hostname() -> String {
+    std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .map(|x| x.trim().to_string())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .filter(|x| !x.is_empty())
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+/// Refuses to void a voided transaction or to unvoid one that is not voided. Backends call
+/// it with the stored state inside their write transaction, so concurrent calls cannot both
+/// pass it.
+#[cfg(any(feature = "sqlite", feature = "postgresql", feature = "mysql"))]
+pub(crate) fn check_void(voided: bool, voiding: bool) -> Result<(), Error> {
+    let reason = match (voided, voiding) {
+        (true, true) => "already voided",
+        (false, false) => "not voided",
+        _ => return Ok(()),
+    };
+    tracing::error!("transaction is {reason}");
+    Err(Error::Invalid {
+        model: "Transaction".to_string(),
+        reason: reason.to_string(),
+    })
+}
+
+/// The rows which keep an account from being deleted: table, column referring to the
+/// account and how the refusal names them. Backends look them up inside their write
+/// transaction, so nothing can start using the account between the check and the delete.
+#[cfg(any(feature = "sqlite", feature = "postgresql", feature = "mysql"))]
+pub(crate) const ACCOUNT_REFERENCES: [(&str, &str, &str); 4] = [
+    ("splits", "account_guid", "splits"),
+    ("accounts", "parent_guid", "children"),
+    ("lots", "account_guid", "lots"),
+    ("budget_amounts", "account_guid", "budget amounts"),
+];
+
+/// The refusal to delete an account which rows of `what` still refer to.
+#[cfg(any(feature = "sqlite", feature = "postgresql", feature = "mysql"))]
+pub(crate) fn account_in_use(guid: &str, what: &str) -> Error {
+    tracing::error!("account {guid} still has {what}");
+    Error::Invalid {
+        model: "Account".to_string(),
+        reason: format!("account {guid} still has {what}"),
+    }
+}
+
 pub trait AccountT {
     fn guid(&self) -> &str;
     fn name(&self) -> &str;
-    fn account_type(&self) -> &str;
+    fn account_type(&self) -> AccountType;
     fn commodity_guid(&self) -> &str;
     fn commodity_scu(&self) -> i64;
     fn non_std_scu(&self) -> bool;
@@ -164,6 +579,40 @@ pub trait AccountT {
     fn hidden(&self) -> bool;
     fn placeholder(&self) -> bool;
 }
+pub trait BillTermT {
+    fn guid(&self) -> &str;
+    fn name(&self) -> &str;
+    fn description(&self) -> &str;
+    fn refcount(&self) -> i64;
+    fn invisible(&self) -> bool;
+    fn parent_guid(&self) -> &str;
+    /// `GNC_TERM_TYPE_DAYS` or `GNC_TERM_TYPE_PROXIMO`.
+    fn r#type(&self) -> &str;
+    fn due_days(&self) -> i64;
+    fn discount_days(&self) -> i64;
+    fn discount_num(&self) -> i64;
+    fn discount_denom(&self) -> i64;
+    fn cutoff(&self) -> i64;
+}
+pub trait BookT {
+    fn guid(&self) -> &str;
+    fn root_account_guid(&self) -> &str;
+    /// The root of the accounts used by scheduled transaction templates.
+    fn root_template_guid(&self) -> &str;
+}
+pub trait BudgetT {
+    fn guid(&self) -> &str;
+    fn name(&self) -> &str;
+    fn description(&self) -> &str;
+    fn num_periods(&self) -> i64;
+}
+pub trait BudgetAmountT {
+    fn budget_guid(&self) -> &str;
+    fn account_guid(&self) -> &str;
+    fn period_num(&self) -> i64;
+    fn amount_num(&self) -> i64;
+    fn amount_denom(&self) -> i64;
+}
 pub trait CommodityT {
     fn guid(&self) -> &str;
     fn namespace(&self) -> &str;
@@ -175,6 +624,121 @@ pub trait CommodityT {
     fn quote_source(&self) -> &str;
     fn quote_tz(&self) -> &str;
 }
+pub trait CustomerT {
+    fn guid(&self) -> &str;
+    fn name(&self) -> &str;
+    /// The number the customer is known by, e.g. `000001`.
+    fn id(&self) -> &str;
+    fn notes(&self) -> &str;
+    fn active(&self) -> bool;
+    fn discount_num(&self) -> i64;
+    fn discount_denom(&self) -> i64;
+    fn credit_num(&self) -> i64;
+    fn credit_denom(&self) -> i64;
+    fn currency_guid(&self) -> &str;
+    fn tax_override(&self) -> bool;
+    fn addr(&self) -> Address;
+    fn shipaddr(&self) -> Address;
+    fn terms_guid(&self) -> &str;
+    /// One of `YES`, `NO` or `USEGLOBAL`.
+    fn tax_included(&self) -> &str;
+    fn taxtable_guid(&self) -> &str;
+}
+pub trait EmployeeT {
+    fn guid(&self) -> &str;
+    fn username(&self) -> &str;
+    fn id(&self) -> &str;
+    fn language(&self) -> &str;
+    fn acl(&self) -> &str;
+    fn active(&self) -> bool;
+    fn currency_guid(&self) -> &str;
+    /// The credit card account expenses are charged to.
+    fn ccard_guid(&self) -> &str;
+    fn workday_num(&self) -> i64;
+    fn workday_denom(&self) -> i64;
+    fn rate_num(&self) -> i64;
+    fn rate_denom(&self) -> i64;
+    fn addr(&self) -> Address;
+}
+pub trait EntryT {
+    fn guid(&self) -> &str;
+    fn date(&self) -> NaiveDateTime;
+    fn date_entered(&self) -> Option<NaiveDateTime>;
+    fn description(&self) -> &str;
+    fn action(&self) -> &str;
+    fn notes(&self) -> &str;
+    fn quantity_num(&self) -> i64;
+    fn quantity_denom(&self) -> i64;
+    fn i_acct_guid(&self) -> &str;
+    fn i_price_num(&self) -> i64;
+    fn i_price_denom(&self) -> i64;
+    fn i_discount_num(&self) -> i64;
+    fn i_discount_denom(&self) -> i64;
+    fn invoice_guid(&self) -> &str;
+    /// `PERCENT` or `VALUE`.
+    fn i_disc_type(&self) -> &str;
+    /// `PRETAX`, `SAMETIME` or `POSTTAX`.
+    fn i_disc_how(&self) -> &str;
+    fn i_taxable(&self) -> bool;
+    fn i_taxincluded(&self) -> bool;
+    fn i_taxtable_guid(&self) -> &str;
+    fn b_acct_guid(&self) -> &str;
+    fn b_price_num(&self) -> i64;
+    fn b_price_denom(&self) -> i64;
+    fn bill_guid(&self) -> &str;
+    fn b_taxable(&self) -> bool;
+    fn b_taxincluded(&self) -> bool;
+    fn b_taxtable_guid(&self) -> &str;
+    /// 1 cash, 2 credit card.
+    fn b_paytype(&self) -> i64;
+    fn billable(&self) -> bool;
+    fn order_guid(&self) -> &str;
+}
+pub trait InvoiceT {
+    fn guid(&self) -> &str;
+    fn id(&self) -> &str;
+    fn date_opened(&self) -> Option<NaiveDateTime>;
+    fn date_posted(&self) -> Option<NaiveDateTime>;
+    fn notes(&self) -> &str;
+    fn active(&self) -> bool;
+    fn currency_guid(&self) -> &str;
+    /// As `GncOwnerType`: 2 customer, 3 job, 4 vendor, 5 employee.
+    fn owner_type(&self) -> i64;
+    fn owner_guid(&self) -> &str;
+    fn terms_guid(&self) -> &str;
+    fn billing_id(&self) -> &str;
+    fn post_txn_guid(&self) -> &str;
+    fn post_lot_guid(&self) -> &str;
+    fn post_acc_guid(&self) -> &str;
+    fn charge_amt_num(&self) -> i64;
+    fn charge_amt_denom(&self) -> i64;
+}
+pub trait JobT {
+    fn guid(&self) -> &str;
+    fn id(&self) -> &str;
+    fn name(&self) -> &str;
+    fn reference(&self) -> &str;
+    fn active(&self) -> bool;
+    fn owner_type(&self) -> i64;
+    fn owner_guid(&self) -> &str;
+}
+pub trait LotT {
+    fn guid(&self) -> &str;
+    fn account_guid(&self) -> &str;
+    /// The closed state cached by the backend, `None` where it is not stored (XML).
+    fn is_closed(&self) -> Option<bool>;
+}
+pub trait OrderT {
+    fn guid(&self) -> &str;
+    fn id(&self) -> &str;
+    fn notes(&self) -> &str;
+    fn reference(&self) -> &str;
+    fn active(&self) -> bool;
+    fn date_opened(&self) -> NaiveDateTime;
+    fn date_closed(&self) -> Option<NaiveDateTime>;
+    fn owner_type(&self) -> i64;
+    fn owner_guid(&self) -> &str;
+}
 pub trait PriceT {
     fn guid(&self) -> &str;
     fn commodity_guid(&self) -> &str;
@@ -183,6 +747,50 @@ pub trait PriceT {
     fn source(&self) -> &str;
     fn r#type(&self) -> &str;
     fn value(&self) -> crate::Num;
+    fn value_num(&self) -> i64;
+    fn value_denom(&self) -> i64;
+}
+pub trait RecurrenceT {
+    fn obj_guid(&self) -> &str;
+    fn mult(&self) -> i64;
+    /// One of `once`, `day`, `week`, `month`, `end of month`, `nth weekday`,
+    /// `last weekday` or `year`.
+    fn period_type(&self) -> &str;
+    fn period_start(&self) -> NaiveDate;
+    fn weekend_adjust(&self) -> &str;
+}
+pub trait ScheduledTransactionT {
+    fn guid(&self) -> &str;
+    fn name(&self) -> &str;
+    fn enabled(&self) -> bool;
+    fn start_date(&self) -> Option<NaiveDate>;
+    fn end_date(&self) -> Option<NaiveDate>;
+    /// The date of the last occurrence that was created.
+    fn last_occur(&self) -> Option<NaiveDate>;
+    /// The total number of occurrences, 0 when unlimited.
+    fn num_occur(&self) -> i64;
+    fn rem_occur(&self) -> i64;
+    fn auto_create(&self) -> bool;
+    fn auto_notify(&self) -> bool;
+    fn adv_creation(&self) -> i64;
+    fn adv_notify(&self) -> i64;
+    fn instance_count(&self) -> i64;
+    /// The account under the template root holding the template splits.
+    fn template_act_guid(&self) -> &str;
+}
+pub trait SlotT {
+    fn obj_guid(&self) -> &str;
+    /// Full path of the slot, e.g. `options/Accounts/Use Trading Accounts`.
+    fn name(&self) -> &str;
+    fn slot_type(&self) -> i64;
+    fn int64_val(&self) -> i64;
+    fn string_val(&self) -> &str;
+    fn double_val(&self) -> f64;
+    fn timespec_val(&self) -> Option<NaiveDateTime>;
+    fn guid_val(&self) -> &str;
+    fn numeric_val_num(&self) -> i64;
+    fn numeric_val_denom(&self) -> i64;
+    fn gdate_val(&self) -> Option<NaiveDate>;
 }
 pub trait SplitT {
     fn guid(&self) -> &str;
@@ -191,10 +799,31 @@ pub trait SplitT {
     fn memo(&self) -> &str;
     fn action(&self) -> &str;
     fn reconcile_state(&self) -> bool;
+    /// The raw reconcile flag: `n`, `c`, `y`, `f` or `v`.
+    fn reconcile_flag(&self) -> char;
     fn reconcile_datetime(&self) -> Option<NaiveDateTime>;
     fn lot_guid(&self) -> &str;
     fn value(&self) -> crate::Num;
     fn quantity(&self) -> crate::Num;
+    fn value_num(&self) -> i64;
+    fn value_denom(&self) -> i64;
+    fn quantity_num(&self) -> i64;
+    fn quantity_denom(&self) -> i64;
+}
+pub trait TaxTableT {
+    fn guid(&self) -> &str;
+    fn name(&self) -> &str;
+    fn refcount(&self) -> i64;
+    fn invisible(&self) -> bool;
+    fn parent_guid(&self) -> &str;
+}
+pub trait TaxTableEntryT {
+    fn taxtable_guid(&self) -> &str;
+    fn account_guid(&self) -> &str;
+    fn amount_num(&self) -> i64;
+    fn amount_denom(&self) -> i64;
+    /// As `GncAmountType`: 1 value, 2 percent.
+    fn r#type(&self) -> i64;
 }
 pub trait TransactionT {
     fn guid(&self) -> &str;
@@ -204,9 +833,166 @@ pub trait TransactionT {
     fn enter_datetime(&self) -> NaiveDateTime;
     fn description(&self) -> &str;
 }
+pub trait VendorT {
+    fn guid(&self) -> &str;
+    fn name(&self) -> &str;
+    fn id(&self) -> &str;
+    fn notes(&self) -> &str;
+    fn currency_guid(&self) -> &str;
+    fn active(&self) -> bool;
+    fn tax_override(&self) -> bool;
+    fn addr(&self) -> Address;
+    fn terms_guid(&self) -> &str;
+    /// One of `YES`, `NO` or `USEGLOBAL`.
+    fn tax_included(&self) -> &str;
+    fn taxtable_guid(&self) -> &str;
+}
+pub trait VersionT {
+    /// A table name, or `Gnucash` and `Gnucash-Resave` for the application versions.
+    fn table_name(&self) -> &str;
+    fn table_version(&self) -> i64;
+}
+
+/// An `accounts` row to be written by an [`AccountMutQ`] backend.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AccountRecord {
+    pub guid: String,
+    pub name: String,
+    pub account_type: String,
+    pub commodity_guid: String,
+    pub commodity_scu: i64,
+    pub non_std_scu: bool,
+    pub parent_guid: String,
+    pub code: String,
+    pub description: String,
+    pub hidden: bool,
+    pub placeholder: bool,
+}
+
+#[cfg(any(feature = "sqlite", feature = "postgresql", feature = "mysql"))]
+impl AccountRecord {
+    pub(crate) fn from_item<T: AccountT>(item: &T) -> Self {
+        Self {
+            guid: item.guid().to_string(),
+            name: item.name().to_string(),
+            account_type: item.account_type().to_string(),
+            commodity_guid: item.commodity_guid().to_string(),
+            commodity_scu: item.commodity_scu(),
+            non_std_scu: item.non_std_scu(),
+            parent_guid: item.parent_guid().to_string(),
+            code: item.code().to_string(),
+            description: item.description().to_string(),
+            hidden: item.hidden(),
+            placeholder: item.placeholder(),
+        }
+    }
+}
+
+impl AccountT for AccountRecord {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn account_type(&self) -> AccountType {
+        self.account_type.as_str().into()
+    }
+    fn commodity_guid(&self) -> &str {
+        &self.commodity_guid
+    }
+    fn commodity_scu(&self) -> i64 {
+        self.commodity_scu
+    }
+    fn non_std_scu(&self) -> bool {
+        self.non_std_scu
+    }
+    fn parent_guid(&self) -> &str {
+        &self.parent_guid
+    }
+    fn code(&self) -> &str {
+        &self.code
+    }
+    fn description(&self) -> &str {
+        &self.description
+    }
+    fn hidden(&self) -> bool {
+        self.hidden
+    }
+    fn placeholder(&self) -> bool {
+        self.placeholder
+    }
+}
+
+/// A `commodities` row to be written when converting a book.
+#[cfg(feature = "sqlite")]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CommodityRecord {
+    pub guid: String,
+    pub namespace: String,
+    pub mnemonic: String,
+    pub fullname: String,
+    pub cusip: String,
+    pub fraction: i64,
+    pub quote_flag: bool,
+    pub quote_source: String,
+    pub quote_tz: String,
+}
+
+/// A `prices` row to be written by a [`PriceMutQ`] backend.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PriceRecord {
+    pub guid: String,
+    pub commodity_guid: String,
+    pub currency_guid: String,
+    pub datetime: NaiveDateTime,
+    pub source: String,
+    pub r#type: String,
+    pub value_num: i64,
+    pub value_denom: i64,
+}
+
+/// A `transactions` row to be written by a [`TransactionMutQ`] backend.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TransactionRecord {
+    pub guid: String,
+    pub currency_guid: String,
+    pub num: String,
+    pub post_datetime: NaiveDateTime,
+    pub enter_datetime: NaiveDateTime,
+    pub description: String,
+}
+
+/// A `splits` row to be written by a [`TransactionMutQ`] backend.
+///
+/// Amounts are kept as the `num/denom` pairs `GnuCash` stores, so no precision is lost.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SplitRecord {
+    pub guid: String,
+    pub tx_guid: String,
+    pub account_guid: String,
+    pub memo: String,
+    pub action: String,
+    pub reconcile_state: char,
+    pub reconcile_datetime: Option<NaiveDateTime>,
+    pub value_num: i64,
+    pub value_denom: i64,
+    pub quantity_num: i64,
+    pub quantity_denom: i64,
+    pub lot_guid: String,
+}
+
+/// The reconcile columns of a `splits` row to be written by a [`SplitMutQ`] backend.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ReconcileRecord {
+    pub guid: String,
+    pub reconcile_state: char,
+    pub reconcile_datetime: Option<NaiveDateTime>,
+}
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     #[cfg(not(feature = "decimal"))]
     use float_cmp::assert_approx_eq;
     #[cfg(feature = "decimal")]
@@ -215,6 +1001,225 @@ mod tests {
 
     use super::*;
 
+    /// Rows the sample book lacks, added to copies of it with the `setup_with` helper of
+    /// each SQL backend.
+    #[cfg(any(feature = "sqlite", feature = "postgresql", feature = "mysql"))]
+    pub(crate) mod fixtures {
+        /// A lot of `Foo stock` holding one of its splits, with a title and notes.
+        pub(crate) const LOT: &str = "
+            INSERT INTO lots (guid, account_guid, is_closed)
+            VALUES ('a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01', '1c089803052e85f5c6d8e786057dbaee', 0);
+            UPDATE splits SET lot_guid = 'a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01'
+            WHERE guid = 'e07539176a70222b16369bd246b174b9';
+            INSERT INTO slots (obj_guid, name, slot_type, string_val) VALUES
+            ('a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01', 'title', 4, 'Lot 1'),
+            ('a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01', 'notes', 4, 'bought on margin');
+        ";
+
+        /// A monthly budget for 2018.
+        pub(crate) const BUDGET: &str = "
+            INSERT INTO budgets (guid, name, description, num_periods)
+            VALUES ('5f1a0c7d1e9b4b6f8a2d3c4e5f607182', 'Household', 'monthly spending', 12);
+            INSERT INTO recurrences (obj_guid, recurrence_mult, recurrence_period_type,
+                                     recurrence_period_start, recurrence_weekend_adjust)
+            VALUES ('5f1a0c7d1e9b4b6f8a2d3c4e5f607182', 1, 'month', '20180101', 'none');
+            INSERT INTO budget_amounts (budget_guid, account_guid, period_num, amount_num, amount_denom) VALUES
+            ('5f1a0c7d1e9b4b6f8a2d3c4e5f607182', 'af88d386d44b14acf244362b85ccaf4c', 1, 5000, 100),
+            ('5f1a0c7d1e9b4b6f8a2d3c4e5f607182', 'af88d386d44b14acf244362b85ccaf4c', 2, 4000, 100),
+            ('5f1a0c7d1e9b4b6f8a2d3c4e5f607182', '6bbc8f20544452cac1637fb9a9b851bb', 1, -20000, 100);
+        ";
+
+        /// A monthly rent payment scheduled from March 2018, whose template transaction
+        /// debits 500 to `Expense` and credits it to `Checking`.
+        #[cfg(feature = "sqlite")]
+        pub(crate) const SCHEDULED_TRANSACTION: &str = "
+            INSERT INTO schedxactions (guid, name, enabled, start_date, end_date, last_occur,
+                                       num_occur, rem_occur, auto_create, auto_notify,
+                                       adv_creation, adv_notify, instance_count, template_act_guid)
+            VALUES ('3b4c5d6e7f8091a2b3c4d5e6f7081920', 'Rent', 1, '20180301', NULL, '20180301',
+                    6, 5, 0, 0, 2, 0, 1, '9a8b7c6d5e4f30211203f4e5d6c7b8a9');
+            INSERT INTO recurrences (obj_guid, recurrence_mult, recurrence_period_type,
+                                     recurrence_period_start, recurrence_weekend_adjust)
+            VALUES ('3b4c5d6e7f8091a2b3c4d5e6f7081920', 1, 'month', '20180301', 'none');
+            INSERT INTO accounts (guid, name, account_type, commodity_guid, commodity_scu,
+                                  non_std_scu, parent_guid, code, description, hidden, placeholder)
+            VALUES ('9a8b7c6d5e4f30211203f4e5d6c7b8a9', '3b4c5d6e7f8091a2b3c4d5e6f7081920', 'BANK',
+                    '346629655191dcf59a7e2c2a85b70f69', 100, 0, 'f6c0cd00ec04169a44f170181882adab',
+                    '', '', 0, 0);
+            INSERT INTO transactions (guid, currency_guid, num, post_date, enter_date, description)
+            VALUES ('b1c2d3e4f5a60718293a4b5c6d7e8f90', '346629655191dcf59a7e2c2a85b70f69', '',
+                    '2018-03-01 10:59:00', '2018-03-01 10:59:00', 'Rent');
+            INSERT INTO splits (guid, tx_guid, account_guid, memo, action, reconcile_state,
+                                reconcile_date, value_num, value_denom, quantity_num,
+                                quantity_denom, lot_guid) VALUES
+            ('c1d2e3f4a5b60718293a4b5c6d7e8f91', 'b1c2d3e4f5a60718293a4b5c6d7e8f90',
+             '9a8b7c6d5e4f30211203f4e5d6c7b8a9', '', '', 'n', NULL, 0, 1, 0, 1, NULL),
+            ('c1d2e3f4a5b60718293a4b5c6d7e8f92', 'b1c2d3e4f5a60718293a4b5c6d7e8f90',
+             '9a8b7c6d5e4f30211203f4e5d6c7b8a9', '', '', 'n', NULL, 0, 1, 0, 1, NULL);
+            INSERT INTO slots (obj_guid, name, slot_type, string_val, guid_val,
+                               numeric_val_num, numeric_val_denom) VALUES
+            ('c1d2e3f4a5b60718293a4b5c6d7e8f91', 'sched-xaction', 9, NULL, 'frame1', NULL, NULL),
+            ('frame1', 'sched-xaction/account', 5, NULL, 'af88d386d44b14acf244362b85ccaf4c', NULL, NULL),
+            ('frame1', 'sched-xaction/debit-formula', 4, '500', NULL, NULL, NULL),
+            ('frame1', 'sched-xaction/debit-numeric', 3, NULL, NULL, 500, 1),
+            ('frame1', 'sched-xaction/credit-formula', 4, '', NULL, NULL, NULL),
+            ('frame1', 'sched-xaction/credit-numeric', 3, NULL, NULL, 0, 1),
+            ('c1d2e3f4a5b60718293a4b5c6d7e8f92', 'sched-xaction', 9, NULL, 'frame2', NULL, NULL),
+            ('frame2', 'sched-xaction/account', 5, NULL, 'adc619f0ac7fa27d5768bfd73ecbc01e', NULL, NULL),
+            ('frame2', 'sched-xaction/debit-formula', 4, '', NULL, NULL, NULL),
+            ('frame2', 'sched-xaction/debit-numeric', 3, NULL, NULL, 0, 1),
+            ('frame2', 'sched-xaction/credit-formula', 4, '500', NULL, NULL, NULL),
+            ('frame2', 'sched-xaction/credit-numeric', 3, NULL, NULL, 500, 1);
+        ";
+
+        /// A customer, a vendor and an employee.
+        pub(crate) const BUSINESS: &str = "
+            INSERT INTO customers (guid, name, id, notes, active, discount_num, discount_denom,
+                                   credit_num, credit_denom, currency, tax_override,
+                                   addr_name, addr_addr1, addr_addr2, addr_addr3, addr_addr4,
+                                   addr_phone, addr_fax, addr_email,
+                                   shipaddr_name, shipaddr_addr1, shipaddr_addr2, shipaddr_addr3,
+                                   shipaddr_addr4, shipaddr_phone, shipaddr_fax, shipaddr_email,
+                                   terms, tax_included, taxtable)
+            VALUES ('d4e5f6a7b8c9d0e1f2a3b4c5d6e7f801', 'Acme Corp', '000001', 'pays late', 1,
+                    500, 100, 100000, 100, '346629655191dcf59a7e2c2a85b70f69', 0,
+                    'Acme Corp', 'Main Street 1', 'Springfield', '', '',
+                    '555-0100', '', 'billing@acme.example',
+                    'Acme Warehouse', 'Dock Road 9', '', '', '', '', '', '',
+                    NULL, 3, NULL);
+            INSERT INTO vendors (guid, name, id, notes, currency, active, tax_override,
+                                 addr_name, addr_addr1, addr_addr2, addr_addr3, addr_addr4,
+                                 addr_phone, addr_fax, addr_email, terms, tax_inc, tax_table)
+            VALUES ('e5f6a7b8c9d0e1f2a3b4c5d6e7f80912', 'Paper Supplies Ltd', '000001', '',
+                    '346629655191dcf59a7e2c2a85b70f69', 1, 0,
+                    'Paper Supplies Ltd', 'Mill Lane 3', '', '', '', '', '', '',
+                    NULL, 'YES', NULL);
+            INSERT INTO employees (guid, username, id, language, acl, active, currency, ccard_guid,
+                                   workday_num, workday_denom, rate_num, rate_denom,
+                                   addr_name, addr_addr1, addr_addr2, addr_addr3, addr_addr4,
+                                   addr_phone, addr_fax, addr_email)
+            VALUES ('f6a7b8c9d0e1f2a3b4c5d6e7f8091a23', 'jdoe', '000001', '', '', 1,
+                    '346629655191dcf59a7e2c2a85b70f69', NULL, 8, 1, 2500, 100,
+                    'Jane Doe', 'Elm Street 5', '', '', '', '', '', 'jane@example.com');
+        ";
+
+        /// On top of [`BUSINESS`], a posted customer invoice with a partial payment,
+        /// a posted vendor bill, a draft invoice for a job, and an order.
+        pub(crate) const INVOICES: &str = "
+            INSERT INTO accounts (guid, name, account_type, commodity_guid, commodity_scu, non_std_scu,
+                                  parent_guid, code, description, hidden, placeholder)
+            VALUES ('0a1b2c3d4e5f60718293a4b5c6d7e801', 'Accounts Receivable', 'RECEIVABLE',
+                    '346629655191dcf59a7e2c2a85b70f69', 100, 0, 'fcd795021c976ba75621ec39e75f6214',
+                    '', '', 0, 0),
+                   ('0a1b2c3d4e5f60718293a4b5c6d7e802', 'Accounts Payable', 'PAYABLE',
+                    '346629655191dcf59a7e2c2a85b70f69', 100, 0, '96ed7a45459fb5fe570e48fcd46f05d0',
+                    '', '', 0, 0);
+            INSERT INTO jobs (guid, id, name, reference, active, owner_type, owner_guid)
+            VALUES ('1b2c3d4e5f60718293a4b5c6d7e8f901', '000001', 'Website', 'PO-17', 1,
+                    2, 'd4e5f6a7b8c9d0e1f2a3b4c5d6e7f801');
+            INSERT INTO orders (guid, id, notes, reference, active, date_opened, date_closed,
+                                owner_type, owner_guid)
+            VALUES ('2c3d4e5f60718293a4b5c6d7e8f90a01', '000001', '', 'PO-17', 1,
+                    '2018-03-01 10:59:00', '1970-01-01 00:00:00', 3, '1b2c3d4e5f60718293a4b5c6d7e8f901');
+            INSERT INTO invoices (guid, id, date_opened, date_posted, notes, active, currency,
+                                  owner_type, owner_guid, terms, billing_id, post_txn, post_lot, post_acc,
+                                  billto_type, billto_guid, charge_amt_num, charge_amt_denom)
+            VALUES ('3d4e5f60718293a4b5c6d7e8f90a1b01', '000001', '2018-03-01 10:59:00',
+                    '2018-03-05 10:59:00', '', 1, '346629655191dcf59a7e2c2a85b70f69',
+                    2, 'd4e5f6a7b8c9d0e1f2a3b4c5d6e7f801', NULL, 'PO-17',
+                    '60718293a4b5c6d7e8f90a1b2c3d4e01', '5f60718293a4b5c6d7e8f90a1b2c3d01',
+                    '0a1b2c3d4e5f60718293a4b5c6d7e801', NULL, NULL, 0, 1),
+                   ('3d4e5f60718293a4b5c6d7e8f90a1b02', '000001', '2018-03-10 10:59:00',
+                    '2018-03-10 10:59:00', '', 1, '346629655191dcf59a7e2c2a85b70f69',
+                    4, 'e5f6a7b8c9d0e1f2a3b4c5d6e7f80912', NULL, '',
+                    '60718293a4b5c6d7e8f90a1b2c3d4e03', '5f60718293a4b5c6d7e8f90a1b2c3d02',
+                    '0a1b2c3d4e5f60718293a4b5c6d7e802', NULL, NULL, 0, 1),
+                   ('3d4e5f60718293a4b5c6d7e8f90a1b03', '000002', '2018-03-25 10:59:00', NULL, 'draft', 1,
+                    '346629655191dcf59a7e2c2a85b70f69', 3, '1b2c3d4e5f60718293a4b5c6d7e8f901', NULL, '',
+                    NULL, NULL, NULL, NULL, NULL, 0, 1);
+            INSERT INTO entries (guid, date, date_entered, description, action, notes,
+                                 quantity_num, quantity_denom, i_acct, i_price_num, i_price_denom,
+                                 i_discount_num, i_discount_denom, invoice, i_disc_type, i_disc_how,
+                                 i_taxable, i_taxincluded, i_taxtable, b_acct, b_price_num, b_price_denom,
+                                 bill, b_taxable, b_taxincluded, b_taxtable, b_paytype, billable,
+                                 billto_type, billto_guid, order_guid)
+            VALUES ('4e5f60718293a4b5c6d7e8f90a1b2c02', '2018-03-02 10:59:00', '2018-03-02 11:00:00',
+                    'Hosting', 'Material', '', 1, 1, '6bbc8f20544452cac1637fb9a9b851bb', 100, 1, 10, 1,
+                    '3d4e5f60718293a4b5c6d7e8f90a1b01', 'PERCENT', 'PRETAX', 1, 0, NULL,
+                    NULL, 0, 1, NULL, 1, 0, NULL, 1, 0, NULL, NULL, NULL),
+                   ('4e5f60718293a4b5c6d7e8f90a1b2c01', '2018-03-01 10:59:00', '2018-03-01 11:00:00',
+                    'Consulting', 'Hours', '', 10, 1, '6bbc8f20544452cac1637fb9a9b851bb', 50, 1, 0, 1,
+                    '3d4e5f60718293a4b5c6d7e8f90a1b01', 'PERCENT', 'PRETAX', 1, 0, NULL,
+                    NULL, 0, 1, NULL, 1, 0, NULL, 1, 0, NULL, NULL, '2c3d4e5f60718293a4b5c6d7e8f90a01'),
+                   ('4e5f60718293a4b5c6d7e8f90a1b2c03', '2018-03-10 10:59:00', '2018-03-10 11:00:00',
+                    'Paper', '', '', 4, 1, NULL, 0, 1, 0, 1, NULL, 'PERCENT', 'PRETAX', 1, 0, NULL,
+                    'af88d386d44b14acf244362b85ccaf4c', 30, 1, '3d4e5f60718293a4b5c6d7e8f90a1b02',
+                    1, 1, NULL, 2, 0, NULL, NULL, NULL);
+            INSERT INTO lots (guid, account_guid, is_closed)
+            VALUES ('5f60718293a4b5c6d7e8f90a1b2c3d01', '0a1b2c3d4e5f60718293a4b5c6d7e801', 0),
+                   ('5f60718293a4b5c6d7e8f90a1b2c3d02', '0a1b2c3d4e5f60718293a4b5c6d7e802', 0);
+            INSERT INTO transactions (guid, currency_guid, num, post_date, enter_date, description)
+            VALUES ('60718293a4b5c6d7e8f90a1b2c3d4e01', '346629655191dcf59a7e2c2a85b70f69', '000001',
+                    '2018-03-05 10:59:00', '2018-03-05 11:00:00', 'Acme Corp'),
+                   ('60718293a4b5c6d7e8f90a1b2c3d4e02', '346629655191dcf59a7e2c2a85b70f69', '',
+                    '2018-03-20 10:59:00', '2018-03-20 11:00:00', 'Acme Corp'),
+                   ('60718293a4b5c6d7e8f90a1b2c3d4e03', '346629655191dcf59a7e2c2a85b70f69', '000001',
+                    '2018-03-10 10:59:00', '2018-03-10 11:00:00', 'Paper Supplies Ltd');
+            INSERT INTO splits (guid, tx_guid, account_guid, memo, action, reconcile_state, reconcile_date,
+                                value_num, value_denom, quantity_num, quantity_denom, lot_guid)
+            VALUES ('718293a4b5c6d7e8f90a1b2c3d4e5f01', '60718293a4b5c6d7e8f90a1b2c3d4e01',
+                    '0a1b2c3d4e5f60718293a4b5c6d7e801', '', '', 'n', NULL, 59000, 100, 59000, 100,
+                    '5f60718293a4b5c6d7e8f90a1b2c3d01'),
+                   ('718293a4b5c6d7e8f90a1b2c3d4e5f02', '60718293a4b5c6d7e8f90a1b2c3d4e01',
+                    '6bbc8f20544452cac1637fb9a9b851bb', '', '', 'n', NULL, -59000, 100, -59000, 100, NULL),
+                   ('718293a4b5c6d7e8f90a1b2c3d4e5f03', '60718293a4b5c6d7e8f90a1b2c3d4e02',
+                    'adc619f0ac7fa27d5768bfd73ecbc01e', '', '', 'n', NULL, 20000, 100, 20000, 100, NULL),
+                   ('718293a4b5c6d7e8f90a1b2c3d4e5f04', '60718293a4b5c6d7e8f90a1b2c3d4e02',
+                    '0a1b2c3d4e5f60718293a4b5c6d7e801', '', 'Payment', 'n', NULL, -20000, 100, -20000, 100,
+                    '5f60718293a4b5c6d7e8f90a1b2c3d01'),
+                   ('718293a4b5c6d7e8f90a1b2c3d4e5f05', '60718293a4b5c6d7e8f90a1b2c3d4e03',
+                    '0a1b2c3d4e5f60718293a4b5c6d7e802', '', '', 'n', NULL, -12000, 100, -12000, 100,
+                    '5f60718293a4b5c6d7e8f90a1b2c3d02'),
+                   ('718293a4b5c6d7e8f90a1b2c3d4e5f06', '60718293a4b5c6d7e8f90a1b2c3d4e03',
+                    'af88d386d44b14acf244362b85ccaf4c', '', '', 'n', NULL, 12000, 100, 12000, 100, NULL);
+            INSERT INTO slots (obj_guid, name, slot_type, int64_val, string_val, double_val, timespec_val,
+                               guid_val, numeric_val_num, numeric_val_denom, gdate_val)
+            VALUES ('60718293a4b5c6d7e8f90a1b2c3d4e01', 'trans-date-due', 6, 0, NULL, 0,
+                    '2018-04-04 10:59:00', NULL, 0, 1, NULL);
+        ";
+
+        /// On top of [`INVOICES`], a "Net 30" term with an early payment discount, used by
+        /// the customer and their invoice, and a proximo term.
+        pub(crate) const TERMS: &str = "
+            INSERT INTO billterms (guid, name, description, refcount, invisible, parent, type,
+                                   duedays, discountdays, discount_num, discount_denom, cutoff)
+            VALUES ('9b0c1d2e3f405162738495a6b7c8d901', 'Net 30', '2% within 10 days', 2, 0, NULL,
+                    'GNC_TERM_TYPE_DAYS', 30, 10, 2, 1, 0),
+                   ('9b0c1d2e3f405162738495a6b7c8d902', 'Proximo 31', '', 0, 0, NULL,
+                    'GNC_TERM_TYPE_PROXIMO', 31, NULL, NULL, NULL, 25);
+            UPDATE customers SET terms = '9b0c1d2e3f405162738495a6b7c8d901';
+            UPDATE invoices SET terms = '9b0c1d2e3f405162738495a6b7c8d901'
+            WHERE guid = '3d4e5f60718293a4b5c6d7e8f90a1b01';
+        ";
+
+        /// On top of [`INVOICES`], a 20% VAT table, used by the customer and by every
+        /// invoice and bill line, and a table mixing a percentage with a fixed levy.
+        pub(crate) const TAX: &str = "
+            INSERT INTO taxtables (guid, name, refcount, invisible, parent)
+            VALUES ('8a9b0c1d2e3f405162738495a6b7c801', 'VAT', 4, 0, NULL),
+                   ('8a9b0c1d2e3f405162738495a6b7c802', 'Levy', 0, 0, NULL);
+            INSERT INTO taxtable_entries (taxtable, account, amount_num, amount_denom, type)
+            VALUES ('8a9b0c1d2e3f405162738495a6b7c801', '96ed7a45459fb5fe570e48fcd46f05d0', 20, 1, 2),
+                   ('8a9b0c1d2e3f405162738495a6b7c802', '96ed7a45459fb5fe570e48fcd46f05d0', 10, 1, 2),
+                   ('8a9b0c1d2e3f405162738495a6b7c802', 'af88d386d44b14acf244362b85ccaf4c', 500, 100, 1);
+            UPDATE entries SET i_taxtable = '8a9b0c1d2e3f405162738495a6b7c801'
+            WHERE invoice IS NOT NULL;
+            UPDATE entries SET b_taxtable = '8a9b0c1d2e3f405162738495a6b7c801'
+            WHERE bill IS NOT NULL;
+            UPDATE customers SET taxtable = '8a9b0c1d2e3f405162738495a6b7c801';
+        ";
+    }
+
     #[cfg(feature = "sqlite")]
     mod sqlite {
         use super::*;
@@ -548,7 +1553,7 @@ mod tests {
 
                 assert_eq!(result.guid(), "fcd795021c976ba75621ec39e75f6214");
                 assert_eq!(result.name(), "Asset");
-                assert_eq!(result.account_type(), "ASSET");
+                assert_eq!(result.account_type(), AccountType::Asset);
                 assert_eq!(result.commodity_guid(), "346629655191dcf59a7e2c2a85b70f69");
                 assert_eq!(result.commodity_scu(), 100);
                 assert!(!result.non_std_scu());
@@ -1008,7 +2013,7 @@ mod tests {
 
                 assert_eq!(result.guid(), "fcd795021c976ba75621ec39e75f6214");
                 assert_eq!(result.name(), "Asset");
-                assert_eq!(result.account_type(), "ASSET");
+                assert_eq!(result.account_type(), AccountType::Asset);
                 assert_eq!(result.commodity_guid(), "346629655191dcf59a7e2c2a85b70f69");
                 assert_eq!(result.commodity_scu(), 100);
                 assert!(!result.non_std_scu());
@@ -1468,7 +2473,7 @@ mod tests {
 
                 assert_eq!(result.guid(), "fcd795021c976ba75621ec39e75f6214");
                 assert_eq!(result.name(), "Asset");
-                assert_eq!(result.account_type(), "ASSET");
+                assert_eq!(result.account_type(), AccountType::Asset);
                 assert_eq!(result.commodity_guid(), "346629655191dcf59a7e2c2a85b70f69");
                 assert_eq!(result.commodity_scu(), 100);
                 assert!(!result.non_std_scu());
@@ -1924,7 +2929,7 @@ mod tests {
 
                 assert_eq!(result.guid(), "fcd795021c976ba75621ec39e75f6214");
                 assert_eq!(result.name(), "Asset");
-                assert_eq!(result.account_type(), "ASSET");
+                assert_eq!(result.account_type(), AccountType::Asset);
                 assert_eq!(result.commodity_guid(), "EUR");
                 assert_eq!(result.commodity_scu(), 100);
                 assert!(!result.non_std_scu());
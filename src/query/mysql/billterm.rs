@@ -0,0 +1,190 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use sqlx::AssertSqlSafe;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::query::mysql::MySQLQuery;
+use crate::query::{BillTermQ, BillTermT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct BillTerm {
+    pub(crate) guid: String,
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) refcount: i64,
+    pub(crate) invisible: i64,
+    pub(crate) parent: Option<String>,
+    pub(crate) r#type: String,
+    pub(crate) duedays: Option<i64>,
+    pub(crate) discountdays: Option<i64>,
+    pub(crate) discount_num: Option<i64>,
+    pub(crate) discount_denom: Option<i64>,
+    pub(crate) cutoff: Option<i64>,
+}
+
+impl BillTermT for BillTerm {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn description(&self) -> &str {
+        &self.description
+    }
+    fn refcount(&self) -> i64 {
+        self.refcount
+    }
+    fn invisible(&self) -> bool {
+        self.invisible != 0
+    }
+    fn parent_guid(&self) -> &str {
+        self.parent.as_deref().unwrap_or_default()
+    }
+    fn r#type(&self) -> &str {
+        &self.r#type
+    }
+    fn due_days(&self) -> i64 {
+        self.duedays.unwrap_or(0)
+    }
+    fn discount_days(&self) -> i64 {
+        self.discountdays.unwrap_or(0)
+    }
+    fn discount_num(&self) -> i64 {
+        self.discount_num.unwrap_or(0)
+    }
+    fn discount_denom(&self) -> i64 {
+        self.discount_denom.unwrap_or(1)
+    }
+    fn cutoff(&self) -> i64 {
+        self.cutoff.unwrap_or(0)
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+name,
+description,
+refcount,
+invisible,
+parent,
+type,
+duedays,
+discountdays,
+discount_num,
+discount_denom,
+cutoff
+FROM billterms
+";
+
+impl BillTermQ for MySQLQuery {
+    type Item = BillTerm;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all bill terms from mysql");
+        sqlx::query_as(SEL)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching bill term by guid from mysql");
+        sqlx::query_as(AssertSqlSafe(format!("{SEL}\nWHERE guid = ?")))
+            .bind(guid)
+            .fetch_optional(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::mysql::tests::setup_with;
+    use crate::query::mysql::tests::teardown;
+    use crate::query::tests::fixtures::{BUSINESS, INVOICES, TERMS};
+
+    #[cfg(feature = "schema")]
+    // test schemas on compile time
+    #[allow(dead_code)]
+    fn test_billterm_schemas() {
+        let _ = sqlx::query_as!(
+            BillTerm,
+            r"
+    			SELECT
+    			guid,
+    			name,
+    			description,
+    			refcount,
+    			invisible,
+    			parent,
+    			type,
+    			duedays,
+    			discountdays,
+    			discount_num,
+    			discount_denom,
+    			cutoff
+    			FROM billterms
+    			",
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_days() {
+        let (query, database) = setup_with(&[BUSINESS, INVOICES, TERMS]).await;
+        let result = BillTermQ::guid(&query, "9b0c1d2e3f405162738495a6b7c8d901")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.name(), "Net 30");
+        assert_eq!(result.description(), "2% within 10 days");
+        assert_eq!(result.refcount(), 2);
+        assert_eq!(result.invisible(), false);
+        assert_eq!(result.parent_guid(), "");
+        assert_eq!(result.r#type(), "GNC_TERM_TYPE_DAYS");
+        assert_eq!(result.due_days(), 30);
+        assert_eq!(result.discount_days(), 10);
+        assert_eq!(result.discount_num(), 2);
+        assert_eq!(result.discount_denom(), 1);
+
+        teardown(&database).await;
+    }
+
+    #[test(tokio::test)]
+    async fn test_proximo() {
+        let (query, database) = setup_with(&[BUSINESS, INVOICES, TERMS]).await;
+        let result = BillTermQ::guid(&query, "9b0c1d2e3f405162738495a6b7c8d902")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.r#type(), "GNC_TERM_TYPE_PROXIMO");
+        assert_eq!(result.due_days(), 31);
+        assert_eq!(result.discount_days(), 0);
+        assert_eq!(result.discount_denom(), 1);
+        assert_eq!(result.cutoff(), 25);
+
+        teardown(&database).await;
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (query, database) = setup_with(&[BUSINESS, INVOICES, TERMS]).await;
+        let result = BillTermQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 2);
+
+        teardown(&database).await;
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::NaiveDateTime;
+use sqlx::{AssertSqlSafe, MySqlConnection};
+use tracing::instrument;
+
+use super::MySQLQuery;
+use crate::error::Error;
+use crate::query::{AccountRecord, Change, ChangeSetQ, StoredState};
+
+/// Reads the rows a change set is validated against; the caller owns the surrounding
+/// database transaction.
+async fn stored(conn: &mut MySqlConnection) -> Result<StoredState, sqlx::Error> {
+    // shared locks on the rows and gaps read keep other writers out until the commit
+    let accounts: Vec<super::account::Account> = sqlx::query_as(AssertSqlSafe(format!(
+        "{}\nLOCK IN SHARE MODE",
+        super::account::SEL
+    )))
+    .fetch_all(&mut *conn)
+    .await?;
+    let used_accounts: Vec<String> =
+        sqlx::query_scalar("SELECT account_guid FROM splits LOCK IN SHARE MODE")
+            .fetch_all(&mut *conn)
+            .await?;
+    let prices: Vec<(String, String, NaiveDateTime)> =
+        sqlx::query_as("SELECT commodity_guid, currency_guid, date FROM prices LOCK IN SHARE MODE")
+            .fetch_all(&mut *conn)
+            .await?;
+
+    Ok(StoredState {
+        accounts: accounts.iter().map(AccountRecord::from_item).collect(),
+        used_accounts: used_accounts.into_iter().collect(),
+        prices: prices
+            .into_iter()
+            .map(|(commodity, currency, date)| (commodity, currency, date.date()))
+            .collect(),
+    })
+}
+
+/// Applies a single change; the caller owns the surrounding database transaction.
+async fn apply(conn: &mut MySqlConnection, change: &Change) -> Result<(), Error> {
+    match change {
+        Change::InsertAccount(account) => super::account::insert(conn, account).await?,
+        Change::UpdateAccount(account) => super::account::update(conn, account).await?,
+        Change::DeleteAccount(guid) => super::account::delete(conn, guid).await?,
+        Change::InsertTransaction(transaction, splits) => {
+            super::transaction::insert(conn, transaction)
+                .await
+                .inspect_err(|e| tracing::error!("failed to insert transaction: {e}"))?;
+            for split in splits {
+                super::split::insert(conn, split)
+                    .await
+                    .inspect_err(|e| tracing::error!("failed to insert split: {e}"))?;
+            }
+        }
+        Change::InsertPrice(price) => super::price::insert(conn, price)
+            .await
+            .inspect_err(|e| tracing::error!("failed to insert price: {e}"))?,
+    }
+    Ok(())
+}
+
+impl ChangeSetQ for MySQLQuery {
+    #[instrument(skip(self, changes, check), fields(count = changes.len()))]
+    async fn apply(
+        &self,
+        changes: &[Change],
+        check: impl FnOnce(&StoredState) -> Result<(), Error> + Send + 'static,
+    ) -> Result<(), Error> {
+        tracing::debug!("applying change set to mysql");
+        let mut tx = self.begin().await?;
+
+        check(&stored(&mut tx).await?)?;
+        for change in changes {
+            apply(&mut tx, change).await?;
+        }
+
+        super::lock::commit(tx).await?;
+
+        tracing::debug!(count = changes.len(), "change set applied to mysql");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::query::mysql::tests::{setup_writable, teardown};
+    use crate::query::{AccountQ, AccountRecord};
+
+    #[test(tokio::test)]
+    async fn test_apply_rollback() {
+        let (query, database) = setup_writable().await;
+        let account = AccountRecord {
+            guid: "11111111111111111111111111111111".to_string(),
+            name: "Wallet".to_string(),
+            account_type: "CASH".to_string(),
+            commodity_guid: "346629655191dcf59a7e2c2a85b70f69".to_string(),
+            commodity_scu: 100,
+            non_std_scu: false,
+            parent_guid: "3bc319753945b6dba3e1928abed49e35".to_string(),
+            code: String::new(),
+            description: String::new(),
+            hidden: false,
+            placeholder: false,
+        };
+        let changes = [
+            Change::InsertAccount(account.clone()),
+            Change::DeleteAccount("unknown".to_string()),
+        ];
+
+        let result = query.apply(&changes, |_| Ok(())).await;
+        assert!(matches!(result, Err(Error::GuidNotFound { .. })));
+        assert!(
+            AccountQ::guid(&query, &account.guid)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        query.apply(&changes[..1], |_| Ok(())).await.unwrap();
+        assert!(
+            AccountQ::guid(&query, &account.guid)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        teardown(&database).await;
+    }
+}
@@ -0,0 +1,261 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use sqlx::AssertSqlSafe;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::model::Address;
+use crate::query::mysql::MySQLQuery;
+use crate::query::{CustomerQ, CustomerT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct Customer {
+    pub(crate) guid: String,
+    pub(crate) name: String,
+    pub(crate) id: String,
+    pub(crate) notes: String,
+    pub(crate) active: i64,
+    pub(crate) discount_num: i64,
+    pub(crate) discount_denom: i64,
+    pub(crate) credit_num: i64,
+    pub(crate) credit_denom: i64,
+    pub(crate) currency: String,
+    pub(crate) tax_override: i64,
+    pub(crate) addr_name: Option<String>,
+    pub(crate) addr_addr1: Option<String>,
+    pub(crate) addr_addr2: Option<String>,
+    pub(crate) addr_addr3: Option<String>,
+    pub(crate) addr_addr4: Option<String>,
+    pub(crate) addr_phone: Option<String>,
+    pub(crate) addr_fax: Option<String>,
+    pub(crate) addr_email: Option<String>,
+    pub(crate) shipaddr_name: Option<String>,
+    pub(crate) shipaddr_addr1: Option<String>,
+    pub(crate) shipaddr_addr2: Option<String>,
+    pub(crate) shipaddr_addr3: Option<String>,
+    pub(crate) shipaddr_addr4: Option<String>,
+    pub(crate) shipaddr_phone: Option<String>,
+    pub(crate) shipaddr_fax: Option<String>,
+    pub(crate) shipaddr_email: Option<String>,
+    pub(crate) terms: Option<String>,
+    pub(crate) tax_included: Option<i64>,
+    pub(crate) taxtable: Option<String>,
+}
+
+impl CustomerT for Customer {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn notes(&self) -> &str {
+        &self.notes
+    }
+    fn active(&self) -> bool {
+        self.active != 0
+    }
+    fn discount_num(&self) -> i64 {
+        self.discount_num
+    }
+    fn discount_denom(&self) -> i64 {
+        self.discount_denom
+    }
+    fn credit_num(&self) -> i64 {
+        self.credit_num
+    }
+    fn credit_denom(&self) -> i64 {
+        self.credit_denom
+    }
+    fn currency_guid(&self) -> &str {
+        &self.currency
+    }
+    fn tax_override(&self) -> bool {
+        self.tax_override != 0
+    }
+    fn addr(&self) -> Address {
+        Address {
+            name: self.addr_name.clone().unwrap_or_default(),
+            addr1: self.addr_addr1.clone().unwrap_or_default(),
+            addr2: self.addr_addr2.clone().unwrap_or_default(),
+            addr3: self.addr_addr3.clone().unwrap_or_default(),
+            addr4: self.addr_addr4.clone().unwrap_or_default(),
+            phone: self.addr_phone.clone().unwrap_or_default(),
+            fax: self.addr_fax.clone().unwrap_or_default(),
+            email: self.addr_email.clone().unwrap_or_default(),
+        }
+    }
+    fn shipaddr(&self) -> Address {
+        Address {
+            name: self.shipaddr_name.clone().unwrap_or_default(),
+            addr1: self.shipaddr_addr1.clone().unwrap_or_default(),
+            addr2: self.shipaddr_addr2.clone().unwrap_or_default(),
+            addr3: self.shipaddr_addr3.clone().unwrap_or_default(),
+            addr4: self.shipaddr_addr4.clone().unwrap_or_default(),
+            phone: self.shipaddr_phone.clone().unwrap_or_default(),
+            fax: self.shipaddr_fax.clone().unwrap_or_default(),
+            email: self.shipaddr_email.clone().unwrap_or_default(),
+        }
+    }
+    fn terms_guid(&self) -> &str {
+        self.terms.as_deref().unwrap_or_default()
+    }
+    fn tax_included(&self) -> &str {
+        // stored as GncTaxIncluded: 1 yes, 2 no, 3 use global
+        match self.tax_included {
+            Some(1) => "YES",
+            Some(2) => "NO",
+            _ => "USEGLOBAL",
+        }
+    }
+    fn taxtable_guid(&self) -> &str {
+        self.taxtable.as_deref().unwrap_or_default()
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+name,
+id,
+notes,
+active,
+discount_num,
+discount_denom,
+credit_num,
+credit_denom,
+currency,
+tax_override,
+addr_name,
+addr_addr1,
+addr_addr2,
+addr_addr3,
+addr_addr4,
+addr_phone,
+addr_fax,
+addr_email,
+shipaddr_name,
+shipaddr_addr1,
+shipaddr_addr2,
+shipaddr_addr3,
+shipaddr_addr4,
+shipaddr_phone,
+shipaddr_fax,
+shipaddr_email,
+terms,
+tax_included,
+taxtable
+FROM customers
+";
+
+impl CustomerQ for MySQLQuery {
+    type Item = Customer;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all customers from mysql");
+        sqlx::query_as(SEL)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching customer by guid from mysql");
+        sqlx::query_as(AssertSqlSafe(format!("{SEL}\nWHERE guid = ?")))
+            .bind(guid)
+            .fetch_optional(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::mysql::tests::{setup_with, teardown};
+    use crate::query::tests::fixtures::BUSINESS;
+
+    #[cfg(feature = "schema")]
+    // test schemas on compile time
+    #[allow(dead_code)]
+    fn test_customer_schemas() {
+        let _ = sqlx::query_as!(
+            Customer,
+            r"
+    			SELECT
+    			guid,
+    			name,
+    			id,
+    			notes,
+    			active,
+    			discount_num,
+    			discount_denom,
+    			credit_num,
+    			credit_denom,
+    			currency,
+    			tax_override,
+    			addr_name,
+    			addr_addr1,
+    			addr_addr2,
+    			addr_addr3,
+    			addr_addr4,
+    			addr_phone,
+    			addr_fax,
+    			addr_email,
+    			shipaddr_name,
+    			shipaddr_addr1,
+    			shipaddr_addr2,
+    			shipaddr_addr3,
+    			shipaddr_addr4,
+    			shipaddr_phone,
+    			shipaddr_fax,
+    			shipaddr_email,
+    			terms,
+    			tax_included,
+    			taxtable
+    			FROM customers
+    			",
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_customer() {
+        let (query, database) = setup_with(&[BUSINESS]).await;
+        let result = CustomerQ::guid(&query, "d4e5f6a7b8c9d0e1f2a3b4c5d6e7f801")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.name(), "Acme Corp");
+        assert_eq!(result.id(), "000001");
+        assert_eq!(result.notes(), "pays late");
+        assert_eq!(result.active(), true);
+        assert_eq!(result.discount_num(), 500);
+        assert_eq!(result.discount_denom(), 100);
+        assert_eq!(result.credit_num(), 100_000);
+        assert_eq!(result.credit_denom(), 100);
+        assert_eq!(result.currency_guid(), "346629655191dcf59a7e2c2a85b70f69");
+        assert_eq!(result.tax_override(), false);
+        assert_eq!(result.addr().addr2, "Springfield");
+        assert_eq!(result.addr().email, "billing@acme.example");
+        assert_eq!(result.shipaddr().name, "Acme Warehouse");
+        assert_eq!(result.terms_guid(), "");
+        assert_eq!(result.tax_included(), "USEGLOBAL");
+        assert_eq!(result.taxtable_guid(), "");
+        assert_eq!(CustomerQ::all(&query).await.unwrap().len(), 1);
+        assert_eq!(CustomerQ::guid(&query, "unknown").await.unwrap(), None);
+
+        teardown(&database).await;
+    }
+}
@@ -0,0 +1,297 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use chrono::NaiveDateTime;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::query::mysql::MySQLQuery;
+use crate::query::{EntryQ, EntryT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct Entry {
+    pub(crate) guid: String,
+    pub(crate) date: NaiveDateTime,
+    pub(crate) date_entered: Option<NaiveDateTime>,
+    pub(crate) description: Option<String>,
+    pub(crate) action: Option<String>,
+    pub(crate) notes: Option<String>,
+    pub(crate) quantity_num: Option<i64>,
+    pub(crate) quantity_denom: Option<i64>,
+    pub(crate) i_acct: Option<String>,
+    pub(crate) i_price_num: Option<i64>,
+    pub(crate) i_price_denom: Option<i64>,
+    pub(crate) i_discount_num: Option<i64>,
+    pub(crate) i_discount_denom: Option<i64>,
+    pub(crate) invoice: Option<String>,
+    pub(crate) i_disc_type: Option<String>,
+    pub(crate) i_disc_how: Option<String>,
+    pub(crate) i_taxable: Option<i64>,
+    pub(crate) i_taxincluded: Option<i64>,
+    pub(crate) i_taxtable: Option<String>,
+    pub(crate) b_acct: Option<String>,
+    pub(crate) b_price_num: Option<i64>,
+    pub(crate) b_price_denom: Option<i64>,
+    pub(crate) bill: Option<String>,
+    pub(crate) b_taxable: Option<i64>,
+    pub(crate) b_taxincluded: Option<i64>,
+    pub(crate) b_taxtable: Option<String>,
+    pub(crate) b_paytype: Option<i64>,
+    pub(crate) billable: Option<i64>,
+    pub(crate) order_guid: Option<String>,
+}
+
+impl EntryT for Entry {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn date(&self) -> NaiveDateTime {
+        self.date
+    }
+    fn date_entered(&self) -> Option<NaiveDateTime> {
+        self.date_entered
+    }
+    fn description(&self) -> &str {
+        self.description.as_deref().unwrap_or_default()
+    }
+    fn action(&self) -> &str {
+        self.action.as_deref().unwrap_or_default()
+    }
+    fn notes(&self) -> &str {
+        self.notes.as_deref().unwrap_or_default()
+    }
+    fn quantity_num(&self) -> i64 {
+        self.quantity_num.unwrap_or(0)
+    }
+    fn quantity_denom(&self) -> i64 {
+        self.quantity_denom.unwrap_or(1)
+    }
+    fn i_acct_guid(&self) -> &str {
+        self.i_acct.as_deref().unwrap_or_default()
+    }
+    fn i_price_num(&self) -> i64 {
+        self.i_price_num.unwrap_or(0)
+    }
+    fn i_price_denom(&self) -> i64 {
+        self.i_price_denom.unwrap_or(1)
+    }
+    fn i_discount_num(&self) -> i64 {
+        self.i_discount_num.unwrap_or(0)
+    }
+    fn i_discount_denom(&self) -> i64 {
+        self.i_discount_denom.unwrap_or(1)
+    }
+    fn invoice_guid(&self) -> &str {
+        self.invoice.as_deref().unwrap_or_default()
+    }
+    fn i_disc_type(&self) -> &str {
+        self.i_disc_type.as_deref().unwrap_or_default()
+    }
+    fn i_disc_how(&self) -> &str {
+        self.i_disc_how.as_deref().unwrap_or_default()
+    }
+    fn i_taxable(&self) -> bool {
+        self.i_taxable.is_some_and(|x| x != 0)
+    }
+    fn i_taxincluded(&self) -> bool {
+        self.i_taxincluded.is_some_and(|x| x != 0)
+    }
+    fn i_taxtable_guid(&self) -> &str {
+        self.i_taxtable.as_deref().unwrap_or_default()
+    }
+    fn b_acct_guid(&self) -> &str {
+        self.b_acct.as_deref().unwrap_or_default()
+    }
+    fn b_price_num(&self) -> i64 {
+        self.b_price_num.unwrap_or(0)
+    }
+    fn b_price_denom(&self) -> i64 {
+        self.b_price_denom.unwrap_or(1)
+    }
+    fn bill_guid(&self) -> &str {
+        self.bill.as_deref().unwrap_or_default()
+    }
+    fn b_taxable(&self) -> bool {
+        self.b_taxable.is_some_and(|x| x != 0)
+    }
+    fn b_taxincluded(&self) -> bool {
+        self.b_taxincluded.is_some_and(|x| x != 0)
+    }
+    fn b_taxtable_guid(&self) -> &str {
+        self.b_taxtable.as_deref().unwrap_or_default()
+    }
+    fn b_paytype(&self) -> i64 {
+        self.b_paytype.unwrap_or(0)
+    }
+    fn billable(&self) -> bool {
+        self.billable.is_some_and(|x| x != 0)
+    }
+    fn order_guid(&self) -> &str {
+        self.order_guid.as_deref().unwrap_or_default()
+    }
+}
+
+const SEL_INVOICE: &str = r"
+SELECT
+guid,
+date,
+date_entered,
+description,
+action,
+notes,
+quantity_num,
+quantity_denom,
+i_acct,
+i_price_num,
+i_price_denom,
+i_discount_num,
+i_discount_denom,
+invoice,
+i_disc_type,
+i_disc_how,
+i_taxable,
+i_taxincluded,
+i_taxtable,
+b_acct,
+b_price_num,
+b_price_denom,
+bill,
+b_taxable,
+b_taxincluded,
+b_taxtable,
+b_paytype,
+billable,
+order_guid
+FROM entries
+WHERE invoice = ? OR bill = ?
+ORDER BY date, date_entered
+";
+
+impl EntryQ for MySQLQuery {
+    type Item = Entry;
+
+    #[instrument(skip(self))]
+    async fn invoice(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching entries by invoice from mysql");
+        sqlx::query_as(SEL_INVOICE)
+            .bind(guid)
+            .bind(guid)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::mysql::tests::setup_with;
+    use crate::query::mysql::tests::teardown;
+    use crate::query::tests::fixtures::{BUSINESS, INVOICES};
+
+    #[cfg(feature = "schema")]
+    // test schemas on compile time
+    #[allow(dead_code)]
+    fn test_entry_schemas() {
+        let _ = sqlx::query_as!(
+            Entry,
+            r"
+    			SELECT
+    			guid,
+    			date,
+    			date_entered,
+    			description,
+    			action,
+    			notes,
+    			quantity_num,
+    			quantity_denom,
+    			i_acct,
+    			i_price_num,
+    			i_price_denom,
+    			i_discount_num,
+    			i_discount_denom,
+    			invoice,
+    			i_disc_type,
+    			i_disc_how,
+    			i_taxable,
+    			i_taxincluded,
+    			i_taxtable,
+    			b_acct,
+    			b_price_num,
+    			b_price_denom,
+    			bill,
+    			b_taxable,
+    			b_taxincluded,
+    			b_taxtable,
+    			b_paytype,
+    			billable,
+    			order_guid
+    			FROM entries
+    			",
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_invoice() {
+        let (query, database) = setup_with(&[BUSINESS, INVOICES]).await;
+        let result = EntryQ::invoice(&query, "3d4e5f60718293a4b5c6d7e8f90a1b01")
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 2);
+
+        let result = &result[0];
+        assert_eq!(result.guid(), "4e5f60718293a4b5c6d7e8f90a1b2c01");
+        assert_eq!(
+            result.date(),
+            NaiveDateTime::parse_from_str("2018-03-01 10:59:00", "%Y-%m-%d %H:%M:%S").unwrap()
+        );
+        assert_eq!(
+            result.date_entered(),
+            NaiveDateTime::parse_from_str("2018-03-01 11:00:00", "%Y-%m-%d %H:%M:%S").ok()
+        );
+        assert_eq!(result.description(), "Consulting");
+        assert_eq!(result.action(), "Hours");
+        assert_eq!(result.quantity_num(), 10);
+        assert_eq!(result.quantity_denom(), 1);
+        assert_eq!(result.i_acct_guid(), "6bbc8f20544452cac1637fb9a9b851bb");
+        assert_eq!(result.i_price_num(), 50);
+        assert_eq!(result.i_discount_num(), 0);
+        assert_eq!(result.invoice_guid(), "3d4e5f60718293a4b5c6d7e8f90a1b01");
+        assert_eq!(result.i_disc_type(), "PERCENT");
+        assert_eq!(result.i_disc_how(), "PRETAX");
+        assert_eq!(result.i_taxable(), true);
+        assert_eq!(result.i_taxincluded(), false);
+        assert_eq!(result.i_taxtable_guid(), "");
+        assert_eq!(result.bill_guid(), "");
+        assert_eq!(result.order_guid(), "2c3d4e5f60718293a4b5c6d7e8f90a01");
+
+        teardown(&database).await;
+    }
+
+    #[test(tokio::test)]
+    async fn test_bill() {
+        let (query, database) = setup_with(&[BUSINESS, INVOICES]).await;
+        let result = EntryQ::invoice(&query, "3d4e5f60718293a4b5c6d7e8f90a1b02")
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+
+        let result = &result[0];
+        assert_eq!(result.description(), "Paper");
+        assert_eq!(result.invoice_guid(), "");
+        assert_eq!(result.b_acct_guid(), "af88d386d44b14acf244362b85ccaf4c");
+        assert_eq!(result.b_price_num(), 30);
+        assert_eq!(result.b_price_denom(), 1);
+        assert_eq!(result.bill_guid(), "3d4e5f60718293a4b5c6d7e8f90a1b02");
+        assert_eq!(result.b_taxable(), true);
+        assert_eq!(result.b_taxincluded(), true);
+        assert_eq!(result.b_paytype(), 2);
+        assert_eq!(result.billable(), false);
+
+        teardown(&database).await;
+    }
+}
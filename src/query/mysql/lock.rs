@@ -0,0 +1,93 @@
+use sqlx::{AssertSqlSafe, MySqlConnection};
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::model::Lock;
+use crate::query::mysql::MySQLQuery;
+use crate::query::{LockQ, hostname};
+
+const SEL: &str = "SELECT Hostname, PID FROM gnclock";
+const INS: &str = "INSERT INTO gnclock (Hostname, PID) VALUES (?, ?)";
+// only one entry of this process is removed, a concurrent write of it keeps its own
+const DEL: &str = "DELETE FROM gnclock WHERE Hostname = ? AND PID = ? LIMIT 1";
+
+/// The process ID as stored in the `pid` column, an `INTEGER`.
+pub(super) fn pid() -> i32 {
+    i32::try_from(std::process::id()).unwrap_or(i32::MAX)
+}
+
+/// Takes the `gnclock` entry of this process inside the write transaction `conn`, or fails
+/// with [`Error::Locked`] if another process holds one and `force` is unset.
+///
+/// The entries read, and the gaps between them, stay locked until the transaction ends,
+/// so `GnuCash` cannot take over the book while the write is in progress. The entry is
+/// removed by [`commit`], or discarded with the rest of the transaction on rollback.
+pub(crate) async fn check(conn: &mut MySqlConnection, force: bool) -> Result<(), Error> {
+    let hostname = hostname();
+    let pid = pid();
+
+    let rows: Vec<(Option<String>, Option<i32>)> =
+        sqlx::query_as(AssertSqlSafe(format!("{SEL} FOR UPDATE")))
+            .fetch_all(&mut *conn)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))?;
+
+    let other = rows
+        .into_iter()
+        .map(|(h, p)| (h.unwrap_or_default(), p.unwrap_or_default()))
+        .find(|(h, p)| *h != hostname || *p != pid);
+    if let Some((hostname, pid)) = other {
+        let pid = i64::from(pid);
+        if !force {
+            tracing::error!(hostname, pid, "book is locked");
+            return Err(Error::Locked { hostname, pid });
+        }
+        tracing::warn!(hostname, pid, "ignoring lock held by another process");
+    }
+
+    sqlx::query(INS)
+        .bind(&hostname)
+        .bind(pid)
+        .execute(&mut *conn)
+        .await
+        .inspect_err(|e| tracing::error!("failed to take lock: {e}"))?;
+
+    tracing::debug!("lock taken");
+    Ok(())
+}
+
+/// Removes the `gnclock` entry taken by [`check`] and commits the write transaction `tx`.
+pub(crate) async fn commit(mut tx: sqlx::Transaction<'static, sqlx::MySql>) -> Result<(), Error> {
+    sqlx::query(DEL)
+        .bind(hostname())
+        .bind(pid())
+        .execute(&mut *tx)
+        .await
+        .inspect_err(|e| tracing::error!("failed to release lock: {e}"))?;
+
+    tx.commit()
+        .await
+        .inspect_err(|e| tracing::error!("failed to commit transaction: {e}"))?;
+
+    tracing::debug!("lock released");
+    Ok(())
+}
+
+impl LockQ for MySQLQuery {
+    #[instrument(skip(self))]
+    async fn locks(&self) -> Result<Vec<Lock>, Error> {
+        tracing::debug!("fetching locks from mysql");
+        let rows: Vec<(Option<String>, Option<i32>)> = sqlx::query_as(SEL)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(hostname, pid)| Lock {
+                hostname: hostname.unwrap_or_default(),
+                pid: pid.map(i64::from).unwrap_or_default(),
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,135 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use sqlx::AssertSqlSafe;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::query::mysql::MySQLQuery;
+use crate::query::{LotQ, LotT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct Lot {
+    pub(crate) guid: String,
+    pub(crate) account_guid: Option<String>,
+    pub(crate) is_closed: i64,
+}
+
+impl LotT for Lot {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn account_guid(&self) -> &str {
+        self.account_guid.as_deref().unwrap_or_default()
+    }
+    fn is_closed(&self) -> Option<bool> {
+        Some(self.is_closed != 0)
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+account_guid,
+is_closed
+FROM lots
+";
+
+impl LotQ for MySQLQuery {
+    type Item = Lot;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all lots from mysql");
+        sqlx::query_as(SEL)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching lot by guid from mysql");
+        sqlx::query_as(AssertSqlSafe(format!("{SEL}\nWHERE guid = ?")))
+            .bind(guid)
+            .fetch_optional(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+
+    #[instrument(skip(self))]
+    async fn account(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching lots by account_guid from mysql");
+        sqlx::query_as(AssertSqlSafe(format!("{SEL}\nWHERE account_guid = ?")))
+            .bind(guid)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::mysql::tests::{setup_with, teardown};
+    use crate::query::tests::fixtures::LOT;
+
+    #[cfg(feature = "schema")]
+    // test schemas on compile time
+    #[allow(dead_code)]
+    fn test_lot_schemas() {
+        let _ = sqlx::query_as!(
+            Lot,
+            r"
+    			SELECT
+    			guid,
+    			account_guid,
+    			is_closed
+    			FROM lots
+    			",
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_lot() {
+        let (query, database) = setup_with(&[LOT]).await;
+        let result = query
+            .guid("a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.guid(), "a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01");
+        assert_eq!(result.account_guid(), "1c089803052e85f5c6d8e786057dbaee");
+        assert_eq!(result.is_closed(), Some(false));
+
+        teardown(&database).await;
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (query, database) = setup_with(&[LOT]).await;
+        let result = query.all().await.unwrap();
+        assert_eq!(result.len(), 1);
+
+        teardown(&database).await;
+    }
+
+    #[test(tokio::test)]
+    async fn test_account_guid() {
+        let (query, database) = setup_with(&[LOT]).await;
+        let result = query
+            .account("1c089803052e85f5c6d8e786057dbaee")
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+
+        teardown(&database).await;
+    }
+}
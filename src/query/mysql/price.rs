@@ -4,12 +4,12 @@
 use chrono::NaiveDateTime;
 #[cfg(feature = "decimal")]
 use rust_decimal::Decimal;
-use sqlx::AssertSqlSafe;
+use sqlx::{AssertSqlSafe, MySqlConnection};
 use tracing::instrument;
 
 use crate::error::Error;
 use crate::query::mysql::MySQLQuery;
-use crate::query::{PriceQ, PriceT};
+use crate::query::{PriceMutQ, PriceQ, PriceRecord, PriceT};
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
 pub struct Price {
@@ -43,6 +43,13 @@ impl PriceT for Price {
         self.r#type.as_deref().unwrap_or_default()
     }
 
+    fn value_num(&self) -> i64 {
+        self.value_num
+    }
+    fn value_denom(&self) -> i64 {
+        self.value_denom
+    }
+
     #[cfg(not(feature = "decimal"))]
     #[allow(clippy::cast_precision_loss)]
     fn value(&self) -> f64 {
@@ -125,6 +132,82 @@ impl PriceQ for MySQLQuery {
     }
 }
 
+const INS: &str = r"
+INSERT INTO prices (
+guid,
+commodity_guid,
+currency_guid,
+date,
+source,
+type,
+value_num,
+value_denom
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+";
+
+const DUP: &str = r"
+SELECT EXISTS (
+SELECT 1 FROM prices
+WHERE commodity_guid = ?
+AND currency_guid = ?
+AND DATE(date) = ?
+)
+";
+
+/// Inserts a price row without checking for duplicates; the caller owns the surrounding
+/// database transaction.
+pub(crate) async fn insert(
+    conn: &mut MySqlConnection,
+    price: &PriceRecord,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(INS)
+        .bind(&price.guid)
+        .bind(&price.commodity_guid)
+        .bind(&price.currency_guid)
+        .bind(price.datetime)
+        .bind(&price.source)
+        .bind(&price.r#type)
+        .bind(price.value_num)
+        .bind(price.value_denom)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+impl PriceMutQ for MySQLQuery {
+    #[instrument(skip(self, prices), fields(count = prices.len()))]
+    async fn insert(&self, prices: &[PriceRecord]) -> Result<Vec<PriceRecord>, Error> {
+        tracing::debug!("inserting prices into mysql");
+        let mut tx = self.begin().await?;
+
+        let mut inserted = Vec::with_capacity(prices.len());
+        for price in prices {
+            let exists: bool = sqlx::query_scalar(DUP)
+                .bind(&price.commodity_guid)
+                .bind(&price.currency_guid)
+                .bind(price.datetime.date())
+                .fetch_one(&mut *tx)
+                .await
+                .inspect_err(|e| tracing::error!("failed to execute query: {e}"))?;
+            if exists {
+                tracing::debug!(date = %price.datetime, "skipping duplicate price");
+                continue;
+            }
+
+            insert(&mut tx, price)
+                .await
+                .inspect_err(|e| tracing::error!("failed to insert price: {e}"))?;
+            inserted.push(price.clone());
+        }
+
+        super::lock::commit(tx).await?;
+
+        tracing::debug!(count = inserted.len(), "prices inserted into mysql");
+        Ok(inserted)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(not(feature = "decimal"))]
@@ -134,6 +217,7 @@ mod tests {
     use tokio::sync::OnceCell;
 
     use super::*;
+    use crate::query::mysql::tests::{setup_writable, teardown};
 
     #[cfg(feature = "schema")]
     // test schemas on compile time
@@ -189,6 +273,8 @@ mod tests {
         assert_approx_eq!(f64, result.value(), 1.5);
         #[cfg(feature = "decimal")]
         assert_eq!(result.value(), Decimal::new(15, 1));
+        assert_eq!(result.value_num(), 3);
+        assert_eq!(result.value_denom(), 2);
     }
 
     #[test(tokio::test)]
@@ -250,4 +336,32 @@ mod tests {
             .unwrap();
         assert_eq!(result.len(), 4);
     }
+
+    #[test(tokio::test)]
+    async fn test_insert() {
+        let (query, database) = setup_writable().await;
+        let record = |guid: &str, datetime: &str| PriceRecord {
+            guid: guid.to_string(),
+            commodity_guid: "1e5d65e2726a5d4595741cb204992991".to_string(),
+            currency_guid: "346629655191dcf59a7e2c2a85b70f69".to_string(),
+            datetime: NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M:%S").unwrap(),
+            source: "user:price-editor".to_string(),
+            r#type: "last".to_string(),
+            value_num: 9,
+            value_denom: 10,
+        };
+        let prices = [
+            record("11111111111111111111111111111111", "2024-03-01 10:00:00"),
+            record("22222222222222222222222222222222", "2024-03-01 18:00:00"),
+            record("33333333333333333333333333333333", "2018-02-20 12:00:00"),
+        ];
+
+        let result = PriceMutQ::insert(&query, &prices).await.unwrap();
+        assert_eq!(result, prices[..1]);
+        let result = query.guid(&prices[0].guid).await.unwrap().unwrap();
+        assert_eq!(result.datetime(), prices[0].datetime);
+        assert_eq!(result.value_num(), 9);
+
+        teardown(&database).await;
+    }
 }
@@ -0,0 +1,111 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use chrono::NaiveDate;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::query::mysql::MySQLQuery;
+use crate::query::{RecurrenceQ, RecurrenceT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct Recurrence {
+    pub(crate) obj_guid: String,
+    pub(crate) mult: i64,
+    pub(crate) period_type: String,
+    pub(crate) period_start: NaiveDate,
+    pub(crate) weekend_adjust: String,
+}
+
+impl RecurrenceT for Recurrence {
+    fn obj_guid(&self) -> &str {
+        &self.obj_guid
+    }
+    fn mult(&self) -> i64 {
+        self.mult
+    }
+    fn period_type(&self) -> &str {
+        &self.period_type
+    }
+    fn period_start(&self) -> NaiveDate {
+        self.period_start
+    }
+    fn weekend_adjust(&self) -> &str {
+        &self.weekend_adjust
+    }
+}
+
+const SEL: &str = r"
+SELECT
+obj_guid,
+recurrence_mult AS mult,
+recurrence_period_type AS period_type,
+recurrence_period_start AS period_start,
+recurrence_weekend_adjust AS weekend_adjust
+FROM recurrences
+WHERE obj_guid = ?
+ORDER BY id
+";
+
+impl RecurrenceQ for MySQLQuery {
+    type Item = Recurrence;
+
+    #[instrument(skip(self))]
+    async fn obj_guid(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching recurrences by obj_guid from mysql");
+        sqlx::query_as(SEL)
+            .bind(guid)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::mysql::tests::setup_with;
+    use crate::query::mysql::tests::teardown;
+    use crate::query::tests::fixtures::BUDGET;
+
+    #[cfg(feature = "schema")]
+    // test schemas on compile time
+    #[allow(dead_code)]
+    fn test_recurrence_schemas() {
+        let _ = sqlx::query_as!(
+            Recurrence,
+            r"
+    			SELECT
+    			obj_guid,
+    			recurrence_mult AS mult,
+    			recurrence_period_type AS period_type,
+    			recurrence_period_start AS period_start,
+    			recurrence_weekend_adjust AS weekend_adjust
+    			FROM recurrences
+    			",
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_obj_guid() {
+        let (query, database) = setup_with(&[BUDGET]).await;
+        let result = query
+            .obj_guid("5f1a0c7d1e9b4b6f8a2d3c4e5f607182")
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].mult(), 1);
+        assert_eq!(result[0].period_type(), "month");
+        assert_eq!(
+            result[0].period_start(),
+            NaiveDate::from_ymd_opt(2018, 1, 1).unwrap()
+        );
+
+        teardown(&database).await;
+    }
+}
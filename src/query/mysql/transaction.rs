@@ -2,12 +2,15 @@
 // ref: https://wiki.gnucash.org/wiki/SQL
 
 use chrono::NaiveDateTime;
-use sqlx::AssertSqlSafe;
+use sqlx::{AssertSqlSafe, MySqlConnection};
 use tracing::instrument;
 
+use super::slot::insert_string;
 use crate::error::Error;
 use crate::query::mysql::MySQLQuery;
-use crate::query::{TransactionQ, TransactionT};
+use crate::query::{
+    SplitRecord, TransactionMutQ, TransactionQ, TransactionRecord, TransactionT, check_void,
+};
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
 pub struct Transaction {
@@ -87,6 +90,247 @@ impl TransactionQ for MySQLQuery {
     }
 }
 
+const INS: &str = r"
+INSERT INTO transactions (
+guid,
+currency_guid,
+num,
+post_date,
+enter_date,
+description
+) VALUES (?, ?, ?, ?, ?, ?)
+";
+
+/// Inserts a transaction row without its splits; the caller owns the surrounding
+/// database transaction.
+pub(crate) async fn insert(
+    conn: &mut MySqlConnection,
+    transaction: &TransactionRecord,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(INS)
+        .bind(&transaction.guid)
+        .bind(&transaction.currency_guid)
+        .bind(&transaction.num)
+        .bind(transaction.post_datetime)
+        .bind(transaction.enter_datetime)
+        .bind(&transaction.description)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Notes `GnuCash` puts on a voided transaction, its former notes are kept aside.
+const VOID_NOTES: &str = "Voided transaction";
+const VOID_READ_ONLY: &str = "Transaction Voided";
+/// `GnuCash` records the void time with its UTC offset.
+const VOID_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S %z";
+
+const INS_FORMER_AMOUNTS: &str = r"
+INSERT INTO slots (obj_guid, name, slot_type, int64_val, timespec_val, numeric_val_num, numeric_val_denom)
+SELECT guid, 'void-former-amount', 3, 0, '1970-01-01 00:00:00', quantity_num, quantity_denom
+FROM splits WHERE tx_guid = ?
+UNION ALL
+SELECT guid, 'void-former-value', 3, 0, '1970-01-01 00:00:00', value_num, value_denom
+FROM splits WHERE tx_guid = ?
+";
+
+const UPD_RESTORE_AMOUNTS: &str = r"
+UPDATE splits
+JOIN slots AS former_amount
+ON former_amount.obj_guid = splits.guid AND former_amount.name = 'void-former-amount'
+JOIN slots AS former_value
+ON former_value.obj_guid = splits.guid AND former_value.name = 'void-former-value'
+SET
+splits.quantity_num = former_amount.numeric_val_num,
+splits.quantity_denom = former_amount.numeric_val_denom,
+splits.value_num = former_value.numeric_val_num,
+splits.value_denom = former_value.numeric_val_denom,
+splits.reconcile_state = 'n'
+WHERE splits.tx_guid = ?
+";
+
+async fn string_slot(
+    conn: &mut MySqlConnection,
+    guid: &str,
+    name: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    let value: Option<Option<String>> =
+        sqlx::query_scalar("SELECT string_val FROM slots WHERE obj_guid = ? AND name = ?")
+            .bind(guid)
+            .bind(name)
+            .fetch_optional(conn)
+            .await?;
+    Ok(value.flatten())
+}
+
+async fn delete_slots(
+    conn: &mut MySqlConnection,
+    guid: &str,
+    names: &[&str],
+) -> Result<(), sqlx::Error> {
+    for name in names {
+        sqlx::query("DELETE FROM slots WHERE obj_guid = ? AND name = ?")
+            .bind(guid)
+            .bind(name)
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Whether the transaction carries the `void-reason` slot written by [`void`]. Its row stays
+/// locked until the commit, so a concurrent call waits for this one before checking.
+async fn is_voided(conn: &mut MySqlConnection, guid: &str) -> Result<bool, sqlx::Error> {
+    sqlx::query("SELECT guid FROM transactions WHERE guid = ? FOR UPDATE")
+        .bind(guid)
+        .execute(&mut *conn)
+        .await?;
+    let voided: Option<i64> =
+        sqlx::query_scalar("SELECT 1 FROM slots WHERE obj_guid = ? AND name = 'void-reason'")
+            .bind(guid)
+            .fetch_optional(conn)
+            .await?;
+    Ok(voided.is_some())
+}
+
+/// Voids a transaction like `xaccTransVoid`; the caller owns the surrounding database transaction.
+async fn void(
+    conn: &mut MySqlConnection,
+    guid: &str,
+    reason: &str,
+    datetime: NaiveDateTime,
+) -> Result<(), sqlx::Error> {
+    if let Some(notes) = string_slot(conn, guid, "notes").await? {
+        insert_string(conn, guid, "void-former-notes", &notes).await?;
+    }
+    delete_slots(conn, guid, &["notes"]).await?;
+    insert_string(conn, guid, "notes", VOID_NOTES).await?;
+    insert_string(conn, guid, "void-reason", reason).await?;
+    insert_string(
+        conn,
+        guid,
+        "void-time",
+        &datetime.and_utc().format(VOID_TIME_FORMAT).to_string(),
+    )
+    .await?;
+    insert_string(conn, guid, "trans-read-only", VOID_READ_ONLY).await?;
+
+    sqlx::query(INS_FORMER_AMOUNTS)
+        .bind(guid)
+        .bind(guid)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query(
+        "UPDATE splits SET quantity_num = 0, value_num = 0, reconcile_state = 'v' WHERE tx_guid = ?",
+    )
+    .bind(guid)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Reverts [`void`] like `xaccTransUnvoid`; the caller owns the surrounding database transaction.
+async fn unvoid(conn: &mut MySqlConnection, guid: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(UPD_RESTORE_AMOUNTS)
+        .bind(guid)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query(
+        r"
+        DELETE FROM slots
+        WHERE obj_guid IN (SELECT guid FROM splits WHERE tx_guid = ?)
+        AND name IN ('void-former-amount', 'void-former-value')
+        ",
+    )
+    .bind(guid)
+    .execute(&mut *conn)
+    .await?;
+
+    if let Some(notes) = string_slot(conn, guid, "void-former-notes").await? {
+        delete_slots(conn, guid, &["notes"]).await?;
+        insert_string(conn, guid, "notes", &notes).await?;
+    }
+    delete_slots(
+        conn,
+        guid,
+        &[
+            "void-former-notes",
+            "void-reason",
+            "void-time",
+            "trans-read-only",
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
+
+impl TransactionMutQ for MySQLQuery {
+    #[instrument(skip(self, transaction, splits), fields(transaction_guid = %transaction.guid))]
+    async fn insert(
+        &self,
+        transaction: &TransactionRecord,
+        splits: &[SplitRecord],
+    ) -> Result<(), Error> {
+        tracing::debug!("inserting transaction into mysql");
+        let mut tx = self.begin().await?;
+
+        insert(&mut tx, transaction)
+            .await
+            .inspect_err(|e| tracing::error!("failed to insert transaction: {e}"))?;
+
+        for split in splits {
+            super::split::insert(&mut tx, split)
+                .await
+                .inspect_err(|e| tracing::error!("failed to insert split: {e}"))?;
+        }
+
+        super::lock::commit(tx).await?;
+
+        tracing::debug!(
+            split_count = splits.len(),
+            "transaction inserted into mysql"
+        );
+        Ok(())
+    }
+
+    #[instrument(skip(self, reason))]
+    async fn void(&self, guid: &str, reason: &str, datetime: NaiveDateTime) -> Result<(), Error> {
+        tracing::debug!("voiding transaction in mysql");
+        let mut tx = self.begin().await?;
+
+        check_void(is_voided(&mut tx, guid).await?, true)?;
+
+        void(&mut tx, guid, reason, datetime)
+            .await
+            .inspect_err(|e| tracing::error!("failed to void transaction: {e}"))?;
+
+        super::lock::commit(tx).await?;
+
+        tracing::debug!("transaction voided in mysql");
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn unvoid(&self, guid: &str) -> Result<(), Error> {
+        tracing::debug!("unvoiding transaction in mysql");
+        let mut tx = self.begin().await?;
+
+        check_void(is_voided(&mut tx, guid).await?, false)?;
+
+        unvoid(&mut tx, guid)
+            .await
+            .inspect_err(|e| tracing::error!("failed to unvoid transaction: {e}"))?;
+
+        super::lock::commit(tx).await?;
+
+        tracing::debug!("transaction unvoided in mysql");
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -94,6 +338,7 @@ mod tests {
     use tokio::sync::OnceCell;
 
     use super::*;
+    use crate::query::mysql::tests::{setup_writable, teardown};
 
     #[cfg(feature = "schema")]
     // test schemas on compile time
@@ -185,4 +430,96 @@ mod tests {
 
         assert_eq!(result.len(), 11);
     }
+
+    #[test(tokio::test)]
+    async fn test_insert() {
+        let (query, database) = setup_writable().await;
+        let datetime =
+            NaiveDateTime::parse_from_str("2024-03-01 10:59:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let transaction = TransactionRecord {
+            guid: "11111111111111111111111111111111".to_string(),
+            currency_guid: "346629655191dcf59a7e2c2a85b70f69".to_string(),
+            num: "7".to_string(),
+            post_datetime: datetime,
+            enter_datetime: datetime,
+            description: "groceries".to_string(),
+        };
+        let split = |guid: &str, account_guid: &str, value_num| SplitRecord {
+            guid: guid.to_string(),
+            tx_guid: transaction.guid.clone(),
+            account_guid: account_guid.to_string(),
+            memo: String::new(),
+            action: String::new(),
+            reconcile_state: 'n',
+            reconcile_datetime: None,
+            value_num,
+            value_denom: 100,
+            quantity_num: value_num,
+            quantity_denom: 100,
+            lot_guid: String::new(),
+        };
+        let splits = [
+            split(
+                "22222222222222222222222222222222",
+                "93fc043c3062aaa1297b30e543d2cd0d",
+                -1250,
+            ),
+            split(
+                "33333333333333333333333333333333",
+                "af88d386d44b14acf244362b85ccaf4c",
+                1250,
+            ),
+        ];
+
+        TransactionMutQ::insert(&query, &transaction, &splits)
+            .await
+            .unwrap();
+        let result = query.guid(&transaction.guid).await.unwrap().unwrap();
+        assert_eq!(result.num(), "7");
+        assert_eq!(result.post_datetime(), datetime);
+        assert_eq!(result.description(), "groceries");
+
+        teardown(&database).await;
+    }
+
+    #[test(tokio::test)]
+    async fn test_void_unvoid() {
+        let (query, database) = setup_writable().await;
+        let guid = "6c8876003c4a6026e38e3afb67d6f2b1";
+        let datetime =
+            NaiveDateTime::parse_from_str("2024-03-01 10:59:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let amounts = || async {
+            let splits: Vec<(i64, i64, String)> = sqlx::query_as(
+                "SELECT quantity_num, value_num, reconcile_state FROM splits WHERE tx_guid = ? ORDER BY guid",
+            )
+            .bind(guid)
+            .fetch_all(&query.pool)
+            .await
+            .unwrap();
+            splits
+        };
+        let before = amounts().await;
+
+        query.void(guid, "typo", datetime).await.unwrap();
+        assert!(
+            amounts()
+                .await
+                .iter()
+                .all(|(quantity, value, state)| *quantity == 0 && *value == 0 && state == "v")
+        );
+        let mut tx = query.pool.acquire().await.unwrap();
+        assert_eq!(
+            string_slot(&mut tx, guid, "void-reason").await.unwrap(),
+            Some("typo".to_string())
+        );
+
+        query.unvoid(guid).await.unwrap();
+        assert_eq!(amounts().await, before);
+        assert_eq!(
+            string_slot(&mut tx, guid, "void-reason").await.unwrap(),
+            None
+        );
+
+        teardown(&database).await;
+    }
 }
@@ -1,12 +1,13 @@
 // ref: https://piecash.readthedocs.io/en/master/object_model.html
 // ref: https://wiki.gnucash.org/wiki/SQL
 
-use sqlx::AssertSqlSafe;
+use sqlx::{AssertSqlSafe, PgConnection};
 use tracing::instrument;
 
 use crate::error::Error;
+use crate::model::AccountType;
 use crate::query::postgresql::PostgreSQLQuery;
-use crate::query::{AccountQ, AccountT};
+use crate::query::{AccountMutQ, AccountQ, AccountRecord, AccountT};
 
 #[allow(clippy::struct_field_names)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
@@ -31,8 +32,8 @@ impl AccountT for Account {
     fn name(&self) -> &str {
         &self.name
     }
-    fn account_type(&self) -> &str {
-        &self.account_type
+    fn account_type(&self) -> AccountType {
+        self.account_type.as_str().into()
     }
     fn commodity_guid(&self) -> &str {
         self.commodity_guid.as_deref().unwrap_or_default()
@@ -60,7 +61,7 @@ impl AccountT for Account {
     }
 }
 
-const SEL: &str = r"
+pub(super) const SEL: &str = r"
 SELECT 
 guid, 
 name,
@@ -146,6 +147,188 @@ impl AccountQ for PostgreSQLQuery {
     }
 }
 
+const INS: &str = r"
+INSERT INTO accounts (
+guid,
+name,
+account_type,
+commodity_guid,
+commodity_scu,
+non_std_scu,
+parent_guid,
+code,
+description,
+hidden,
+placeholder
+) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+";
+
+const UPD: &str = r"
+UPDATE accounts SET
+name = $2,
+account_type = $3,
+commodity_guid = $4,
+commodity_scu = $5,
+non_std_scu = $6,
+parent_guid = $7,
+code = $8,
+description = $9,
+hidden = $10,
+placeholder = $11
+WHERE guid = $1
+";
+
+async fn execute(
+    conn: &mut PgConnection,
+    sql: &'static str,
+    account: &AccountRecord,
+) -> Result<u64, Error> {
+    let non_empty = |x: &String| (!x.is_empty()).then(|| x.clone());
+    let commodity_scu = i32::try_from(account.commodity_scu).map_err(|_| Error::Invalid {
+        model: "Account".to_string(),
+        reason: format!("SCU {} is out of range", account.commodity_scu),
+    })?;
+
+    let result = sqlx::query(sql)
+        .bind(&account.guid)
+        .bind(&account.name)
+        .bind(&account.account_type)
+        .bind(non_empty(&account.commodity_guid))
+        .bind(commodity_scu)
+        .bind(i32::from(account.non_std_scu))
+        .bind(non_empty(&account.parent_guid))
+        .bind(&account.code)
+        .bind(&account.description)
+        .bind(i32::from(account.hidden))
+        .bind(i32::from(account.placeholder))
+        .execute(conn)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// `GnuCash` mirrors the placeholder flag into a string slot, keep both in sync.
+async fn write_placeholder_slot(
+    conn: &mut PgConnection,
+    guid: &str,
+    placeholder: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM slots WHERE obj_guid = $1 AND name = 'placeholder'")
+        .bind(guid)
+        .execute(&mut *conn)
+        .await?;
+    if placeholder {
+        super::slot::insert_string(conn, guid, "placeholder", "true").await?;
+    }
+    Ok(())
+}
+
+/// Inserts an account row with its placeholder slot; the caller owns the surrounding
+/// database transaction.
+pub(crate) async fn insert(conn: &mut PgConnection, account: &AccountRecord) -> Result<(), Error> {
+    execute(conn, INS, account)
+        .await
+        .inspect_err(|e| tracing::error!("failed to insert account: {e}"))?;
+    write_placeholder_slot(conn, &account.guid, account.placeholder)
+        .await
+        .inspect_err(|e| tracing::error!("failed to write placeholder slot: {e}"))?;
+    Ok(())
+}
+
+pub(crate) async fn update(conn: &mut PgConnection, account: &AccountRecord) -> Result<(), Error> {
+    let count = execute(conn, UPD, account)
+        .await
+        .inspect_err(|e| tracing::error!("failed to update account: {e}"))?;
+    if count == 0 {
+        tracing::error!("account not found");
+        return Err(Error::GuidNotFound {
+            model: "Account".to_string(),
+            guid: account.guid.clone(),
+        });
+    }
+    write_placeholder_slot(conn, &account.guid, account.placeholder)
+        .await
+        .inspect_err(|e| tracing::error!("failed to write placeholder slot: {e}"))?;
+    Ok(())
+}
+
+pub(crate) async fn delete(conn: &mut PgConnection, guid: &str) -> Result<(), Error> {
+    // keeps other writers from referring to the account until the commit
+    sqlx::query("LOCK TABLE accounts, splits, lots, budget_amounts IN SHARE ROW EXCLUSIVE MODE")
+        .execute(&mut *conn)
+        .await
+        .inspect_err(|e| tracing::error!("failed to lock account references: {e}"))?;
+    for (table, column, what) in crate::query::ACCOUNT_REFERENCES {
+        let used: Option<i32> = sqlx::query_scalar(AssertSqlSafe(format!(
+            "SELECT 1 FROM {table} WHERE {column} = $1 LIMIT 1"
+        )))
+        .bind(guid)
+        .fetch_optional(&mut *conn)
+        .await
+        .inspect_err(|e| tracing::error!("failed to check account {what}: {e}"))?;
+        if used.is_some() {
+            return Err(crate::query::account_in_use(guid, what));
+        }
+    }
+    let count = sqlx::query("DELETE FROM accounts WHERE guid = $1")
+        .bind(guid)
+        .execute(&mut *conn)
+        .await
+        .inspect_err(|e| tracing::error!("failed to delete account: {e}"))?
+        .rows_affected();
+    if count == 0 {
+        tracing::error!("account not found");
+        return Err(Error::GuidNotFound {
+            model: "Account".to_string(),
+            guid: guid.to_string(),
+        });
+    }
+    super::slot::delete(conn, guid)
+        .await
+        .inspect_err(|e| tracing::error!("failed to delete account slots: {e}"))?;
+    Ok(())
+}
+
+impl AccountMutQ for PostgreSQLQuery {
+    #[instrument(skip(self, account), fields(account_guid = %account.guid))]
+    async fn insert(&self, account: &AccountRecord) -> Result<(), Error> {
+        tracing::debug!("inserting account into postgresql");
+        let mut tx = self.begin().await?;
+
+        insert(&mut tx, account).await?;
+
+        super::lock::commit(tx).await?;
+
+        tracing::debug!("account inserted into postgresql");
+        Ok(())
+    }
+
+    #[instrument(skip(self, account), fields(account_guid = %account.guid))]
+    async fn update(&self, account: &AccountRecord) -> Result<(), Error> {
+        tracing::debug!("updating account in postgresql");
+        let mut tx = self.begin().await?;
+
+        update(&mut tx, account).await?;
+
+        super::lock::commit(tx).await?;
+
+        tracing::debug!("account updated in postgresql");
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, guid: &str) -> Result<(), Error> {
+        tracing::debug!("deleting account from postgresql");
+        let mut tx = self.begin().await?;
+
+        delete(&mut tx, guid).await?;
+
+        super::lock::commit(tx).await?;
+
+        tracing::debug!("account deleted from postgresql");
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -153,6 +336,7 @@ mod tests {
     use tokio::sync::OnceCell;
 
     use super::*;
+    use crate::query::postgresql::tests::{setup_writable, teardown};
 
     #[cfg(feature = "schema")]
     // test schemas on compile time
@@ -202,7 +386,7 @@ mod tests {
 
         assert_eq!(result.guid(), "fcd795021c976ba75621ec39e75f6214");
         assert_eq!(result.name(), "Asset");
-        assert_eq!(result.account_type(), "ASSET");
+        assert_eq!(result.account_type(), AccountType::Asset);
         assert_eq!(result.commodity_guid(), "346629655191dcf59a7e2c2a85b70f69");
         assert_eq!(result.commodity_scu(), 100);
         assert_eq!(result.non_std_scu(), false);
@@ -265,4 +449,41 @@ mod tests {
         let result = query.contains_name_ignore_case("AS").await.unwrap();
         assert_eq!(result.len(), 3);
     }
+
+    #[test(tokio::test)]
+    async fn test_insert_update_delete() {
+        let (query, schema) = setup_writable().await;
+        let mut record = AccountRecord {
+            guid: "11111111111111111111111111111111".to_string(),
+            name: "Wallet".to_string(),
+            account_type: "CASH".to_string(),
+            commodity_guid: "346629655191dcf59a7e2c2a85b70f69".to_string(),
+            commodity_scu: 100,
+            non_std_scu: false,
+            parent_guid: "3bc319753945b6dba3e1928abed49e35".to_string(),
+            code: String::new(),
+            description: String::new(),
+            hidden: false,
+            placeholder: true,
+        };
+
+        AccountMutQ::insert(&query, &record).await.unwrap();
+        let result = query.guid(&record.guid).await.unwrap().unwrap();
+        assert_eq!(result.name(), "Wallet");
+        assert!(result.placeholder());
+
+        record.name = "Purse".to_string();
+        record.hidden = true;
+        AccountMutQ::update(&query, &record).await.unwrap();
+        let result = query.guid(&record.guid).await.unwrap().unwrap();
+        assert_eq!(result.name(), "Purse");
+        assert!(result.hidden());
+
+        AccountMutQ::delete(&query, &record.guid).await.unwrap();
+        assert!(query.guid(&record.guid).await.unwrap().is_none());
+        let result = AccountMutQ::delete(&query, &record.guid).await;
+        assert!(matches!(result, Err(Error::GuidNotFound { .. })));
+
+        teardown(&schema).await;
+    }
 }
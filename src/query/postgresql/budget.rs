@@ -0,0 +1,190 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use sqlx::AssertSqlSafe;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::query::postgresql::PostgreSQLQuery;
+use crate::query::{BudgetAmountQ, BudgetAmountT, BudgetQ, BudgetT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct Budget {
+    pub(crate) guid: String,
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) num_periods: i32,
+}
+
+impl BudgetT for Budget {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn description(&self) -> &str {
+        self.description.as_deref().unwrap_or_default()
+    }
+    fn num_periods(&self) -> i64 {
+        self.num_periods.into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct BudgetAmount {
+    pub(crate) budget_guid: String,
+    pub(crate) account_guid: String,
+    pub(crate) period_num: i32,
+    pub(crate) amount_num: i64,
+    pub(crate) amount_denom: i64,
+}
+
+impl BudgetAmountT for BudgetAmount {
+    fn budget_guid(&self) -> &str {
+        &self.budget_guid
+    }
+    fn account_guid(&self) -> &str {
+        &self.account_guid
+    }
+    fn period_num(&self) -> i64 {
+        self.period_num.into()
+    }
+    fn amount_num(&self) -> i64 {
+        self.amount_num
+    }
+    fn amount_denom(&self) -> i64 {
+        self.amount_denom
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+name,
+description,
+num_periods
+FROM budgets
+";
+
+const SEL_AMOUNT: &str = r"
+SELECT
+budget_guid,
+account_guid,
+period_num,
+amount_num,
+amount_denom
+FROM budget_amounts
+WHERE budget_guid = $1
+ORDER BY account_guid, period_num
+";
+
+impl BudgetQ for PostgreSQLQuery {
+    type Item = Budget;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all budgets from postgresql");
+        sqlx::query_as(SEL)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching budget by guid from postgresql");
+        sqlx::query_as(AssertSqlSafe(format!("{SEL}\nWHERE guid = $1")))
+            .bind(guid)
+            .fetch_optional(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+impl BudgetAmountQ for PostgreSQLQuery {
+    type Item = BudgetAmount;
+
+    #[instrument(skip(self))]
+    async fn budget(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching budget amounts by budget_guid from postgresql");
+        sqlx::query_as(SEL_AMOUNT)
+            .bind(guid)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::postgresql::tests::{setup_with, teardown};
+    use crate::query::tests::fixtures::BUDGET;
+
+    #[cfg(feature = "schema")]
+    // test schemas on compile time
+    #[allow(dead_code)]
+    fn test_budget_schemas() {
+        let _ = sqlx::query_as!(
+            Budget,
+            r"
+    			SELECT
+    			guid,
+    			name,
+    			description,
+    			num_periods
+    			FROM budgets
+    			",
+        );
+        let _ = sqlx::query_as!(
+            BudgetAmount,
+            r"
+    			SELECT
+    			budget_guid,
+    			account_guid,
+    			period_num,
+    			amount_num,
+    			amount_denom
+    			FROM budget_amounts
+    			",
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_budget() {
+        let (query, schema) = setup_with(&[BUDGET]).await;
+        let result = BudgetQ::guid(&query, "5f1a0c7d1e9b4b6f8a2d3c4e5f607182")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.name(), "Household");
+        assert_eq!(result.description(), "monthly spending");
+        assert_eq!(result.num_periods(), 12);
+        assert_eq!(BudgetQ::all(&query).await.unwrap().len(), 1);
+
+        teardown(&schema).await;
+    }
+
+    #[test(tokio::test)]
+    async fn test_amounts() {
+        let (query, schema) = setup_with(&[BUDGET]).await;
+        let result = BudgetAmountQ::budget(&query, "5f1a0c7d1e9b4b6f8a2d3c4e5f607182")
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].account_guid(), "6bbc8f20544452cac1637fb9a9b851bb");
+        assert_eq!(result[1].period_num(), 1);
+        assert_eq!(result[1].amount_num(), 5000);
+
+        teardown(&schema).await;
+    }
+}
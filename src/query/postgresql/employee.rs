@@ -0,0 +1,204 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use sqlx::AssertSqlSafe;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::model::Address;
+use crate::query::postgresql::PostgreSQLQuery;
+use crate::query::{EmployeeQ, EmployeeT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct Employee {
+    pub(crate) guid: String,
+    pub(crate) username: String,
+    pub(crate) id: String,
+    pub(crate) language: String,
+    pub(crate) acl: String,
+    pub(crate) active: i32,
+    pub(crate) currency: String,
+    pub(crate) ccard_guid: Option<String>,
+    pub(crate) workday_num: i64,
+    pub(crate) workday_denom: i64,
+    pub(crate) rate_num: i64,
+    pub(crate) rate_denom: i64,
+    pub(crate) addr_name: Option<String>,
+    pub(crate) addr_addr1: Option<String>,
+    pub(crate) addr_addr2: Option<String>,
+    pub(crate) addr_addr3: Option<String>,
+    pub(crate) addr_addr4: Option<String>,
+    pub(crate) addr_phone: Option<String>,
+    pub(crate) addr_fax: Option<String>,
+    pub(crate) addr_email: Option<String>,
+}
+
+impl EmployeeT for Employee {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn username(&self) -> &str {
+        &self.username
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn language(&self) -> &str {
+        &self.language
+    }
+    fn acl(&self) -> &str {
+        &self.acl
+    }
+    fn active(&self) -> bool {
+        self.active != 0
+    }
+    fn currency_guid(&self) -> &str {
+        &self.currency
+    }
+    fn ccard_guid(&self) -> &str {
+        self.ccard_guid.as_deref().unwrap_or_default()
+    }
+    fn workday_num(&self) -> i64 {
+        self.workday_num
+    }
+    fn workday_denom(&self) -> i64 {
+        self.workday_denom
+    }
+    fn rate_num(&self) -> i64 {
+        self.rate_num
+    }
+    fn rate_denom(&self) -> i64 {
+        self.rate_denom
+    }
+    fn addr(&self) -> Address {
+        Address {
+            name: self.addr_name.clone().unwrap_or_default(),
+            addr1: self.addr_addr1.clone().unwrap_or_default(),
+            addr2: self.addr_addr2.clone().unwrap_or_default(),
+            addr3: self.addr_addr3.clone().unwrap_or_default(),
+            addr4: self.addr_addr4.clone().unwrap_or_default(),
+            phone: self.addr_phone.clone().unwrap_or_default(),
+            fax: self.addr_fax.clone().unwrap_or_default(),
+            email: self.addr_email.clone().unwrap_or_default(),
+        }
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+username,
+id,
+language,
+acl,
+active,
+currency,
+ccard_guid,
+workday_num,
+workday_denom,
+rate_num,
+rate_denom,
+addr_name,
+addr_addr1,
+addr_addr2,
+addr_addr3,
+addr_addr4,
+addr_phone,
+addr_fax,
+addr_email
+FROM employees
+";
+
+impl EmployeeQ for PostgreSQLQuery {
+    type Item = Employee;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all employees from postgresql");
+        sqlx::query_as(SEL)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching employee by guid from postgresql");
+        sqlx::query_as(AssertSqlSafe(format!("{SEL}\nWHERE guid = $1")))
+            .bind(guid)
+            .fetch_optional(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::postgresql::tests::setup_with;
+    use crate::query::postgresql::tests::teardown;
+    use crate::query::tests::fixtures::BUSINESS;
+
+    #[cfg(feature = "schema")]
+    // test schemas on compile time
+    #[allow(dead_code)]
+    fn test_employee_schemas() {
+        let _ = sqlx::query_as!(
+            Employee,
+            r"
+    			SELECT
+    			guid,
+    			username,
+    			id,
+    			language,
+    			acl,
+    			active,
+    			currency,
+    			ccard_guid,
+    			workday_num,
+    			workday_denom,
+    			rate_num,
+    			rate_denom,
+    			addr_name,
+    			addr_addr1,
+    			addr_addr2,
+    			addr_addr3,
+    			addr_addr4,
+    			addr_phone,
+    			addr_fax,
+    			addr_email
+    			FROM employees
+    			",
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_employee() {
+        let (query, schema) = setup_with(&[BUSINESS]).await;
+        let result = EmployeeQ::guid(&query, "f6a7b8c9d0e1f2a3b4c5d6e7f8091a23")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.username(), "jdoe");
+        assert_eq!(result.id(), "000001");
+        assert_eq!(result.active(), true);
+        assert_eq!(result.currency_guid(), "346629655191dcf59a7e2c2a85b70f69");
+        assert_eq!(result.ccard_guid(), "");
+        assert_eq!(result.workday_num(), 8);
+        assert_eq!(result.workday_denom(), 1);
+        assert_eq!(result.rate_num(), 2500);
+        assert_eq!(result.rate_denom(), 100);
+        assert_eq!(result.addr().name, "Jane Doe");
+        assert_eq!(result.addr().email, "jane@example.com");
+        assert_eq!(EmployeeQ::all(&query).await.unwrap().len(), 1);
+        assert_eq!(EmployeeQ::guid(&query, "unknown").await.unwrap(), None);
+
+        teardown(&schema).await;
+    }
+}
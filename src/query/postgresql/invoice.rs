@@ -0,0 +1,225 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use chrono::NaiveDateTime;
+use sqlx::AssertSqlSafe;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::query::postgresql::PostgreSQLQuery;
+use crate::query::{InvoiceQ, InvoiceT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct Invoice {
+    pub(crate) guid: String,
+    pub(crate) id: String,
+    pub(crate) date_opened: Option<NaiveDateTime>,
+    pub(crate) date_posted: Option<NaiveDateTime>,
+    pub(crate) notes: String,
+    pub(crate) active: i32,
+    pub(crate) currency: String,
+    pub(crate) owner_type: Option<i32>,
+    pub(crate) owner_guid: Option<String>,
+    pub(crate) terms: Option<String>,
+    pub(crate) billing_id: Option<String>,
+    pub(crate) post_txn: Option<String>,
+    pub(crate) post_lot: Option<String>,
+    pub(crate) post_acc: Option<String>,
+    pub(crate) charge_amt_num: Option<i64>,
+    pub(crate) charge_amt_denom: Option<i64>,
+}
+
+impl InvoiceT for Invoice {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn date_opened(&self) -> Option<NaiveDateTime> {
+        self.date_opened
+    }
+    fn date_posted(&self) -> Option<NaiveDateTime> {
+        self.date_posted
+    }
+    fn notes(&self) -> &str {
+        &self.notes
+    }
+    fn active(&self) -> bool {
+        self.active != 0
+    }
+    fn currency_guid(&self) -> &str {
+        &self.currency
+    }
+    fn owner_type(&self) -> i64 {
+        self.owner_type.map_or(0, i64::from)
+    }
+    fn owner_guid(&self) -> &str {
+        self.owner_guid.as_deref().unwrap_or_default()
+    }
+    fn terms_guid(&self) -> &str {
+        self.terms.as_deref().unwrap_or_default()
+    }
+    fn billing_id(&self) -> &str {
+        self.billing_id.as_deref().unwrap_or_default()
+    }
+    fn post_txn_guid(&self) -> &str {
+        self.post_txn.as_deref().unwrap_or_default()
+    }
+    fn post_lot_guid(&self) -> &str {
+        self.post_lot.as_deref().unwrap_or_default()
+    }
+    fn post_acc_guid(&self) -> &str {
+        self.post_acc.as_deref().unwrap_or_default()
+    }
+    fn charge_amt_num(&self) -> i64 {
+        self.charge_amt_num.unwrap_or(0)
+    }
+    fn charge_amt_denom(&self) -> i64 {
+        self.charge_amt_denom.unwrap_or(1)
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+id,
+date_opened,
+date_posted,
+notes,
+active,
+currency,
+owner_type,
+owner_guid,
+terms,
+billing_id,
+post_txn,
+post_lot,
+post_acc,
+charge_amt_num,
+charge_amt_denom
+FROM invoices
+";
+
+impl InvoiceQ for PostgreSQLQuery {
+    type Item = Invoice;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all invoices from postgresql");
+        sqlx::query_as(SEL)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching invoice by guid from postgresql");
+        sqlx::query_as(AssertSqlSafe(format!("{SEL}\nWHERE guid = $1")))
+            .bind(guid)
+            .fetch_optional(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::postgresql::tests::setup_with;
+    use crate::query::postgresql::tests::teardown;
+    use crate::query::tests::fixtures::{BUSINESS, INVOICES};
+
+    #[cfg(feature = "schema")]
+    // test schemas on compile time
+    #[allow(dead_code)]
+    fn test_invoice_schemas() {
+        let _ = sqlx::query_as!(
+            Invoice,
+            r"
+    			SELECT
+    			guid,
+    			id,
+    			date_opened,
+    			date_posted,
+    			notes,
+    			active,
+    			currency,
+    			owner_type,
+    			owner_guid,
+    			terms,
+    			billing_id,
+    			post_txn,
+    			post_lot,
+    			post_acc,
+    			charge_amt_num,
+    			charge_amt_denom
+    			FROM invoices
+    			",
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_invoice() {
+        let (query, schema) = setup_with(&[BUSINESS, INVOICES]).await;
+        let result = InvoiceQ::guid(&query, "3d4e5f60718293a4b5c6d7e8f90a1b01")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.id(), "000001");
+        assert_eq!(
+            result.date_opened(),
+            NaiveDateTime::parse_from_str("2018-03-01 10:59:00", "%Y-%m-%d %H:%M:%S").ok()
+        );
+        assert_eq!(
+            result.date_posted(),
+            NaiveDateTime::parse_from_str("2018-03-05 10:59:00", "%Y-%m-%d %H:%M:%S").ok()
+        );
+        assert_eq!(result.active(), true);
+        assert_eq!(result.currency_guid(), "346629655191dcf59a7e2c2a85b70f69");
+        assert_eq!(result.owner_type(), 2);
+        assert_eq!(result.owner_guid(), "d4e5f6a7b8c9d0e1f2a3b4c5d6e7f801");
+        assert_eq!(result.terms_guid(), "");
+        assert_eq!(result.billing_id(), "PO-17");
+        assert_eq!(result.post_txn_guid(), "60718293a4b5c6d7e8f90a1b2c3d4e01");
+        assert_eq!(result.post_lot_guid(), "5f60718293a4b5c6d7e8f90a1b2c3d01");
+        assert_eq!(result.post_acc_guid(), "0a1b2c3d4e5f60718293a4b5c6d7e801");
+        assert_eq!(result.charge_amt_num(), 0);
+        assert_eq!(result.charge_amt_denom(), 1);
+
+        teardown(&schema).await;
+    }
+
+    #[test(tokio::test)]
+    async fn test_draft() {
+        let (query, schema) = setup_with(&[BUSINESS, INVOICES]).await;
+        let result = InvoiceQ::guid(&query, "3d4e5f60718293a4b5c6d7e8f90a1b03")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.date_posted(), None);
+        assert_eq!(result.notes(), "draft");
+        assert_eq!(result.owner_type(), 3);
+        assert_eq!(result.post_txn_guid(), "");
+        assert_eq!(result.post_lot_guid(), "");
+
+        teardown(&schema).await;
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (query, schema) = setup_with(&[BUSINESS, INVOICES]).await;
+        let result = InvoiceQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 3);
+
+        teardown(&schema).await;
+    }
+}
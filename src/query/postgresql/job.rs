@@ -0,0 +1,139 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use sqlx::AssertSqlSafe;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::query::postgresql::PostgreSQLQuery;
+use crate::query::{JobQ, JobT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct Job {
+    pub(crate) guid: String,
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) reference: String,
+    pub(crate) active: i32,
+    pub(crate) owner_type: Option<i32>,
+    pub(crate) owner_guid: Option<String>,
+}
+
+impl JobT for Job {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn reference(&self) -> &str {
+        &self.reference
+    }
+    fn active(&self) -> bool {
+        self.active != 0
+    }
+    fn owner_type(&self) -> i64 {
+        self.owner_type.map_or(0, i64::from)
+    }
+    fn owner_guid(&self) -> &str {
+        self.owner_guid.as_deref().unwrap_or_default()
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+id,
+name,
+reference,
+active,
+owner_type,
+owner_guid
+FROM jobs
+";
+
+impl JobQ for PostgreSQLQuery {
+    type Item = Job;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all jobs from postgresql");
+        sqlx::query_as(SEL)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching job by guid from postgresql");
+        sqlx::query_as(AssertSqlSafe(format!("{SEL}\nWHERE guid = $1")))
+            .bind(guid)
+            .fetch_optional(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::postgresql::tests::setup_with;
+    use crate::query::postgresql::tests::teardown;
+    use crate::query::tests::fixtures::{BUSINESS, INVOICES};
+
+    #[cfg(feature = "schema")]
+    // test schemas on compile time
+    #[allow(dead_code)]
+    fn test_job_schemas() {
+        let _ = sqlx::query_as!(
+            Job,
+            r"
+    			SELECT
+    			guid,
+    			id,
+    			name,
+    			reference,
+    			active,
+    			owner_type,
+    			owner_guid
+    			FROM jobs
+    			",
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_job() {
+        let (query, schema) = setup_with(&[BUSINESS, INVOICES]).await;
+        let result = JobQ::guid(&query, "1b2c3d4e5f60718293a4b5c6d7e8f901")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.id(), "000001");
+        assert_eq!(result.name(), "Website");
+        assert_eq!(result.reference(), "PO-17");
+        assert_eq!(result.active(), true);
+        assert_eq!(result.owner_type(), 2);
+        assert_eq!(result.owner_guid(), "d4e5f6a7b8c9d0e1f2a3b4c5d6e7f801");
+
+        teardown(&schema).await;
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (query, schema) = setup_with(&[BUSINESS, INVOICES]).await;
+        let result = JobQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 1);
+
+        teardown(&schema).await;
+    }
+}
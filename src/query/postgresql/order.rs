@@ -0,0 +1,158 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use chrono::NaiveDateTime;
+use sqlx::AssertSqlSafe;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::query::postgresql::PostgreSQLQuery;
+use crate::query::{OrderQ, OrderT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct Order {
+    pub(crate) guid: String,
+    pub(crate) id: String,
+    pub(crate) notes: String,
+    pub(crate) reference: String,
+    pub(crate) active: i32,
+    pub(crate) date_opened: NaiveDateTime,
+    pub(crate) date_closed: Option<NaiveDateTime>,
+    pub(crate) owner_type: i32,
+    pub(crate) owner_guid: String,
+}
+
+impl OrderT for Order {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn notes(&self) -> &str {
+        &self.notes
+    }
+    fn reference(&self) -> &str {
+        &self.reference
+    }
+    fn active(&self) -> bool {
+        self.active != 0
+    }
+    fn date_opened(&self) -> NaiveDateTime {
+        self.date_opened
+    }
+    fn date_closed(&self) -> Option<NaiveDateTime> {
+        // 0 when not set
+        self.date_closed.filter(|x| x.and_utc().timestamp() != 0)
+    }
+    fn owner_type(&self) -> i64 {
+        self.owner_type.into()
+    }
+    fn owner_guid(&self) -> &str {
+        &self.owner_guid
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+id,
+notes,
+reference,
+active,
+date_opened,
+date_closed,
+owner_type,
+owner_guid
+FROM orders
+";
+
+impl OrderQ for PostgreSQLQuery {
+    type Item = Order;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all orders from postgresql");
+        sqlx::query_as(SEL)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching order by guid from postgresql");
+        sqlx::query_as(AssertSqlSafe(format!("{SEL}\nWHERE guid = $1")))
+            .bind(guid)
+            .fetch_optional(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::postgresql::tests::setup_with;
+    use crate::query::postgresql::tests::teardown;
+    use crate::query::tests::fixtures::{BUSINESS, INVOICES};
+
+    #[cfg(feature = "schema")]
+    // test schemas on compile time
+    #[allow(dead_code)]
+    fn test_order_schemas() {
+        let _ = sqlx::query_as!(
+            Order,
+            r"
+    			SELECT
+    			guid,
+    			id,
+    			notes,
+    			reference,
+    			active,
+    			date_opened,
+    			date_closed,
+    			owner_type,
+    			owner_guid
+    			FROM orders
+    			",
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_order() {
+        let (query, schema) = setup_with(&[BUSINESS, INVOICES]).await;
+        let result = OrderQ::guid(&query, "2c3d4e5f60718293a4b5c6d7e8f90a01")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.id(), "000001");
+        assert_eq!(result.notes(), "");
+        assert_eq!(result.reference(), "PO-17");
+        assert_eq!(result.active(), true);
+        assert_eq!(
+            result.date_opened(),
+            NaiveDateTime::parse_from_str("2018-03-01 10:59:00", "%Y-%m-%d %H:%M:%S").unwrap()
+        );
+        assert_eq!(result.date_closed(), None);
+        assert_eq!(result.owner_type(), 3);
+        assert_eq!(result.owner_guid(), "1b2c3d4e5f60718293a4b5c6d7e8f901");
+
+        teardown(&schema).await;
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (query, schema) = setup_with(&[BUSINESS, INVOICES]).await;
+        let result = OrderQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 1);
+
+        teardown(&schema).await;
+    }
+}
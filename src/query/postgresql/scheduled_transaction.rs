@@ -0,0 +1,187 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use chrono::NaiveDate;
+use sqlx::AssertSqlSafe;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::query::postgresql::PostgreSQLQuery;
+use crate::query::{ScheduledTransactionQ, ScheduledTransactionT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct ScheduledTransaction {
+    pub(crate) guid: String,
+    pub(crate) name: Option<String>,
+    pub(crate) enabled: i32,
+    pub(crate) start_date: Option<NaiveDate>,
+    pub(crate) end_date: Option<NaiveDate>,
+    pub(crate) last_occur: Option<NaiveDate>,
+    pub(crate) num_occur: i32,
+    pub(crate) rem_occur: i32,
+    pub(crate) auto_create: i32,
+    pub(crate) auto_notify: i32,
+    pub(crate) adv_creation: i32,
+    pub(crate) adv_notify: i32,
+    pub(crate) instance_count: i32,
+    pub(crate) template_act_guid: String,
+}
+
+impl ScheduledTransactionT for ScheduledTransaction {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or_default()
+    }
+    fn enabled(&self) -> bool {
+        self.enabled != 0
+    }
+    fn start_date(&self) -> Option<NaiveDate> {
+        self.start_date
+    }
+    fn end_date(&self) -> Option<NaiveDate> {
+        self.end_date
+    }
+    fn last_occur(&self) -> Option<NaiveDate> {
+        self.last_occur
+    }
+    fn num_occur(&self) -> i64 {
+        self.num_occur.into()
+    }
+    fn rem_occur(&self) -> i64 {
+        self.rem_occur.into()
+    }
+    fn auto_create(&self) -> bool {
+        self.auto_create != 0
+    }
+    fn auto_notify(&self) -> bool {
+        self.auto_notify != 0
+    }
+    fn adv_creation(&self) -> i64 {
+        self.adv_creation.into()
+    }
+    fn adv_notify(&self) -> i64 {
+        self.adv_notify.into()
+    }
+    fn instance_count(&self) -> i64 {
+        self.instance_count.into()
+    }
+    fn template_act_guid(&self) -> &str {
+        &self.template_act_guid
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+name,
+enabled,
+start_date,
+end_date,
+last_occur,
+num_occur,
+rem_occur,
+auto_create,
+auto_notify,
+adv_creation,
+adv_notify,
+instance_count,
+template_act_guid
+FROM schedxactions
+";
+
+impl ScheduledTransactionQ for PostgreSQLQuery {
+    type Item = ScheduledTransaction;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all scheduled transactions from postgresql");
+        sqlx::query_as(SEL)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching scheduled transaction by guid from postgresql");
+        sqlx::query_as(AssertSqlSafe(format!("{SEL}\nWHERE guid = $1")))
+            .bind(guid)
+            .fetch_optional(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::postgresql::tests::{setup_writable, teardown};
+
+    #[cfg(feature = "schema")]
+    // test schemas on compile time
+    #[allow(dead_code)]
+    fn test_scheduled_transaction_schemas() {
+        let _ = sqlx::query_as!(
+            ScheduledTransaction,
+            r"
+    			SELECT
+    			guid,
+    			name,
+    			enabled,
+    			start_date,
+    			end_date,
+    			last_occur,
+    			num_occur,
+    			rem_occur,
+    			auto_create,
+    			auto_notify,
+    			adv_creation,
+    			adv_notify,
+    			instance_count,
+    			template_act_guid
+    			FROM schedxactions
+    			",
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_scheduled_transaction() {
+        let (query, schema) = setup_writable().await;
+        sqlx::raw_sql(
+            "
+            INSERT INTO schedxactions (guid, name, enabled, start_date, end_date, last_occur,
+                                       num_occur, rem_occur, auto_create, auto_notify,
+                                       adv_creation, adv_notify, instance_count, template_act_guid)
+            VALUES ('3b4c5d6e7f8091a2b3c4d5e6f7081920', 'Rent', 1, '2018-03-01', NULL, '2018-03-01',
+                    6, 5, 0, 0, 2, 0, 1, '9a8b7c6d5e4f30211203f4e5d6c7b8a9');
+            ",
+        )
+        .execute(&query.pool)
+        .await
+        .unwrap();
+
+        let result = ScheduledTransactionQ::guid(&query, "3b4c5d6e7f8091a2b3c4d5e6f7081920")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.name(), "Rent");
+        assert_eq!(result.enabled(), true);
+        assert_eq!(result.start_date(), NaiveDate::from_ymd_opt(2018, 3, 1));
+        assert_eq!(result.end_date(), None);
+        assert_eq!(result.rem_occur(), 5);
+        assert_eq!(
+            result.template_act_guid(),
+            "9a8b7c6d5e4f30211203f4e5d6c7b8a9"
+        );
+        assert_eq!(ScheduledTransactionQ::all(&query).await.unwrap().len(), 1);
+
+        teardown(&schema).await;
+    }
+}
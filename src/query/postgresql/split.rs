@@ -4,12 +4,12 @@
 use chrono::{DateTime, NaiveDateTime};
 #[cfg(feature = "decimal")]
 use rust_decimal::Decimal;
-use sqlx::AssertSqlSafe;
+use sqlx::{AssertSqlSafe, PgConnection};
 use tracing::instrument;
 
 use crate::error::Error;
 use crate::query::postgresql::PostgreSQLQuery;
-use crate::query::{SplitQ, SplitT};
+use crate::query::{ReconcileRecord, SplitMutQ, SplitQ, SplitRecord, SplitT};
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
 pub struct Split {
@@ -46,6 +46,12 @@ impl SplitT for Split {
     fn reconcile_state(&self) -> bool {
         self.reconcile_state == "y" || self.reconcile_state == "Y"
     }
+    fn reconcile_flag(&self) -> char {
+        self.reconcile_state
+            .chars()
+            .next()
+            .map_or('n', |x| x.to_ascii_lowercase())
+    }
     fn reconcile_datetime(&self) -> Option<NaiveDateTime> {
         let datetime = self.reconcile_date?;
         if datetime.and_utc() == DateTime::UNIX_EPOCH {
@@ -57,6 +63,19 @@ impl SplitT for Split {
         self.lot_guid.as_deref().unwrap_or_default()
     }
 
+    fn value_num(&self) -> i64 {
+        self.value_num
+    }
+    fn value_denom(&self) -> i64 {
+        self.value_denom
+    }
+    fn quantity_num(&self) -> i64 {
+        self.quantity_num
+    }
+    fn quantity_denom(&self) -> i64 {
+        self.quantity_denom
+    }
+
     #[cfg(not(feature = "decimal"))]
     #[allow(clippy::cast_precision_loss)]
     fn value(&self) -> f64 {
@@ -97,6 +116,36 @@ lot_guid
 FROM splits
 ";
 
+const BETWEEN: &str = r"
+SELECT
+splits.guid,
+splits.tx_guid,
+splits.account_guid,
+splits.memo,
+splits.action,
+splits.reconcile_state,
+splits.reconcile_date,
+splits.value_num,
+splits.value_denom,
+splits.quantity_num,
+splits.quantity_denom,
+splits.lot_guid,
+transactions.post_date
+FROM splits
+JOIN transactions ON transactions.guid = splits.tx_guid
+WHERE splits.account_guid = $1
+AND ($2::timestamp IS NULL OR transactions.post_date >= $2)
+AND ($3::timestamp IS NULL OR transactions.post_date < $3)
+ORDER BY transactions.post_date
+";
+
+/// A split with the post date of its transaction, as `BETWEEN` returns it.
+#[derive(sqlx::FromRow)]
+struct DatedSplit {
+    #[sqlx(flatten)]
+    split: Split,
+    post_date: NaiveDateTime,
+}
 impl SplitQ for PostgreSQLQuery {
     type Item = Split;
 
@@ -132,6 +181,24 @@ impl SplitQ for PostgreSQLQuery {
             .map_err(std::convert::Into::into)
     }
 
+    #[instrument(skip(self))]
+    async fn account_between(
+        &self,
+        guid: &str,
+        from: Option<NaiveDateTime>,
+        to: Option<NaiveDateTime>,
+    ) -> Result<Vec<(NaiveDateTime, Self::Item)>, Error> {
+        tracing::debug!("fetching splits by account_guid and post date from postgresql");
+        let rows: Vec<DatedSplit> = sqlx::query_as(BETWEEN)
+            .bind(guid)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))?;
+        Ok(rows.into_iter().map(|x| (x.post_date, x.split)).collect())
+    }
+
     #[instrument(skip(self))]
     async fn transaction(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
         tracing::debug!("fetching splits by tx_guid from postgresql");
@@ -144,6 +211,85 @@ impl SplitQ for PostgreSQLQuery {
     }
 }
 
+const INS: &str = r"
+INSERT INTO splits (
+guid,
+tx_guid,
+account_guid,
+memo,
+action,
+reconcile_state,
+reconcile_date,
+value_num,
+value_denom,
+quantity_num,
+quantity_denom,
+lot_guid
+) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+";
+
+const UPD_RECONCILE: &str = r"
+UPDATE splits SET
+reconcile_state = $2,
+reconcile_date = COALESCE($3, reconcile_date)
+WHERE guid = $1
+";
+
+/// Inserts a split row; the caller owns the surrounding database transaction.
+pub(crate) async fn insert(
+    conn: &mut PgConnection,
+    split: &SplitRecord,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(INS)
+        .bind(&split.guid)
+        .bind(&split.tx_guid)
+        .bind(&split.account_guid)
+        .bind(&split.memo)
+        .bind(&split.action)
+        .bind(split.reconcile_state.to_string())
+        .bind(split.reconcile_datetime)
+        .bind(split.value_num)
+        .bind(split.value_denom)
+        .bind(split.quantity_num)
+        .bind(split.quantity_denom)
+        .bind((!split.lot_guid.is_empty()).then_some(&split.lot_guid))
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+impl SplitMutQ for PostgreSQLQuery {
+    #[instrument(skip(self, splits), fields(split_count = splits.len()))]
+    async fn reconcile(&self, splits: &[ReconcileRecord]) -> Result<(), Error> {
+        tracing::debug!("updating reconcile state in postgresql");
+        let mut tx = self.begin().await?;
+
+        for split in splits {
+            let count = sqlx::query(UPD_RECONCILE)
+                .bind(&split.guid)
+                .bind(split.reconcile_state.to_string())
+                .bind(split.reconcile_datetime)
+                .execute(&mut *tx)
+                .await
+                .inspect_err(|e| tracing::error!("failed to update split: {e}"))?
+                .rows_affected();
+            if count == 0 {
+                tracing::error!("split not found");
+                return Err(Error::GuidNotFound {
+                    model: "Split".to_string(),
+                    guid: split.guid.clone(),
+                });
+            }
+        }
+
+        super::lock::commit(tx).await?;
+
+        tracing::debug!("reconcile state updated in postgresql");
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(not(feature = "decimal"))]
@@ -153,6 +299,7 @@ mod tests {
     use tokio::sync::OnceCell;
 
     use super::*;
+    use crate::query::postgresql::tests::{setup_writable, teardown};
 
     #[cfg(feature = "schema")]
     // test schemas on compile time
@@ -217,6 +364,10 @@ mod tests {
         assert_approx_eq!(f64, result.quantity(), 150.0);
         #[cfg(feature = "decimal")]
         assert_eq!(result.quantity(), Decimal::new(150, 0));
+        assert_eq!(result.value_num(), 15000);
+        assert_eq!(result.value_denom(), 100);
+        assert_eq!(result.quantity_num(), 15000);
+        assert_eq!(result.quantity_denom(), 100);
     }
 
     #[test(tokio::test)]
@@ -251,6 +402,43 @@ mod tests {
         assert_eq!(result.len(), 3);
     }
 
+    #[test(tokio::test)]
+    async fn test_account_between() {
+        let query = setup().await;
+        let guid = "93fc043c3062aaa1297b30e543d2cd0d";
+        let date = |x| NaiveDateTime::parse_from_str(x, "%Y-%m-%d %H:%M:%S").unwrap();
+
+        let result = query.account_between(guid, None, None).await.unwrap();
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].0, date("2014-12-24 10:59:00"));
+        assert_eq!(result[0].1.tx_guid, "6c8876003c4a6026e38e3afb67d6f2b1");
+        assert!(result.is_sorted_by_key(|(x, _)| *x));
+        let result = query
+            .account_between(guid, None, Some(date("2018-01-01 00:00:00")))
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        let result = query
+            .account_between(guid, Some(date("2018-02-20 10:59:00")), None)
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 2);
+        let result = query
+            .account_between(
+                guid,
+                Some(date("2014-12-24 10:59:00")),
+                Some(date("2018-02-20 10:59:00")),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        let result = query
+            .account_between(guid, Some(date("2018-02-21 00:00:00")), None)
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 0);
+    }
+
     #[test(tokio::test)]
     async fn test_tx_guid() {
         let query = setup().await;
@@ -260,4 +448,35 @@ mod tests {
             .unwrap();
         assert_eq!(result.len(), 2);
     }
+
+    #[test(tokio::test)]
+    async fn test_reconcile() {
+        let (query, schema) = setup_writable().await;
+        let datetime =
+            NaiveDateTime::parse_from_str("2015-01-01 23:59:59", "%Y-%m-%d %H:%M:%S").ok();
+        let records = [
+            ReconcileRecord {
+                guid: "de832fe97e37811a7fff7e28b3a43425".to_string(),
+                reconcile_state: 'y',
+                reconcile_datetime: datetime,
+            },
+            ReconcileRecord {
+                guid: "unknown".to_string(),
+                reconcile_state: 'c',
+                reconcile_datetime: None,
+            },
+        ];
+
+        let result = query.reconcile(&records).await;
+        assert!(matches!(result, Err(Error::GuidNotFound { .. })));
+        let result = query.guid(&records[0].guid).await.unwrap().unwrap();
+        assert_eq!(result.reconcile_flag(), 'n');
+
+        query.reconcile(&records[..1]).await.unwrap();
+        let result = query.guid(&records[0].guid).await.unwrap().unwrap();
+        assert_eq!(result.reconcile_flag(), 'y');
+        assert_eq!(result.reconcile_datetime(), datetime);
+
+        teardown(&schema).await;
+    }
 }
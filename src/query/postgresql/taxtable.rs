@@ -0,0 +1,210 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use sqlx::AssertSqlSafe;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::query::postgresql::PostgreSQLQuery;
+use crate::query::{TaxTableEntryQ, TaxTableEntryT, TaxTableQ, TaxTableT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct TaxTable {
+    pub(crate) guid: String,
+    pub(crate) name: String,
+    pub(crate) refcount: i64,
+    pub(crate) invisible: i32,
+    pub(crate) parent: Option<String>,
+}
+
+impl TaxTableT for TaxTable {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn refcount(&self) -> i64 {
+        self.refcount
+    }
+    fn invisible(&self) -> bool {
+        self.invisible != 0
+    }
+    fn parent_guid(&self) -> &str {
+        self.parent.as_deref().unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct TaxTableEntry {
+    pub(crate) taxtable: String,
+    pub(crate) account: String,
+    pub(crate) amount_num: i64,
+    pub(crate) amount_denom: i64,
+    pub(crate) r#type: i32,
+}
+
+impl TaxTableEntryT for TaxTableEntry {
+    fn taxtable_guid(&self) -> &str {
+        &self.taxtable
+    }
+    fn account_guid(&self) -> &str {
+        &self.account
+    }
+    fn amount_num(&self) -> i64 {
+        self.amount_num
+    }
+    fn amount_denom(&self) -> i64 {
+        self.amount_denom
+    }
+    fn r#type(&self) -> i64 {
+        self.r#type.into()
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+name,
+refcount,
+invisible,
+parent
+FROM taxtables
+";
+
+const SEL_TAXTABLE: &str = r"
+SELECT
+taxtable,
+account,
+amount_num,
+amount_denom,
+type
+FROM taxtable_entries
+WHERE taxtable = $1
+ORDER BY id
+";
+
+impl TaxTableQ for PostgreSQLQuery {
+    type Item = TaxTable;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all tax tables from postgresql");
+        sqlx::query_as(SEL)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching tax table by guid from postgresql");
+        sqlx::query_as(AssertSqlSafe(format!("{SEL}\nWHERE guid = $1")))
+            .bind(guid)
+            .fetch_optional(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+impl TaxTableEntryQ for PostgreSQLQuery {
+    type Item = TaxTableEntry;
+
+    #[instrument(skip(self))]
+    async fn taxtable(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching tax table entries by taxtable from postgresql");
+        sqlx::query_as(SEL_TAXTABLE)
+            .bind(guid)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::postgresql::tests::setup_with;
+    use crate::query::postgresql::tests::teardown;
+    use crate::query::tests::fixtures::{BUSINESS, INVOICES, TAX};
+
+    #[cfg(feature = "schema")]
+    // test schemas on compile time
+    #[allow(dead_code)]
+    fn test_taxtable_schemas() {
+        let _ = sqlx::query_as!(
+            TaxTable,
+            r"
+    			SELECT
+    			guid,
+    			name,
+    			refcount,
+    			invisible,
+    			parent
+    			FROM taxtables
+    			",
+        );
+        let _ = sqlx::query_as!(
+            TaxTableEntry,
+            r"
+    			SELECT
+    			taxtable,
+    			account,
+    			amount_num,
+    			amount_denom,
+    			type
+    			FROM taxtable_entries
+    			",
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_taxtable() {
+        let (query, schema) = setup_with(&[BUSINESS, INVOICES, TAX]).await;
+        let result = TaxTableQ::guid(&query, "8a9b0c1d2e3f405162738495a6b7c801")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.name(), "VAT");
+        assert_eq!(result.refcount(), 4);
+        assert_eq!(result.invisible(), false);
+        assert_eq!(result.parent_guid(), "");
+
+        teardown(&schema).await;
+    }
+
+    #[test(tokio::test)]
+    async fn test_entries() {
+        let (query, schema) = setup_with(&[BUSINESS, INVOICES, TAX]).await;
+        let result = TaxTableEntryQ::taxtable(&query, "8a9b0c1d2e3f405162738495a6b7c802")
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].account_guid(), "96ed7a45459fb5fe570e48fcd46f05d0");
+        assert_eq!(result[0].amount_num(), 10);
+        assert_eq!(result[0].r#type(), 2);
+        assert_eq!(result[1].account_guid(), "af88d386d44b14acf244362b85ccaf4c");
+        assert_eq!(result[1].amount_num(), 500);
+        assert_eq!(result[1].amount_denom(), 100);
+        assert_eq!(result[1].r#type(), 1);
+
+        teardown(&schema).await;
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (query, schema) = setup_with(&[BUSINESS, INVOICES, TAX]).await;
+        let result = TaxTableQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 2);
+
+        teardown(&schema).await;
+    }
+}
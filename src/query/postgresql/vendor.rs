@@ -0,0 +1,191 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use sqlx::AssertSqlSafe;
+use tracing::instrument;
+
+use crate::error::Error;
+use crate::model::Address;
+use crate::query::postgresql::PostgreSQLQuery;
+use crate::query::{VendorQ, VendorT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, sqlx::FromRow)]
+pub struct Vendor {
+    pub(crate) guid: String,
+    pub(crate) name: String,
+    pub(crate) id: String,
+    pub(crate) notes: String,
+    pub(crate) currency: String,
+    pub(crate) active: i32,
+    pub(crate) tax_override: i32,
+    pub(crate) addr_name: Option<String>,
+    pub(crate) addr_addr1: Option<String>,
+    pub(crate) addr_addr2: Option<String>,
+    pub(crate) addr_addr3: Option<String>,
+    pub(crate) addr_addr4: Option<String>,
+    pub(crate) addr_phone: Option<String>,
+    pub(crate) addr_fax: Option<String>,
+    pub(crate) addr_email: Option<String>,
+    pub(crate) terms: Option<String>,
+    pub(crate) tax_inc: Option<String>,
+    pub(crate) tax_table: Option<String>,
+}
+
+impl VendorT for Vendor {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn notes(&self) -> &str {
+        &self.notes
+    }
+    fn currency_guid(&self) -> &str {
+        &self.currency
+    }
+    fn active(&self) -> bool {
+        self.active != 0
+    }
+    fn tax_override(&self) -> bool {
+        self.tax_override != 0
+    }
+    fn addr(&self) -> Address {
+        Address {
+            name: self.addr_name.clone().unwrap_or_default(),
+            addr1: self.addr_addr1.clone().unwrap_or_default(),
+            addr2: self.addr_addr2.clone().unwrap_or_default(),
+            addr3: self.addr_addr3.clone().unwrap_or_default(),
+            addr4: self.addr_addr4.clone().unwrap_or_default(),
+            phone: self.addr_phone.clone().unwrap_or_default(),
+            fax: self.addr_fax.clone().unwrap_or_default(),
+            email: self.addr_email.clone().unwrap_or_default(),
+        }
+    }
+    fn terms_guid(&self) -> &str {
+        self.terms.as_deref().unwrap_or_default()
+    }
+    fn tax_included(&self) -> &str {
+        self.tax_inc.as_deref().unwrap_or("USEGLOBAL")
+    }
+    fn taxtable_guid(&self) -> &str {
+        self.tax_table.as_deref().unwrap_or_default()
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+name,
+id,
+notes,
+currency,
+active,
+tax_override,
+addr_name,
+addr_addr1,
+addr_addr2,
+addr_addr3,
+addr_addr4,
+addr_phone,
+addr_fax,
+addr_email,
+terms,
+tax_inc,
+tax_table
+FROM vendors
+";
+
+impl VendorQ for PostgreSQLQuery {
+    type Item = Vendor;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all vendors from postgresql");
+        sqlx::query_as(SEL)
+            .fetch_all(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching vendor by guid from postgresql");
+        sqlx::query_as(AssertSqlSafe(format!("{SEL}\nWHERE guid = $1")))
+            .bind(guid)
+            .fetch_optional(&self.pool)
+            .await
+            .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+            .map_err(std::convert::Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::postgresql::tests::setup_with;
+    use crate::query::postgresql::tests::teardown;
+    use crate::query::tests::fixtures::BUSINESS;
+
+    #[cfg(feature = "schema")]
+    // test schemas on compile time
+    #[allow(dead_code)]
+    fn test_vendor_schemas() {
+        let _ = sqlx::query_as!(
+            Vendor,
+            r"
+    			SELECT
+    			guid,
+    			name,
+    			id,
+    			notes,
+    			currency,
+    			active,
+    			tax_override,
+    			addr_name,
+    			addr_addr1,
+    			addr_addr2,
+    			addr_addr3,
+    			addr_addr4,
+    			addr_phone,
+    			addr_fax,
+    			addr_email,
+    			terms,
+    			tax_inc,
+    			tax_table
+    			FROM vendors
+    			",
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_vendor() {
+        let (query, schema) = setup_with(&[BUSINESS]).await;
+        let result = VendorQ::guid(&query, "e5f6a7b8c9d0e1f2a3b4c5d6e7f80912")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.name(), "Paper Supplies Ltd");
+        assert_eq!(result.id(), "000001");
+        assert_eq!(result.notes(), "");
+        assert_eq!(result.currency_guid(), "346629655191dcf59a7e2c2a85b70f69");
+        assert_eq!(result.active(), true);
+        assert_eq!(result.tax_override(), false);
+        assert_eq!(result.addr().addr1, "Mill Lane 3");
+        assert_eq!(result.terms_guid(), "");
+        assert_eq!(result.tax_included(), "YES");
+        assert_eq!(result.taxtable_guid(), "");
+        assert_eq!(VendorQ::all(&query).await.unwrap().len(), 1);
+        assert_eq!(VendorQ::guid(&query, "unknown").await.unwrap(), None);
+
+        teardown(&schema).await;
+    }
+}
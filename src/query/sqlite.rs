@@ -1,8 +1,26 @@
 pub(crate) mod account;
+pub(crate) mod billterm;
+pub(crate) mod book;
+pub(crate) mod budget;
+pub(crate) mod change_set;
 pub(crate) mod commodity;
+pub(crate) mod customer;
+pub(crate) mod employee;
+pub(crate) mod entry;
+pub(crate) mod invoice;
+pub(crate) mod job;
+pub(crate) mod lock;
+pub(crate) mod lot;
+pub(crate) mod order;
 pub(crate) mod price;
+pub(crate) mod recurrence;
+pub(crate) mod scheduled_transaction;
+pub(crate) mod slot;
 pub(crate) mod split;
+pub(crate) mod taxtable;
 pub(crate) mod transaction;
+pub(crate) mod vendor;
+pub(crate) mod version;
 
 use r2d2::ManageConnection;
 use rusqlite::{Connection, OpenFlags};
@@ -11,9 +29,14 @@ use tracing::instrument;
 use super::Query;
 use crate::error::Error;
 
+/// Format of the `text(19)` timestamp columns written by `GnuCash`.
+pub(crate) const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
 #[derive(Debug, Clone)]
 pub struct SQLiteQuery {
     pool: r2d2::Pool<SqliteManager>,
+    writable: bool,
+    ignore_lock: bool,
 }
 
 impl SQLiteQuery {
@@ -28,29 +51,67 @@ impl SQLiteQuery {
     #[instrument]
     pub fn new(uri: &str) -> Result<Self, Error> {
         tracing::debug!("initializing sqlite connection pool");
+        Self::with_mode(uri, false)
+    }
 
-        let manager = SqliteManager::new(uri);
+    /// Same as [`SQLiteQuery::new`], but opens the database for writing.
+    ///
+    /// Only a writable query can be used with the mutation APIs such as
+    /// [`Book::new_transaction`](crate::Book::new_transaction).
+    #[instrument]
+    pub fn new_writable(uri: &str) -> Result<Self, Error> {
+        tracing::debug!("initializing writable sqlite connection pool");
+        Self::with_mode(uri, true)
+    }
+
+    /// Lets writes proceed even when the `gnclock` table shows the book is open elsewhere,
+    /// e.g. to recover from a stale lock left behind by a crashed `GnuCash`.
+    #[must_use]
+    pub fn ignore_lock(mut self, ignore: bool) -> Self {
+        self.ignore_lock = ignore;
+        self
+    }
+
+    fn with_mode(uri: &str, writable: bool) -> Result<Self, Error> {
+        let manager = SqliteManager::new(uri, writable);
 
         let pool = r2d2::Pool::builder()
             .max_size(5)
             .connection_timeout(std::time::Duration::from_secs(5))
             .build(manager)?;
 
-        tracing::info!("sqlite connection pool established");
-        Ok(Self { pool })
+        tracing::info!(writable, "sqlite connection pool established");
+        Ok(Self {
+            pool,
+            writable,
+            ignore_lock: false,
+        })
+    }
+
+    /// Returns the connection pool, or [`Error::ReadOnly`] if the database was not opened for writing.
+    fn writable_pool(&self) -> Result<r2d2::Pool<SqliteManager>, Error> {
+        if !self.writable {
+            tracing::error!("attempted to write to a read-only sqlite database");
+            return Err(Error::ReadOnly);
+        }
+        Ok(self.pool.clone())
     }
 }
 
 impl Query for SQLiteQuery {}
 
 #[derive(Debug)]
-struct SqliteManager {
+pub(crate) struct SqliteManager {
     uri: String,
+    writable: bool,
 }
 
 impl SqliteManager {
-    fn new(uri: impl Into<String>) -> Self {
-        Self { uri: uri.into() }
+    fn new(uri: impl Into<String>, writable: bool) -> Self {
+        Self {
+            uri: uri.into(),
+            writable,
+        }
     }
 }
 
@@ -59,6 +120,24 @@ impl ManageConnection for SqliteManager {
     type Error = rusqlite::Error;
 
     fn connect(&self) -> Result<Self::Connection, Self::Error> {
+        if self.writable {
+            let conn = Connection::open_with_flags(
+                &self.uri,
+                OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_URI,
+            )?;
+
+            // Wait for other writers instead of failing immediately
+            conn.busy_timeout(std::time::Duration::from_secs(5))?;
+            conn.execute_batch(
+                "
+                PRAGMA cache_size = -8000;
+                PRAGMA temp_store = MEMORY;
+                ",
+            )?;
+
+            return Ok(conn);
+        }
+
         let conn = Connection::open_with_flags(
             &self.uri,
             OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI,
@@ -87,11 +166,38 @@ impl ManageConnection for SqliteManager {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use test_log::test;
 
     use super::*;
 
+    /// Copies the sample book into a temporary directory and runs `sql` on the copy,
+    /// usually to add [`fixtures`](crate::query::tests::fixtures) it lacks.
+    ///
+    /// Returns a writable query bound to the copy, which is removed with the directory.
+    pub(crate) fn setup_with(sql: &[&str]) -> (tempfile::TempDir, SQLiteQuery) {
+        let uri: &str = &format!(
+            "{}/tests/db/sqlite/complex_sample.gnucash",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("complex_sample.gnucash");
+        std::fs::copy(uri, &path).unwrap();
+
+        let conn = rusqlite::Connection::open(&path).unwrap();
+        for batch in sql {
+            conn.execute_batch(batch).unwrap();
+        }
+
+        let query = SQLiteQuery::new_writable(path.to_str().unwrap()).unwrap();
+        (dir, query)
+    }
+
+    /// Copies the sample book into a temporary directory, see [`setup_with`].
+    pub(crate) fn setup_writable() -> (tempfile::TempDir, SQLiteQuery) {
+        setup_with(&[])
+    }
+
     #[test]
     fn test_new() {
         let uri: &str = &format!(
@@ -102,4 +208,17 @@ mod tests {
         tracing::debug!("work_dir: {:?}", std::env::current_dir());
         SQLiteQuery::new(uri).unwrap();
     }
+
+    #[test]
+    fn test_new_writable() {
+        let (_dir, query) = setup_writable();
+        assert!(query.writable_pool().is_ok());
+
+        let uri: &str = &format!(
+            "{}/tests/db/sqlite/complex_sample.gnucash",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let query = SQLiteQuery::new(uri).unwrap();
+        assert!(matches!(query.writable_pool(), Err(Error::ReadOnly)));
+    }
 }
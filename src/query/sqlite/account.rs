@@ -1,13 +1,15 @@
 // ref: https://piecash.readthedocs.io/en/master/object_model.html
 // ref: https://wiki.gnucash.org/wiki/SQL
 
-use rusqlite::Row;
+use rusqlite::{Connection, Row, TransactionBehavior, params};
 use tokio::task::spawn_blocking;
 use tracing::instrument;
 
 use super::SQLiteQuery;
+use super::lock::with_lock;
 use crate::error::Error;
-use crate::query::{AccountQ, AccountT};
+use crate::model::AccountType;
+use crate::query::{AccountMutQ, AccountQ, AccountRecord, AccountT};
 
 #[allow(clippy::struct_field_names)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
@@ -52,8 +54,8 @@ impl AccountT for Account {
     fn name(&self) -> &str {
         &self.name
     }
-    fn account_type(&self) -> &str {
-        &self.account_type
+    fn account_type(&self) -> AccountType {
+        self.account_type.as_str().into()
     }
     fn commodity_guid(&self) -> &str {
         self.commodity_guid.as_deref().unwrap_or_default()
@@ -81,7 +83,7 @@ impl AccountT for Account {
     }
 }
 
-const SEL: &str = r"
+pub(super) const SEL: &str = r"
 SELECT
 guid,
 name,
@@ -269,12 +271,229 @@ impl AccountQ for SQLiteQuery {
     }
 }
 
+const INS: &str = r"
+INSERT INTO accounts (
+guid,
+name,
+account_type,
+commodity_guid,
+commodity_scu,
+non_std_scu,
+parent_guid,
+code,
+description,
+hidden,
+placeholder
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+";
+
+const UPD: &str = r"
+UPDATE accounts SET
+name = ?2,
+account_type = ?3,
+commodity_guid = ?4,
+commodity_scu = ?5,
+non_std_scu = ?6,
+parent_guid = ?7,
+code = ?8,
+description = ?9,
+hidden = ?10,
+placeholder = ?11
+WHERE guid = ?1
+";
+
+fn execute(
+    conn: &Connection,
+    sql: &str,
+    account: &AccountRecord,
+) -> Result<usize, rusqlite::Error> {
+    let non_empty = |x: &String| (!x.is_empty()).then(|| x.clone());
+
+    conn.execute(
+        sql,
+        params![
+            account.guid,
+            account.name,
+            account.account_type,
+            non_empty(&account.commodity_guid),
+            account.commodity_scu,
+            i64::from(account.non_std_scu),
+            non_empty(&account.parent_guid),
+            account.code,
+            account.description,
+            i64::from(account.hidden),
+            i64::from(account.placeholder),
+        ],
+    )
+}
+
+/// `GnuCash` mirrors the placeholder flag into a string slot, keep both in sync.
+fn write_placeholder_slot(
+    conn: &Connection,
+    guid: &str,
+    placeholder: bool,
+) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "DELETE FROM slots WHERE obj_guid = ?1 AND name = 'placeholder'",
+        [guid],
+    )?;
+    if placeholder {
+        conn.execute(
+            r"
+            INSERT INTO slots (obj_guid, name, slot_type, int64_val, string_val, timespec_val, numeric_val_num, numeric_val_denom)
+            VALUES (?1, 'placeholder', 4, 0, 'true', '1970-01-01 00:00:00', 0, 1)
+            ",
+            [guid],
+        )?;
+    }
+    Ok(())
+}
+
+/// Inserts an account row with its placeholder slot; the caller owns the surrounding
+/// database transaction.
+pub(crate) fn insert(conn: &Connection, account: &AccountRecord) -> Result<(), rusqlite::Error> {
+    execute(conn, INS, account)
+        .inspect_err(|e| tracing::error!("failed to insert account: {e}"))?;
+    write_placeholder_slot(conn, &account.guid, account.placeholder)
+        .inspect_err(|e| tracing::error!("failed to write placeholder slot: {e}"))
+}
+
+pub(crate) fn update(conn: &Connection, account: &AccountRecord) -> Result<(), Error> {
+    let count = execute(conn, UPD, account)
+        .inspect_err(|e| tracing::error!("failed to update account: {e}"))?;
+    if count == 0 {
+        tracing::error!("account not found");
+        return Err(Error::GuidNotFound {
+            model: "Account".to_string(),
+            guid: account.guid.clone(),
+        });
+    }
+    write_placeholder_slot(conn, &account.guid, account.placeholder)
+        .inspect_err(|e| tracing::error!("failed to write placeholder slot: {e}"))?;
+    Ok(())
+}
+
+pub(crate) fn delete(conn: &Connection, guid: &str) -> Result<(), Error> {
+    for (table, column, what) in crate::query::ACCOUNT_REFERENCES {
+        let used = conn
+            .prepare_cached(&format!("SELECT 1 FROM {table} WHERE {column} = ?1"))?
+            .exists([guid])
+            .inspect_err(|e| tracing::error!("failed to check account {what}: {e}"))?;
+        if used {
+            return Err(crate::query::account_in_use(guid, what));
+        }
+    }
+    let count = conn
+        .execute("DELETE FROM accounts WHERE guid = ?1", [guid])
+        .inspect_err(|e| tracing::error!("failed to delete account: {e}"))?;
+    if count == 0 {
+        tracing::error!("account not found");
+        return Err(Error::GuidNotFound {
+            model: "Account".to_string(),
+            guid: guid.to_string(),
+        });
+    }
+    super::slot::delete(conn, guid)
+        .inspect_err(|e| tracing::error!("failed to delete account slots: {e}"))?;
+    Ok(())
+}
+
+impl AccountMutQ for SQLiteQuery {
+    #[instrument(skip(self, account), fields(account_guid = %account.guid))]
+    async fn insert(&self, account: &AccountRecord) -> Result<(), Error> {
+        let pool = self.writable_pool()?;
+        let force = self.ignore_lock;
+        let account = account.clone();
+
+        spawn_blocking(move || {
+            with_lock(&pool, force, || {
+                tracing::debug!("inserting account into sqlite");
+
+                let mut conn = pool.get()?;
+                let tx = conn
+                    .transaction()
+                    .inspect_err(|e| tracing::error!("failed to begin transaction: {e}"))?;
+
+                insert(&tx, &account)?;
+
+                tx.commit()
+                    .inspect_err(|e| tracing::error!("failed to commit transaction: {e}"))?;
+
+                tracing::debug!("account inserted into sqlite");
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error in spawn_blocking: {e}")))?
+    }
+
+    #[instrument(skip(self, account), fields(account_guid = %account.guid))]
+    async fn update(&self, account: &AccountRecord) -> Result<(), Error> {
+        let pool = self.writable_pool()?;
+        let force = self.ignore_lock;
+        let account = account.clone();
+
+        spawn_blocking(move || {
+            with_lock(&pool, force, || {
+                tracing::debug!("updating account in sqlite");
+
+                let mut conn = pool.get()?;
+                let tx = conn
+                    .transaction()
+                    .inspect_err(|e| tracing::error!("failed to begin transaction: {e}"))?;
+
+                update(&tx, &account)?;
+
+                tx.commit()
+                    .inspect_err(|e| tracing::error!("failed to commit transaction: {e}"))?;
+
+                tracing::debug!("account updated in sqlite");
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error in spawn_blocking: {e}")))?
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, guid: &str) -> Result<(), Error> {
+        let pool = self.writable_pool()?;
+        let force = self.ignore_lock;
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            with_lock(&pool, force, || {
+                tracing::debug!("deleting account from sqlite");
+
+                let mut conn = pool.get()?;
+                // takes the write lock before the reference checks
+                let tx = conn
+                    .transaction_with_behavior(TransactionBehavior::Immediate)
+                    .inspect_err(|e| tracing::error!("failed to begin transaction: {e}"))?;
+
+                delete(&tx, &guid_owned)?;
+
+                tx.commit()
+                    .inspect_err(|e| tracing::error!("failed to commit transaction: {e}"))?;
+
+                tracing::debug!("account deleted from sqlite");
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error in spawn_blocking: {e}")))?
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
     use test_log::test;
     use tokio::sync::OnceCell;
 
+    use crate::query::sqlite::tests::{setup_with, setup_writable};
+    use crate::query::tests::fixtures::{BUDGET, LOT};
+
     use super::*;
 
     #[cfg(feature = "schema")]
@@ -326,7 +545,7 @@ mod tests {
 
         assert_eq!(result.guid(), "fcd795021c976ba75621ec39e75f6214");
         assert_eq!(result.name(), "Asset");
-        assert_eq!(result.account_type(), "ASSET");
+        assert_eq!(result.account_type(), AccountType::Asset);
         assert_eq!(result.commodity_guid(), "346629655191dcf59a7e2c2a85b70f69");
         assert_eq!(result.commodity_scu(), 100);
         assert_eq!(result.non_std_scu(), false);
@@ -389,4 +608,126 @@ mod tests {
         let result = query.contains_name_ignore_case("AS").await.unwrap();
         assert_eq!(result.len(), 3);
     }
+
+    fn record() -> AccountRecord {
+        AccountRecord {
+            guid: "0123456789abcdef0123456789abcdef".to_string(),
+            name: "Wallet".to_string(),
+            account_type: "CASH".to_string(),
+            commodity_guid: "346629655191dcf59a7e2c2a85b70f69".to_string(),
+            commodity_scu: 100,
+            non_std_scu: false,
+            parent_guid: "3bc319753945b6dba3e1928abed49e35".to_string(),
+            code: "101".to_string(),
+            description: "pocket money".to_string(),
+            hidden: false,
+            placeholder: true,
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn test_insert() {
+        let (_dir, query) = setup_writable();
+        let record = record();
+        AccountMutQ::insert(&query, &record).await.unwrap();
+
+        let result = query.guid(&record.guid).await.unwrap().unwrap();
+        assert_eq!(result.name(), "Wallet");
+        assert_eq!(result.account_type(), AccountType::Cash);
+        assert_eq!(result.parent_guid(), "3bc319753945b6dba3e1928abed49e35");
+        assert_eq!(result.code(), "101");
+        assert_eq!(result.placeholder(), true);
+        assert_eq!(
+            query
+                .parent("3bc319753945b6dba3e1928abed49e35")
+                .await
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_update() {
+        let (_dir, query) = setup_writable();
+        let mut record = record();
+        AccountMutQ::insert(&query, &record).await.unwrap();
+
+        record.name = "Purse".to_string();
+        record.hidden = true;
+        record.placeholder = false;
+        AccountMutQ::update(&query, &record).await.unwrap();
+
+        let result = query.guid(&record.guid).await.unwrap().unwrap();
+        assert_eq!(result.name(), "Purse");
+        assert_eq!(result.hidden(), true);
+        assert_eq!(result.placeholder(), false);
+    }
+
+    #[test(tokio::test)]
+    async fn test_update_missing() {
+        let (_dir, query) = setup_writable();
+        let result = AccountMutQ::update(&query, &record()).await;
+        assert!(matches!(result, Err(Error::GuidNotFound { .. })));
+    }
+
+    #[test(tokio::test)]
+    async fn test_delete() {
+        let (_dir, query) = setup_writable();
+        let record = record();
+        AccountMutQ::insert(&query, &record).await.unwrap();
+        AccountMutQ::delete(&query, &record.guid).await.unwrap();
+
+        assert!(query.guid(&record.guid).await.unwrap().is_none());
+        assert!(matches!(
+            AccountMutQ::delete(&query, &record.guid).await,
+            Err(Error::GuidNotFound { .. })
+        ));
+    }
+
+    #[test(tokio::test)]
+    async fn test_delete_nested_slots() {
+        let (_dir, query) = setup_with(&["
+            INSERT INTO slots (obj_guid, name, slot_type, guid_val) VALUES
+            ('0123456789abcdef0123456789abcdef', 'reconcile-info', 9, 'frame1'),
+            ('frame1', 'reconcile-info/last-date', 9, 'frame2');
+            INSERT INTO slots (obj_guid, name, slot_type, int64_val) VALUES
+            ('frame2', 'reconcile-info/last-date/day', 1, 7);
+            "]);
+        let record = record();
+        AccountMutQ::insert(&query, &record).await.unwrap();
+        AccountMutQ::delete(&query, &record.guid).await.unwrap();
+
+        let conn = query.pool.get().unwrap();
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM slots WHERE obj_guid IN (?1, 'frame1', 'frame2')",
+                [&record.guid],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[test(tokio::test)]
+    async fn test_delete_referenced() {
+        let (_dir, query) = setup_with(&[
+            LOT,
+            BUDGET,
+            "DELETE FROM splits WHERE account_guid IN
+                ('1c089803052e85f5c6d8e786057dbaee', 'af88d386d44b14acf244362b85ccaf4c');",
+        ]);
+        for (guid, what) in [
+            ("fcd795021c976ba75621ec39e75f6214", "children"),
+            ("93fc043c3062aaa1297b30e543d2cd0d", "splits"),
+            ("1c089803052e85f5c6d8e786057dbaee", "lots"),
+            ("af88d386d44b14acf244362b85ccaf4c", "budget amounts"),
+        ] {
+            let Err(Error::Invalid { reason, .. }) = AccountMutQ::delete(&query, guid).await else {
+                panic!("{guid} should not be deleted");
+            };
+            assert_eq!(reason, format!("account {guid} still has {what}"));
+            assert!(query.guid(guid).await.unwrap().is_some());
+        }
+    }
 }
@@ -0,0 +1,215 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use rusqlite::Row;
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::query::{BillTermQ, BillTermT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct BillTerm {
+    pub guid: String,
+    pub name: String,
+    pub description: String,
+    pub refcount: i64,
+    pub invisible: i64,
+    pub parent: Option<String>,
+    pub r#type: String,
+    pub duedays: Option<i64>,
+    pub discountdays: Option<i64>,
+    pub discount_num: Option<i64>,
+    pub discount_denom: Option<i64>,
+    pub cutoff: Option<i64>,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for BillTerm {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            guid: row.get(0)?,
+            name: row.get(1)?,
+            description: row.get(2)?,
+            refcount: row.get(3)?,
+            invisible: row.get(4)?,
+            parent: row.get(5)?,
+            r#type: row.get(6)?,
+            duedays: row.get(7)?,
+            discountdays: row.get(8)?,
+            discount_num: row.get(9)?,
+            discount_denom: row.get(10)?,
+            cutoff: row.get(11)?,
+        })
+    }
+}
+
+impl BillTermT for BillTerm {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn description(&self) -> &str {
+        &self.description
+    }
+    fn refcount(&self) -> i64 {
+        self.refcount
+    }
+    fn invisible(&self) -> bool {
+        self.invisible != 0
+    }
+    fn parent_guid(&self) -> &str {
+        self.parent.as_deref().unwrap_or_default()
+    }
+    fn r#type(&self) -> &str {
+        &self.r#type
+    }
+    fn due_days(&self) -> i64 {
+        self.duedays.unwrap_or(0)
+    }
+    fn discount_days(&self) -> i64 {
+        self.discountdays.unwrap_or(0)
+    }
+    fn discount_num(&self) -> i64 {
+        self.discount_num.unwrap_or(0)
+    }
+    fn discount_denom(&self) -> i64 {
+        self.discount_denom.unwrap_or(1)
+    }
+    fn cutoff(&self) -> i64 {
+        self.cutoff.unwrap_or(0)
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+name,
+description,
+refcount,
+invisible,
+parent,
+type,
+duedays,
+discountdays,
+discount_num,
+discount_denom,
+cutoff
+FROM billterms
+";
+
+impl BillTermQ for SQLiteQuery {
+    type Item = BillTerm;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching all bill terms from sqlite");
+
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "bill terms fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching bill term by guid from sqlite");
+            let conn = pool.get()?;
+
+            let sql = format!("{SEL}\nWHERE guid = ?");
+            let mut stmt = conn
+                .prepare_cached(&sql)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let result = stmt.query_row([guid_owned], |row| Self::Item::try_from(row));
+
+            match result {
+                Ok(item) => Ok(Some(item)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => {
+                    tracing::error!("failed to fetch row: {e}");
+                    Err(Error::from(e))
+                }
+            }
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::sqlite::tests::setup_with;
+    use crate::query::tests::fixtures::{BUSINESS, INVOICES, TERMS};
+
+    #[test(tokio::test)]
+    async fn test_days() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES, TERMS]);
+        let result = BillTermQ::guid(&query, "9b0c1d2e3f405162738495a6b7c8d901")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.name(), "Net 30");
+        assert_eq!(result.description(), "2% within 10 days");
+        assert_eq!(result.refcount(), 2);
+        assert_eq!(result.invisible(), false);
+        assert_eq!(result.parent_guid(), "");
+        assert_eq!(result.r#type(), "GNC_TERM_TYPE_DAYS");
+        assert_eq!(result.due_days(), 30);
+        assert_eq!(result.discount_days(), 10);
+        assert_eq!(result.discount_num(), 2);
+        assert_eq!(result.discount_denom(), 1);
+        assert_eq!(result.cutoff(), 0);
+    }
+
+    #[test(tokio::test)]
+    async fn test_proximo() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES, TERMS]);
+        let result = BillTermQ::guid(&query, "9b0c1d2e3f405162738495a6b7c8d902")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.r#type(), "GNC_TERM_TYPE_PROXIMO");
+        assert_eq!(result.due_days(), 31);
+        assert_eq!(result.discount_days(), 0);
+        assert_eq!(result.discount_num(), 0);
+        assert_eq!(result.discount_denom(), 1);
+        assert_eq!(result.cutoff(), 25);
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES, TERMS]);
+        let result = BillTermQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 2);
+    }
+}
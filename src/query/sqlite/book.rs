@@ -0,0 +1,107 @@
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use rusqlite::Row;
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::query::{BookQ, BookT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Book {
+    pub(crate) guid: String,
+    pub(crate) root_account_guid: String,
+    pub(crate) root_template_guid: String,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for Book {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            guid: row.get(0)?,
+            root_account_guid: row.get(1)?,
+            root_template_guid: row.get(2)?,
+        })
+    }
+}
+
+impl BookT for Book {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn root_account_guid(&self) -> &str {
+        &self.root_account_guid
+    }
+    fn root_template_guid(&self) -> &str {
+        &self.root_template_guid
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+root_account_guid,
+root_template_guid
+FROM books
+";
+
+impl BookQ for SQLiteQuery {
+    type Item = Book;
+
+    #[instrument(skip(self))]
+    async fn book(&self) -> Result<Self::Item, Error> {
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching book from sqlite");
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            match stmt.query_row([], |row| Self::Item::try_from(row)) {
+                Ok(item) => Ok(item),
+                Err(rusqlite::Error::QueryReturnedNoRows) => {
+                    tracing::error!("no row in books");
+                    Err(Error::NoBook("books table is empty".to_string()))
+                }
+                Err(e) => {
+                    tracing::error!("failed to fetch row: {e}");
+                    Err(Error::from(e))
+                }
+            }
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_book() {
+        let uri: &str = &format!(
+            "{}/tests/db/sqlite/complex_sample.gnucash",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let query = SQLiteQuery::new(uri).unwrap();
+        let result = query.book().await.unwrap();
+        assert_eq!(result.guid(), "7d4ef4044fd30f41d08914a8174c2f5b");
+        assert_eq!(
+            result.root_account_guid(),
+            "00622dda21937b29e494179de5013f82"
+        );
+        assert_eq!(
+            result.root_template_guid(),
+            "f6c0cd00ec04169a44f170181882adab"
+        );
+    }
+}
@@ -0,0 +1,303 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use rusqlite::{Connection, Row, params};
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::query::{BudgetAmountQ, BudgetAmountT, BudgetQ, BudgetT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Budget {
+    pub guid: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub num_periods: i64,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for Budget {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            guid: row.get(0)?,
+            name: row.get(1)?,
+            description: row.get(2)?,
+            num_periods: row.get(3)?,
+        })
+    }
+}
+
+impl BudgetT for Budget {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn description(&self) -> &str {
+        self.description.as_deref().unwrap_or_default()
+    }
+    fn num_periods(&self) -> i64 {
+        self.num_periods
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct BudgetAmount {
+    pub budget_guid: String,
+    pub account_guid: String,
+    pub period_num: i64,
+    pub amount_num: i64,
+    pub amount_denom: i64,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for BudgetAmount {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            budget_guid: row.get(0)?,
+            account_guid: row.get(1)?,
+            period_num: row.get(2)?,
+            amount_num: row.get(3)?,
+            amount_denom: row.get(4)?,
+        })
+    }
+}
+
+impl BudgetAmountT for BudgetAmount {
+    fn budget_guid(&self) -> &str {
+        &self.budget_guid
+    }
+    fn account_guid(&self) -> &str {
+        &self.account_guid
+    }
+    fn period_num(&self) -> i64 {
+        self.period_num
+    }
+    fn amount_num(&self) -> i64 {
+        self.amount_num
+    }
+    fn amount_denom(&self) -> i64 {
+        self.amount_denom
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+name,
+description,
+num_periods
+FROM budgets
+";
+
+const SEL_AMOUNT: &str = r"
+SELECT
+budget_guid,
+account_guid,
+period_num,
+amount_num,
+amount_denom
+FROM budget_amounts
+WHERE budget_guid = ?
+ORDER BY account_guid, period_num
+";
+
+const INS: &str = r"
+INSERT INTO budgets (
+guid,
+name,
+description,
+num_periods
+) VALUES (?1, ?2, ?3, ?4)
+";
+
+const INS_AMOUNT: &str = r"
+INSERT INTO budget_amounts (
+budget_guid,
+account_guid,
+period_num,
+amount_num,
+amount_denom
+) VALUES (?1, ?2, ?3, ?4, ?5)
+";
+
+/// Inserts a budget row; the caller owns the surrounding database transaction.
+pub(crate) fn insert(conn: &Connection, budget: &Budget) -> Result<(), rusqlite::Error> {
+    let mut stmt = conn
+        .prepare_cached(INS)
+        .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+    stmt.execute(params![
+        budget.guid,
+        budget.name,
+        budget.description,
+        budget.num_periods,
+    ])?;
+
+    Ok(())
+}
+
+/// Inserts a budget amount row; the caller owns the surrounding database transaction.
+pub(crate) fn insert_amount(
+    conn: &Connection,
+    amount: &BudgetAmount,
+) -> Result<(), rusqlite::Error> {
+    let mut stmt = conn
+        .prepare_cached(INS_AMOUNT)
+        .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+    stmt.execute(params![
+        amount.budget_guid,
+        amount.account_guid,
+        amount.period_num,
+        amount.amount_num,
+        amount.amount_denom,
+    ])?;
+
+    Ok(())
+}
+
+impl BudgetQ for SQLiteQuery {
+    type Item = Budget;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching all budgets from sqlite");
+
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "budgets fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching budget by guid from sqlite");
+            let conn = pool.get()?;
+
+            let sql = format!("{SEL}\nWHERE guid = ?");
+            let mut stmt = conn
+                .prepare_cached(&sql)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let result = stmt.query_row([guid_owned], |row| Self::Item::try_from(row));
+
+            match result {
+                Ok(item) => Ok(Some(item)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => {
+                    tracing::error!("failed to fetch row: {e}");
+                    Err(Error::from(e))
+                }
+            }
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+impl BudgetAmountQ for SQLiteQuery {
+    type Item = BudgetAmount;
+
+    #[instrument(skip(self))]
+    async fn budget(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching budget amounts by budget_guid from sqlite");
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL_AMOUNT)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([guid_owned], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "budget amounts fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use crate::query::sqlite::tests::setup_with;
+    use crate::query::tests::fixtures::BUDGET;
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_budget() {
+        let (_dir, query) = setup_with(&[BUDGET]);
+        let result = BudgetQ::guid(&query, "5f1a0c7d1e9b4b6f8a2d3c4e5f607182")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.guid(), "5f1a0c7d1e9b4b6f8a2d3c4e5f607182");
+        assert_eq!(result.name(), "Household");
+        assert_eq!(result.description(), "monthly spending");
+        assert_eq!(result.num_periods(), 12);
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (_dir, query) = setup_with(&[BUDGET]);
+        let result = BudgetQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test(tokio::test)]
+    async fn test_guid() {
+        let (_dir, query) = setup_with(&[BUDGET]);
+        let result = BudgetQ::guid(&query, "unknown").await.unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[test(tokio::test)]
+    async fn test_amounts() {
+        let (_dir, query) = setup_with(&[BUDGET]);
+        let result = BudgetAmountQ::budget(&query, "5f1a0c7d1e9b4b6f8a2d3c4e5f607182")
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].account_guid(), "6bbc8f20544452cac1637fb9a9b851bb");
+        assert_eq!(result[1].period_num(), 1);
+        assert_eq!(result[1].amount_num(), 5000);
+        assert_eq!(result[1].amount_denom(), 100);
+    }
+}
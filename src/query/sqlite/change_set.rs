@@ -0,0 +1,196 @@
+use chrono::NaiveDateTime;
+use rusqlite::{Connection, TransactionBehavior};
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use super::lock::with_lock;
+use crate::error::Error;
+use crate::query::{AccountRecord, Change, ChangeSetQ, StoredState};
+
+/// Reads the rows a change set is validated against; the caller owns the surrounding
+/// database transaction.
+fn stored(conn: &Connection) -> Result<StoredState, rusqlite::Error> {
+    let accounts = conn
+        .prepare(super::account::SEL)?
+        .query_map([], |row| super::account::Account::try_from(row))?
+        .map(|x| x.map(|x| AccountRecord::from_item(&x)))
+        .collect::<Result<_, _>>()?;
+    let used_accounts = conn
+        .prepare("SELECT DISTINCT account_guid FROM splits")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    let prices = conn
+        .prepare("SELECT commodity_guid, currency_guid, date FROM prices")?
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get::<_, NaiveDateTime>(2)?.date(),
+            ))
+        })?
+        .collect::<Result<_, _>>()?;
+
+    Ok(StoredState {
+        accounts,
+        used_accounts,
+        prices,
+    })
+}
+
+/// Applies a single change; the caller owns the surrounding database transaction.
+fn apply(conn: &Connection, change: &Change) -> Result<(), Error> {
+    match change {
+        Change::InsertAccount(account) => super::account::insert(conn, account)?,
+        Change::UpdateAccount(account) => super::account::update(conn, account)?,
+        Change::DeleteAccount(guid) => super::account::delete(conn, guid)?,
+        Change::InsertTransaction(transaction, splits) => {
+            super::transaction::insert(conn, transaction)
+                .inspect_err(|e| tracing::error!("failed to insert transaction: {e}"))?;
+            for split in splits {
+                super::split::insert(conn, split)
+                    .inspect_err(|e| tracing::error!("failed to insert split: {e}"))?;
+            }
+        }
+        Change::InsertPrice(price) => super::price::insert(conn, price)
+            .inspect_err(|e| tracing::error!("failed to insert price: {e}"))?,
+    }
+    Ok(())
+}
+
+impl ChangeSetQ for SQLiteQuery {
+    #[instrument(skip(self, changes, check), fields(count = changes.len()))]
+    async fn apply(
+        &self,
+        changes: &[Change],
+        check: impl FnOnce(&StoredState) -> Result<(), Error> + Send + 'static,
+    ) -> Result<(), Error> {
+        let pool = self.writable_pool()?;
+        let force = self.ignore_lock;
+        let changes = changes.to_vec();
+
+        spawn_blocking(move || {
+            with_lock(&pool, force, || {
+                tracing::debug!("applying change set to sqlite");
+
+                let mut conn = pool.get()?;
+                // taking the write lock up front keeps other writers out until the commit
+                let tx = conn
+                    .transaction_with_behavior(TransactionBehavior::Immediate)
+                    .inspect_err(|e| tracing::error!("failed to begin transaction: {e}"))?;
+
+                check(&stored(&tx)?)?;
+                for change in &changes {
+                    apply(&tx, change)?;
+                }
+
+                tx.commit()
+                    .inspect_err(|e| tracing::error!("failed to commit transaction: {e}"))?;
+
+                tracing::debug!(count = changes.len(), "change set applied to sqlite");
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error in spawn_blocking: {e}")))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use crate::query::sqlite::tests::setup_writable;
+
+    use super::*;
+    use crate::query::{AccountQ, AccountRecord, AccountT};
+
+    fn record(guid: &str, name: &str) -> AccountRecord {
+        AccountRecord {
+            guid: guid.to_string(),
+            name: name.to_string(),
+            account_type: "ASSET".to_string(),
+            commodity_guid: "346629655191dcf59a7e2c2a85b70f69".to_string(),
+            commodity_scu: 100,
+            non_std_scu: false,
+            parent_guid: "00622dda21937b29e494179de5013f82".to_string(),
+            code: String::new(),
+            description: String::new(),
+            hidden: false,
+            placeholder: false,
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn test_apply() {
+        let (_dir, query) = setup_writable();
+        let mut renamed = record("11111111111111111111111111111111", "Renamed");
+        renamed.placeholder = true;
+        let changes = [
+            Change::InsertAccount(record("11111111111111111111111111111111", "New")),
+            Change::UpdateAccount(renamed),
+            Change::InsertAccount(record("22222222222222222222222222222222", "Gone")),
+            Change::DeleteAccount("22222222222222222222222222222222".to_string()),
+        ];
+
+        query.apply(&changes, |_| Ok(())).await.unwrap();
+
+        let result = AccountQ::guid(&query, "11111111111111111111111111111111")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.name(), "Renamed");
+        assert!(result.placeholder());
+        let result = AccountQ::guid(&query, "22222222222222222222222222222222")
+            .await
+            .unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test(tokio::test)]
+    async fn test_apply_rollback() {
+        let (_dir, query) = setup_writable();
+        let changes = [
+            Change::InsertAccount(record("11111111111111111111111111111111", "New")),
+            Change::DeleteAccount("unknown".to_string()),
+        ];
+
+        let result = query.apply(&changes, |_| Ok(())).await;
+        assert!(matches!(result, Err(Error::GuidNotFound { .. })));
+        let result = AccountQ::guid(&query, "11111111111111111111111111111111")
+            .await
+            .unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test(tokio::test)]
+    async fn test_apply_check() {
+        let (_dir, query) = setup_writable();
+        let changes = [Change::InsertAccount(record(
+            "11111111111111111111111111111111",
+            "New",
+        ))];
+
+        let result = query
+            .apply(&changes, |stored| {
+                assert!(
+                    stored
+                        .accounts
+                        .iter()
+                        .any(|x| x.guid == "00622dda21937b29e494179de5013f82")
+                );
+                assert!(!stored.used_accounts.is_empty());
+                Err(Error::Invalid {
+                    model: "Account".to_string(),
+                    reason: "rejected".to_string(),
+                })
+            })
+            .await;
+        assert!(matches!(result, Err(Error::Invalid { .. })));
+        let result = AccountQ::guid(&query, "11111111111111111111111111111111")
+            .await
+            .unwrap();
+        assert!(result.is_none());
+    }
+}
@@ -1,12 +1,12 @@
 // ref: https://piecash.readthedocs.io/en/master/object_model.html
 // ref: https://wiki.gnucash.org/wiki/SQL
-use rusqlite::Row;
+use rusqlite::{Connection, Row, params};
 use tokio::task::spawn_blocking;
 use tracing::instrument;
 
 use super::SQLiteQuery;
 use crate::error::Error;
-use crate::query::{CommodityQ, CommodityT};
+use crate::query::{CommodityQ, CommodityRecord, CommodityT};
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
 pub struct Commodity {
@@ -171,6 +171,44 @@ impl CommodityQ for SQLiteQuery {
     }
 }
 
+const INS: &str = r"
+INSERT INTO commodities (
+guid,
+namespace,
+mnemonic,
+fullname,
+cusip,
+fraction,
+quote_flag,
+quote_source,
+quote_tz
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+";
+
+/// Inserts a commodity row; the caller owns the surrounding database transaction.
+pub(crate) fn insert(
+    conn: &Connection,
+    commodity: &CommodityRecord,
+) -> Result<(), rusqlite::Error> {
+    let mut stmt = conn
+        .prepare_cached(INS)
+        .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+    stmt.execute(params![
+        commodity.guid,
+        commodity.namespace,
+        commodity.mnemonic,
+        commodity.fullname,
+        commodity.cusip,
+        commodity.fraction,
+        i64::from(commodity.quote_flag),
+        commodity.quote_source,
+        commodity.quote_tz,
+    ])?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
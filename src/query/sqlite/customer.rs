@@ -0,0 +1,302 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use rusqlite::Row;
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::model::Address;
+use crate::query::{CustomerQ, CustomerT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Customer {
+    pub guid: String,
+    pub name: String,
+    pub id: String,
+    pub notes: String,
+    pub active: i64,
+    pub discount_num: i64,
+    pub discount_denom: i64,
+    pub credit_num: i64,
+    pub credit_denom: i64,
+    pub currency: String,
+    pub tax_override: i64,
+    pub addr_name: Option<String>,
+    pub addr_addr1: Option<String>,
+    pub addr_addr2: Option<String>,
+    pub addr_addr3: Option<String>,
+    pub addr_addr4: Option<String>,
+    pub addr_phone: Option<String>,
+    pub addr_fax: Option<String>,
+    pub addr_email: Option<String>,
+    pub shipaddr_name: Option<String>,
+    pub shipaddr_addr1: Option<String>,
+    pub shipaddr_addr2: Option<String>,
+    pub shipaddr_addr3: Option<String>,
+    pub shipaddr_addr4: Option<String>,
+    pub shipaddr_phone: Option<String>,
+    pub shipaddr_fax: Option<String>,
+    pub shipaddr_email: Option<String>,
+    pub terms: Option<String>,
+    pub tax_included: Option<i64>,
+    pub taxtable: Option<String>,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for Customer {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            guid: row.get(0)?,
+            name: row.get(1)?,
+            id: row.get(2)?,
+            notes: row.get(3)?,
+            active: row.get(4)?,
+            discount_num: row.get(5)?,
+            discount_denom: row.get(6)?,
+            credit_num: row.get(7)?,
+            credit_denom: row.get(8)?,
+            currency: row.get(9)?,
+            tax_override: row.get(10)?,
+            addr_name: row.get(11)?,
+            addr_addr1: row.get(12)?,
+            addr_addr2: row.get(13)?,
+            addr_addr3: row.get(14)?,
+            addr_addr4: row.get(15)?,
+            addr_phone: row.get(16)?,
+            addr_fax: row.get(17)?,
+            addr_email: row.get(18)?,
+            shipaddr_name: row.get(19)?,
+            shipaddr_addr1: row.get(20)?,
+            shipaddr_addr2: row.get(21)?,
+            shipaddr_addr3: row.get(22)?,
+            shipaddr_addr4: row.get(23)?,
+            shipaddr_phone: row.get(24)?,
+            shipaddr_fax: row.get(25)?,
+            shipaddr_email: row.get(26)?,
+            terms: row.get(27)?,
+            tax_included: row.get(28)?,
+            taxtable: row.get(29)?,
+        })
+    }
+}
+
+impl CustomerT for Customer {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn notes(&self) -> &str {
+        &self.notes
+    }
+    fn active(&self) -> bool {
+        self.active != 0
+    }
+    fn discount_num(&self) -> i64 {
+        self.discount_num
+    }
+    fn discount_denom(&self) -> i64 {
+        self.discount_denom
+    }
+    fn credit_num(&self) -> i64 {
+        self.credit_num
+    }
+    fn credit_denom(&self) -> i64 {
+        self.credit_denom
+    }
+    fn currency_guid(&self) -> &str {
+        &self.currency
+    }
+    fn tax_override(&self) -> bool {
+        self.tax_override != 0
+    }
+    fn addr(&self) -> Address {
+        Address {
+            name: self.addr_name.clone().unwrap_or_default(),
+            addr1: self.addr_addr1.clone().unwrap_or_default(),
+            addr2: self.addr_addr2.clone().unwrap_or_default(),
+            addr3: self.addr_addr3.clone().unwrap_or_default(),
+            addr4: self.addr_addr4.clone().unwrap_or_default(),
+            phone: self.addr_phone.clone().unwrap_or_default(),
+            fax: self.addr_fax.clone().unwrap_or_default(),
+            email: self.addr_email.clone().unwrap_or_default(),
+        }
+    }
+    fn shipaddr(&self) -> Address {
+        Address {
+            name: self.shipaddr_name.clone().unwrap_or_default(),
+            addr1: self.shipaddr_addr1.clone().unwrap_or_default(),
+            addr2: self.shipaddr_addr2.clone().unwrap_or_default(),
+            addr3: self.shipaddr_addr3.clone().unwrap_or_default(),
+            addr4: self.shipaddr_addr4.clone().unwrap_or_default(),
+            phone: self.shipaddr_phone.clone().unwrap_or_default(),
+            fax: self.shipaddr_fax.clone().unwrap_or_default(),
+            email: self.shipaddr_email.clone().unwrap_or_default(),
+        }
+    }
+    fn terms_guid(&self) -> &str {
+        self.terms.as_deref().unwrap_or_default()
+    }
+    fn tax_included(&self) -> &str {
+        // stored as GncTaxIncluded: 1 yes, 2 no, 3 use global
+        match self.tax_included {
+            Some(1) => "YES",
+            Some(2) => "NO",
+            _ => "USEGLOBAL",
+        }
+    }
+    fn taxtable_guid(&self) -> &str {
+        self.taxtable.as_deref().unwrap_or_default()
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+name,
+id,
+notes,
+active,
+discount_num,
+discount_denom,
+credit_num,
+credit_denom,
+currency,
+tax_override,
+addr_name,
+addr_addr1,
+addr_addr2,
+addr_addr3,
+addr_addr4,
+addr_phone,
+addr_fax,
+addr_email,
+shipaddr_name,
+shipaddr_addr1,
+shipaddr_addr2,
+shipaddr_addr3,
+shipaddr_addr4,
+shipaddr_phone,
+shipaddr_fax,
+shipaddr_email,
+terms,
+tax_included,
+taxtable
+FROM customers
+";
+
+impl CustomerQ for SQLiteQuery {
+    type Item = Customer;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching all customers from sqlite");
+
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "customers fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching customer by guid from sqlite");
+            let conn = pool.get()?;
+
+            let sql = format!("{SEL}\nWHERE guid = ?");
+            let mut stmt = conn
+                .prepare_cached(&sql)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let result = stmt.query_row([guid_owned], |row| Self::Item::try_from(row));
+
+            match result {
+                Ok(item) => Ok(Some(item)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => {
+                    tracing::error!("failed to fetch row: {e}");
+                    Err(Error::from(e))
+                }
+            }
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use crate::query::sqlite::tests::setup_with;
+    use crate::query::tests::fixtures::BUSINESS;
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_customer() {
+        let (_dir, query) = setup_with(&[BUSINESS]);
+        let result = CustomerQ::guid(&query, "d4e5f6a7b8c9d0e1f2a3b4c5d6e7f801")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.name(), "Acme Corp");
+        assert_eq!(result.id(), "000001");
+        assert_eq!(result.notes(), "pays late");
+        assert_eq!(result.active(), true);
+        assert_eq!(result.discount_num(), 500);
+        assert_eq!(result.discount_denom(), 100);
+        assert_eq!(result.credit_num(), 100_000);
+        assert_eq!(result.credit_denom(), 100);
+        assert_eq!(result.currency_guid(), "346629655191dcf59a7e2c2a85b70f69");
+        assert_eq!(result.tax_override(), false);
+        assert_eq!(result.addr().addr2, "Springfield");
+        assert_eq!(result.addr().email, "billing@acme.example");
+        assert_eq!(result.shipaddr().name, "Acme Warehouse");
+        assert_eq!(result.terms_guid(), "");
+        assert_eq!(result.tax_included(), "USEGLOBAL");
+        assert_eq!(result.taxtable_guid(), "");
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (_dir, query) = setup_with(&[BUSINESS]);
+        let result = CustomerQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test(tokio::test)]
+    async fn test_guid() {
+        let (_dir, query) = setup_with(&[BUSINESS]);
+        let result = CustomerQ::guid(&query, "unknown").await.unwrap();
+        assert_eq!(result, None);
+    }
+}
@@ -0,0 +1,243 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use rusqlite::Row;
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::model::Address;
+use crate::query::{EmployeeQ, EmployeeT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Employee {
+    pub guid: String,
+    pub username: String,
+    pub id: String,
+    pub language: String,
+    pub acl: String,
+    pub active: i64,
+    pub currency: String,
+    pub ccard_guid: Option<String>,
+    pub workday_num: i64,
+    pub workday_denom: i64,
+    pub rate_num: i64,
+    pub rate_denom: i64,
+    pub addr_name: Option<String>,
+    pub addr_addr1: Option<String>,
+    pub addr_addr2: Option<String>,
+    pub addr_addr3: Option<String>,
+    pub addr_addr4: Option<String>,
+    pub addr_phone: Option<String>,
+    pub addr_fax: Option<String>,
+    pub addr_email: Option<String>,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for Employee {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            guid: row.get(0)?,
+            username: row.get(1)?,
+            id: row.get(2)?,
+            language: row.get(3)?,
+            acl: row.get(4)?,
+            active: row.get(5)?,
+            currency: row.get(6)?,
+            ccard_guid: row.get(7)?,
+            workday_num: row.get(8)?,
+            workday_denom: row.get(9)?,
+            rate_num: row.get(10)?,
+            rate_denom: row.get(11)?,
+            addr_name: row.get(12)?,
+            addr_addr1: row.get(13)?,
+            addr_addr2: row.get(14)?,
+            addr_addr3: row.get(15)?,
+            addr_addr4: row.get(16)?,
+            addr_phone: row.get(17)?,
+            addr_fax: row.get(18)?,
+            addr_email: row.get(19)?,
+        })
+    }
+}
+
+impl EmployeeT for Employee {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn username(&self) -> &str {
+        &self.username
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn language(&self) -> &str {
+        &self.language
+    }
+    fn acl(&self) -> &str {
+        &self.acl
+    }
+    fn active(&self) -> bool {
+        self.active != 0
+    }
+    fn currency_guid(&self) -> &str {
+        &self.currency
+    }
+    fn ccard_guid(&self) -> &str {
+        self.ccard_guid.as_deref().unwrap_or_default()
+    }
+    fn workday_num(&self) -> i64 {
+        self.workday_num
+    }
+    fn workday_denom(&self) -> i64 {
+        self.workday_denom
+    }
+    fn rate_num(&self) -> i64 {
+        self.rate_num
+    }
+    fn rate_denom(&self) -> i64 {
+        self.rate_denom
+    }
+    fn addr(&self) -> Address {
+        Address {
+            name: self.addr_name.clone().unwrap_or_default(),
+            addr1: self.addr_addr1.clone().unwrap_or_default(),
+            addr2: self.addr_addr2.clone().unwrap_or_default(),
+            addr3: self.addr_addr3.clone().unwrap_or_default(),
+            addr4: self.addr_addr4.clone().unwrap_or_default(),
+            phone: self.addr_phone.clone().unwrap_or_default(),
+            fax: self.addr_fax.clone().unwrap_or_default(),
+            email: self.addr_email.clone().unwrap_or_default(),
+        }
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+username,
+id,
+language,
+acl,
+active,
+currency,
+ccard_guid,
+workday_num,
+workday_denom,
+rate_num,
+rate_denom,
+addr_name,
+addr_addr1,
+addr_addr2,
+addr_addr3,
+addr_addr4,
+addr_phone,
+addr_fax,
+addr_email
+FROM employees
+";
+
+impl EmployeeQ for SQLiteQuery {
+    type Item = Employee;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching all employees from sqlite");
+
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "employees fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching employee by guid from sqlite");
+            let conn = pool.get()?;
+
+            let sql = format!("{SEL}\nWHERE guid = ?");
+            let mut stmt = conn
+                .prepare_cached(&sql)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let result = stmt.query_row([guid_owned], |row| Self::Item::try_from(row));
+
+            match result {
+                Ok(item) => Ok(Some(item)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => {
+                    tracing::error!("failed to fetch row: {e}");
+                    Err(Error::from(e))
+                }
+            }
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::sqlite::tests::setup_with;
+    use crate::query::tests::fixtures::BUSINESS;
+
+    #[test(tokio::test)]
+    async fn test_employee() {
+        let (_dir, query) = setup_with(&[BUSINESS]);
+        let result = EmployeeQ::guid(&query, "f6a7b8c9d0e1f2a3b4c5d6e7f8091a23")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.username(), "jdoe");
+        assert_eq!(result.id(), "000001");
+        assert_eq!(result.active(), true);
+        assert_eq!(result.currency_guid(), "346629655191dcf59a7e2c2a85b70f69");
+        assert_eq!(result.ccard_guid(), "");
+        assert_eq!(result.workday_num(), 8);
+        assert_eq!(result.workday_denom(), 1);
+        assert_eq!(result.rate_num(), 2500);
+        assert_eq!(result.rate_denom(), 100);
+        assert_eq!(result.addr().name, "Jane Doe");
+        assert_eq!(result.addr().email, "jane@example.com");
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (_dir, query) = setup_with(&[BUSINESS]);
+        let result = EmployeeQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test(tokio::test)]
+    async fn test_guid() {
+        let (_dir, query) = setup_with(&[BUSINESS]);
+        let result = EmployeeQ::guid(&query, "unknown").await.unwrap();
+        assert_eq!(result, None);
+    }
+}
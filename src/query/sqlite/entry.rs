@@ -0,0 +1,304 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use chrono::NaiveDateTime;
+use rusqlite::Row;
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::query::{EntryQ, EntryT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Entry {
+    pub guid: String,
+    pub date: NaiveDateTime,
+    pub date_entered: Option<NaiveDateTime>,
+    pub description: Option<String>,
+    pub action: Option<String>,
+    pub notes: Option<String>,
+    pub quantity_num: Option<i64>,
+    pub quantity_denom: Option<i64>,
+    pub i_acct: Option<String>,
+    pub i_price_num: Option<i64>,
+    pub i_price_denom: Option<i64>,
+    pub i_discount_num: Option<i64>,
+    pub i_discount_denom: Option<i64>,
+    pub invoice: Option<String>,
+    pub i_disc_type: Option<String>,
+    pub i_disc_how: Option<String>,
+    pub i_taxable: Option<i64>,
+    pub i_taxincluded: Option<i64>,
+    pub i_taxtable: Option<String>,
+    pub b_acct: Option<String>,
+    pub b_price_num: Option<i64>,
+    pub b_price_denom: Option<i64>,
+    pub bill: Option<String>,
+    pub b_taxable: Option<i64>,
+    pub b_taxincluded: Option<i64>,
+    pub b_taxtable: Option<String>,
+    pub b_paytype: Option<i64>,
+    pub billable: Option<i64>,
+    pub order_guid: Option<String>,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for Entry {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            guid: row.get(0)?,
+            date: row.get(1)?,
+            date_entered: row.get(2)?,
+            description: row.get(3)?,
+            action: row.get(4)?,
+            notes: row.get(5)?,
+            quantity_num: row.get(6)?,
+            quantity_denom: row.get(7)?,
+            i_acct: row.get(8)?,
+            i_price_num: row.get(9)?,
+            i_price_denom: row.get(10)?,
+            i_discount_num: row.get(11)?,
+            i_discount_denom: row.get(12)?,
+            invoice: row.get(13)?,
+            i_disc_type: row.get(14)?,
+            i_disc_how: row.get(15)?,
+            i_taxable: row.get(16)?,
+            i_taxincluded: row.get(17)?,
+            i_taxtable: row.get(18)?,
+            b_acct: row.get(19)?,
+            b_price_num: row.get(20)?,
+            b_price_denom: row.get(21)?,
+            bill: row.get(22)?,
+            b_taxable: row.get(23)?,
+            b_taxincluded: row.get(24)?,
+            b_taxtable: row.get(25)?,
+            b_paytype: row.get(26)?,
+            billable: row.get(27)?,
+            order_guid: row.get(28)?,
+        })
+    }
+}
+
+impl EntryT for Entry {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn date(&self) -> NaiveDateTime {
+        self.date
+    }
+    fn date_entered(&self) -> Option<NaiveDateTime> {
+        self.date_entered
+    }
+    fn description(&self) -> &str {
+        self.description.as_deref().unwrap_or_default()
+    }
+    fn action(&self) -> &str {
+        self.action.as_deref().unwrap_or_default()
+    }
+    fn notes(&self) -> &str {
+        self.notes.as_deref().unwrap_or_default()
+    }
+    fn quantity_num(&self) -> i64 {
+        self.quantity_num.unwrap_or(0)
+    }
+    fn quantity_denom(&self) -> i64 {
+        self.quantity_denom.unwrap_or(1)
+    }
+    fn i_acct_guid(&self) -> &str {
+        self.i_acct.as_deref().unwrap_or_default()
+    }
+    fn i_price_num(&self) -> i64 {
+        self.i_price_num.unwrap_or(0)
+    }
+    fn i_price_denom(&self) -> i64 {
+        self.i_price_denom.unwrap_or(1)
+    }
+    fn i_discount_num(&self) -> i64 {
+        self.i_discount_num.unwrap_or(0)
+    }
+    fn i_discount_denom(&self) -> i64 {
+        self.i_discount_denom.unwrap_or(1)
+    }
+    fn invoice_guid(&self) -> &str {
+        self.invoice.as_deref().unwrap_or_default()
+    }
+    fn i_disc_type(&self) -> &str {
+        self.i_disc_type.as_deref().unwrap_or_default()
+    }
+    fn i_disc_how(&self) -> &str {
+        self.i_disc_how.as_deref().unwrap_or_default()
+    }
+    fn i_taxable(&self) -> bool {
+        self.i_taxable.is_some_and(|x| x != 0)
+    }
+    fn i_taxincluded(&self) -> bool {
+        self.i_taxincluded.is_some_and(|x| x != 0)
+    }
+    fn i_taxtable_guid(&self) -> &str {
+        self.i_taxtable.as_deref().unwrap_or_default()
+    }
+    fn b_acct_guid(&self) -> &str {
+        self.b_acct.as_deref().unwrap_or_default()
+    }
+    fn b_price_num(&self) -> i64 {
+        self.b_price_num.unwrap_or(0)
+    }
+    fn b_price_denom(&self) -> i64 {
+        self.b_price_denom.unwrap_or(1)
+    }
+    fn bill_guid(&self) -> &str {
+        self.bill.as_deref().unwrap_or_default()
+    }
+    fn b_taxable(&self) -> bool {
+        self.b_taxable.is_some_and(|x| x != 0)
+    }
+    fn b_taxincluded(&self) -> bool {
+        self.b_taxincluded.is_some_and(|x| x != 0)
+    }
+    fn b_taxtable_guid(&self) -> &str {
+        self.b_taxtable.as_deref().unwrap_or_default()
+    }
+    fn b_paytype(&self) -> i64 {
+        self.b_paytype.unwrap_or(0)
+    }
+    fn billable(&self) -> bool {
+        self.billable.is_some_and(|x| x != 0)
+    }
+    fn order_guid(&self) -> &str {
+        self.order_guid.as_deref().unwrap_or_default()
+    }
+}
+
+const SEL_INVOICE: &str = r"
+SELECT
+guid,
+date,
+date_entered,
+description,
+action,
+notes,
+quantity_num,
+quantity_denom,
+i_acct,
+i_price_num,
+i_price_denom,
+i_discount_num,
+i_discount_denom,
+invoice,
+i_disc_type,
+i_disc_how,
+i_taxable,
+i_taxincluded,
+i_taxtable,
+b_acct,
+b_price_num,
+b_price_denom,
+bill,
+b_taxable,
+b_taxincluded,
+b_taxtable,
+b_paytype,
+billable,
+order_guid
+FROM entries
+WHERE invoice = ? OR bill = ?
+ORDER BY date, date_entered
+";
+
+impl EntryQ for SQLiteQuery {
+    type Item = Entry;
+
+    #[instrument(skip(self))]
+    async fn invoice(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching entries by invoice from sqlite");
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL_INVOICE)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows =
+                stmt.query_map([&guid_owned, &guid_owned], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "entries fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::sqlite::tests::setup_with;
+    use crate::query::tests::fixtures::{BUSINESS, INVOICES};
+
+    #[test(tokio::test)]
+    async fn test_invoice() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+        let result = EntryQ::invoice(&query, "3d4e5f60718293a4b5c6d7e8f90a1b01")
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 2);
+
+        let result = &result[0];
+        assert_eq!(result.guid(), "4e5f60718293a4b5c6d7e8f90a1b2c01");
+        assert_eq!(
+            result.date(),
+            NaiveDateTime::parse_from_str("2018-03-01 10:59:00", "%Y-%m-%d %H:%M:%S").unwrap()
+        );
+        assert_eq!(
+            result.date_entered(),
+            NaiveDateTime::parse_from_str("2018-03-01 11:00:00", "%Y-%m-%d %H:%M:%S").ok()
+        );
+        assert_eq!(result.description(), "Consulting");
+        assert_eq!(result.action(), "Hours");
+        assert_eq!(result.quantity_num(), 10);
+        assert_eq!(result.quantity_denom(), 1);
+        assert_eq!(result.i_acct_guid(), "6bbc8f20544452cac1637fb9a9b851bb");
+        assert_eq!(result.i_price_num(), 50);
+        assert_eq!(result.i_discount_num(), 0);
+        assert_eq!(result.invoice_guid(), "3d4e5f60718293a4b5c6d7e8f90a1b01");
+        assert_eq!(result.i_disc_type(), "PERCENT");
+        assert_eq!(result.i_disc_how(), "PRETAX");
+        assert_eq!(result.i_taxable(), true);
+        assert_eq!(result.i_taxincluded(), false);
+        assert_eq!(result.i_taxtable_guid(), "");
+        assert_eq!(result.bill_guid(), "");
+        assert_eq!(result.order_guid(), "2c3d4e5f60718293a4b5c6d7e8f90a01");
+    }
+
+    #[test(tokio::test)]
+    async fn test_bill() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+        let result = EntryQ::invoice(&query, "3d4e5f60718293a4b5c6d7e8f90a1b02")
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+
+        let result = &result[0];
+        assert_eq!(result.description(), "Paper");
+        assert_eq!(result.invoice_guid(), "");
+        assert_eq!(result.b_acct_guid(), "af88d386d44b14acf244362b85ccaf4c");
+        assert_eq!(result.b_price_num(), 30);
+        assert_eq!(result.b_price_denom(), 1);
+        assert_eq!(result.bill_guid(), "3d4e5f60718293a4b5c6d7e8f90a1b02");
+        assert_eq!(result.b_taxable(), true);
+        assert_eq!(result.b_taxincluded(), true);
+        assert_eq!(result.b_paytype(), 2);
+        assert_eq!(result.billable(), false);
+    }
+}
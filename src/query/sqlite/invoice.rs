@@ -0,0 +1,248 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use chrono::NaiveDateTime;
+use rusqlite::Row;
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::query::{InvoiceQ, InvoiceT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Invoice {
+    pub guid: String,
+    pub id: String,
+    pub date_opened: Option<NaiveDateTime>,
+    pub date_posted: Option<NaiveDateTime>,
+    pub notes: String,
+    pub active: i64,
+    pub currency: String,
+    pub owner_type: Option<i64>,
+    pub owner_guid: Option<String>,
+    pub terms: Option<String>,
+    pub billing_id: Option<String>,
+    pub post_txn: Option<String>,
+    pub post_lot: Option<String>,
+    pub post_acc: Option<String>,
+    pub charge_amt_num: Option<i64>,
+    pub charge_amt_denom: Option<i64>,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for Invoice {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            guid: row.get(0)?,
+            id: row.get(1)?,
+            date_opened: row.get(2)?,
+            date_posted: row.get(3)?,
+            notes: row.get(4)?,
+            active: row.get(5)?,
+            currency: row.get(6)?,
+            owner_type: row.get(7)?,
+            owner_guid: row.get(8)?,
+            terms: row.get(9)?,
+            billing_id: row.get(10)?,
+            post_txn: row.get(11)?,
+            post_lot: row.get(12)?,
+            post_acc: row.get(13)?,
+            charge_amt_num: row.get(14)?,
+            charge_amt_denom: row.get(15)?,
+        })
+    }
+}
+
+impl InvoiceT for Invoice {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn date_opened(&self) -> Option<NaiveDateTime> {
+        self.date_opened
+    }
+    fn date_posted(&self) -> Option<NaiveDateTime> {
+        self.date_posted
+    }
+    fn notes(&self) -> &str {
+        &self.notes
+    }
+    fn active(&self) -> bool {
+        self.active != 0
+    }
+    fn currency_guid(&self) -> &str {
+        &self.currency
+    }
+    fn owner_type(&self) -> i64 {
+        self.owner_type.unwrap_or(0)
+    }
+    fn owner_guid(&self) -> &str {
+        self.owner_guid.as_deref().unwrap_or_default()
+    }
+    fn terms_guid(&self) -> &str {
+        self.terms.as_deref().unwrap_or_default()
+    }
+    fn billing_id(&self) -> &str {
+        self.billing_id.as_deref().unwrap_or_default()
+    }
+    fn post_txn_guid(&self) -> &str {
+        self.post_txn.as_deref().unwrap_or_default()
+    }
+    fn post_lot_guid(&self) -> &str {
+        self.post_lot.as_deref().unwrap_or_default()
+    }
+    fn post_acc_guid(&self) -> &str {
+        self.post_acc.as_deref().unwrap_or_default()
+    }
+    fn charge_amt_num(&self) -> i64 {
+        self.charge_amt_num.unwrap_or(0)
+    }
+    fn charge_amt_denom(&self) -> i64 {
+        self.charge_amt_denom.unwrap_or(1)
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+id,
+date_opened,
+date_posted,
+notes,
+active,
+currency,
+owner_type,
+owner_guid,
+terms,
+billing_id,
+post_txn,
+post_lot,
+post_acc,
+charge_amt_num,
+charge_amt_denom
+FROM invoices
+";
+
+impl InvoiceQ for SQLiteQuery {
+    type Item = Invoice;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching all invoices from sqlite");
+
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "invoices fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching invoice by guid from sqlite");
+            let conn = pool.get()?;
+
+            let sql = format!("{SEL}\nWHERE guid = ?");
+            let mut stmt = conn
+                .prepare_cached(&sql)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let result = stmt.query_row([guid_owned], |row| Self::Item::try_from(row));
+
+            match result {
+                Ok(item) => Ok(Some(item)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => {
+                    tracing::error!("failed to fetch row: {e}");
+                    Err(Error::from(e))
+                }
+            }
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::sqlite::tests::setup_with;
+    use crate::query::tests::fixtures::{BUSINESS, INVOICES};
+
+    #[test(tokio::test)]
+    async fn test_invoice() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+        let result = InvoiceQ::guid(&query, "3d4e5f60718293a4b5c6d7e8f90a1b01")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.id(), "000001");
+        assert_eq!(
+            result.date_opened(),
+            NaiveDateTime::parse_from_str("2018-03-01 10:59:00", "%Y-%m-%d %H:%M:%S").ok()
+        );
+        assert_eq!(
+            result.date_posted(),
+            NaiveDateTime::parse_from_str("2018-03-05 10:59:00", "%Y-%m-%d %H:%M:%S").ok()
+        );
+        assert_eq!(result.active(), true);
+        assert_eq!(result.currency_guid(), "346629655191dcf59a7e2c2a85b70f69");
+        assert_eq!(result.owner_type(), 2);
+        assert_eq!(result.owner_guid(), "d4e5f6a7b8c9d0e1f2a3b4c5d6e7f801");
+        assert_eq!(result.terms_guid(), "");
+        assert_eq!(result.billing_id(), "PO-17");
+        assert_eq!(result.post_txn_guid(), "60718293a4b5c6d7e8f90a1b2c3d4e01");
+        assert_eq!(result.post_lot_guid(), "5f60718293a4b5c6d7e8f90a1b2c3d01");
+        assert_eq!(result.post_acc_guid(), "0a1b2c3d4e5f60718293a4b5c6d7e801");
+        assert_eq!(result.charge_amt_num(), 0);
+        assert_eq!(result.charge_amt_denom(), 1);
+    }
+
+    #[test(tokio::test)]
+    async fn test_draft() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+        let result = InvoiceQ::guid(&query, "3d4e5f60718293a4b5c6d7e8f90a1b03")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.date_posted(), None);
+        assert_eq!(result.notes(), "draft");
+        assert_eq!(result.owner_type(), 3);
+        assert_eq!(result.post_txn_guid(), "");
+        assert_eq!(result.post_lot_guid(), "");
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+        let result = InvoiceQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 3);
+    }
+}
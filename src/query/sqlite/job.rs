@@ -0,0 +1,164 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use rusqlite::Row;
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::query::{JobQ, JobT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Job {
+    pub guid: String,
+    pub id: String,
+    pub name: String,
+    pub reference: String,
+    pub active: i64,
+    pub owner_type: Option<i64>,
+    pub owner_guid: Option<String>,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for Job {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            guid: row.get(0)?,
+            id: row.get(1)?,
+            name: row.get(2)?,
+            reference: row.get(3)?,
+            active: row.get(4)?,
+            owner_type: row.get(5)?,
+            owner_guid: row.get(6)?,
+        })
+    }
+}
+
+impl JobT for Job {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn reference(&self) -> &str {
+        &self.reference
+    }
+    fn active(&self) -> bool {
+        self.active != 0
+    }
+    fn owner_type(&self) -> i64 {
+        self.owner_type.unwrap_or(0)
+    }
+    fn owner_guid(&self) -> &str {
+        self.owner_guid.as_deref().unwrap_or_default()
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+id,
+name,
+reference,
+active,
+owner_type,
+owner_guid
+FROM jobs
+";
+
+impl JobQ for SQLiteQuery {
+    type Item = Job;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching all jobs from sqlite");
+
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "jobs fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching job by guid from sqlite");
+            let conn = pool.get()?;
+
+            let sql = format!("{SEL}\nWHERE guid = ?");
+            let mut stmt = conn
+                .prepare_cached(&sql)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let result = stmt.query_row([guid_owned], |row| Self::Item::try_from(row));
+
+            match result {
+                Ok(item) => Ok(Some(item)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => {
+                    tracing::error!("failed to fetch row: {e}");
+                    Err(Error::from(e))
+                }
+            }
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::sqlite::tests::setup_with;
+    use crate::query::tests::fixtures::{BUSINESS, INVOICES};
+
+    #[test(tokio::test)]
+    async fn test_job() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+        let result = JobQ::guid(&query, "1b2c3d4e5f60718293a4b5c6d7e8f901")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.id(), "000001");
+        assert_eq!(result.name(), "Website");
+        assert_eq!(result.reference(), "PO-17");
+        assert_eq!(result.active(), true);
+        assert_eq!(result.owner_type(), 2);
+        assert_eq!(result.owner_guid(), "d4e5f6a7b8c9d0e1f2a3b4c5d6e7f801");
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+        let result = JobQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 1);
+    }
+}
@@ -0,0 +1,175 @@
+use rusqlite::{Connection, TransactionBehavior, params};
+use tracing::instrument;
+
+use super::{SQLiteQuery, SqliteManager};
+use crate::error::Error;
+use crate::model::Lock;
+use crate::query::{LockQ, hostname};
+
+const SEL: &str = "SELECT Hostname, PID FROM gnclock";
+const INS: &str = "INSERT INTO gnclock (Hostname, PID) VALUES (?1, ?2)";
+// only one entry of this process is removed, a concurrent write of it keeps its own
+const DEL: &str = r"
+DELETE FROM gnclock WHERE rowid = (
+    SELECT rowid FROM gnclock WHERE Hostname = ?1 AND PID = ?2 LIMIT 1
+)
+";
+
+fn select(conn: &Connection) -> Result<Vec<Lock>, rusqlite::Error> {
+    conn.prepare(SEL)?
+        .query_map([], |row| {
+            Ok(Lock {
+                hostname: row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+                pid: row.get::<_, Option<i64>>(1)?.unwrap_or_default(),
+            })
+        })?
+        .collect()
+}
+
+/// The `gnclock` entry of this process while a write is in progress.
+///
+/// The entry is committed on its own, so other processes see the book as locked.
+/// Entries of this process itself do not block it.
+struct Guard {
+    hostname: String,
+    pid: i64,
+}
+
+impl Guard {
+    /// Takes the lock, or fails with [`Error::Locked`] if another process holds it and `force` is unset.
+    fn acquire(pool: &r2d2::Pool<SqliteManager>, force: bool) -> Result<Self, Error> {
+        let hostname = hostname();
+        let pid = i64::from(std::process::id());
+
+        let mut conn = pool.get()?;
+        let tx = conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .inspect_err(|e| tracing::error!("failed to begin transaction: {e}"))?;
+
+        let other = select(&tx)?
+            .into_iter()
+            .find(|lock| lock.hostname != hostname || lock.pid != pid);
+        if let Some(lock) = other {
+            if !force {
+                tracing::error!(hostname = lock.hostname, pid = lock.pid, "book is locked");
+                return Err(Error::Locked {
+                    hostname: lock.hostname,
+                    pid: lock.pid,
+                });
+            }
+            tracing::warn!(
+                hostname = lock.hostname,
+                pid = lock.pid,
+                "ignoring lock held by another process"
+            );
+        }
+
+        tx.execute(INS, params![hostname, pid])
+            .inspect_err(|e| tracing::error!("failed to take lock: {e}"))?;
+        tx.commit()
+            .inspect_err(|e| tracing::error!("failed to commit transaction: {e}"))?;
+
+        tracing::debug!("lock taken");
+        Ok(Self { hostname, pid })
+    }
+
+    fn release(self, pool: &r2d2::Pool<SqliteManager>) -> Result<(), Error> {
+        pool.get()?
+            .execute(DEL, params![self.hostname, self.pid])
+            .inspect_err(|e| tracing::error!("failed to release lock: {e}"))?;
+
+        tracing::debug!("lock released");
+        Ok(())
+    }
+}
+
+/// Runs the blocking write `f` while holding the lock, which is released afterwards
+/// whether `f` succeeds or not.
+pub(crate) fn with_lock<T>(
+    pool: &r2d2::Pool<SqliteManager>,
+    force: bool,
+    f: impl FnOnce() -> Result<T, Error>,
+) -> Result<T, Error> {
+    let guard = Guard::acquire(pool, force)?;
+    let result = f();
+    match (result, guard.release(pool)) {
+        (Ok(value), released) => released.map(|()| value),
+        (Err(e), _) => Err(e),
+    }
+}
+
+impl LockQ for SQLiteQuery {
+    #[instrument(skip(self))]
+    async fn locks(&self) -> Result<Vec<Lock>, Error> {
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            tracing::debug!("fetching locks from sqlite");
+
+            let conn = pool.get()?;
+            select(&conn)
+                .inspect_err(|e| tracing::error!("failed to execute query: {e}"))
+                .map_err(std::convert::Into::into)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error in spawn_blocking: {e}")))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use crate::query::sqlite::tests::{setup_with, setup_writable};
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_with_lock() {
+        let (_dir, query) = setup_writable();
+        assert_eq!(query.locks().await.unwrap(), vec![]);
+
+        let locks = with_lock(&query.pool, false, || {
+            // a nested write of the same process is not blocked by its own entry
+            with_lock(&query.pool, false, || Ok(()))?;
+            Ok(select(&*query.pool.get()?)?)
+        })
+        .unwrap();
+        assert_eq!(locks.len(), 1);
+        assert_eq!(locks[0].hostname, hostname());
+        assert_eq!(locks[0].pid, i64::from(std::process::id()));
+
+        assert_eq!(query.locks().await.unwrap(), vec![]);
+
+        let result: Result<(), Error> = with_lock(&query.pool, false, || {
+            Err(Error::Internal("failed".to_string()))
+        });
+        assert!(matches!(result, Err(Error::Internal(_))));
+        assert_eq!(query.locks().await.unwrap(), vec![]);
+    }
+
+    #[test(tokio::test)]
+    async fn test_with_lock_locked() {
+        let (_dir, query) =
+            setup_with(&["INSERT INTO gnclock (Hostname, PID) VALUES ('other-host', 4242)"]);
+        let result = with_lock(&query.pool, false, || Ok(()));
+        assert!(matches!(
+            result,
+            Err(Error::Locked { hostname, pid: 4242 }) if hostname == "other-host"
+        ));
+
+        let count =
+            with_lock(&query.pool, true, || Ok(select(&*query.pool.get()?)?.len())).unwrap();
+        assert_eq!(count, 2);
+
+        let locks = query.locks().await.unwrap();
+        assert_eq!(
+            locks,
+            vec![Lock {
+                hostname: "other-host".to_string(),
+                pid: 4242
+            }]
+        );
+    }
+}
@@ -0,0 +1,206 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use rusqlite::{Connection, Row, params};
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::query::{LotQ, LotT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Lot {
+    pub guid: String,
+    pub account_guid: Option<String>,
+    pub is_closed: i64,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for Lot {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            guid: row.get(0)?,
+            account_guid: row.get(1)?,
+            is_closed: row.get(2)?,
+        })
+    }
+}
+
+impl LotT for Lot {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn account_guid(&self) -> &str {
+        self.account_guid.as_deref().unwrap_or_default()
+    }
+    fn is_closed(&self) -> Option<bool> {
+        Some(self.is_closed != 0)
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+account_guid,
+is_closed
+FROM lots
+";
+
+const INS: &str = r"
+INSERT INTO lots (
+guid,
+account_guid,
+is_closed
+) VALUES (?1, ?2, ?3)
+";
+
+/// Inserts a lot row; the caller owns the surrounding database transaction.
+pub(crate) fn insert(conn: &Connection, lot: &Lot) -> Result<(), rusqlite::Error> {
+    let mut stmt = conn
+        .prepare_cached(INS)
+        .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+    stmt.execute(params![lot.guid, lot.account_guid, lot.is_closed])?;
+
+    Ok(())
+}
+
+impl LotQ for SQLiteQuery {
+    type Item = Lot;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching all lots from sqlite");
+
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "lots fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching lot by guid from sqlite");
+            let conn = pool.get()?;
+
+            let sql = format!("{SEL}\nWHERE guid = ?");
+            let mut stmt = conn
+                .prepare_cached(&sql)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let result = stmt.query_row([guid_owned], |row| Self::Item::try_from(row));
+
+            match result {
+                Ok(item) => Ok(Some(item)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => {
+                    tracing::error!("failed to fetch row: {e}");
+                    Err(Error::from(e))
+                }
+            }
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+
+    #[instrument(skip(self))]
+    async fn account(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching lots by account_guid from sqlite");
+            let conn = pool.get()?;
+
+            let sql = format!("{SEL}\nWHERE account_guid = ?");
+            let mut stmt = conn
+                .prepare_cached(&sql)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([guid_owned], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "lots found by account_guid");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use crate::query::sqlite::tests::setup_with;
+    use crate::query::tests::fixtures::LOT;
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_lot() {
+        let (_dir, query) = setup_with(&[LOT]);
+        let result = query
+            .guid("a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.guid(), "a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01");
+        assert_eq!(result.account_guid(), "1c089803052e85f5c6d8e786057dbaee");
+        assert_eq!(result.is_closed(), Some(false));
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (_dir, query) = setup_with(&[LOT]);
+        let result = query.all().await.unwrap();
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test(tokio::test)]
+    async fn test_guid() {
+        let (_dir, query) = setup_with(&[LOT]);
+        let result = query.guid("unknown").await.unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[test(tokio::test)]
+    async fn test_account_guid() {
+        let (_dir, query) = setup_with(&[LOT]);
+        let result = query
+            .account("1c089803052e85f5c6d8e786057dbaee")
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        let result = query
+            .account("93fc043c3062aaa1297b30e543d2cd0d")
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 0);
+    }
+}
@@ -0,0 +1,183 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use chrono::NaiveDateTime;
+use rusqlite::Row;
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::query::{OrderQ, OrderT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Order {
+    pub guid: String,
+    pub id: String,
+    pub notes: String,
+    pub reference: String,
+    pub active: i64,
+    pub date_opened: NaiveDateTime,
+    pub date_closed: Option<NaiveDateTime>,
+    pub owner_type: i64,
+    pub owner_guid: String,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for Order {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            guid: row.get(0)?,
+            id: row.get(1)?,
+            notes: row.get(2)?,
+            reference: row.get(3)?,
+            active: row.get(4)?,
+            date_opened: row.get(5)?,
+            date_closed: row.get(6)?,
+            owner_type: row.get(7)?,
+            owner_guid: row.get(8)?,
+        })
+    }
+}
+
+impl OrderT for Order {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn notes(&self) -> &str {
+        &self.notes
+    }
+    fn reference(&self) -> &str {
+        &self.reference
+    }
+    fn active(&self) -> bool {
+        self.active != 0
+    }
+    fn date_opened(&self) -> NaiveDateTime {
+        self.date_opened
+    }
+    fn date_closed(&self) -> Option<NaiveDateTime> {
+        // 0 when not set
+        self.date_closed.filter(|x| x.and_utc().timestamp() != 0)
+    }
+    fn owner_type(&self) -> i64 {
+        self.owner_type
+    }
+    fn owner_guid(&self) -> &str {
+        &self.owner_guid
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+id,
+notes,
+reference,
+active,
+date_opened,
+date_closed,
+owner_type,
+owner_guid
+FROM orders
+";
+
+impl OrderQ for SQLiteQuery {
+    type Item = Order;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching all orders from sqlite");
+
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "orders fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching order by guid from sqlite");
+            let conn = pool.get()?;
+
+            let sql = format!("{SEL}\nWHERE guid = ?");
+            let mut stmt = conn
+                .prepare_cached(&sql)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let result = stmt.query_row([guid_owned], |row| Self::Item::try_from(row));
+
+            match result {
+                Ok(item) => Ok(Some(item)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => {
+                    tracing::error!("failed to fetch row: {e}");
+                    Err(Error::from(e))
+                }
+            }
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::sqlite::tests::setup_with;
+    use crate::query::tests::fixtures::{BUSINESS, INVOICES};
+
+    #[test(tokio::test)]
+    async fn test_order() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+        let result = OrderQ::guid(&query, "2c3d4e5f60718293a4b5c6d7e8f90a01")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.id(), "000001");
+        assert_eq!(result.notes(), "");
+        assert_eq!(result.reference(), "PO-17");
+        assert_eq!(result.active(), true);
+        assert_eq!(
+            result.date_opened(),
+            NaiveDateTime::parse_from_str("2018-03-01 10:59:00", "%Y-%m-%d %H:%M:%S").unwrap()
+        );
+        assert_eq!(result.date_closed(), None);
+        assert_eq!(result.owner_type(), 3);
+        assert_eq!(result.owner_guid(), "1b2c3d4e5f60718293a4b5c6d7e8f901");
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES]);
+        let result = OrderQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 1);
+    }
+}
@@ -2,15 +2,16 @@
 // ref: https://wiki.gnucash.org/wiki/SQL
 
 use chrono::NaiveDateTime;
-use rusqlite::Row;
+use rusqlite::{Connection, Row, params};
 #[cfg(feature = "decimal")]
 use rust_decimal::Decimal;
 use tokio::task::spawn_blocking;
 use tracing::instrument;
 
-use super::SQLiteQuery;
+use super::lock::with_lock;
+use super::{DATETIME_FORMAT, SQLiteQuery};
 use crate::error::Error;
-use crate::query::{PriceQ, PriceT};
+use crate::query::{PriceMutQ, PriceQ, PriceRecord, PriceT};
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
 pub struct Price {
@@ -61,6 +62,13 @@ impl PriceT for Price {
         self.r#type.as_deref().unwrap_or_default()
     }
 
+    fn value_num(&self) -> i64 {
+        self.value_num
+    }
+    fn value_denom(&self) -> i64 {
+        self.value_denom
+    }
+
     #[cfg(not(feature = "decimal"))]
     #[allow(clippy::cast_precision_loss)]
     fn value(&self) -> f64 {
@@ -232,6 +240,100 @@ impl PriceQ for SQLiteQuery {
     }
 }
 
+const INS: &str = r"
+INSERT INTO prices (
+guid,
+commodity_guid,
+currency_guid,
+date,
+source,
+type,
+value_num,
+value_denom
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+";
+
+const DUP: &str = r"
+SELECT EXISTS (
+SELECT 1 FROM prices
+WHERE commodity_guid = ?1
+AND currency_guid = ?2
+AND date(date) = date(?3)
+)
+";
+
+/// Inserts a price row without checking for duplicates; the caller owns the surrounding
+/// database transaction.
+pub(crate) fn insert(conn: &Connection, price: &PriceRecord) -> Result<(), rusqlite::Error> {
+    let mut stmt = conn
+        .prepare_cached(INS)
+        .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+    stmt.execute(params![
+        price.guid,
+        price.commodity_guid,
+        price.currency_guid,
+        price.datetime.format(DATETIME_FORMAT).to_string(),
+        price.source,
+        price.r#type,
+        price.value_num,
+        price.value_denom,
+    ])?;
+
+    Ok(())
+}
+
+impl PriceMutQ for SQLiteQuery {
+    #[instrument(skip(self, prices), fields(count = prices.len()))]
+    async fn insert(&self, prices: &[PriceRecord]) -> Result<Vec<PriceRecord>, Error> {
+        let pool = self.writable_pool()?;
+        let force = self.ignore_lock;
+        let prices = prices.to_vec();
+
+        spawn_blocking(move || {
+            with_lock(&pool, force, || {
+                tracing::debug!("inserting prices into sqlite");
+
+                let mut conn = pool.get()?;
+                let tx = conn
+                    .transaction()
+                    .inspect_err(|e| tracing::error!("failed to begin transaction: {e}"))?;
+
+                let mut inserted = Vec::with_capacity(prices.len());
+                {
+                    let mut dup = tx
+                        .prepare_cached(DUP)
+                        .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+                    for price in prices {
+                        let date = price.datetime.format(DATETIME_FORMAT).to_string();
+                        let exists: bool = dup.query_row(
+                            params![price.commodity_guid, price.currency_guid, date],
+                            |row| row.get(0),
+                        )?;
+                        if exists {
+                            tracing::debug!(%date, "skipping duplicate price");
+                            continue;
+                        }
+
+                        insert(&tx, &price)
+                            .inspect_err(|e| tracing::error!("failed to insert price: {e}"))?;
+                        inserted.push(price);
+                    }
+                }
+
+                tx.commit()
+                    .inspect_err(|e| tracing::error!("failed to commit transaction: {e}"))?;
+
+                tracing::debug!(count = inserted.len(), "prices inserted into sqlite");
+                Ok(inserted)
+            })
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error in spawn_blocking: {e}")))?
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(not(feature = "decimal"))]
@@ -240,6 +342,8 @@ mod tests {
     use test_log::test;
     use tokio::sync::OnceCell;
 
+    use crate::query::sqlite::tests::setup_writable;
+
     use super::*;
 
     #[cfg(feature = "schema")]
@@ -299,6 +403,8 @@ mod tests {
         assert_approx_eq!(f64, result.value(), 1.5);
         #[cfg(feature = "decimal")]
         assert_eq!(result.value(), Decimal::new(15, 1));
+        assert_eq!(result.value_num(), 3);
+        assert_eq!(result.value_denom(), 2);
     }
 
     #[test(tokio::test)]
@@ -360,4 +466,46 @@ mod tests {
             .unwrap();
         assert_eq!(result.len(), 4);
     }
+
+    fn record(date: &str, value_num: i64) -> PriceRecord {
+        PriceRecord {
+            guid: crate::builder::new_guid(),
+            commodity_guid: "d821d6776fde9f7c2d01b67876406fd3".to_string(),
+            currency_guid: "5f586908098232e67edb1371408bfaa8".to_string(),
+            datetime: NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap(),
+            source: "Finance::Quote".to_string(),
+            r#type: "last".to_string(),
+            value_num,
+            value_denom: 100,
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn test_insert() {
+        let (_dir, query) = setup_writable();
+
+        let prices = [
+            record("2018-02-20 10:00:00", 160),
+            record("2018-02-21 10:00:00", 170),
+            record("2018-02-21 18:00:00", 180),
+        ];
+        let inserted = query.insert(&prices).await.unwrap();
+        assert_eq!(inserted, vec![prices[1].clone()]);
+
+        let result = query.guid(&prices[1].guid).await.unwrap().unwrap();
+        assert_eq!(result.source(), "Finance::Quote");
+        assert_eq!(result.r#type(), "last");
+        #[cfg(not(feature = "decimal"))]
+        assert_approx_eq!(f64, result.value(), 1.7);
+        #[cfg(feature = "decimal")]
+        assert_eq!(result.value(), Decimal::new(17, 1));
+        assert_eq!(query.all().await.unwrap().len(), 6);
+    }
+
+    #[test(tokio::test)]
+    async fn test_insert_read_only() {
+        let query = setup().await;
+        let result = query.insert(&[record("2018-02-21 10:00:00", 170)]).await;
+        assert!(matches!(result, Err(Error::ReadOnly)));
+    }
 }
@@ -0,0 +1,157 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use chrono::NaiveDate;
+use rusqlite::{Connection, Row, params};
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::query::{RecurrenceQ, RecurrenceT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Recurrence {
+    pub obj_guid: String,
+    pub mult: i64,
+    pub period_type: String,
+    pub period_start: NaiveDate,
+    pub weekend_adjust: String,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for Recurrence {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        // stored as text(8), e.g. 20180101
+        let start: String = row.get(3)?;
+        let period_start = NaiveDate::parse_from_str(&start, "%Y%m%d").map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+
+        Ok(Self {
+            obj_guid: row.get(0)?,
+            mult: row.get(1)?,
+            period_type: row.get(2)?,
+            period_start,
+            weekend_adjust: row.get(4)?,
+        })
+    }
+}
+
+impl RecurrenceT for Recurrence {
+    fn obj_guid(&self) -> &str {
+        &self.obj_guid
+    }
+    fn mult(&self) -> i64 {
+        self.mult
+    }
+    fn period_type(&self) -> &str {
+        &self.period_type
+    }
+    fn period_start(&self) -> NaiveDate {
+        self.period_start
+    }
+    fn weekend_adjust(&self) -> &str {
+        &self.weekend_adjust
+    }
+}
+
+const SEL: &str = r"
+SELECT
+obj_guid,
+recurrence_mult,
+recurrence_period_type,
+recurrence_period_start,
+recurrence_weekend_adjust
+FROM recurrences
+WHERE obj_guid = ?
+ORDER BY id
+";
+
+const INS: &str = r"
+INSERT INTO recurrences (
+obj_guid,
+recurrence_mult,
+recurrence_period_type,
+recurrence_period_start,
+recurrence_weekend_adjust
+) VALUES (?1, ?2, ?3, ?4, ?5)
+";
+
+/// Inserts a recurrence row; the caller owns the surrounding database transaction.
+pub(crate) fn insert(conn: &Connection, recurrence: &Recurrence) -> Result<(), rusqlite::Error> {
+    let mut stmt = conn
+        .prepare_cached(INS)
+        .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+    stmt.execute(params![
+        recurrence.obj_guid,
+        recurrence.mult,
+        recurrence.period_type,
+        recurrence.period_start.format("%Y%m%d").to_string(),
+        recurrence.weekend_adjust,
+    ])?;
+
+    Ok(())
+}
+
+impl RecurrenceQ for SQLiteQuery {
+    type Item = Recurrence;
+
+    #[instrument(skip(self))]
+    async fn obj_guid(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching recurrences by obj_guid from sqlite");
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([guid_owned], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "recurrences fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::sqlite::tests::setup_with;
+    use crate::query::tests::fixtures::BUDGET;
+
+    #[test(tokio::test)]
+    async fn test_obj_guid() {
+        let (_dir, query) = setup_with(&[BUDGET]);
+        let result = query
+            .obj_guid("5f1a0c7d1e9b4b6f8a2d3c4e5f607182")
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].mult(), 1);
+        assert_eq!(result[0].period_type(), "month");
+        assert_eq!(
+            result[0].period_start(),
+            NaiveDate::from_ymd_opt(2018, 1, 1).unwrap()
+        );
+        assert_eq!(result[0].weekend_adjust(), "none");
+
+        let result = query.obj_guid("unknown").await.unwrap();
+        assert!(result.is_empty());
+    }
+}
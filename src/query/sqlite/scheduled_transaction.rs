@@ -0,0 +1,281 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use chrono::NaiveDate;
+use rusqlite::{Connection, Row, params};
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::query::{ScheduledTransactionQ, ScheduledTransactionT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct ScheduledTransaction {
+    pub guid: String,
+    pub name: Option<String>,
+    pub enabled: i64,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub last_occur: Option<String>,
+    pub num_occur: i64,
+    pub rem_occur: i64,
+    pub auto_create: i64,
+    pub auto_notify: i64,
+    pub adv_creation: i64,
+    pub adv_notify: i64,
+    pub instance_count: i64,
+    pub template_act_guid: String,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for ScheduledTransaction {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            guid: row.get(0)?,
+            name: row.get(1)?,
+            enabled: row.get(2)?,
+            start_date: row.get(3)?,
+            end_date: row.get(4)?,
+            last_occur: row.get(5)?,
+            num_occur: row.get(6)?,
+            rem_occur: row.get(7)?,
+            auto_create: row.get(8)?,
+            auto_notify: row.get(9)?,
+            adv_creation: row.get(10)?,
+            adv_notify: row.get(11)?,
+            instance_count: row.get(12)?,
+            template_act_guid: row.get(13)?,
+        })
+    }
+}
+
+// dates are stored as text(8), e.g. 20180301
+fn parse_date(date: Option<&str>) -> Option<NaiveDate> {
+    date.and_then(|x| NaiveDate::parse_from_str(x, "%Y%m%d").ok())
+}
+
+impl ScheduledTransactionT for ScheduledTransaction {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or_default()
+    }
+    fn enabled(&self) -> bool {
+        self.enabled != 0
+    }
+    fn start_date(&self) -> Option<NaiveDate> {
+        parse_date(self.start_date.as_deref())
+    }
+    fn end_date(&self) -> Option<NaiveDate> {
+        parse_date(self.end_date.as_deref())
+    }
+    fn last_occur(&self) -> Option<NaiveDate> {
+        parse_date(self.last_occur.as_deref())
+    }
+    fn num_occur(&self) -> i64 {
+        self.num_occur
+    }
+    fn rem_occur(&self) -> i64 {
+        self.rem_occur
+    }
+    fn auto_create(&self) -> bool {
+        self.auto_create != 0
+    }
+    fn auto_notify(&self) -> bool {
+        self.auto_notify != 0
+    }
+    fn adv_creation(&self) -> i64 {
+        self.adv_creation
+    }
+    fn adv_notify(&self) -> i64 {
+        self.adv_notify
+    }
+    fn instance_count(&self) -> i64 {
+        self.instance_count
+    }
+    fn template_act_guid(&self) -> &str {
+        &self.template_act_guid
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+name,
+enabled,
+start_date,
+end_date,
+last_occur,
+num_occur,
+rem_occur,
+auto_create,
+auto_notify,
+adv_creation,
+adv_notify,
+instance_count,
+template_act_guid
+FROM schedxactions
+";
+
+const INS: &str = r"
+INSERT INTO schedxactions (
+guid,
+name,
+enabled,
+start_date,
+end_date,
+last_occur,
+num_occur,
+rem_occur,
+auto_create,
+auto_notify,
+adv_creation,
+adv_notify,
+instance_count,
+template_act_guid
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+";
+
+/// Inserts a scheduled transaction row; the caller owns the surrounding database transaction.
+pub(crate) fn insert(
+    conn: &Connection,
+    scheduled: &ScheduledTransaction,
+) -> Result<(), rusqlite::Error> {
+    let mut stmt = conn
+        .prepare_cached(INS)
+        .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+    stmt.execute(params![
+        scheduled.guid,
+        scheduled.name,
+        scheduled.enabled,
+        scheduled.start_date,
+        scheduled.end_date,
+        scheduled.last_occur,
+        scheduled.num_occur,
+        scheduled.rem_occur,
+        scheduled.auto_create,
+        scheduled.auto_notify,
+        scheduled.adv_creation,
+        scheduled.adv_notify,
+        scheduled.instance_count,
+        scheduled.template_act_guid,
+    ])?;
+
+    Ok(())
+}
+
+impl ScheduledTransactionQ for SQLiteQuery {
+    type Item = ScheduledTransaction;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching all scheduled transactions from sqlite");
+
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(
+                count = items.len(),
+                "scheduled transactions fetched from sqlite"
+            );
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching scheduled transaction by guid from sqlite");
+            let conn = pool.get()?;
+
+            let sql = format!("{SEL}\nWHERE guid = ?");
+            let mut stmt = conn
+                .prepare_cached(&sql)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let result = stmt.query_row([guid_owned], |row| Self::Item::try_from(row));
+
+            match result {
+                Ok(item) => Ok(Some(item)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => {
+                    tracing::error!("failed to fetch row: {e}");
+                    Err(Error::from(e))
+                }
+            }
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use crate::query::sqlite::tests::setup_with;
+    use crate::query::tests::fixtures::SCHEDULED_TRANSACTION;
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_scheduled_transaction() {
+        let (_dir, query) = setup_with(&[SCHEDULED_TRANSACTION]);
+        let result = ScheduledTransactionQ::guid(&query, "3b4c5d6e7f8091a2b3c4d5e6f7081920")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.name(), "Rent");
+        assert_eq!(result.enabled(), true);
+        assert_eq!(result.start_date(), NaiveDate::from_ymd_opt(2018, 3, 1));
+        assert_eq!(result.end_date(), None);
+        assert_eq!(result.last_occur(), NaiveDate::from_ymd_opt(2018, 3, 1));
+        assert_eq!(result.num_occur(), 6);
+        assert_eq!(result.rem_occur(), 5);
+        assert_eq!(result.auto_create(), false);
+        assert_eq!(result.adv_creation(), 2);
+        assert_eq!(result.instance_count(), 1);
+        assert_eq!(
+            result.template_act_guid(),
+            "9a8b7c6d5e4f30211203f4e5d6c7b8a9"
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (_dir, query) = setup_with(&[SCHEDULED_TRANSACTION]);
+        let result = ScheduledTransactionQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test(tokio::test)]
+    async fn test_guid() {
+        let (_dir, query) = setup_with(&[SCHEDULED_TRANSACTION]);
+        let result = ScheduledTransactionQ::guid(&query, "unknown")
+            .await
+            .unwrap();
+        assert_eq!(result, None);
+    }
+}
@@ -0,0 +1,270 @@
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::{Connection, Row, params};
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::{DATETIME_FORMAT, SQLiteQuery};
+use crate::error::Error;
+use crate::query::{SlotQ, SlotT};
+
+#[allow(clippy::struct_field_names)]
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Slot {
+    pub(crate) obj_guid: String,
+    pub(crate) name: String,
+    pub(crate) slot_type: i64,
+    pub(crate) int64_val: Option<i64>,
+    pub(crate) string_val: Option<String>,
+    pub(crate) double_val: Option<f64>,
+    pub(crate) timespec_val: Option<NaiveDateTime>,
+    pub(crate) guid_val: Option<String>,
+    pub(crate) numeric_val_num: Option<i64>,
+    pub(crate) numeric_val_denom: Option<i64>,
+    pub(crate) gdate_val: Option<String>,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for Slot {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            obj_guid: row.get(0)?,
+            name: row.get(1)?,
+            slot_type: row.get(2)?,
+            int64_val: row.get(3)?,
+            string_val: row.get(4)?,
+            double_val: row.get(5)?,
+            timespec_val: row.get(6)?,
+            guid_val: row.get(7)?,
+            numeric_val_num: row.get(8)?,
+            numeric_val_denom: row.get(9)?,
+            gdate_val: row.get(10)?,
+        })
+    }
+}
+
+impl SlotT for Slot {
+    fn obj_guid(&self) -> &str {
+        &self.obj_guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn slot_type(&self) -> i64 {
+        self.slot_type
+    }
+    fn int64_val(&self) -> i64 {
+        self.int64_val.unwrap_or_default()
+    }
+    fn string_val(&self) -> &str {
+        self.string_val.as_deref().unwrap_or_default()
+    }
+    fn double_val(&self) -> f64 {
+        self.double_val.unwrap_or_default()
+    }
+    fn timespec_val(&self) -> Option<NaiveDateTime> {
+        self.timespec_val
+    }
+    fn guid_val(&self) -> &str {
+        self.guid_val.as_deref().unwrap_or_default()
+    }
+    fn numeric_val_num(&self) -> i64 {
+        self.numeric_val_num.unwrap_or_default()
+    }
+    fn numeric_val_denom(&self) -> i64 {
+        self.numeric_val_denom.unwrap_or(1)
+    }
+    fn gdate_val(&self) -> Option<NaiveDate> {
+        self.gdate_val
+            .as_deref()
+            .and_then(|x| NaiveDate::parse_from_str(x, "%Y%m%d").ok())
+    }
+}
+
+const SEL: &str = r"
+SELECT
+obj_guid,
+name,
+slot_type,
+int64_val,
+string_val,
+double_val,
+timespec_val,
+guid_val,
+numeric_val_num,
+numeric_val_denom,
+gdate_val
+FROM slots
+";
+
+const INS_STRING: &str = r"
+INSERT INTO slots (obj_guid, name, slot_type, int64_val, string_val, timespec_val, numeric_val_num, numeric_val_denom)
+VALUES (?1, ?2, 4, 0, ?3, '1970-01-01 00:00:00', 0, 1)
+";
+
+/// Inserts a string slot; the caller owns the surrounding database transaction.
+pub(crate) fn insert_string(
+    conn: &Connection,
+    obj_guid: &str,
+    name: &str,
+    value: &str,
+) -> Result<(), rusqlite::Error> {
+    conn.prepare_cached(INS_STRING)?
+        .execute(params![obj_guid, name, value])?;
+    Ok(())
+}
+
+const INS: &str = r"
+INSERT INTO slots (
+obj_guid,
+name,
+slot_type,
+int64_val,
+string_val,
+double_val,
+timespec_val,
+guid_val,
+numeric_val_num,
+numeric_val_denom,
+gdate_val
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+";
+
+/// Inserts a slot row; the caller owns the surrounding database transaction.
+pub(crate) fn insert(conn: &Connection, slot: &Slot) -> Result<(), rusqlite::Error> {
+    let mut stmt = conn
+        .prepare_cached(INS)
+        .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+    stmt.execute(params![
+        slot.obj_guid,
+        slot.name,
+        slot.slot_type,
+        slot.int64_val,
+        slot.string_val,
+        slot.double_val,
+        slot.timespec_val
+            .map(|x| x.format(DATETIME_FORMAT).to_string()),
+        slot.guid_val,
+        slot.numeric_val_num,
+        slot.numeric_val_denom,
+        slot.gdate_val,
+    ])?;
+
+    Ok(())
+}
+
+const DEL: &str = r"
+WITH RECURSIVE owners(guid) AS (
+    SELECT ?1
+    UNION
+    SELECT slots.guid_val FROM slots JOIN owners ON slots.obj_guid = owners.guid
+    WHERE slots.slot_type IN (8, 9)
+)
+DELETE FROM slots WHERE obj_guid IN (SELECT guid FROM owners)
+";
+
+/// Deletes the slots of `obj_guid` along with the rows nested in its frames and lists;
+/// the caller owns the surrounding database transaction.
+pub(crate) fn delete(conn: &Connection, obj_guid: &str) -> Result<(), rusqlite::Error> {
+    conn.execute(DEL, [obj_guid])?;
+    Ok(())
+}
+
+impl SlotQ for SQLiteQuery {
+    type Item = Slot;
+
+    #[instrument(skip(self))]
+    async fn obj_guid(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching slots by obj_guid from sqlite");
+
+            let conn = pool.get()?;
+
+            let sql = format!("{SEL}\nWHERE obj_guid = ?\nORDER BY id");
+            let mut stmt = conn
+                .prepare_cached(&sql)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([guid_owned], |row| Self::Item::try_from(row))?;
+
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "slots fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error in spawn_blocking: {e}")))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+    use tokio::sync::OnceCell;
+
+    use super::*;
+
+    static Q: OnceCell<SQLiteQuery> = OnceCell::const_new();
+    async fn setup() -> &'static SQLiteQuery {
+        Q.get_or_init(|| async {
+            let uri: &str = &format!(
+                "{}/tests/db/sqlite/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+
+            tracing::debug!("work_dir: {:?}", std::env::current_dir());
+            SQLiteQuery::new(uri).unwrap()
+        })
+        .await
+    }
+
+    #[test(tokio::test)]
+    async fn test_obj_guid() {
+        let query = setup().await;
+        let result = query
+            .obj_guid("7d4ef4044fd30f41d08914a8174c2f5b")
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].name(), "features");
+        assert_eq!(result[0].slot_type(), 9);
+        assert_eq!(result[0].guid_val(), "6c3d2900f3d74d67820df763258fb8af");
+        assert_eq!(result[2].name(), "remove-color-not-set-slots");
+        assert_eq!(result[2].slot_type(), 4);
+        assert_eq!(result[2].string_val(), "true");
+
+        let result = query
+            .obj_guid("6c3d2900f3d74d67820df763258fb8af")
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].name(),
+            "features/ISO-8601 formatted date strings in SQLite3 databases."
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn test_gdate_val() {
+        let query = setup().await;
+        let result = query
+            .obj_guid("0fac88d8412f46d5e16beb87aee0fd22")
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name(), "date-posted");
+        assert_eq!(result[0].slot_type(), 10);
+        assert_eq!(result[0].gdate_val(), NaiveDate::from_ymd_opt(2018, 2, 20));
+    }
+}
@@ -2,15 +2,16 @@
 // ref: https://wiki.gnucash.org/wiki/SQL
 
 use chrono::{DateTime, NaiveDateTime};
-use rusqlite::Row;
+use rusqlite::{Connection, Row, params};
 #[cfg(feature = "decimal")]
 use rust_decimal::Decimal;
 use tokio::task::spawn_blocking;
 use tracing::instrument;
 
-use super::SQLiteQuery;
+use super::lock::with_lock;
+use super::{DATETIME_FORMAT, SQLiteQuery};
 use crate::error::Error;
-use crate::query::{SplitQ, SplitT};
+use crate::query::{ReconcileRecord, SplitMutQ, SplitQ, SplitRecord, SplitT};
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
 pub struct Split {
@@ -68,6 +69,12 @@ impl SplitT for Split {
     fn reconcile_state(&self) -> bool {
         self.reconcile_state == "y" || self.reconcile_state == "Y"
     }
+    fn reconcile_flag(&self) -> char {
+        self.reconcile_state
+            .chars()
+            .next()
+            .map_or('n', |x| x.to_ascii_lowercase())
+    }
     fn reconcile_datetime(&self) -> Option<NaiveDateTime> {
         let datetime = self.reconcile_date?;
         if datetime.and_utc() == DateTime::UNIX_EPOCH {
@@ -79,6 +86,19 @@ impl SplitT for Split {
         self.lot_guid.as_deref().unwrap_or_default()
     }
 
+    fn value_num(&self) -> i64 {
+        self.value_num
+    }
+    fn value_denom(&self) -> i64 {
+        self.value_denom
+    }
+    fn quantity_num(&self) -> i64 {
+        self.quantity_num
+    }
+    fn quantity_denom(&self) -> i64 {
+        self.quantity_denom
+    }
+
     #[cfg(not(feature = "decimal"))]
     #[allow(clippy::cast_precision_loss)]
     fn value(&self) -> f64 {
@@ -119,6 +139,73 @@ lot_guid
 FROM splits
 ";
 
+// post_date is stored as text in DATETIME_FORMAT, which sorts by date.
+const BETWEEN: &str = r"
+SELECT
+splits.guid,
+splits.tx_guid,
+splits.account_guid,
+splits.memo,
+splits.action,
+splits.reconcile_state,
+splits.reconcile_date,
+splits.value_num,
+splits.value_denom,
+splits.quantity_num,
+splits.quantity_denom,
+splits.lot_guid,
+transactions.post_date
+FROM splits
+JOIN transactions ON transactions.guid = splits.tx_guid
+WHERE splits.account_guid = ?1
+AND (?2 IS NULL OR transactions.post_date >= ?2)
+AND (?3 IS NULL OR transactions.post_date < ?3)
+ORDER BY transactions.post_date
+";
+
+const INS: &str = r"
+INSERT INTO splits (
+guid,
+tx_guid,
+account_guid,
+memo,
+action,
+reconcile_state,
+reconcile_date,
+value_num,
+value_denom,
+quantity_num,
+quantity_denom,
+lot_guid
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+";
+
+/// Inserts a split row; the caller owns the surrounding database transaction.
+pub(crate) fn insert(conn: &Connection, split: &SplitRecord) -> Result<(), rusqlite::Error> {
+    let mut stmt = conn
+        .prepare_cached(INS)
+        .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+    stmt.execute(params![
+        split.guid,
+        split.tx_guid,
+        split.account_guid,
+        split.memo,
+        split.action,
+        split.reconcile_state.to_string(),
+        split
+            .reconcile_datetime
+            .map(|x| x.format(DATETIME_FORMAT).to_string()),
+        split.value_num,
+        split.value_denom,
+        split.quantity_num,
+        split.quantity_denom,
+        (!split.lot_guid.is_empty()).then_some(&split.lot_guid),
+    ])?;
+
+    Ok(())
+}
+
 impl SplitQ for SQLiteQuery {
     type Item = Split;
 
@@ -206,6 +293,45 @@ impl SplitQ for SQLiteQuery {
         .map_err(|e| Error::Internal(format!("Join error: {e}")))?
     }
 
+    #[instrument(skip(self))]
+    async fn account_between(
+        &self,
+        guid: &str,
+        from: Option<NaiveDateTime>,
+        to: Option<NaiveDateTime>,
+    ) -> Result<Vec<(NaiveDateTime, Self::Item)>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+        let from = from.map(|x| x.format(DATETIME_FORMAT).to_string());
+        let to = to.map(|x| x.format(DATETIME_FORMAT).to_string());
+
+        tokio::task::spawn_blocking(move || {
+            tracing::debug!("fetching splits by account_guid and post date from sqlite");
+
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(BETWEEN)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map(params![guid_owned, from, to], |row| {
+                Ok((row.get(12)?, Self::Item::try_from(row)?))
+            })?;
+
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(
+                count = items.len(),
+                "splits found by account_guid and post date"
+            );
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+
     #[instrument(skip(self))]
     async fn transaction(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
         let pool = self.pool.clone();
@@ -235,6 +361,65 @@ impl SplitQ for SQLiteQuery {
     }
 }
 
+const UPD_RECONCILE: &str = r"
+UPDATE splits SET
+reconcile_state = ?2,
+reconcile_date = COALESCE(?3, reconcile_date)
+WHERE guid = ?1
+";
+
+impl SplitMutQ for SQLiteQuery {
+    #[instrument(skip(self, splits), fields(split_count = splits.len()))]
+    async fn reconcile(&self, splits: &[ReconcileRecord]) -> Result<(), Error> {
+        let pool = self.writable_pool()?;
+        let force = self.ignore_lock;
+        let splits = splits.to_vec();
+
+        spawn_blocking(move || {
+            with_lock(&pool, force, || {
+                tracing::debug!("updating reconcile state in sqlite");
+
+                let mut conn = pool.get()?;
+                let tx = conn
+                    .transaction()
+                    .inspect_err(|e| tracing::error!("failed to begin transaction: {e}"))?;
+
+                {
+                    let mut stmt = tx
+                        .prepare_cached(UPD_RECONCILE)
+                        .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+                    for split in splits {
+                        let count = stmt
+                            .execute(params![
+                                split.guid,
+                                split.reconcile_state.to_string(),
+                                split
+                                    .reconcile_datetime
+                                    .map(|x| x.format(DATETIME_FORMAT).to_string()),
+                            ])
+                            .inspect_err(|e| tracing::error!("failed to update split: {e}"))?;
+                        if count == 0 {
+                            tracing::error!("split not found");
+                            return Err(Error::GuidNotFound {
+                                model: "Split".to_string(),
+                                guid: split.guid,
+                            });
+                        }
+                    }
+                }
+
+                tx.commit()
+                    .inspect_err(|e| tracing::error!("failed to commit transaction: {e}"))?;
+
+                tracing::debug!("reconcile state updated in sqlite");
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error in spawn_blocking: {e}")))?
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(not(feature = "decimal"))]
@@ -243,6 +428,8 @@ mod tests {
     use test_log::test;
     use tokio::sync::OnceCell;
 
+    use crate::query::sqlite::tests::setup_writable;
+
     use super::*;
 
     #[cfg(feature = "schema")]
@@ -309,6 +496,10 @@ mod tests {
         assert_approx_eq!(f64, result.quantity(), 150.0);
         #[cfg(feature = "decimal")]
         assert_eq!(result.quantity(), Decimal::new(150, 0));
+        assert_eq!(result.value_num(), 15000);
+        assert_eq!(result.value_denom(), 100);
+        assert_eq!(result.quantity_num(), 15000);
+        assert_eq!(result.quantity_denom(), 100);
     }
 
     #[test(tokio::test)]
@@ -343,6 +534,43 @@ mod tests {
         assert_eq!(result.len(), 3);
     }
 
+    #[test(tokio::test)]
+    async fn test_account_between() {
+        let query = setup().await;
+        let guid = "93fc043c3062aaa1297b30e543d2cd0d";
+        let date = |x| NaiveDateTime::parse_from_str(x, "%Y-%m-%d %H:%M:%S").unwrap();
+
+        let result = query.account_between(guid, None, None).await.unwrap();
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].0, date("2014-12-24 10:59:00"));
+        assert_eq!(result[0].1.tx_guid, "6c8876003c4a6026e38e3afb67d6f2b1");
+        assert!(result.is_sorted_by_key(|(x, _)| *x));
+        let result = query
+            .account_between(guid, None, Some(date("2018-01-01 00:00:00")))
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        let result = query
+            .account_between(guid, Some(date("2018-02-20 10:59:00")), None)
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 2);
+        let result = query
+            .account_between(
+                guid,
+                Some(date("2014-12-24 10:59:00")),
+                Some(date("2018-02-20 10:59:00")),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        let result = query
+            .account_between(guid, Some(date("2018-02-21 00:00:00")), None)
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 0);
+    }
+
     #[test(tokio::test)]
     async fn test_tx_guid() {
         let query = setup().await;
@@ -352,4 +580,77 @@ mod tests {
             .unwrap();
         assert_eq!(result.len(), 2);
     }
+
+    #[test(tokio::test)]
+    async fn test_reconcile() {
+        let (_dir, query) = setup_writable();
+        let datetime = NaiveDateTime::parse_from_str("2015-01-01 23:59:59", DATETIME_FORMAT).ok();
+        let record = |guid: &str, reconcile_state, reconcile_datetime| ReconcileRecord {
+            guid: guid.to_string(),
+            reconcile_state,
+            reconcile_datetime,
+        };
+
+        query
+            .reconcile(&[
+                record("de832fe97e37811a7fff7e28b3a43425", 'y', datetime),
+                record("1e612f650eb598d9e803902b6aca73e3", 'c', None),
+            ])
+            .await
+            .unwrap();
+        let result = query
+            .guid("de832fe97e37811a7fff7e28b3a43425")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.reconcile_flag(), 'y');
+        assert_eq!(result.reconcile_state(), true);
+        assert_eq!(result.reconcile_datetime(), datetime);
+        let result = query
+            .guid("1e612f650eb598d9e803902b6aca73e3")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.reconcile_flag(), 'c');
+        assert_eq!(result.reconcile_datetime(), None);
+
+        // a None date keeps the stored one
+        query
+            .reconcile(&[record("de832fe97e37811a7fff7e28b3a43425", 'n', None)])
+            .await
+            .unwrap();
+        let result = query
+            .guid("de832fe97e37811a7fff7e28b3a43425")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.reconcile_flag(), 'n');
+        assert_eq!(result.reconcile_datetime(), datetime);
+    }
+
+    #[test(tokio::test)]
+    async fn test_reconcile_missing() {
+        let (_dir, query) = setup_writable();
+        let records = [
+            ReconcileRecord {
+                guid: "de832fe97e37811a7fff7e28b3a43425".to_string(),
+                reconcile_state: 'c',
+                reconcile_datetime: None,
+            },
+            ReconcileRecord {
+                guid: "unknown".to_string(),
+                reconcile_state: 'c',
+                reconcile_datetime: None,
+            },
+        ];
+
+        let result = query.reconcile(&records).await;
+        assert!(matches!(result, Err(Error::GuidNotFound { .. })));
+        let result = query
+            .guid("de832fe97e37811a7fff7e28b3a43425")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.reconcile_flag(), 'n');
+    }
 }
@@ -0,0 +1,253 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use rusqlite::Row;
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::query::{TaxTableEntryQ, TaxTableEntryT, TaxTableQ, TaxTableT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct TaxTable {
+    pub guid: String,
+    pub name: String,
+    pub refcount: i64,
+    pub invisible: i64,
+    pub parent: Option<String>,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for TaxTable {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            guid: row.get(0)?,
+            name: row.get(1)?,
+            refcount: row.get(2)?,
+            invisible: row.get(3)?,
+            parent: row.get(4)?,
+        })
+    }
+}
+
+impl TaxTableT for TaxTable {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn refcount(&self) -> i64 {
+        self.refcount
+    }
+    fn invisible(&self) -> bool {
+        self.invisible != 0
+    }
+    fn parent_guid(&self) -> &str {
+        self.parent.as_deref().unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct TaxTableEntry {
+    pub taxtable: String,
+    pub account: String,
+    pub amount_num: i64,
+    pub amount_denom: i64,
+    pub r#type: i64,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for TaxTableEntry {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            taxtable: row.get(0)?,
+            account: row.get(1)?,
+            amount_num: row.get(2)?,
+            amount_denom: row.get(3)?,
+            r#type: row.get(4)?,
+        })
+    }
+}
+
+impl TaxTableEntryT for TaxTableEntry {
+    fn taxtable_guid(&self) -> &str {
+        &self.taxtable
+    }
+    fn account_guid(&self) -> &str {
+        &self.account
+    }
+    fn amount_num(&self) -> i64 {
+        self.amount_num
+    }
+    fn amount_denom(&self) -> i64 {
+        self.amount_denom
+    }
+    fn r#type(&self) -> i64 {
+        self.r#type
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+name,
+refcount,
+invisible,
+parent
+FROM taxtables
+";
+
+const SEL_TAXTABLE: &str = r"
+SELECT
+taxtable,
+account,
+amount_num,
+amount_denom,
+type
+FROM taxtable_entries
+WHERE taxtable = ?
+ORDER BY id
+";
+
+impl TaxTableQ for SQLiteQuery {
+    type Item = TaxTable;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching all tax tables from sqlite");
+
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "tax tables fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching tax table by guid from sqlite");
+            let conn = pool.get()?;
+
+            let sql = format!("{SEL}\nWHERE guid = ?");
+            let mut stmt = conn
+                .prepare_cached(&sql)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let result = stmt.query_row([guid_owned], |row| Self::Item::try_from(row));
+
+            match result {
+                Ok(item) => Ok(Some(item)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => {
+                    tracing::error!("failed to fetch row: {e}");
+                    Err(Error::from(e))
+                }
+            }
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+impl TaxTableEntryQ for SQLiteQuery {
+    type Item = TaxTableEntry;
+
+    #[instrument(skip(self))]
+    async fn taxtable(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching tax table entries by taxtable from sqlite");
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL_TAXTABLE)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([guid_owned], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "tax table entries fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::sqlite::tests::setup_with;
+    use crate::query::tests::fixtures::{BUSINESS, INVOICES, TAX};
+
+    #[test(tokio::test)]
+    async fn test_taxtable() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES, TAX]);
+        let result = TaxTableQ::guid(&query, "8a9b0c1d2e3f405162738495a6b7c801")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.name(), "VAT");
+        assert_eq!(result.refcount(), 4);
+        assert_eq!(result.invisible(), false);
+        assert_eq!(result.parent_guid(), "");
+    }
+
+    #[test(tokio::test)]
+    async fn test_entries() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES, TAX]);
+        let result = TaxTableEntryQ::taxtable(&query, "8a9b0c1d2e3f405162738495a6b7c802")
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(
+            result[0].taxtable_guid(),
+            "8a9b0c1d2e3f405162738495a6b7c802"
+        );
+        assert_eq!(result[0].account_guid(), "96ed7a45459fb5fe570e48fcd46f05d0");
+        assert_eq!(result[0].amount_num(), 10);
+        assert_eq!(result[0].r#type(), 2);
+        assert_eq!(result[1].account_guid(), "af88d386d44b14acf244362b85ccaf4c");
+        assert_eq!(result[1].amount_num(), 500);
+        assert_eq!(result[1].amount_denom(), 100);
+        assert_eq!(result[1].r#type(), 1);
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (_dir, query) = setup_with(&[BUSINESS, INVOICES, TAX]);
+        let result = TaxTableQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 2);
+    }
+}
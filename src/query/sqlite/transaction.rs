@@ -2,13 +2,17 @@
 // ref: https://wiki.gnucash.org/wiki/SQL
 
 use chrono::NaiveDateTime;
-use rusqlite::Row;
+use rusqlite::{Connection, Row, TransactionBehavior, params};
 use tokio::task::spawn_blocking;
 use tracing::instrument;
 
-use super::SQLiteQuery;
+use super::lock::with_lock;
+use super::slot::insert_string;
+use super::{DATETIME_FORMAT, SQLiteQuery};
 use crate::error::Error;
-use crate::query::{TransactionQ, TransactionT};
+use crate::query::{
+    SplitRecord, TransactionMutQ, TransactionQ, TransactionRecord, TransactionT, check_void,
+};
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
 pub struct Transaction {
@@ -155,12 +159,278 @@ impl TransactionQ for SQLiteQuery {
     }
 }
 
+const INS: &str = r"
+INSERT INTO transactions (
+guid,
+currency_guid,
+num,
+post_date,
+enter_date,
+description
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+";
+
+/// Inserts a transaction row without its splits; the caller owns the surrounding
+/// database transaction.
+pub(crate) fn insert(
+    conn: &Connection,
+    transaction: &TransactionRecord,
+) -> Result<(), rusqlite::Error> {
+    let mut stmt = conn
+        .prepare_cached(INS)
+        .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+    stmt.execute(params![
+        transaction.guid,
+        transaction.currency_guid,
+        transaction.num,
+        transaction
+            .post_datetime
+            .format(DATETIME_FORMAT)
+            .to_string(),
+        transaction
+            .enter_datetime
+            .format(DATETIME_FORMAT)
+            .to_string(),
+        transaction.description,
+    ])?;
+
+    Ok(())
+}
+
+/// Notes `GnuCash` puts on a voided transaction, its former notes are kept aside.
+const VOID_NOTES: &str = "Voided transaction";
+const VOID_READ_ONLY: &str = "Transaction Voided";
+/// `GnuCash` records the void time with its UTC offset.
+const VOID_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S %z";
+
+const INS_FORMER_AMOUNTS: &str = r"
+INSERT INTO slots (obj_guid, name, slot_type, int64_val, timespec_val, numeric_val_num, numeric_val_denom)
+SELECT guid, 'void-former-amount', 3, 0, '1970-01-01 00:00:00', quantity_num, quantity_denom
+FROM splits WHERE tx_guid = ?1
+UNION ALL
+SELECT guid, 'void-former-value', 3, 0, '1970-01-01 00:00:00', value_num, value_denom
+FROM splits WHERE tx_guid = ?1
+";
+
+const UPD_RESTORE_AMOUNTS: &str = r"
+UPDATE splits SET
+quantity_num = former_amount.numeric_val_num,
+quantity_denom = former_amount.numeric_val_denom,
+value_num = former_value.numeric_val_num,
+value_denom = former_value.numeric_val_denom,
+reconcile_state = 'n'
+FROM slots AS former_amount, slots AS former_value
+WHERE splits.tx_guid = ?1
+AND former_amount.obj_guid = splits.guid AND former_amount.name = 'void-former-amount'
+AND former_value.obj_guid = splits.guid AND former_value.name = 'void-former-value'
+";
+
+fn string_slot(
+    conn: &Connection,
+    guid: &str,
+    name: &str,
+) -> Result<Option<String>, rusqlite::Error> {
+    let mut stmt =
+        conn.prepare_cached("SELECT string_val FROM slots WHERE obj_guid = ?1 AND name = ?2")?;
+    match stmt.query_row(params![guid, name], |row| row.get(0)) {
+        Ok(value) => Ok(value),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn delete_slots(conn: &Connection, guid: &str, names: &[&str]) -> Result<(), rusqlite::Error> {
+    let mut stmt = conn.prepare_cached("DELETE FROM slots WHERE obj_guid = ?1 AND name = ?2")?;
+    for name in names {
+        stmt.execute(params![guid, name])?;
+    }
+    Ok(())
+}
+
+/// Whether the transaction carries the `void-reason` slot written by [`void`].
+fn is_voided(conn: &Connection, guid: &str) -> Result<bool, rusqlite::Error> {
+    conn.prepare_cached("SELECT 1 FROM slots WHERE obj_guid = ?1 AND name = 'void-reason'")?
+        .exists([guid])
+}
+
+/// Voids a transaction like `xaccTransVoid`; the caller owns the surrounding database transaction.
+fn void(
+    conn: &Connection,
+    guid: &str,
+    reason: &str,
+    datetime: NaiveDateTime,
+) -> Result<(), rusqlite::Error> {
+    if let Some(notes) = string_slot(conn, guid, "notes")? {
+        insert_string(conn, guid, "void-former-notes", &notes)?;
+    }
+    delete_slots(conn, guid, &["notes"])?;
+    insert_string(conn, guid, "notes", VOID_NOTES)?;
+    insert_string(conn, guid, "void-reason", reason)?;
+    insert_string(
+        conn,
+        guid,
+        "void-time",
+        &datetime.and_utc().format(VOID_TIME_FORMAT).to_string(),
+    )?;
+    insert_string(conn, guid, "trans-read-only", VOID_READ_ONLY)?;
+
+    conn.execute(INS_FORMER_AMOUNTS, [guid])?;
+    conn.execute(
+        "UPDATE splits SET quantity_num = 0, value_num = 0, reconcile_state = 'v' WHERE tx_guid = ?1",
+        [guid],
+    )?;
+
+    Ok(())
+}
+
+/// Reverts [`void`] like `xaccTransUnvoid`; the caller owns the surrounding database transaction.
+fn unvoid(conn: &Connection, guid: &str) -> Result<(), rusqlite::Error> {
+    conn.execute(UPD_RESTORE_AMOUNTS, [guid])?;
+    conn.execute(
+        r"
+        DELETE FROM slots
+        WHERE obj_guid IN (SELECT guid FROM splits WHERE tx_guid = ?1)
+        AND name IN ('void-former-amount', 'void-former-value')
+        ",
+        [guid],
+    )?;
+
+    if let Some(notes) = string_slot(conn, guid, "void-former-notes")? {
+        delete_slots(conn, guid, &["notes"])?;
+        insert_string(conn, guid, "notes", &notes)?;
+    }
+    delete_slots(
+        conn,
+        guid,
+        &[
+            "void-former-notes",
+            "void-reason",
+            "void-time",
+            "trans-read-only",
+        ],
+    )?;
+
+    Ok(())
+}
+
+impl TransactionMutQ for SQLiteQuery {
+    #[instrument(skip(self, transaction, splits), fields(transaction_guid = %transaction.guid))]
+    async fn insert(
+        &self,
+        transaction: &TransactionRecord,
+        splits: &[SplitRecord],
+    ) -> Result<(), Error> {
+        let pool = self.writable_pool()?;
+        let force = self.ignore_lock;
+        let transaction = transaction.clone();
+        let splits = splits.to_vec();
+
+        spawn_blocking(move || {
+            with_lock(&pool, force, || {
+                tracing::debug!("inserting transaction into sqlite");
+
+                let mut conn = pool.get()?;
+                let tx = conn
+                    .transaction()
+                    .inspect_err(|e| tracing::error!("failed to begin transaction: {e}"))?;
+
+                insert(&tx, &transaction)
+                    .inspect_err(|e| tracing::error!("failed to insert transaction: {e}"))?;
+
+                for split in &splits {
+                    super::split::insert(&tx, split)
+                        .inspect_err(|e| tracing::error!("failed to insert split: {e}"))?;
+                }
+
+                tx.commit()
+                    .inspect_err(|e| tracing::error!("failed to commit transaction: {e}"))?;
+
+                tracing::debug!(
+                    split_count = splits.len(),
+                    "transaction inserted into sqlite"
+                );
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error in spawn_blocking: {e}")))?
+    }
+
+    #[instrument(skip(self, reason))]
+    async fn void(&self, guid: &str, reason: &str, datetime: NaiveDateTime) -> Result<(), Error> {
+        let pool = self.writable_pool()?;
+        let force = self.ignore_lock;
+        let guid_owned = guid.to_string();
+        let reason = reason.to_string();
+
+        spawn_blocking(move || {
+            with_lock(&pool, force, || {
+                tracing::debug!("voiding transaction in sqlite");
+
+                let mut conn = pool.get()?;
+                // taking the write lock up front keeps a concurrent call from passing the check
+                let tx = conn
+                    .transaction_with_behavior(TransactionBehavior::Immediate)
+                    .inspect_err(|e| tracing::error!("failed to begin transaction: {e}"))?;
+
+                check_void(is_voided(&tx, &guid_owned)?, true)?;
+
+                void(&tx, &guid_owned, &reason, datetime)
+                    .inspect_err(|e| tracing::error!("failed to void transaction: {e}"))?;
+
+                tx.commit()
+                    .inspect_err(|e| tracing::error!("failed to commit transaction: {e}"))?;
+
+                tracing::debug!("transaction voided in sqlite");
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error in spawn_blocking: {e}")))?
+    }
+
+    #[instrument(skip(self))]
+    async fn unvoid(&self, guid: &str) -> Result<(), Error> {
+        let pool = self.writable_pool()?;
+        let force = self.ignore_lock;
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            with_lock(&pool, force, || {
+                tracing::debug!("unvoiding transaction in sqlite");
+
+                let mut conn = pool.get()?;
+                // taking the write lock up front keeps a concurrent call from passing the check
+                let tx = conn
+                    .transaction_with_behavior(TransactionBehavior::Immediate)
+                    .inspect_err(|e| tracing::error!("failed to begin transaction: {e}"))?;
+
+                check_void(is_voided(&tx, &guid_owned)?, false)?;
+
+                unvoid(&tx, &guid_owned)
+                    .inspect_err(|e| tracing::error!("failed to unvoid transaction: {e}"))?;
+
+                tx.commit()
+                    .inspect_err(|e| tracing::error!("failed to commit transaction: {e}"))?;
+
+                tracing::debug!("transaction unvoided in sqlite");
+                Ok(())
+            })
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error in spawn_blocking: {e}")))?
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
     use test_log::test;
     use tokio::sync::OnceCell;
 
+    use crate::query::sqlite::tests::setup_writable;
+
     use super::*;
 
     #[cfg(feature = "schema")]
@@ -256,4 +526,115 @@ mod tests {
 
         assert_eq!(result.len(), 11);
     }
+
+    #[test(tokio::test)]
+    async fn test_insert() {
+        let (_dir, query) = setup_writable();
+
+        let datetime =
+            NaiveDateTime::parse_from_str("2024-01-02 10:59:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let transaction = TransactionRecord {
+            guid: "0123456789abcdef0123456789abcdef".to_string(),
+            currency_guid: "346629655191dcf59a7e2c2a85b70f69".to_string(),
+            num: "42".to_string(),
+            post_datetime: datetime,
+            enter_datetime: datetime,
+            description: "inserted".to_string(),
+        };
+        let split = |guid: &str, account_guid: &str, num: i64| SplitRecord {
+            guid: guid.to_string(),
+            tx_guid: transaction.guid.clone(),
+            account_guid: account_guid.to_string(),
+            memo: String::new(),
+            action: String::new(),
+            reconcile_state: 'n',
+            reconcile_datetime: None,
+            value_num: num,
+            value_denom: 100,
+            quantity_num: num,
+            quantity_denom: 100,
+            lot_guid: String::new(),
+        };
+        let splits = [
+            split(
+                "10000000000000000000000000000001",
+                "93fc043c3062aaa1297b30e543d2cd0d",
+                1000,
+            ),
+            split(
+                "10000000000000000000000000000002",
+                "af88d386d44b14acf244362b85ccaf4c",
+                -1000,
+            ),
+        ];
+
+        TransactionMutQ::insert(&query, &transaction, &splits)
+            .await
+            .unwrap();
+
+        let result = query.guid(&transaction.guid).await.unwrap().unwrap();
+        assert_eq!(result.num(), "42");
+        assert_eq!(result.post_datetime(), datetime);
+        assert_eq!(result.description(), "inserted");
+        assert_eq!(query.all().await.unwrap().len(), 12);
+
+        let splits = crate::query::SplitQ::transaction(&query, &transaction.guid)
+            .await
+            .unwrap();
+        assert_eq!(splits.len(), 2);
+        assert_eq!(splits[0].reconcile_date, None);
+        assert_eq!(splits[0].lot_guid, None);
+    }
+
+    #[test(tokio::test)]
+    async fn test_insert_read_only() {
+        let query = setup().await;
+        let datetime =
+            NaiveDateTime::parse_from_str("2024-01-02 10:59:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let transaction = TransactionRecord {
+            guid: "0123456789abcdef0123456789abcdef".to_string(),
+            currency_guid: "346629655191dcf59a7e2c2a85b70f69".to_string(),
+            num: String::new(),
+            post_datetime: datetime,
+            enter_datetime: datetime,
+            description: String::new(),
+        };
+
+        let result = TransactionMutQ::insert(query, &transaction, &[]).await;
+        assert!(matches!(result, Err(Error::ReadOnly)));
+    }
+
+    #[test(tokio::test)]
+    async fn test_void_twice() {
+        let (_dir, query) = setup_writable();
+        let guid = "6c8876003c4a6026e38e3afb67d6f2b1";
+        let datetime =
+            NaiveDateTime::parse_from_str("2024-01-02 10:59:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        let (first, second) = tokio::join!(
+            TransactionMutQ::void(&query, guid, "first", datetime),
+            TransactionMutQ::void(&query, guid, "second", datetime),
+        );
+        // exactly one of the concurrent calls voids the transaction
+        assert!(first.is_ok() != second.is_ok());
+        assert!(matches!(first.and(second), Err(Error::Invalid { .. })));
+
+        let splits = crate::query::SplitQ::transaction(&query, guid)
+            .await
+            .unwrap();
+        for split in &splits {
+            let slots = crate::query::SlotQ::obj_guid(&query, &split.guid)
+                .await
+                .unwrap();
+            assert_eq!(slots.len(), 2);
+        }
+
+        TransactionMutQ::unvoid(&query, guid).await.unwrap();
+        let result = TransactionMutQ::unvoid(&query, guid).await;
+        assert!(matches!(result, Err(Error::Invalid { .. })));
+        let splits = crate::query::SplitQ::transaction(&query, guid)
+            .await
+            .unwrap();
+        assert!(splits.iter().any(|x| x.value_num != 0));
+    }
 }
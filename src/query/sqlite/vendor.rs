@@ -0,0 +1,230 @@
+// ref: https://piecash.readthedocs.io/en/master/object_model.html
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use rusqlite::Row;
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::model::Address;
+use crate::query::{VendorQ, VendorT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Vendor {
+    pub guid: String,
+    pub name: String,
+    pub id: String,
+    pub notes: String,
+    pub currency: String,
+    pub active: i64,
+    pub tax_override: i64,
+    pub addr_name: Option<String>,
+    pub addr_addr1: Option<String>,
+    pub addr_addr2: Option<String>,
+    pub addr_addr3: Option<String>,
+    pub addr_addr4: Option<String>,
+    pub addr_phone: Option<String>,
+    pub addr_fax: Option<String>,
+    pub addr_email: Option<String>,
+    pub terms: Option<String>,
+    pub tax_inc: Option<String>,
+    pub tax_table: Option<String>,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for Vendor {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            guid: row.get(0)?,
+            name: row.get(1)?,
+            id: row.get(2)?,
+            notes: row.get(3)?,
+            currency: row.get(4)?,
+            active: row.get(5)?,
+            tax_override: row.get(6)?,
+            addr_name: row.get(7)?,
+            addr_addr1: row.get(8)?,
+            addr_addr2: row.get(9)?,
+            addr_addr3: row.get(10)?,
+            addr_addr4: row.get(11)?,
+            addr_phone: row.get(12)?,
+            addr_fax: row.get(13)?,
+            addr_email: row.get(14)?,
+            terms: row.get(15)?,
+            tax_inc: row.get(16)?,
+            tax_table: row.get(17)?,
+        })
+    }
+}
+
+impl VendorT for Vendor {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn notes(&self) -> &str {
+        &self.notes
+    }
+    fn currency_guid(&self) -> &str {
+        &self.currency
+    }
+    fn active(&self) -> bool {
+        self.active != 0
+    }
+    fn tax_override(&self) -> bool {
+        self.tax_override != 0
+    }
+    fn addr(&self) -> Address {
+        Address {
+            name: self.addr_name.clone().unwrap_or_default(),
+            addr1: self.addr_addr1.clone().unwrap_or_default(),
+            addr2: self.addr_addr2.clone().unwrap_or_default(),
+            addr3: self.addr_addr3.clone().unwrap_or_default(),
+            addr4: self.addr_addr4.clone().unwrap_or_default(),
+            phone: self.addr_phone.clone().unwrap_or_default(),
+            fax: self.addr_fax.clone().unwrap_or_default(),
+            email: self.addr_email.clone().unwrap_or_default(),
+        }
+    }
+    fn terms_guid(&self) -> &str {
+        self.terms.as_deref().unwrap_or_default()
+    }
+    fn tax_included(&self) -> &str {
+        self.tax_inc.as_deref().unwrap_or("USEGLOBAL")
+    }
+    fn taxtable_guid(&self) -> &str {
+        self.tax_table.as_deref().unwrap_or_default()
+    }
+}
+
+const SEL: &str = r"
+SELECT
+guid,
+name,
+id,
+notes,
+currency,
+active,
+tax_override,
+addr_name,
+addr_addr1,
+addr_addr2,
+addr_addr3,
+addr_addr4,
+addr_phone,
+addr_fax,
+addr_email,
+terms,
+tax_inc,
+tax_table
+FROM vendors
+";
+
+impl VendorQ for SQLiteQuery {
+    type Item = Vendor;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching all vendors from sqlite");
+
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "vendors fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        let pool = self.pool.clone();
+        let guid_owned = guid.to_string();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching vendor by guid from sqlite");
+            let conn = pool.get()?;
+
+            let sql = format!("{SEL}\nWHERE guid = ?");
+            let mut stmt = conn
+                .prepare_cached(&sql)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let result = stmt.query_row([guid_owned], |row| Self::Item::try_from(row));
+
+            match result {
+                Ok(item) => Ok(Some(item)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => {
+                    tracing::error!("failed to fetch row: {e}");
+                    Err(Error::from(e))
+                }
+            }
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+    use crate::query::sqlite::tests::setup_with;
+    use crate::query::tests::fixtures::BUSINESS;
+
+    #[test(tokio::test)]
+    async fn test_vendor() {
+        let (_dir, query) = setup_with(&[BUSINESS]);
+        let result = VendorQ::guid(&query, "e5f6a7b8c9d0e1f2a3b4c5d6e7f80912")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(result.name(), "Paper Supplies Ltd");
+        assert_eq!(result.id(), "000001");
+        assert_eq!(result.notes(), "");
+        assert_eq!(result.currency_guid(), "346629655191dcf59a7e2c2a85b70f69");
+        assert_eq!(result.active(), true);
+        assert_eq!(result.tax_override(), false);
+        assert_eq!(result.addr().addr1, "Mill Lane 3");
+        assert_eq!(result.terms_guid(), "");
+        assert_eq!(result.tax_included(), "YES");
+        assert_eq!(result.taxtable_guid(), "");
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let (_dir, query) = setup_with(&[BUSINESS]);
+        let result = VendorQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test(tokio::test)]
+    async fn test_guid() {
+        let (_dir, query) = setup_with(&[BUSINESS]);
+        let result = VendorQ::guid(&query, "unknown").await.unwrap();
+        assert_eq!(result, None);
+    }
+}
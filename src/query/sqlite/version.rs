@@ -0,0 +1,95 @@
+// ref: https://wiki.gnucash.org/wiki/SQL
+
+use rusqlite::Row;
+use tokio::task::spawn_blocking;
+use tracing::instrument;
+
+use super::SQLiteQuery;
+use crate::error::Error;
+use crate::query::{VersionQ, VersionT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Version {
+    pub(crate) table_name: String,
+    pub(crate) table_version: i64,
+}
+
+impl<'a> TryFrom<&'a Row<'a>> for Version {
+    type Error = rusqlite::Error;
+
+    fn try_from(row: &'a Row<'a>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            table_name: row.get(0)?,
+            table_version: row.get(1)?,
+        })
+    }
+}
+
+impl VersionT for Version {
+    fn table_name(&self) -> &str {
+        &self.table_name
+    }
+    fn table_version(&self) -> i64 {
+        self.table_version
+    }
+}
+
+const SEL: &str = r"
+SELECT
+table_name,
+table_version
+FROM versions
+";
+
+impl VersionQ for SQLiteQuery {
+    type Item = Version;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            tracing::debug!("fetching all versions from sqlite");
+
+            let conn = pool.get()?;
+
+            let mut stmt = conn
+                .prepare_cached(SEL)
+                .inspect_err(|e| tracing::error!("failed to prepare statement: {e}"))?;
+
+            let rows = stmt.query_map([], |row| Self::Item::try_from(row))?;
+            let items = rows
+                .collect::<Result<Vec<_>, _>>()
+                .inspect_err(|e| tracing::error!("failed to collect rows: {e}"))?;
+
+            tracing::debug!(count = items.len(), "versions fetched from sqlite");
+            Ok(items)
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Join error: {e}")))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let uri: &str = &format!(
+            "{}/tests/db/sqlite/complex_sample.gnucash",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let query = SQLiteQuery::new(uri).unwrap();
+        let result = VersionQ::all(&query).await.unwrap();
+        assert_eq!(result.len(), 24);
+
+        let gnucash = result.iter().find(|x| x.table_name() == "Gnucash").unwrap();
+        assert_eq!(gnucash.table_version(), 3_000_005);
+        let splits = result.iter().find(|x| x.table_name() == "splits").unwrap();
+        assert_eq!(splits.table_version(), 5);
+    }
+}
@@ -1,11 +1,26 @@
 pub(crate) mod account;
+pub(crate) mod billterm;
+pub(crate) mod book;
+pub(crate) mod budget;
 pub(crate) mod commodity;
+pub(crate) mod customer;
+pub(crate) mod employee;
+pub(crate) mod entry;
+pub(crate) mod invoice;
+pub(crate) mod job;
+pub(crate) mod lot;
+pub(crate) mod order;
 pub(crate) mod price;
+pub(crate) mod recurrence;
+pub(crate) mod scheduled_transaction;
+pub(crate) mod slot;
 pub(crate) mod split;
+pub(crate) mod taxtable;
 pub(crate) mod transaction;
+pub(crate) mod vendor;
 
 use flate2::read::GzDecoder;
-use roxmltree::Document;
+use roxmltree::{Document, Node};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
@@ -16,43 +31,111 @@ use tracing::instrument;
 
 use super::Query;
 use crate::error::Error;
+use crate::model::Address;
 use account::Account;
+use billterm::BillTerm;
+use book::Book;
+use budget::{Budget, BudgetAmount};
 use commodity::Commodity;
+use customer::Customer;
+use employee::Employee;
+use entry::Entry;
+use invoice::Invoice;
+use job::Job;
+use lot::Lot;
+use order::Order;
 use price::Price;
+use recurrence::Recurrence;
+use scheduled_transaction::ScheduledTransaction;
+use slot::Slot;
 use split::Split;
+use taxtable::{TaxTable, TaxTableEntry};
 use transaction::Transaction;
+use vendor::Vendor;
 
 type AccountMap = Arc<HashMap<String, Arc<Account>>>;
 type AccountsMap = Arc<HashMap<String, Vec<Arc<Account>>>>;
 
+type BillTermMap = Arc<HashMap<String, Arc<BillTerm>>>;
+
+type BudgetMap = Arc<HashMap<String, Arc<Budget>>>;
+type BudgetAmountsMap = Arc<HashMap<String, Vec<Arc<BudgetAmount>>>>;
+
 type CommodityMap = Arc<HashMap<String, Arc<Commodity>>>;
 type CommoditiesMap = Arc<HashMap<String, Vec<Arc<Commodity>>>>;
 
+type CustomerMap = Arc<HashMap<String, Arc<Customer>>>;
+
+type EmployeeMap = Arc<HashMap<String, Arc<Employee>>>;
+
+type EntryMap = Arc<HashMap<String, Arc<Entry>>>;
+
+type InvoiceMap = Arc<HashMap<String, Arc<Invoice>>>;
+
+type JobMap = Arc<HashMap<String, Arc<Job>>>;
+
+type LotMap = Arc<HashMap<String, Arc<Lot>>>;
+type LotsMap = Arc<HashMap<String, Vec<Arc<Lot>>>>;
+
+type OrderMap = Arc<HashMap<String, Arc<Order>>>;
+
 type PriceMap = Arc<HashMap<String, Arc<Price>>>;
 type PricesMap = Arc<HashMap<String, Vec<Arc<Price>>>>;
 
+type RecurrencesMap = Arc<HashMap<String, Vec<Arc<Recurrence>>>>;
+
+type ScheduledTransactionMap = Arc<HashMap<String, Arc<ScheduledTransaction>>>;
+
+type SlotsMap = Arc<HashMap<String, Vec<Arc<Slot>>>>;
+
 type SplitMap = Arc<HashMap<String, Arc<Split>>>;
 type SplitsMap = Arc<HashMap<String, Vec<Arc<Split>>>>;
+type DatedSplitsMap = Arc<HashMap<String, Vec<(chrono::NaiveDateTime, Arc<Split>)>>>;
+
+type TaxTableMap = Arc<HashMap<String, Arc<TaxTable>>>;
+type TaxTableEntriesMap = Arc<HashMap<String, Vec<Arc<TaxTableEntry>>>>;
 
 type TransactionMap = Arc<HashMap<String, Arc<Transaction>>>;
 type TransactionsMap = Arc<HashMap<String, Vec<Arc<Transaction>>>>;
 
+type VendorMap = Arc<HashMap<String, Arc<Vendor>>>;
+
 #[derive(Debug, Default, Clone)]
 struct XMLCache {
+    book: Arc<Book>,
     accounts: AccountMap,
     commodity_accounts: AccountsMap,
     same_parent_accounts: AccountsMap,
     name_accounts: AccountsMap,
+    billterms: BillTermMap,
+    budgets: BudgetMap,
+    budget_amounts: BudgetAmountsMap,
     commodities: CommodityMap,
     namespace_commodities: CommoditiesMap,
+    customers: CustomerMap,
+    employees: EmployeeMap,
+    entries: EntryMap,
+    invoices: InvoiceMap,
+    jobs: JobMap,
+    lots: LotMap,
+    account_lots: LotsMap,
+    orders: OrderMap,
     prices: PriceMap,
     commodity_prices: PricesMap,
     currency_prices: PricesMap,
+    recurrences: RecurrencesMap,
+    scheduled_transactions: ScheduledTransactionMap,
+    slots: SlotsMap,
     splits: SplitMap,
     account_splits: SplitsMap,
+    /// The splits of each account with their transaction's post date, in date order.
+    account_dated_splits: DatedSplitsMap,
     transaction_splits: SplitsMap,
+    taxtables: TaxTableMap,
+    taxtable_entries: TaxTableEntriesMap,
     transactions: TransactionMap,
     currency_transactions: TransactionsMap,
+    vendors: VendorMap,
 }
 
 #[derive(Debug, Clone)]
@@ -90,41 +173,85 @@ impl XMLQuery {
             .find(|n| n.has_tag_name("book"))
             .ok_or_else(|| Error::NoBook(path.display().to_string()))?;
 
+        let bk = Arc::new(Book::try_from(book)?);
         let (acc, acc_c, acc_p, acc_n) = Self::parse_accounts_map(book)?;
+        let bt = Self::parse_billterm_map(book)?;
+        let (bgt, bgt_a) = Self::parse_budget_map(book)?;
         let (comm, comm_n) = Self::parse_commodity_map(book)?;
+        let cust = Self::parse_customer_map(book)?;
+        let emp = Self::parse_employee_map(book)?;
+        let ent = Self::parse_entry_map(book)?;
+        let inv = Self::parse_invoice_map(book)?;
+        let job = Self::parse_job_map(book)?;
+        let (lot, lot_a) = Self::parse_lot_map(book)?;
+        let ord = Self::parse_order_map(book)?;
         let (prc, prc_c, prc_cur) = Self::parse_price_map(book)?;
+        let rec = Self::parse_recurrence_map(book)?;
+        let sx = Self::parse_scheduled_transaction_map(book)?;
+        let slt = Self::parse_slot_map(book)?;
         let (spl, spl_a, spl_t) = Self::parse_split_map(book)?;
+        let (tt, tt_e) = Self::parse_taxtable_map(book)?;
         let (txn, txn_c) = Self::parse_transaction_map(book)?;
+        let vnd = Self::parse_vendor_map(book)?;
+        let spl_d = Self::index_splits_by_date(&spl_a, &txn);
 
         Ok(XMLCache {
+            book: bk,
             accounts: acc,
             commodity_accounts: acc_c,
             same_parent_accounts: acc_p,
             name_accounts: acc_n,
+            billterms: bt,
+            budgets: bgt,
+            budget_amounts: bgt_a,
             commodities: comm,
             namespace_commodities: comm_n,
+            customers: cust,
+            employees: emp,
+            entries: ent,
+            invoices: inv,
+            jobs: job,
+            lots: lot,
+            account_lots: lot_a,
+            orders: ord,
             prices: prc,
             commodity_prices: prc_c,
             currency_prices: prc_cur,
+            recurrences: rec,
+            scheduled_transactions: sx,
+            slots: slt,
             splits: spl,
             account_splits: spl_a,
+            account_dated_splits: spl_d,
             transaction_splits: spl_t,
+            taxtables: tt,
+            taxtable_entries: tt_e,
             transactions: txn,
             currency_transactions: txn_c,
+            vendors: vnd,
         })
     }
 
     #[instrument(skip(file_path), fields(path = %file_path.display()))]
     fn gnucash_data(file_path: &Path) -> Result<String, Error> {
-        tracing::debug!("opening gzip file");
-        let f =
-            File::open(file_path).inspect_err(|e| tracing::error!("failed to open file: {e}"))?;
-        let mut d = GzDecoder::new(f);
-        let mut data = String::new();
+        tracing::debug!("opening gnucash xml file");
+        let mut raw = Vec::new();
+        File::open(file_path)
+            .and_then(|mut f| f.read_to_end(&mut raw))
+            .inspect_err(|e| tracing::error!("failed to read file: {e}"))?;
 
-        tracing::debug!("decompressing gzip data");
-        d.read_to_string(&mut data)
-            .inspect_err(|e| tracing::error!("failed to decompress data: {e}"))?;
+        // `GnuCash` only compresses the file when its compression preference is on
+        let mut data = String::new();
+        if raw.starts_with(&[0x1f, 0x8b]) {
+            tracing::debug!("decompressing gzip data");
+            GzDecoder::new(raw.as_slice())
+                .read_to_string(&mut data)
+                .inspect_err(|e| tracing::error!("failed to decompress data: {e}"))?;
+        } else {
+            data = String::from_utf8(raw)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+                .inspect_err(|e| tracing::error!("failed to decode data: {e}"))?;
+        }
 
         tracing::debug!(size = data.len(), "gnucash data loaded");
         Ok(data)
@@ -202,6 +329,137 @@ impl XMLQuery {
         Ok((Arc::new(commodity_map), Arc::new(namespace_commodities)))
     }
 
+    fn parse_billterm_map(book: roxmltree::Node) -> Result<BillTermMap, Error> {
+        let mut billterm_map = HashMap::new();
+
+        for n in book.children().filter(|n| n.has_tag_name("GncBillTerm")) {
+            let billterm = Arc::new(BillTerm::try_from(n)?);
+            billterm_map.insert(billterm.guid.clone(), billterm);
+        }
+
+        Ok(Arc::new(billterm_map))
+    }
+
+    fn parse_budget_map(book: roxmltree::Node) -> Result<(BudgetMap, BudgetAmountsMap), Error> {
+        let mut budget_map = HashMap::new();
+        let mut budget_amounts_map: HashMap<String, Vec<Arc<BudgetAmount>>> = HashMap::new();
+
+        for n in book.children().filter(|n| n.has_tag_name("budget")) {
+            let budget = Arc::new(Budget::try_from(n)?);
+
+            if let Some(slots) = n.children().find(|n| n.has_tag_name("slots")) {
+                budget_amounts_map.insert(
+                    budget.guid.clone(),
+                    BudgetAmount::parse_all(&budget.guid, slots)?
+                        .into_iter()
+                        .map(Arc::new)
+                        .collect(),
+                );
+            }
+            budget_map.insert(budget.guid.clone(), budget);
+        }
+
+        Ok((Arc::new(budget_map), Arc::new(budget_amounts_map)))
+    }
+
+    fn parse_customer_map(book: roxmltree::Node) -> Result<CustomerMap, Error> {
+        let mut customer_map = HashMap::new();
+
+        for n in book.children().filter(|n| n.has_tag_name("GncCustomer")) {
+            let customer = Arc::new(Customer::try_from(n)?);
+            customer_map.insert(customer.guid.clone(), customer);
+        }
+
+        Ok(Arc::new(customer_map))
+    }
+
+    fn parse_employee_map(book: roxmltree::Node) -> Result<EmployeeMap, Error> {
+        let mut employee_map = HashMap::new();
+
+        for n in book.children().filter(|n| n.has_tag_name("GncEmployee")) {
+            let employee = Arc::new(Employee::try_from(n)?);
+            employee_map.insert(employee.guid.clone(), employee);
+        }
+
+        Ok(Arc::new(employee_map))
+    }
+
+    fn parse_entry_map(book: roxmltree::Node) -> Result<EntryMap, Error> {
+        let mut entry_map = HashMap::new();
+
+        for n in book.children().filter(|n| n.has_tag_name("GncEntry")) {
+            let entry = Arc::new(Entry::try_from(n)?);
+            entry_map.insert(entry.guid.clone(), entry);
+        }
+
+        Ok(Arc::new(entry_map))
+    }
+
+    fn parse_invoice_map(book: roxmltree::Node) -> Result<InvoiceMap, Error> {
+        let mut invoice_map = HashMap::new();
+
+        for n in book.children().filter(|n| n.has_tag_name("GncInvoice")) {
+            let invoice = Arc::new(Invoice::try_from(n)?);
+            invoice_map.insert(invoice.guid.clone(), invoice);
+        }
+
+        Ok(Arc::new(invoice_map))
+    }
+
+    fn parse_job_map(book: roxmltree::Node) -> Result<JobMap, Error> {
+        let mut job_map = HashMap::new();
+
+        for n in book.children().filter(|n| n.has_tag_name("GncJob")) {
+            let job = Arc::new(Job::try_from(n)?);
+            job_map.insert(job.guid.clone(), job);
+        }
+
+        Ok(Arc::new(job_map))
+    }
+
+    fn parse_lot_map(book: roxmltree::Node) -> Result<(LotMap, LotsMap), Error> {
+        let mut lot_map = HashMap::new();
+        let mut account_lots_map: HashMap<String, Vec<Arc<Lot>>> = HashMap::new();
+
+        for account in book.children().filter(|n| n.has_tag_name("account")) {
+            let Some(lots) = account.children().find(|n| n.has_tag_name("lots")) else {
+                continue;
+            };
+            let account_guid = account
+                .children()
+                .find(|n| n.has_tag_name("id"))
+                .and_then(|n| n.text())
+                .map(std::string::ToString::to_string)
+                .ok_or_else(|| Error::XMLMissingField {
+                    model: "Account".to_string(),
+                    field: "guid".to_string(),
+                })?;
+
+            for n in lots.children().filter(|n| n.has_tag_name("lot")) {
+                let lot = Arc::new(Lot::try_from(account_guid.clone(), n)?);
+                lot_map.insert(lot.guid.clone(), lot.clone());
+
+                account_lots_map
+                    .entry(account_guid.clone())
+                    .or_default()
+                    .push(lot);
+            }
+        }
+
+        Ok((Arc::new(lot_map), Arc::new(account_lots_map)))
+    }
+
+    fn parse_order_map(book: roxmltree::Node) -> Result<OrderMap, Error> {
+        let mut order_map = HashMap::new();
+
+        for n in book.children().filter(|n| n.has_tag_name("GncOrder")) {
+            let order = Arc::new(Order::try_from(n)?);
+            order_map.insert(order.guid.clone(), order);
+        }
+
+        Ok(Arc::new(order_map))
+    }
+
     fn parse_price_map(book: roxmltree::Node) -> Result<(PriceMap, PricesMap, PricesMap), Error> {
         let mut price_map = HashMap::new();
         let mut commodity_prices: HashMap<String, Vec<Arc<Price>>> = HashMap::new();
@@ -232,12 +490,98 @@ impl XMLQuery {
         ))
     }
 
+    /// Collects the `*:slots` of every object, keyed by the GUID of their owner.
+    fn parse_recurrence_map(book: roxmltree::Node) -> Result<RecurrencesMap, Error> {
+        let mut recurrences_map: HashMap<String, Vec<Arc<Recurrence>>> = HashMap::new();
+
+        // budgets hold theirs directly, scheduled transactions inside `sx:schedule`
+        for obj in book
+            .children()
+            .filter(|n| n.has_tag_name("budget") || n.has_tag_name("schedxaction"))
+        {
+            let obj_guid = obj
+                .children()
+                .find(|n| n.has_tag_name("id"))
+                .and_then(|n| n.text())
+                .ok_or_else(|| Error::XMLMissingField {
+                    model: "Recurrence".to_string(),
+                    field: "obj_guid".to_string(),
+                })?;
+            let schedule = obj.children().find(|n| n.has_tag_name("schedule"));
+
+            for n in schedule
+                .unwrap_or(obj)
+                .children()
+                .filter(|n| n.has_tag_name("recurrence"))
+            {
+                recurrences_map
+                    .entry(obj_guid.to_string())
+                    .or_default()
+                    .push(Arc::new(Recurrence::try_from(obj_guid, n)?));
+            }
+        }
+
+        Ok(Arc::new(recurrences_map))
+    }
+
+    fn parse_scheduled_transaction_map(
+        book: roxmltree::Node,
+    ) -> Result<ScheduledTransactionMap, Error> {
+        let mut scheduled_transaction_map = HashMap::new();
+
+        for n in book.children().filter(|n| n.has_tag_name("schedxaction")) {
+            let sx = Arc::new(ScheduledTransaction::try_from(n)?);
+            scheduled_transaction_map.insert(sx.guid.clone(), sx);
+        }
+
+        Ok(Arc::new(scheduled_transaction_map))
+    }
+
+    fn parse_slot_map(book: roxmltree::Node) -> Result<SlotsMap, Error> {
+        let mut slots_map: HashMap<String, Vec<Arc<Slot>>> = HashMap::new();
+
+        for n in book.descendants().filter(|n| n.has_tag_name("slots")) {
+            let Some(obj_guid) = n
+                .parent_element()
+                .and_then(|p| p.children().find(|n| n.has_tag_name("id")))
+                .and_then(|n| n.text())
+            else {
+                continue;
+            };
+
+            let mut slots = Vec::new();
+            Slot::parse_all(obj_guid, "", n, &mut slots)?;
+            for slot in slots {
+                slots_map
+                    .entry(slot.obj_guid.clone())
+                    .or_default()
+                    .push(Arc::new(slot));
+            }
+        }
+
+        Ok(Arc::new(slots_map))
+    }
+
+    /// The transactions of the book followed by the templates of its scheduled transactions,
+    /// which the SQL backends keep in the same table.
+    fn transaction_nodes<'a, 'input>(
+        book: roxmltree::Node<'a, 'input>,
+    ) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+        book.children()
+            .chain(
+                book.children()
+                    .filter(|n| n.has_tag_name("template-transactions"))
+                    .flat_map(|n| n.children()),
+            )
+            .filter(|n| n.has_tag_name("transaction"))
+    }
+
     fn parse_split_map(book: roxmltree::Node) -> Result<(SplitMap, SplitsMap, SplitsMap), Error> {
         let mut split_map = HashMap::new();
         let mut account_splits_map: HashMap<String, Vec<Arc<Split>>> = HashMap::new();
         let mut transaction_splits_map: HashMap<String, Vec<Arc<Split>>> = HashMap::new();
 
-        for transaction in book.children().filter(|n| n.has_tag_name("transaction")) {
+        for transaction in Self::transaction_nodes(book) {
             let tx_guid = transaction
                 .children()
                 .find(|n| n.has_tag_name("id"))
@@ -280,13 +624,58 @@ impl XMLQuery {
         ))
     }
 
+    fn index_splits_by_date(
+        account_splits: &SplitsMap,
+        transactions: &TransactionMap,
+    ) -> DatedSplitsMap {
+        let mut dated_splits_map = HashMap::new();
+
+        for (account_guid, splits) in account_splits.iter() {
+            let mut dated: Vec<_> = splits
+                .iter()
+                .filter_map(|x| {
+                    let transaction = transactions.get(&x.tx_guid)?;
+                    Some((transaction.post_date, x.clone()))
+                })
+                .collect();
+            dated.sort_by_key(|(date, _)| *date);
+            dated_splits_map.insert(account_guid.clone(), dated);
+        }
+
+        Arc::new(dated_splits_map)
+    }
+
+    fn parse_taxtable_map(
+        book: roxmltree::Node,
+    ) -> Result<(TaxTableMap, TaxTableEntriesMap), Error> {
+        let mut taxtable_map = HashMap::new();
+        let mut taxtable_entries_map = HashMap::new();
+
+        for n in book.children().filter(|n| n.has_tag_name("GncTaxTable")) {
+            let taxtable = Arc::new(TaxTable::try_from(n)?);
+
+            if let Some(entries) = n.children().find(|n| n.has_tag_name("entries")) {
+                taxtable_entries_map.insert(
+                    taxtable.guid.clone(),
+                    TaxTableEntry::parse_all(&taxtable.guid, entries)?
+                        .into_iter()
+                        .map(Arc::new)
+                        .collect(),
+                );
+            }
+            taxtable_map.insert(taxtable.guid.clone(), taxtable);
+        }
+
+        Ok((Arc::new(taxtable_map), Arc::new(taxtable_entries_map)))
+    }
+
     fn parse_transaction_map(
         book: roxmltree::Node,
     ) -> Result<(TransactionMap, TransactionsMap), Error> {
         let mut transaction_map = HashMap::new();
         let mut currency_transactions_map: HashMap<String, Vec<Arc<Transaction>>> = HashMap::new();
 
-        for n in book.children().filter(|n| n.has_tag_name("transaction")) {
+        for n in Self::transaction_nodes(book) {
             let transaction = Arc::new(Transaction::try_from(n)?);
 
             transaction_map.insert(transaction.guid.clone(), transaction.clone());
@@ -303,6 +692,17 @@ impl XMLQuery {
         ))
     }
 
+    fn parse_vendor_map(book: roxmltree::Node) -> Result<VendorMap, Error> {
+        let mut vendor_map = HashMap::new();
+
+        for n in book.children().filter(|n| n.has_tag_name("GncVendor")) {
+            let vendor = Arc::new(Vendor::try_from(n)?);
+            vendor_map.insert(vendor.guid.clone(), vendor);
+        }
+
+        Ok(Arc::new(vendor_map))
+    }
+
     fn update_cache(&self) -> Result<(), Error> {
         let current_mtime = self.file_path.metadata()?.modified()?;
 
@@ -344,11 +744,73 @@ impl XMLQuery {
 
 impl Query for XMLQuery {}
 
+/// Reads a `num/denom` fraction, as numerics are written, e.g. `500/100`.
+fn parse_fraction(text: &str) -> Result<(i64, i64), Error> {
+    let (num, denom) = text.trim().split_once('/').unwrap_or((text.trim(), "1"));
+    Ok((num.parse()?, denom.parse()?))
+}
+
+/// Reads an address element such as `cust:addr`, missing lines being empty.
+fn parse_address(n: Node) -> Address {
+    let mut address = Address::default();
+    for child in n.children() {
+        let text = child.text().unwrap_or_default().to_string();
+        match child.tag_name().name() {
+            "name" => address.name = text,
+            "addr1" => address.addr1 = text,
+            "addr2" => address.addr2 = text,
+            "addr3" => address.addr3 = text,
+            "addr4" => address.addr4 = text,
+            "phone" => address.phone = text,
+            "fax" => address.fax = text,
+            "email" => address.email = text,
+            _ => {}
+        }
+    }
+    address
+}
+
+/// Reads a timestamp element such as `invoice:posted`, wrapping a `ts:date`.
+fn parse_ts_date(n: Node) -> Result<chrono::NaiveDateTime, Error> {
+    let date = n
+        .children()
+        .find(|n| n.has_tag_name("date"))
+        .and_then(|n| n.text())
+        .unwrap_or_default();
+    Ok(chrono::NaiveDateTime::parse_from_str(
+        date.trim(),
+        "%Y-%m-%d %H:%M:%S%z",
+    )?)
+}
+
+/// Reads an owner element such as `invoice:owner` into its `GncOwnerType` and guid.
+fn parse_owner(n: Node) -> (i64, String) {
+    let mut owner = (0, String::new());
+    for child in n.children() {
+        let text = child.text().unwrap_or_default().trim();
+        match child.tag_name().name() {
+            "type" => {
+                owner.0 = match text {
+                    "gncCustomer" => 2,
+                    "gncJob" => 3,
+                    "gncVendor" => 4,
+                    "gncEmployee" => 5,
+                    _ => 0,
+                };
+            }
+            "id" => owner.1 = text.to_string(),
+            _ => {}
+        }
+    }
+    owner
+}
+
 #[cfg(test)]
 mod tests {
     use test_log::test;
 
     use super::*;
+    use crate::query::Query;
 
     #[test(tokio::test)]
     async fn test_new() {
@@ -360,4 +822,19 @@ mod tests {
         tracing::debug!("work_dir: {:?}", std::env::current_dir());
         XMLQuery::new(path).unwrap();
     }
+
+    #[test(tokio::test)]
+    async fn test_new_uncompressed() {
+        let path: &str = &format!(
+            "{}/tests/db/xml/complex_sample.gnucash",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let data = XMLQuery::gnucash_data(Path::new(path)).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("complex_sample.xml");
+        std::fs::write(&plain, data).unwrap();
+
+        let query = XMLQuery::new(plain.to_str().unwrap()).unwrap();
+        assert_eq!(query.accounts().await.unwrap().len(), 20);
+    }
 }
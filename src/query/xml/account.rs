@@ -6,6 +6,7 @@ use tracing::instrument;
 
 use super::XMLQuery;
 use crate::error::Error;
+use crate::model::AccountType;
 use crate::query::{AccountQ, AccountT};
 
 #[allow(clippy::struct_field_names)]
@@ -130,9 +131,15 @@ impl TryFrom<Node<'_, '_>> for Account {
                 }
                 "slots" => {
                     account.placeholder = child
-                        .descendants()
-                        .find(|n| n.has_tag_name("key"))
-                        .and_then(|n| n.next_sibling_element())
+                        .children()
+                        .filter(|n| n.has_tag_name("slot"))
+                        .find(|n| {
+                            n.children()
+                                .find(|n| n.has_tag_name("key"))
+                                .and_then(|n| n.text())
+                                == Some("placeholder")
+                        })
+                        .and_then(|n| n.children().find(|n| n.has_tag_name("value")))
                         .and_then(|n| n.text())
                         .is_some_and(|x| x == "true");
                 }
@@ -151,8 +158,8 @@ impl AccountT for Account {
     fn name(&self) -> &str {
         &self.name
     }
-    fn account_type(&self) -> &str {
-        &self.account_type
+    fn account_type(&self) -> AccountType {
+        self.account_type.as_str().into()
     }
     fn commodity_guid(&self) -> &str {
         self.commodity_guid.as_deref().unwrap_or_default()
@@ -367,6 +374,56 @@ mod tests {
         assert_eq!(account.placeholder, true);
     }
 
+    #[test]
+    fn test_try_from_element_placeholder_key() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+            <gnc-v2
+                xmlns:gnc="http://www.gnucash.org/XML/gnc"
+                xmlns:act="http://www.gnucash.org/XML/act"
+                xmlns:slot="http://www.gnucash.org/XML/slot">
+            <gnc:account version="2.0.0">
+                <act:name>Asset</act:name>
+                <act:id type="guid">fcd795021c976ba75621ec39e75f6214</act:id>
+                <act:type>ASSET</act:type>
+                <act:slots>
+                    <slot>
+                        <slot:key>color</slot:key>
+                        <slot:value type="string">true</slot:value>
+                    </slot>
+                    <slot>
+                        <slot:key>placeholder</slot:key>
+                        <slot:value type="string">false</slot:value>
+                    </slot>
+                </act:slots>
+            </gnc:account>
+            <gnc:account version="2.0.0">
+                <act:name>Current</act:name>
+                <act:id type="guid">3bc319753945b6dba3e1928abed49e35</act:id>
+                <act:type>ASSET</act:type>
+                <act:slots>
+                    <slot>
+                        <slot:key>notes</slot:key>
+                        <slot:value type="string">savings</slot:value>
+                    </slot>
+                    <slot>
+                        <slot:key>placeholder</slot:key>
+                        <slot:value type="string">true</slot:value>
+                    </slot>
+                </act:slots>
+            </gnc:account>
+            </gnc-v2>
+            "#;
+
+        let doc = Document::parse(data).unwrap();
+        let mut accounts = doc
+            .descendants()
+            .filter(|n| n.has_tag_name("account"))
+            .map(|n| Account::try_from(n).unwrap());
+
+        assert_eq!(accounts.next().unwrap().placeholder, false);
+        assert_eq!(accounts.next().unwrap().placeholder, true);
+    }
+
     #[test(tokio::test)]
     async fn test_account() {
         let query = setup().await;
@@ -378,7 +435,7 @@ mod tests {
 
         assert_eq!(result.guid(), "fcd795021c976ba75621ec39e75f6214");
         assert_eq!(result.name(), "Asset");
-        assert_eq!(result.account_type(), "ASSET");
+        assert_eq!(result.account_type(), AccountType::Asset);
         assert_eq!(result.commodity_guid(), "EUR");
         assert_eq!(result.commodity_scu(), 100);
         assert_eq!(result.non_std_scu(), false);
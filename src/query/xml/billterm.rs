@@ -0,0 +1,242 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::{XMLQuery, parse_fraction};
+use crate::error::Error;
+use crate::query::{BillTermQ, BillTermT};
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct BillTerm {
+    pub guid: String,
+    pub name: String,
+    pub description: String,
+    pub refcount: i64,
+    pub invisible: bool,
+    pub parent_guid: String,
+    pub r#type: String,
+    pub due_days: i64,
+    pub discount_days: i64,
+    pub discount_num: i64,
+    pub discount_denom: i64,
+    pub cutoff: i64,
+}
+
+impl XMLQuery {
+    fn billterm_map(&self) -> Result<super::BillTermMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.billterms))
+    }
+}
+
+impl TryFrom<Node<'_, '_>> for BillTerm {
+    type Error = Error;
+    fn try_from(n: Node) -> Result<Self, Error> {
+        let mut billterm = Self {
+            discount_denom: 1,
+            ..Self::default()
+        };
+
+        for child in n.children() {
+            let text = child.text().unwrap_or_default().trim();
+            match child.tag_name().name() {
+                "guid" => billterm.guid = text.to_string(),
+                "name" => billterm.name = text.to_string(),
+                "desc" => billterm.description = text.to_string(),
+                "refcount" => billterm.refcount = text.parse()?,
+                "invisible" => billterm.invisible = text == "1",
+                "parent" => billterm.parent_guid = text.to_string(),
+                // the rule is nested in an element named after its type
+                "days" | "proximo" => {
+                    billterm.r#type = if child.has_tag_name("days") {
+                        "GNC_TERM_TYPE_DAYS".to_string()
+                    } else {
+                        "GNC_TERM_TYPE_PROXIMO".to_string()
+                    };
+                    for rule in child.children() {
+                        let text = rule.text().unwrap_or_default().trim();
+                        match rule.tag_name().name() {
+                            "due-days" | "due-day" => billterm.due_days = text.parse()?,
+                            "disc-days" | "disc-day" => billterm.discount_days = text.parse()?,
+                            "discount" => {
+                                (billterm.discount_num, billterm.discount_denom) =
+                                    parse_fraction(text)?;
+                            }
+                            "cutoff-day" => billterm.cutoff = text.parse()?,
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if billterm.guid.is_empty() {
+            return Err(Error::XMLMissingField {
+                model: "BillTerm".to_string(),
+                field: "guid".to_string(),
+            });
+        }
+
+        Ok(billterm)
+    }
+}
+
+impl BillTermT for BillTerm {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn description(&self) -> &str {
+        &self.description
+    }
+    fn refcount(&self) -> i64 {
+        self.refcount
+    }
+    fn invisible(&self) -> bool {
+        self.invisible
+    }
+    fn parent_guid(&self) -> &str {
+        &self.parent_guid
+    }
+    fn r#type(&self) -> &str {
+        &self.r#type
+    }
+    fn due_days(&self) -> i64 {
+        self.due_days
+    }
+    fn discount_days(&self) -> i64 {
+        self.discount_days
+    }
+    fn discount_num(&self) -> i64 {
+        self.discount_num
+    }
+    fn discount_denom(&self) -> i64 {
+        self.discount_denom
+    }
+    fn cutoff(&self) -> i64 {
+        self.cutoff
+    }
+}
+
+impl BillTermQ for XMLQuery {
+    type Item = BillTerm;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all bill terms from xml");
+        let map = self
+            .billterm_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.values().map(|x| (**x).clone()).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching bill term by guid from xml");
+        let map = self
+            .billterm_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.get(guid).map(|x| (**x).clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use roxmltree::Document;
+    use test_log::test;
+    use tokio::sync::OnceCell;
+
+    use super::*;
+
+    static Q: OnceCell<XMLQuery> = OnceCell::const_new();
+    async fn setup() -> &'static XMLQuery {
+        Q.get_or_init(|| async {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+
+            tracing::debug!("work_dir: {:?}", std::env::current_dir());
+            XMLQuery::new(path).unwrap()
+        })
+        .await
+    }
+
+    #[test]
+    fn test_try_from_element() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+                <gnc-v2
+                    xmlns:gnc="http://www.gnucash.org/XML/gnc"
+                    xmlns:billterm="http://www.gnucash.org/XML/billterm"
+                    xmlns:bt-days="http://www.gnucash.org/XML/bt-days"
+                    xmlns:bt-prox="http://www.gnucash.org/XML/bt-prox">
+                    <gnc:GncBillTerm version="2.0.0">
+                        <billterm:guid type="guid">9b0c1d2e3f405162738495a6b7c8d901</billterm:guid>
+                        <billterm:name>Net 30</billterm:name>
+                        <billterm:desc>2% within 10 days</billterm:desc>
+                        <billterm:refcount>1</billterm:refcount>
+                        <billterm:invisible>0</billterm:invisible>
+                        <billterm:days>
+                            <bt-days:due-days>30</bt-days:due-days>
+                            <bt-days:disc-days>10</bt-days:disc-days>
+                            <bt-days:discount>2/1</bt-days:discount>
+                        </billterm:days>
+                    </gnc:GncBillTerm>
+                    <gnc:GncBillTerm version="2.0.0">
+                        <billterm:guid type="guid">9b0c1d2e3f405162738495a6b7c8d902</billterm:guid>
+                        <billterm:name>Proximo 15</billterm:name>
+                        <billterm:desc></billterm:desc>
+                        <billterm:refcount>0</billterm:refcount>
+                        <billterm:invisible>0</billterm:invisible>
+                        <billterm:proximo>
+                            <bt-prox:due-day>15</bt-prox:due-day>
+                            <bt-prox:cutoff-day>25</bt-prox:cutoff-day>
+                        </billterm:proximo>
+                    </gnc:GncBillTerm>
+                </gnc-v2>
+                "#;
+
+        let doc = Document::parse(data).unwrap();
+        let mut nodes = doc.descendants().filter(|n| n.has_tag_name("GncBillTerm"));
+
+        let days = BillTerm::try_from(nodes.next().unwrap()).unwrap();
+        assert_eq!(days.guid(), "9b0c1d2e3f405162738495a6b7c8d901");
+        assert_eq!(days.name(), "Net 30");
+        assert_eq!(days.description(), "2% within 10 days");
+        assert_eq!(days.refcount(), 1);
+        assert_eq!(days.invisible(), false);
+        assert_eq!(days.r#type(), "GNC_TERM_TYPE_DAYS");
+        assert_eq!(days.due_days(), 30);
+        assert_eq!(days.discount_days(), 10);
+        assert_eq!(days.discount_num(), 2);
+        assert_eq!(days.discount_denom(), 1);
+        assert_eq!(days.cutoff(), 0);
+
+        let proximo = BillTerm::try_from(nodes.next().unwrap()).unwrap();
+        assert_eq!(proximo.r#type(), "GNC_TERM_TYPE_PROXIMO");
+        assert_eq!(proximo.due_days(), 15);
+        assert_eq!(proximo.discount_days(), 0);
+        assert_eq!(proximo.cutoff(), 25);
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let query = setup().await;
+        let result = BillTermQ::all(query).await.unwrap();
+        assert_eq!(result.len(), 0);
+    }
+}
@@ -0,0 +1,144 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::XMLQuery;
+use crate::error::Error;
+use crate::query::{BookQ, BookT, VersionQ, VersionT};
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Book {
+    pub guid: String,
+    pub root_account_guid: String,
+    pub root_template_guid: String,
+}
+
+/// XML files keep no `versions` table, so there is never a row to return.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub enum Version {}
+
+impl XMLQuery {
+    fn book_item(&self) -> Result<Arc<Book>, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.book))
+    }
+}
+
+impl TryFrom<Node<'_, '_>> for Book {
+    type Error = Error;
+    fn try_from(n: Node) -> Result<Self, Error> {
+        let guid = n
+            .children()
+            .find(|n| n.has_tag_name("id"))
+            .and_then(|n| n.text())
+            .ok_or_else(|| Error::XMLMissingField {
+                model: "Book".to_string(),
+                field: "guid".to_string(),
+            })?
+            .to_string();
+
+        let root_account_guid = root_guid(n).unwrap_or_default();
+        // scheduled transaction templates keep their accounts under a root of their own
+        let root_template_guid = n
+            .children()
+            .find(|n| n.has_tag_name("template-transactions"))
+            .and_then(root_guid)
+            .unwrap_or_default();
+
+        Ok(Self {
+            guid,
+            root_account_guid,
+            root_template_guid,
+        })
+    }
+}
+
+/// The guid of the `ROOT` account among the children of `n`.
+fn root_guid(n: Node) -> Option<String> {
+    n.children()
+        .filter(|n| n.has_tag_name("account"))
+        .find(|n| {
+            n.children()
+                .any(|c| c.has_tag_name("type") && c.text() == Some("ROOT"))
+        })
+        .and_then(|n| n.children().find(|c| c.has_tag_name("id")))
+        .and_then(|n| n.text())
+        .map(std::string::ToString::to_string)
+}
+
+impl BookT for Book {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn root_account_guid(&self) -> &str {
+        &self.root_account_guid
+    }
+    fn root_template_guid(&self) -> &str {
+        &self.root_template_guid
+    }
+}
+
+impl VersionT for Version {
+    fn table_name(&self) -> &str {
+        match *self {}
+    }
+    fn table_version(&self) -> i64 {
+        match *self {}
+    }
+}
+
+impl BookQ for XMLQuery {
+    type Item = Book;
+
+    #[instrument(skip(self))]
+    async fn book(&self) -> Result<Self::Item, Error> {
+        tracing::debug!("fetching book from xml");
+        let book = self
+            .book_item()
+            .inspect_err(|e| tracing::error!("failed to get book: {e}"))?;
+
+        Ok((*book).clone())
+    }
+}
+
+impl VersionQ for XMLQuery {
+    type Item = Version;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("xml files have no versions");
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use test_log::test;
+
+    use super::*;
+
+    #[test(tokio::test)]
+    async fn test_book() {
+        let path: &str = &format!(
+            "{}/tests/db/xml/complex_sample.gnucash",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let query = XMLQuery::new(path).unwrap();
+        let result = query.book().await.unwrap();
+        assert_eq!(result.guid(), "7d4ef4044fd30f41d08914a8174c2f5b");
+        assert_eq!(
+            result.root_account_guid(),
+            "00622dda21937b29e494179de5013f82"
+        );
+        assert_eq!(result.root_template_guid(), "");
+    }
+}
@@ -0,0 +1,319 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::XMLQuery;
+use crate::error::Error;
+use crate::query::{BudgetAmountQ, BudgetAmountT, BudgetQ, BudgetT};
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Budget {
+    pub guid: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub num_periods: i64,
+}
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct BudgetAmount {
+    pub budget_guid: String,
+    pub account_guid: String,
+    pub period_num: i64,
+    pub amount_num: i64,
+    pub amount_denom: i64,
+}
+
+impl XMLQuery {
+    fn budget_map(&self) -> Result<super::BudgetMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.budgets))
+    }
+
+    fn budget_amounts_map(&self) -> Result<super::BudgetAmountsMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.budget_amounts))
+    }
+}
+
+impl TryFrom<Node<'_, '_>> for Budget {
+    type Error = Error;
+    fn try_from(n: Node) -> Result<Self, Error> {
+        let mut budget = Self::default();
+
+        for child in n.children() {
+            match child.tag_name().name() {
+                "id" => {
+                    budget.guid = child
+                        .text()
+                        .map(std::string::ToString::to_string)
+                        .ok_or_else(|| Error::XMLMissingField {
+                            model: "Budget".to_string(),
+                            field: "guid".to_string(),
+                        })?;
+                }
+                "name" => {
+                    budget.name = child.text().unwrap_or_default().to_string();
+                }
+                "description" => {
+                    budget.description = child.text().map(std::string::ToString::to_string);
+                }
+                "num-periods" => {
+                    budget.num_periods = child
+                        .text()
+                        .ok_or_else(|| Error::XMLMissingField {
+                            model: "Budget".to_string(),
+                            field: "num_periods".to_string(),
+                        })?
+                        .parse()?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(budget)
+    }
+}
+
+impl BudgetAmount {
+    /// The amounts are kept in the budget's slots, as a frame per account
+    /// whose keys are the period numbers.
+    pub(super) fn parse_all(budget_guid: &str, slots: Node) -> Result<Vec<Self>, Error> {
+        let mut amounts = Vec::new();
+
+        for account in slots.children().filter(|n| n.has_tag_name("slot")) {
+            let Some(account_guid) = account
+                .children()
+                .find(|n| n.has_tag_name("key"))
+                .and_then(|n| n.text())
+            else {
+                continue;
+            };
+            let Some(frame) = account
+                .children()
+                .find(|n| n.has_tag_name("value") && n.attribute("type") == Some("frame"))
+            else {
+                continue;
+            };
+
+            for period in frame.children().filter(|n| n.has_tag_name("slot")) {
+                let key = period
+                    .children()
+                    .find(|n| n.has_tag_name("key"))
+                    .and_then(|n| n.text())
+                    .ok_or_else(|| Error::XMLMissingField {
+                        model: "BudgetAmount".to_string(),
+                        field: "period_num".to_string(),
+                    })?;
+                let value = period
+                    .children()
+                    .find(|n| n.has_tag_name("value"))
+                    .and_then(|n| n.text())
+                    .ok_or_else(|| Error::XMLMissingField {
+                        model: "BudgetAmount".to_string(),
+                        field: "amount".to_string(),
+                    })?;
+                let (num, denom) = value.split_once('/').unwrap_or((value, "1"));
+
+                amounts.push(Self {
+                    budget_guid: budget_guid.to_string(),
+                    account_guid: account_guid.to_string(),
+                    period_num: key.parse()?,
+                    amount_num: num.parse()?,
+                    amount_denom: denom.parse()?,
+                });
+            }
+        }
+
+        Ok(amounts)
+    }
+}
+
+impl BudgetT for Budget {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn description(&self) -> &str {
+        self.description.as_deref().unwrap_or_default()
+    }
+    fn num_periods(&self) -> i64 {
+        self.num_periods
+    }
+}
+
+impl BudgetAmountT for BudgetAmount {
+    fn budget_guid(&self) -> &str {
+        &self.budget_guid
+    }
+    fn account_guid(&self) -> &str {
+        &self.account_guid
+    }
+    fn period_num(&self) -> i64 {
+        self.period_num
+    }
+    fn amount_num(&self) -> i64 {
+        self.amount_num
+    }
+    fn amount_denom(&self) -> i64 {
+        self.amount_denom
+    }
+}
+
+impl BudgetQ for XMLQuery {
+    type Item = Budget;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all budgets from xml");
+        let map = self
+            .budget_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.values().map(|x| (**x).clone()).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching budget by guid from xml");
+        let map = self
+            .budget_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.get(guid).map(|x| (**x).clone()))
+    }
+}
+
+impl BudgetAmountQ for XMLQuery {
+    type Item = BudgetAmount;
+
+    #[instrument(skip(self))]
+    async fn budget(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching budget amounts by budget_guid from xml");
+        let map = self
+            .budget_amounts_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map
+            .get(guid)
+            .map(|v| v.iter().map(|x| (**x).clone()).collect())
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use roxmltree::Document;
+    use test_log::test;
+    use tokio::sync::OnceCell;
+
+    use super::*;
+
+    static Q: OnceCell<XMLQuery> = OnceCell::const_new();
+    async fn setup() -> &'static XMLQuery {
+        Q.get_or_init(|| async {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+
+            tracing::debug!("work_dir: {:?}", std::env::current_dir());
+            XMLQuery::new(path).unwrap()
+        })
+        .await
+    }
+
+    const DATA: &str = r#"<?xml version="1.0" encoding="utf-8" ?>
+        <gnc-v2
+            xmlns:gnc="http://www.gnucash.org/XML/gnc"
+            xmlns:bgt="http://www.gnucash.org/XML/bgt"
+            xmlns:recurrence="http://www.gnucash.org/XML/recurrence"
+            xmlns:slot="http://www.gnucash.org/XML/slot">
+            <gnc:budget version="2.0.0">
+                <bgt:id type="guid">5f1a0c7d1e9b4b6f8a2d3c4e5f607182</bgt:id>
+                <bgt:name>Household</bgt:name>
+                <bgt:description>monthly spending</bgt:description>
+                <bgt:num-periods>12</bgt:num-periods>
+                <bgt:recurrence version="1.0.0">
+                    <recurrence:mult>1</recurrence:mult>
+                    <recurrence:period_type>month</recurrence:period_type>
+                    <recurrence:start>
+                        <gdate>2018-01-01</gdate>
+                    </recurrence:start>
+                </bgt:recurrence>
+                <bgt:slots>
+                    <slot>
+                        <slot:key>af88d386d44b14acf244362b85ccaf4c</slot:key>
+                        <slot:value type="frame">
+                            <slot>
+                                <slot:key>1</slot:key>
+                                <slot:value type="numeric">5000/100</slot:value>
+                            </slot>
+                            <slot>
+                                <slot:key>2</slot:key>
+                                <slot:value type="numeric">4000/100</slot:value>
+                            </slot>
+                        </slot:value>
+                    </slot>
+                </bgt:slots>
+            </gnc:budget>
+        </gnc-v2>
+        "#;
+
+    #[test]
+    fn test_try_from_element() {
+        let doc = Document::parse(DATA).unwrap();
+        let n = doc
+            .descendants()
+            .find(|n| n.has_tag_name("budget"))
+            .unwrap();
+
+        let budget = Budget::try_from(n).unwrap();
+
+        assert_eq!(budget.guid(), "5f1a0c7d1e9b4b6f8a2d3c4e5f607182");
+        assert_eq!(budget.name(), "Household");
+        assert_eq!(budget.description(), "monthly spending");
+        assert_eq!(budget.num_periods(), 12);
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let doc = Document::parse(DATA).unwrap();
+        let n = doc.descendants().find(|n| n.has_tag_name("slots")).unwrap();
+
+        let amounts = BudgetAmount::parse_all("5f1a0c7d1e9b4b6f8a2d3c4e5f607182", n).unwrap();
+
+        assert_eq!(amounts.len(), 2);
+        assert_eq!(
+            amounts[0].account_guid(),
+            "af88d386d44b14acf244362b85ccaf4c"
+        );
+        assert_eq!(amounts[0].period_num(), 1);
+        assert_eq!(amounts[0].amount_num(), 5000);
+        assert_eq!(amounts[0].amount_denom(), 100);
+        assert_eq!(amounts[1].period_num(), 2);
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let query = setup().await;
+        let result = BudgetQ::all(query).await.unwrap();
+        assert_eq!(result.len(), 0);
+    }
+}
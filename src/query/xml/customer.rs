@@ -0,0 +1,259 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::{XMLQuery, parse_address, parse_fraction};
+use crate::error::Error;
+use crate::model::Address;
+use crate::query::{CustomerQ, CustomerT};
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Customer {
+    pub guid: String,
+    pub name: String,
+    pub id: String,
+    pub notes: String,
+    pub active: bool,
+    pub discount_num: i64,
+    pub discount_denom: i64,
+    pub credit_num: i64,
+    pub credit_denom: i64,
+    pub currency_guid: String,
+    pub tax_override: bool,
+    pub addr: Address,
+    pub shipaddr: Address,
+    pub terms_guid: String,
+    pub tax_included: String,
+    pub taxtable_guid: String,
+}
+
+impl XMLQuery {
+    fn customer_map(&self) -> Result<super::CustomerMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.customers))
+    }
+}
+
+impl TryFrom<Node<'_, '_>> for Customer {
+    type Error = Error;
+    fn try_from(n: Node) -> Result<Self, Error> {
+        let mut customer = Self {
+            discount_denom: 1,
+            credit_denom: 1,
+            ..Self::default()
+        };
+
+        for child in n.children() {
+            let text = child.text().unwrap_or_default().trim();
+            match child.tag_name().name() {
+                "guid" => customer.guid = text.to_string(),
+                "name" => customer.name = text.to_string(),
+                "id" => customer.id = text.to_string(),
+                "notes" => customer.notes = text.to_string(),
+                "active" => customer.active = text == "1",
+                "discount" => {
+                    (customer.discount_num, customer.discount_denom) = parse_fraction(text)?;
+                }
+                "credit" => (customer.credit_num, customer.credit_denom) = parse_fraction(text)?,
+                "currency" => {
+                    customer.currency_guid = child
+                        .children()
+                        .find(|n| n.has_tag_name("id"))
+                        .and_then(|n| n.text())
+                        .unwrap_or_default()
+                        .to_string();
+                }
+                "use-tt" => customer.tax_override = text == "1",
+                "addr" => customer.addr = parse_address(child),
+                "shipaddr" => customer.shipaddr = parse_address(child),
+                "terms" => customer.terms_guid = text.to_string(),
+                "taxincluded" => customer.tax_included = text.to_string(),
+                "taxtable" => customer.taxtable_guid = text.to_string(),
+                _ => {}
+            }
+        }
+
+        if customer.guid.is_empty() {
+            return Err(Error::XMLMissingField {
+                model: "Customer".to_string(),
+                field: "guid".to_string(),
+            });
+        }
+
+        Ok(customer)
+    }
+}
+
+impl CustomerT for Customer {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn notes(&self) -> &str {
+        &self.notes
+    }
+    fn active(&self) -> bool {
+        self.active
+    }
+    fn discount_num(&self) -> i64 {
+        self.discount_num
+    }
+    fn discount_denom(&self) -> i64 {
+        self.discount_denom
+    }
+    fn credit_num(&self) -> i64 {
+        self.credit_num
+    }
+    fn credit_denom(&self) -> i64 {
+        self.credit_denom
+    }
+    fn currency_guid(&self) -> &str {
+        &self.currency_guid
+    }
+    fn tax_override(&self) -> bool {
+        self.tax_override
+    }
+    fn addr(&self) -> Address {
+        self.addr.clone()
+    }
+    fn shipaddr(&self) -> Address {
+        self.shipaddr.clone()
+    }
+    fn terms_guid(&self) -> &str {
+        &self.terms_guid
+    }
+    fn tax_included(&self) -> &str {
+        &self.tax_included
+    }
+    fn taxtable_guid(&self) -> &str {
+        &self.taxtable_guid
+    }
+}
+
+impl CustomerQ for XMLQuery {
+    type Item = Customer;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all customers from xml");
+        let map = self
+            .customer_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.values().map(|x| (**x).clone()).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching customer by guid from xml");
+        let map = self
+            .customer_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.get(guid).map(|x| (**x).clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use roxmltree::Document;
+    use test_log::test;
+    use tokio::sync::OnceCell;
+
+    use super::*;
+
+    static Q: OnceCell<XMLQuery> = OnceCell::const_new();
+    async fn setup() -> &'static XMLQuery {
+        Q.get_or_init(|| async {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+
+            tracing::debug!("work_dir: {:?}", std::env::current_dir());
+            XMLQuery::new(path).unwrap()
+        })
+        .await
+    }
+
+    #[test]
+    fn test_try_from_element() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+                <gnc-v2
+                    xmlns:gnc="http://www.gnucash.org/XML/gnc"
+                    xmlns:cust="http://www.gnucash.org/XML/cust"
+                    xmlns:addr="http://www.gnucash.org/XML/addr"
+                    xmlns:cmdty="http://www.gnucash.org/XML/cmdty">
+                    <gnc:GncCustomer version="2.0.0">
+                        <cust:guid type="guid">d4e5f6a7b8c9d0e1f2a3b4c5d6e7f801</cust:guid>
+                        <cust:name>Acme Corp</cust:name>
+                        <cust:id>000001</cust:id>
+                        <cust:addr version="2.0.0">
+                            <addr:name>Acme Corp</addr:name>
+                            <addr:addr1>Main Street 1</addr:addr1>
+                            <addr:addr2>Springfield</addr:addr2>
+                            <addr:email>billing@acme.example</addr:email>
+                        </cust:addr>
+                        <cust:shipaddr version="2.0.0">
+                            <addr:name>Acme Warehouse</addr:name>
+                        </cust:shipaddr>
+                        <cust:notes>pays late</cust:notes>
+                        <cust:taxincluded>USEGLOBAL</cust:taxincluded>
+                        <cust:active>1</cust:active>
+                        <cust:discount>500/100</cust:discount>
+                        <cust:credit>100000/100</cust:credit>
+                        <cust:currency>
+                            <cmdty:space>CURRENCY</cmdty:space>
+                            <cmdty:id>EUR</cmdty:id>
+                        </cust:currency>
+                        <cust:use-tt>0</cust:use-tt>
+                    </gnc:GncCustomer>
+                </gnc-v2>
+                "#;
+
+        let doc = Document::parse(data).unwrap();
+        let n = doc
+            .descendants()
+            .find(|n| n.has_tag_name("GncCustomer"))
+            .unwrap();
+
+        let customer = Customer::try_from(n).unwrap();
+
+        assert_eq!(customer.guid(), "d4e5f6a7b8c9d0e1f2a3b4c5d6e7f801");
+        assert_eq!(customer.name(), "Acme Corp");
+        assert_eq!(customer.id(), "000001");
+        assert_eq!(customer.notes(), "pays late");
+        assert_eq!(customer.active(), true);
+        assert_eq!(customer.discount_num(), 500);
+        assert_eq!(customer.discount_denom(), 100);
+        assert_eq!(customer.credit_num(), 100_000);
+        assert_eq!(customer.currency_guid(), "EUR");
+        assert_eq!(customer.tax_override(), false);
+        assert_eq!(customer.addr().addr2, "Springfield");
+        assert_eq!(customer.addr().email, "billing@acme.example");
+        assert_eq!(customer.shipaddr().name, "Acme Warehouse");
+        assert_eq!(customer.terms_guid(), "");
+        assert_eq!(customer.tax_included(), "USEGLOBAL");
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let query = setup().await;
+        let result = CustomerQ::all(query).await.unwrap();
+        assert_eq!(result.len(), 0);
+    }
+}
@@ -0,0 +1,232 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::{XMLQuery, parse_address, parse_fraction};
+use crate::error::Error;
+use crate::model::Address;
+use crate::query::{EmployeeQ, EmployeeT};
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Employee {
+    pub guid: String,
+    pub username: String,
+    pub id: String,
+    pub language: String,
+    pub acl: String,
+    pub active: bool,
+    pub currency_guid: String,
+    pub ccard_guid: String,
+    pub workday_num: i64,
+    pub workday_denom: i64,
+    pub rate_num: i64,
+    pub rate_denom: i64,
+    pub addr: Address,
+}
+
+impl XMLQuery {
+    fn employee_map(&self) -> Result<super::EmployeeMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.employees))
+    }
+}
+
+impl TryFrom<Node<'_, '_>> for Employee {
+    type Error = Error;
+    fn try_from(n: Node) -> Result<Self, Error> {
+        let mut employee = Self {
+            workday_denom: 1,
+            rate_denom: 1,
+            ..Self::default()
+        };
+
+        for child in n.children() {
+            let text = child.text().unwrap_or_default().trim();
+            match child.tag_name().name() {
+                "guid" => employee.guid = text.to_string(),
+                "username" => employee.username = text.to_string(),
+                "id" => employee.id = text.to_string(),
+                "language" => employee.language = text.to_string(),
+                "acl" => employee.acl = text.to_string(),
+                "active" => employee.active = text == "1",
+                "currency" => {
+                    employee.currency_guid = child
+                        .children()
+                        .find(|n| n.has_tag_name("id"))
+                        .and_then(|n| n.text())
+                        .unwrap_or_default()
+                        .to_string();
+                }
+                "ccard" => employee.ccard_guid = text.to_string(),
+                "workday" => (employee.workday_num, employee.workday_denom) = parse_fraction(text)?,
+                "rate" => (employee.rate_num, employee.rate_denom) = parse_fraction(text)?,
+                "addr" => employee.addr = parse_address(child),
+                _ => {}
+            }
+        }
+
+        if employee.guid.is_empty() {
+            return Err(Error::XMLMissingField {
+                model: "Employee".to_string(),
+                field: "guid".to_string(),
+            });
+        }
+
+        Ok(employee)
+    }
+}
+
+impl EmployeeT for Employee {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn username(&self) -> &str {
+        &self.username
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn language(&self) -> &str {
+        &self.language
+    }
+    fn acl(&self) -> &str {
+        &self.acl
+    }
+    fn active(&self) -> bool {
+        self.active
+    }
+    fn currency_guid(&self) -> &str {
+        &self.currency_guid
+    }
+    fn ccard_guid(&self) -> &str {
+        &self.ccard_guid
+    }
+    fn workday_num(&self) -> i64 {
+        self.workday_num
+    }
+    fn workday_denom(&self) -> i64 {
+        self.workday_denom
+    }
+    fn rate_num(&self) -> i64 {
+        self.rate_num
+    }
+    fn rate_denom(&self) -> i64 {
+        self.rate_denom
+    }
+    fn addr(&self) -> Address {
+        self.addr.clone()
+    }
+}
+
+impl EmployeeQ for XMLQuery {
+    type Item = Employee;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all employees from xml");
+        let map = self
+            .employee_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.values().map(|x| (**x).clone()).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching employee by guid from xml");
+        let map = self
+            .employee_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.get(guid).map(|x| (**x).clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use roxmltree::Document;
+    use test_log::test;
+    use tokio::sync::OnceCell;
+
+    use super::*;
+
+    static Q: OnceCell<XMLQuery> = OnceCell::const_new();
+    async fn setup() -> &'static XMLQuery {
+        Q.get_or_init(|| async {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+
+            tracing::debug!("work_dir: {:?}", std::env::current_dir());
+            XMLQuery::new(path).unwrap()
+        })
+        .await
+    }
+
+    #[test]
+    fn test_try_from_element() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+                <gnc-v2
+                    xmlns:gnc="http://www.gnucash.org/XML/gnc"
+                    xmlns:employee="http://www.gnucash.org/XML/employee"
+                    xmlns:addr="http://www.gnucash.org/XML/addr"
+                    xmlns:cmdty="http://www.gnucash.org/XML/cmdty">
+                    <gnc:GncEmployee version="2.0.0">
+                        <employee:guid type="guid">f6a7b8c9d0e1f2a3b4c5d6e7f8091a23</employee:guid>
+                        <employee:username>jdoe</employee:username>
+                        <employee:id>000001</employee:id>
+                        <employee:addr version="2.0.0">
+                            <addr:name>Jane Doe</addr:name>
+                            <addr:addr1>Elm Street 5</addr:addr1>
+                            <addr:email>jane@example.com</addr:email>
+                        </employee:addr>
+                        <employee:active>1</employee:active>
+                        <employee:workday>8/1</employee:workday>
+                        <employee:rate>2500/100</employee:rate>
+                        <employee:currency>
+                            <cmdty:space>CURRENCY</cmdty:space>
+                            <cmdty:id>EUR</cmdty:id>
+                        </employee:currency>
+                    </gnc:GncEmployee>
+                </gnc-v2>
+                "#;
+
+        let doc = Document::parse(data).unwrap();
+        let n = doc
+            .descendants()
+            .find(|n| n.has_tag_name("GncEmployee"))
+            .unwrap();
+
+        let employee = Employee::try_from(n).unwrap();
+
+        assert_eq!(employee.guid(), "f6a7b8c9d0e1f2a3b4c5d6e7f8091a23");
+        assert_eq!(employee.username(), "jdoe");
+        assert_eq!(employee.id(), "000001");
+        assert_eq!(employee.active(), true);
+        assert_eq!(employee.currency_guid(), "EUR");
+        assert_eq!(employee.ccard_guid(), "");
+        assert_eq!(employee.workday_num(), 8);
+        assert_eq!(employee.workday_denom(), 1);
+        assert_eq!(employee.rate_num(), 2500);
+        assert_eq!(employee.rate_denom(), 100);
+        assert_eq!(employee.addr().name, "Jane Doe");
+        assert_eq!(employee.addr().email, "jane@example.com");
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let query = setup().await;
+        let result = EmployeeQ::all(query).await.unwrap();
+        assert_eq!(result.len(), 0);
+    }
+}
@@ -0,0 +1,327 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use chrono::NaiveDateTime;
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::{XMLQuery, parse_fraction, parse_ts_date};
+use crate::error::Error;
+use crate::query::{EntryQ, EntryT};
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Entry {
+    pub guid: String,
+    pub date: NaiveDateTime,
+    pub date_entered: Option<NaiveDateTime>,
+    pub description: String,
+    pub action: String,
+    pub notes: String,
+    pub quantity_num: i64,
+    pub quantity_denom: i64,
+    pub i_acct_guid: String,
+    pub i_price_num: i64,
+    pub i_price_denom: i64,
+    pub i_discount_num: i64,
+    pub i_discount_denom: i64,
+    pub invoice_guid: String,
+    pub i_disc_type: String,
+    pub i_disc_how: String,
+    pub i_taxable: bool,
+    pub i_taxincluded: bool,
+    pub i_taxtable_guid: String,
+    pub b_acct_guid: String,
+    pub b_price_num: i64,
+    pub b_price_denom: i64,
+    pub bill_guid: String,
+    pub b_taxable: bool,
+    pub b_taxincluded: bool,
+    pub b_taxtable_guid: String,
+    pub b_paytype: i64,
+    pub billable: bool,
+    pub order_guid: String,
+}
+
+impl XMLQuery {
+    fn entry_map(&self) -> Result<super::EntryMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.entries))
+    }
+}
+
+impl TryFrom<Node<'_, '_>> for Entry {
+    type Error = Error;
+    fn try_from(n: Node) -> Result<Self, Error> {
+        let mut entry = Self {
+            quantity_denom: 1,
+            i_price_denom: 1,
+            i_discount_denom: 1,
+            b_price_denom: 1,
+            ..Self::default()
+        };
+
+        for child in n.children() {
+            let text = child.text().unwrap_or_default().trim();
+            match child.tag_name().name() {
+                "guid" => entry.guid = text.to_string(),
+                "date" => entry.date = parse_ts_date(child)?,
+                "entered" => entry.date_entered = Some(parse_ts_date(child)?),
+                "description" => entry.description = text.to_string(),
+                "action" => entry.action = text.to_string(),
+                "notes" => entry.notes = text.to_string(),
+                "qty" => {
+                    (entry.quantity_num, entry.quantity_denom) = parse_fraction(text)?;
+                }
+                "i-acct" => entry.i_acct_guid = text.to_string(),
+                "i-price" => {
+                    (entry.i_price_num, entry.i_price_denom) = parse_fraction(text)?;
+                }
+                "i-discount" => {
+                    (entry.i_discount_num, entry.i_discount_denom) = parse_fraction(text)?;
+                }
+                "invoice" => entry.invoice_guid = text.to_string(),
+                "i-disc-type" => entry.i_disc_type = text.to_string(),
+                "i-disc-how" => entry.i_disc_how = text.to_string(),
+                "i-taxable" => entry.i_taxable = text == "1",
+                "i-taxincluded" => entry.i_taxincluded = text == "1",
+                "i-taxtable" => entry.i_taxtable_guid = text.to_string(),
+                "b-acct" => entry.b_acct_guid = text.to_string(),
+                "b-price" => {
+                    (entry.b_price_num, entry.b_price_denom) = parse_fraction(text)?;
+                }
+                "bill" => entry.bill_guid = text.to_string(),
+                "b-taxable" => entry.b_taxable = text == "1",
+                "b-taxincluded" => entry.b_taxincluded = text == "1",
+                "b-taxtable" => entry.b_taxtable_guid = text.to_string(),
+                "b-pay" => entry.b_paytype = if text == "CARD" { 2 } else { 1 },
+                "billable" => entry.billable = text == "1",
+                "order" => entry.order_guid = text.to_string(),
+                _ => {}
+            }
+        }
+
+        if entry.guid.is_empty() {
+            return Err(Error::XMLMissingField {
+                model: "Entry".to_string(),
+                field: "guid".to_string(),
+            });
+        }
+
+        Ok(entry)
+    }
+}
+
+impl EntryT for Entry {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn date(&self) -> NaiveDateTime {
+        self.date
+    }
+    fn date_entered(&self) -> Option<NaiveDateTime> {
+        self.date_entered
+    }
+    fn description(&self) -> &str {
+        &self.description
+    }
+    fn action(&self) -> &str {
+        &self.action
+    }
+    fn notes(&self) -> &str {
+        &self.notes
+    }
+    fn quantity_num(&self) -> i64 {
+        self.quantity_num
+    }
+    fn quantity_denom(&self) -> i64 {
+        self.quantity_denom
+    }
+    fn i_acct_guid(&self) -> &str {
+        &self.i_acct_guid
+    }
+    fn i_price_num(&self) -> i64 {
+        self.i_price_num
+    }
+    fn i_price_denom(&self) -> i64 {
+        self.i_price_denom
+    }
+    fn i_discount_num(&self) -> i64 {
+        self.i_discount_num
+    }
+    fn i_discount_denom(&self) -> i64 {
+        self.i_discount_denom
+    }
+    fn invoice_guid(&self) -> &str {
+        &self.invoice_guid
+    }
+    fn i_disc_type(&self) -> &str {
+        &self.i_disc_type
+    }
+    fn i_disc_how(&self) -> &str {
+        &self.i_disc_how
+    }
+    fn i_taxable(&self) -> bool {
+        self.i_taxable
+    }
+    fn i_taxincluded(&self) -> bool {
+        self.i_taxincluded
+    }
+    fn i_taxtable_guid(&self) -> &str {
+        &self.i_taxtable_guid
+    }
+    fn b_acct_guid(&self) -> &str {
+        &self.b_acct_guid
+    }
+    fn b_price_num(&self) -> i64 {
+        self.b_price_num
+    }
+    fn b_price_denom(&self) -> i64 {
+        self.b_price_denom
+    }
+    fn bill_guid(&self) -> &str {
+        &self.bill_guid
+    }
+    fn b_taxable(&self) -> bool {
+        self.b_taxable
+    }
+    fn b_taxincluded(&self) -> bool {
+        self.b_taxincluded
+    }
+    fn b_taxtable_guid(&self) -> &str {
+        &self.b_taxtable_guid
+    }
+    fn b_paytype(&self) -> i64 {
+        self.b_paytype
+    }
+    fn billable(&self) -> bool {
+        self.billable
+    }
+    fn order_guid(&self) -> &str {
+        &self.order_guid
+    }
+}
+
+impl EntryQ for XMLQuery {
+    type Item = Entry;
+
+    #[instrument(skip(self))]
+    async fn invoice(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching entries by invoice from xml");
+        let map = self
+            .entry_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        let mut entries: Vec<Entry> = map
+            .values()
+            .filter(|x| x.invoice_guid == guid || x.bill_guid == guid)
+            .map(|x| (**x).clone())
+            .collect();
+        entries.sort_by_key(|x| (x.date, x.date_entered));
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use roxmltree::Document;
+    use test_log::test;
+    use tokio::sync::OnceCell;
+
+    use super::*;
+
+    static Q: OnceCell<XMLQuery> = OnceCell::const_new();
+    async fn setup() -> &'static XMLQuery {
+        Q.get_or_init(|| async {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+
+            tracing::debug!("work_dir: {:?}", std::env::current_dir());
+            XMLQuery::new(path).unwrap()
+        })
+        .await
+    }
+
+    #[test]
+    fn test_try_from_element() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+                <gnc-v2
+                    xmlns:gnc="http://www.gnucash.org/XML/gnc"
+                    xmlns:entry="http://www.gnucash.org/XML/entry"
+                    xmlns:owner="http://www.gnucash.org/XML/owner"
+                    xmlns:cmdty="http://www.gnucash.org/XML/cmdty"
+                    xmlns:ts="http://www.gnucash.org/XML/ts">
+                    <gnc:GncEntry version="2.0.0">
+                        <entry:guid type="guid">4e5f60718293a4b5c6d7e8f90a1b2c03</entry:guid>
+                        <entry:date>
+                            <ts:date>2018-03-10 10:59:00 +0000</ts:date>
+                        </entry:date>
+                        <entry:entered>
+                            <ts:date>2018-03-10 11:00:00 +0000</ts:date>
+                        </entry:entered>
+                        <entry:description>Paper</entry:description>
+                        <entry:qty>4/1</entry:qty>
+                        <entry:b-acct type="guid">af88d386d44b14acf244362b85ccaf4c</entry:b-acct>
+                        <entry:b-price>30/1</entry:b-price>
+                        <entry:bill type="guid">3d4e5f60718293a4b5c6d7e8f90a1b02</entry:bill>
+                        <entry:billable>0</entry:billable>
+                        <entry:b-taxable>1</entry:b-taxable>
+                        <entry:b-taxincluded>1</entry:b-taxincluded>
+                        <entry:b-pay>CARD</entry:b-pay>
+                    </gnc:GncEntry>
+                </gnc-v2>
+                "#;
+
+        let doc = Document::parse(data).unwrap();
+        let n = doc
+            .descendants()
+            .find(|n| n.has_tag_name("GncEntry"))
+            .unwrap();
+
+        let entry = Entry::try_from(n).unwrap();
+
+        assert_eq!(entry.guid(), "4e5f60718293a4b5c6d7e8f90a1b2c03");
+        assert_eq!(
+            entry.date(),
+            NaiveDateTime::parse_from_str("2018-03-10 10:59:00", "%Y-%m-%d %H:%M:%S").unwrap()
+        );
+        assert_eq!(
+            entry.date_entered(),
+            NaiveDateTime::parse_from_str("2018-03-10 11:00:00", "%Y-%m-%d %H:%M:%S").ok()
+        );
+        assert_eq!(entry.description(), "Paper");
+        assert_eq!(entry.action(), "");
+        assert_eq!(entry.quantity_num(), 4);
+        assert_eq!(entry.quantity_denom(), 1);
+        assert_eq!(entry.invoice_guid(), "");
+        assert_eq!(entry.i_price_denom(), 1);
+        assert_eq!(entry.b_acct_guid(), "af88d386d44b14acf244362b85ccaf4c");
+        assert_eq!(entry.b_price_num(), 30);
+        assert_eq!(entry.b_price_denom(), 1);
+        assert_eq!(entry.bill_guid(), "3d4e5f60718293a4b5c6d7e8f90a1b02");
+        assert_eq!(entry.b_taxable(), true);
+        assert_eq!(entry.b_taxincluded(), true);
+        assert_eq!(entry.b_paytype(), 2);
+        assert_eq!(entry.billable(), false);
+        assert_eq!(entry.order_guid(), "");
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let query = setup().await;
+        let result = EntryQ::invoice(query, "3d4e5f60718293a4b5c6d7e8f90a1b02")
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 0);
+    }
+}
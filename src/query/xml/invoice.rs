@@ -0,0 +1,265 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use chrono::NaiveDateTime;
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::{XMLQuery, parse_fraction, parse_owner, parse_ts_date};
+use crate::error::Error;
+use crate::query::{InvoiceQ, InvoiceT};
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Invoice {
+    pub guid: String,
+    pub id: String,
+    pub date_opened: Option<NaiveDateTime>,
+    pub date_posted: Option<NaiveDateTime>,
+    pub notes: String,
+    pub active: bool,
+    pub currency_guid: String,
+    pub owner_type: i64,
+    pub owner_guid: String,
+    pub terms_guid: String,
+    pub billing_id: String,
+    pub post_txn_guid: String,
+    pub post_lot_guid: String,
+    pub post_acc_guid: String,
+    pub charge_amt_num: i64,
+    pub charge_amt_denom: i64,
+}
+
+impl XMLQuery {
+    fn invoice_map(&self) -> Result<super::InvoiceMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.invoices))
+    }
+}
+
+impl TryFrom<Node<'_, '_>> for Invoice {
+    type Error = Error;
+    fn try_from(n: Node) -> Result<Self, Error> {
+        let mut invoice = Self {
+            charge_amt_denom: 1,
+            ..Self::default()
+        };
+
+        for child in n.children() {
+            let text = child.text().unwrap_or_default().trim();
+            match child.tag_name().name() {
+                "guid" => invoice.guid = text.to_string(),
+                "id" => invoice.id = text.to_string(),
+                "opened" => invoice.date_opened = Some(parse_ts_date(child)?),
+                "posted" => invoice.date_posted = Some(parse_ts_date(child)?),
+                "notes" => invoice.notes = text.to_string(),
+                "active" => invoice.active = text == "1",
+                "currency" => {
+                    invoice.currency_guid = child
+                        .children()
+                        .find(|n| n.has_tag_name("id"))
+                        .and_then(|n| n.text())
+                        .unwrap_or_default()
+                        .to_string();
+                }
+                "owner" => (invoice.owner_type, invoice.owner_guid) = parse_owner(child),
+                "terms" => invoice.terms_guid = text.to_string(),
+                "billing_id" => invoice.billing_id = text.to_string(),
+                "posttxn" => invoice.post_txn_guid = text.to_string(),
+                "postlot" => invoice.post_lot_guid = text.to_string(),
+                "postacc" => invoice.post_acc_guid = text.to_string(),
+                "charge-amt" => {
+                    (invoice.charge_amt_num, invoice.charge_amt_denom) = parse_fraction(text)?;
+                }
+                _ => {}
+            }
+        }
+
+        if invoice.guid.is_empty() {
+            return Err(Error::XMLMissingField {
+                model: "Invoice".to_string(),
+                field: "guid".to_string(),
+            });
+        }
+
+        Ok(invoice)
+    }
+}
+
+impl InvoiceT for Invoice {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn date_opened(&self) -> Option<NaiveDateTime> {
+        self.date_opened
+    }
+    fn date_posted(&self) -> Option<NaiveDateTime> {
+        self.date_posted
+    }
+    fn notes(&self) -> &str {
+        &self.notes
+    }
+    fn active(&self) -> bool {
+        self.active
+    }
+    fn currency_guid(&self) -> &str {
+        &self.currency_guid
+    }
+    fn owner_type(&self) -> i64 {
+        self.owner_type
+    }
+    fn owner_guid(&self) -> &str {
+        &self.owner_guid
+    }
+    fn terms_guid(&self) -> &str {
+        &self.terms_guid
+    }
+    fn billing_id(&self) -> &str {
+        &self.billing_id
+    }
+    fn post_txn_guid(&self) -> &str {
+        &self.post_txn_guid
+    }
+    fn post_lot_guid(&self) -> &str {
+        &self.post_lot_guid
+    }
+    fn post_acc_guid(&self) -> &str {
+        &self.post_acc_guid
+    }
+    fn charge_amt_num(&self) -> i64 {
+        self.charge_amt_num
+    }
+    fn charge_amt_denom(&self) -> i64 {
+        self.charge_amt_denom
+    }
+}
+
+impl InvoiceQ for XMLQuery {
+    type Item = Invoice;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all invoices from xml");
+        let map = self
+            .invoice_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.values().map(|x| (**x).clone()).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching invoice by guid from xml");
+        let map = self
+            .invoice_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.get(guid).map(|x| (**x).clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use roxmltree::Document;
+    use test_log::test;
+    use tokio::sync::OnceCell;
+
+    use super::*;
+
+    static Q: OnceCell<XMLQuery> = OnceCell::const_new();
+    async fn setup() -> &'static XMLQuery {
+        Q.get_or_init(|| async {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+
+            tracing::debug!("work_dir: {:?}", std::env::current_dir());
+            XMLQuery::new(path).unwrap()
+        })
+        .await
+    }
+
+    #[test]
+    fn test_try_from_element() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+                <gnc-v2
+                    xmlns:gnc="http://www.gnucash.org/XML/gnc"
+                    xmlns:invoice="http://www.gnucash.org/XML/invoice"
+                    xmlns:owner="http://www.gnucash.org/XML/owner"
+                    xmlns:cmdty="http://www.gnucash.org/XML/cmdty"
+                    xmlns:ts="http://www.gnucash.org/XML/ts">
+                    <gnc:GncInvoice version="2.0.0">
+                        <invoice:guid type="guid">3d4e5f60718293a4b5c6d7e8f90a1b01</invoice:guid>
+                        <invoice:id>000001</invoice:id>
+                        <invoice:owner version="2.0.0">
+                            <owner:type>gncCustomer</owner:type>
+                            <owner:id type="guid">d4e5f6a7b8c9d0e1f2a3b4c5d6e7f801</owner:id>
+                        </invoice:owner>
+                        <invoice:opened>
+                            <ts:date>2018-03-01 10:59:00 +0000</ts:date>
+                        </invoice:opened>
+                        <invoice:posted>
+                            <ts:date>2018-03-05 10:59:00 +0000</ts:date>
+                        </invoice:posted>
+                        <invoice:billing_id>PO-17</invoice:billing_id>
+                        <invoice:active>1</invoice:active>
+                        <invoice:posttxn type="guid">60718293a4b5c6d7e8f90a1b2c3d4e01</invoice:posttxn>
+                        <invoice:postlot type="guid">5f60718293a4b5c6d7e8f90a1b2c3d01</invoice:postlot>
+                        <invoice:postacc type="guid">0a1b2c3d4e5f60718293a4b5c6d7e801</invoice:postacc>
+                        <invoice:currency>
+                            <cmdty:space>CURRENCY</cmdty:space>
+                            <cmdty:id>EUR</cmdty:id>
+                        </invoice:currency>
+                    </gnc:GncInvoice>
+                </gnc-v2>
+                "#;
+
+        let doc = Document::parse(data).unwrap();
+        let n = doc
+            .descendants()
+            .find(|n| n.has_tag_name("GncInvoice"))
+            .unwrap();
+
+        let invoice = Invoice::try_from(n).unwrap();
+
+        assert_eq!(invoice.guid(), "3d4e5f60718293a4b5c6d7e8f90a1b01");
+        assert_eq!(invoice.id(), "000001");
+        assert_eq!(invoice.owner_type(), 2);
+        assert_eq!(invoice.owner_guid(), "d4e5f6a7b8c9d0e1f2a3b4c5d6e7f801");
+        assert_eq!(
+            invoice.date_opened(),
+            NaiveDateTime::parse_from_str("2018-03-01 10:59:00", "%Y-%m-%d %H:%M:%S").ok()
+        );
+        assert_eq!(
+            invoice.date_posted(),
+            NaiveDateTime::parse_from_str("2018-03-05 10:59:00", "%Y-%m-%d %H:%M:%S").ok()
+        );
+        assert_eq!(invoice.notes(), "");
+        assert_eq!(invoice.active(), true);
+        assert_eq!(invoice.currency_guid(), "EUR");
+        assert_eq!(invoice.terms_guid(), "");
+        assert_eq!(invoice.billing_id(), "PO-17");
+        assert_eq!(invoice.post_txn_guid(), "60718293a4b5c6d7e8f90a1b2c3d4e01");
+        assert_eq!(invoice.post_lot_guid(), "5f60718293a4b5c6d7e8f90a1b2c3d01");
+        assert_eq!(invoice.post_acc_guid(), "0a1b2c3d4e5f60718293a4b5c6d7e801");
+        assert_eq!(invoice.charge_amt_num(), 0);
+        assert_eq!(invoice.charge_amt_denom(), 1);
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let query = setup().await;
+        let result = InvoiceQ::all(query).await.unwrap();
+        assert_eq!(result.len(), 0);
+    }
+}
@@ -0,0 +1,181 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::{XMLQuery, parse_owner};
+use crate::error::Error;
+use crate::query::{JobQ, JobT};
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Job {
+    pub guid: String,
+    pub id: String,
+    pub name: String,
+    pub reference: String,
+    pub active: bool,
+    pub owner_type: i64,
+    pub owner_guid: String,
+}
+
+impl XMLQuery {
+    fn job_map(&self) -> Result<super::JobMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.jobs))
+    }
+}
+
+impl TryFrom<Node<'_, '_>> for Job {
+    type Error = Error;
+    fn try_from(n: Node) -> Result<Self, Error> {
+        let mut job = Self::default();
+
+        for child in n.children() {
+            let text = child.text().unwrap_or_default().trim();
+            match child.tag_name().name() {
+                "guid" => job.guid = text.to_string(),
+                "id" => job.id = text.to_string(),
+                "name" => job.name = text.to_string(),
+                "reference" => job.reference = text.to_string(),
+                "active" => job.active = text == "1",
+                "owner" => (job.owner_type, job.owner_guid) = parse_owner(child),
+                _ => {}
+            }
+        }
+
+        if job.guid.is_empty() {
+            return Err(Error::XMLMissingField {
+                model: "Job".to_string(),
+                field: "guid".to_string(),
+            });
+        }
+
+        Ok(job)
+    }
+}
+
+impl JobT for Job {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn reference(&self) -> &str {
+        &self.reference
+    }
+    fn active(&self) -> bool {
+        self.active
+    }
+    fn owner_type(&self) -> i64 {
+        self.owner_type
+    }
+    fn owner_guid(&self) -> &str {
+        &self.owner_guid
+    }
+}
+
+impl JobQ for XMLQuery {
+    type Item = Job;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all jobs from xml");
+        let map = self
+            .job_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.values().map(|x| (**x).clone()).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching job by guid from xml");
+        let map = self
+            .job_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.get(guid).map(|x| (**x).clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use roxmltree::Document;
+    use test_log::test;
+    use tokio::sync::OnceCell;
+
+    use super::*;
+
+    static Q: OnceCell<XMLQuery> = OnceCell::const_new();
+    async fn setup() -> &'static XMLQuery {
+        Q.get_or_init(|| async {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+
+            tracing::debug!("work_dir: {:?}", std::env::current_dir());
+            XMLQuery::new(path).unwrap()
+        })
+        .await
+    }
+
+    #[test]
+    fn test_try_from_element() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+                <gnc-v2
+                    xmlns:gnc="http://www.gnucash.org/XML/gnc"
+                    xmlns:job="http://www.gnucash.org/XML/job"
+                    xmlns:owner="http://www.gnucash.org/XML/owner"
+                    xmlns:cmdty="http://www.gnucash.org/XML/cmdty"
+                    xmlns:ts="http://www.gnucash.org/XML/ts">
+                    <gnc:GncJob version="2.0.0">
+                        <job:guid type="guid">1b2c3d4e5f60718293a4b5c6d7e8f901</job:guid>
+                        <job:id>000001</job:id>
+                        <job:name>Website</job:name>
+                        <job:reference>PO-17</job:reference>
+                        <job:owner version="2.0.0">
+                            <owner:type>gncCustomer</owner:type>
+                            <owner:id type="guid">d4e5f6a7b8c9d0e1f2a3b4c5d6e7f801</owner:id>
+                        </job:owner>
+                        <job:active>1</job:active>
+                    </gnc:GncJob>
+                </gnc-v2>
+                "#;
+
+        let doc = Document::parse(data).unwrap();
+        let n = doc
+            .descendants()
+            .find(|n| n.has_tag_name("GncJob"))
+            .unwrap();
+
+        let job = Job::try_from(n).unwrap();
+
+        assert_eq!(job.guid(), "1b2c3d4e5f60718293a4b5c6d7e8f901");
+        assert_eq!(job.id(), "000001");
+        assert_eq!(job.name(), "Website");
+        assert_eq!(job.reference(), "PO-17");
+        assert_eq!(job.active(), true);
+        assert_eq!(job.owner_type(), 2);
+        assert_eq!(job.owner_guid(), "d4e5f6a7b8c9d0e1f2a3b4c5d6e7f801");
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let query = setup().await;
+        let result = JobQ::all(query).await.unwrap();
+        assert_eq!(result.len(), 0);
+    }
+}
@@ -0,0 +1,184 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::XMLQuery;
+use crate::error::Error;
+use crate::query::{LotQ, LotT};
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Lot {
+    pub guid: String,
+    pub account_guid: String,
+}
+
+impl XMLQuery {
+    fn lot_map(&self) -> Result<super::LotMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.lots))
+    }
+
+    fn account_lots_map(&self) -> Result<super::LotsMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.account_lots))
+    }
+}
+
+impl Lot {
+    /// Lots are stored inside the `act:lots` of the account they belong to.
+    pub(super) fn try_from(account_guid: String, n: Node) -> Result<Self, Error> {
+        let guid = n
+            .children()
+            .find(|n| n.has_tag_name("id"))
+            .and_then(|n| n.text())
+            .ok_or_else(|| Error::XMLMissingField {
+                model: "Lot".to_string(),
+                field: "guid".to_string(),
+            })?
+            .to_string();
+
+        Ok(Self { guid, account_guid })
+    }
+}
+
+impl LotT for Lot {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn account_guid(&self) -> &str {
+        &self.account_guid
+    }
+    // not support in xml
+    fn is_closed(&self) -> Option<bool> {
+        None
+    }
+}
+
+impl LotQ for XMLQuery {
+    type Item = Lot;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all lots from xml");
+        let map = self
+            .lot_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.values().map(|x| (**x).clone()).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching lot by guid from xml");
+        let map = self
+            .lot_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.get(guid).map(|x| (**x).clone()))
+    }
+
+    #[instrument(skip(self))]
+    async fn account(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching lots by account_guid from xml");
+        let map = self
+            .account_lots_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map
+            .get(guid)
+            .map(|v| v.iter().map(|x| (**x).clone()).collect())
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use roxmltree::Document;
+    use test_log::test;
+    use tokio::sync::OnceCell;
+
+    use super::*;
+
+    static Q: OnceCell<XMLQuery> = OnceCell::const_new();
+    async fn setup() -> &'static XMLQuery {
+        Q.get_or_init(|| async {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+
+            tracing::debug!("work_dir: {:?}", std::env::current_dir());
+            XMLQuery::new(path).unwrap()
+        })
+        .await
+    }
+
+    #[test]
+    fn test_try_from_element() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+                <gnc-v2
+                    xmlns:gnc="http://www.gnucash.org/XML/gnc"
+                    xmlns:act="http://www.gnucash.org/XML/act"
+                    xmlns:lot="http://www.gnucash.org/XML/lot"
+                    xmlns:slot="http://www.gnucash.org/XML/slot">
+                    <gnc:account version="2.0.0">
+                        <act:name>Foo stock</act:name>
+                        <act:id type="guid">1c089803052e85f5c6d8e786057dbaee</act:id>
+                        <act:type>STOCK</act:type>
+                        <act:lots>
+                            <gnc:lot version="2.0.0">
+                                <lot:id type="guid">a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01</lot:id>
+                                <lot:slots>
+                                    <slot>
+                                        <slot:key>title</slot:key>
+                                        <slot:value type="string">Lot 1</slot:value>
+                                    </slot>
+                                </lot:slots>
+                            </gnc:lot>
+                        </act:lots>
+                    </gnc:account>
+                </gnc-v2>
+                "#;
+
+        let doc = Document::parse(data).unwrap();
+        let n = doc.descendants().find(|n| n.has_tag_name("lot")).unwrap();
+
+        let lot = Lot::try_from(String::from("1c089803052e85f5c6d8e786057dbaee"), n).unwrap();
+
+        assert_eq!(lot.guid(), "a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01");
+        assert_eq!(lot.account_guid(), "1c089803052e85f5c6d8e786057dbaee");
+        assert_eq!(lot.is_closed(), None);
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let query = setup().await;
+        let result = query.all().await.unwrap();
+        assert_eq!(result.len(), 0);
+    }
+
+    #[test(tokio::test)]
+    async fn test_account_guid() {
+        let query = setup().await;
+        let result = query
+            .account("1c089803052e85f5c6d8e786057dbaee")
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 0);
+    }
+}
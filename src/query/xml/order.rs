@@ -0,0 +1,199 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use chrono::NaiveDateTime;
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::{XMLQuery, parse_owner, parse_ts_date};
+use crate::error::Error;
+use crate::query::{OrderQ, OrderT};
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Order {
+    pub guid: String,
+    pub id: String,
+    pub notes: String,
+    pub reference: String,
+    pub active: bool,
+    pub date_opened: NaiveDateTime,
+    pub date_closed: Option<NaiveDateTime>,
+    pub owner_type: i64,
+    pub owner_guid: String,
+}
+
+impl XMLQuery {
+    fn order_map(&self) -> Result<super::OrderMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.orders))
+    }
+}
+
+impl TryFrom<Node<'_, '_>> for Order {
+    type Error = Error;
+    fn try_from(n: Node) -> Result<Self, Error> {
+        let mut order = Self::default();
+
+        for child in n.children() {
+            let text = child.text().unwrap_or_default().trim();
+            match child.tag_name().name() {
+                "guid" => order.guid = text.to_string(),
+                "id" => order.id = text.to_string(),
+                "notes" => order.notes = text.to_string(),
+                "reference" => order.reference = text.to_string(),
+                "active" => order.active = text == "1",
+                "opened" => order.date_opened = parse_ts_date(child)?,
+                "closed" => order.date_closed = Some(parse_ts_date(child)?),
+                "owner" => (order.owner_type, order.owner_guid) = parse_owner(child),
+                _ => {}
+            }
+        }
+
+        if order.guid.is_empty() {
+            return Err(Error::XMLMissingField {
+                model: "Order".to_string(),
+                field: "guid".to_string(),
+            });
+        }
+
+        Ok(order)
+    }
+}
+
+impl OrderT for Order {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn notes(&self) -> &str {
+        &self.notes
+    }
+    fn reference(&self) -> &str {
+        &self.reference
+    }
+    fn active(&self) -> bool {
+        self.active
+    }
+    fn date_opened(&self) -> NaiveDateTime {
+        self.date_opened
+    }
+    fn date_closed(&self) -> Option<NaiveDateTime> {
+        self.date_closed
+    }
+    fn owner_type(&self) -> i64 {
+        self.owner_type
+    }
+    fn owner_guid(&self) -> &str {
+        &self.owner_guid
+    }
+}
+
+impl OrderQ for XMLQuery {
+    type Item = Order;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all orders from xml");
+        let map = self
+            .order_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.values().map(|x| (**x).clone()).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching order by guid from xml");
+        let map = self
+            .order_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.get(guid).map(|x| (**x).clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use roxmltree::Document;
+    use test_log::test;
+    use tokio::sync::OnceCell;
+
+    use super::*;
+
+    static Q: OnceCell<XMLQuery> = OnceCell::const_new();
+    async fn setup() -> &'static XMLQuery {
+        Q.get_or_init(|| async {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+
+            tracing::debug!("work_dir: {:?}", std::env::current_dir());
+            XMLQuery::new(path).unwrap()
+        })
+        .await
+    }
+
+    #[test]
+    fn test_try_from_element() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+                <gnc-v2
+                    xmlns:gnc="http://www.gnucash.org/XML/gnc"
+                    xmlns:order="http://www.gnucash.org/XML/order"
+                    xmlns:owner="http://www.gnucash.org/XML/owner"
+                    xmlns:cmdty="http://www.gnucash.org/XML/cmdty"
+                    xmlns:ts="http://www.gnucash.org/XML/ts">
+                    <gnc:GncOrder version="2.0.0">
+                        <order:guid type="guid">2c3d4e5f60718293a4b5c6d7e8f90a01</order:guid>
+                        <order:id>000001</order:id>
+                        <order:owner version="2.0.0">
+                            <owner:type>gncJob</owner:type>
+                            <owner:id type="guid">1b2c3d4e5f60718293a4b5c6d7e8f901</owner:id>
+                        </order:owner>
+                        <order:opened>
+                            <ts:date>2018-03-01 10:59:00 +0000</ts:date>
+                        </order:opened>
+                        <order:reference>PO-17</order:reference>
+                        <order:active>1</order:active>
+                    </gnc:GncOrder>
+                </gnc-v2>
+                "#;
+
+        let doc = Document::parse(data).unwrap();
+        let n = doc
+            .descendants()
+            .find(|n| n.has_tag_name("GncOrder"))
+            .unwrap();
+
+        let order = Order::try_from(n).unwrap();
+
+        assert_eq!(order.guid(), "2c3d4e5f60718293a4b5c6d7e8f90a01");
+        assert_eq!(order.id(), "000001");
+        assert_eq!(order.notes(), "");
+        assert_eq!(order.reference(), "PO-17");
+        assert_eq!(order.active(), true);
+        assert_eq!(
+            order.date_opened(),
+            NaiveDateTime::parse_from_str("2018-03-01 10:59:00", "%Y-%m-%d %H:%M:%S").unwrap()
+        );
+        assert_eq!(order.date_closed(), None);
+        assert_eq!(order.owner_type(), 3);
+        assert_eq!(order.owner_guid(), "1b2c3d4e5f60718293a4b5c6d7e8f901");
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let query = setup().await;
+        let result = OrderQ::all(query).await.unwrap();
+        assert_eq!(result.len(), 0);
+    }
+}
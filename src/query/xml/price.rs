@@ -162,6 +162,13 @@ impl PriceT for Price {
         self.r#type.as_deref().unwrap_or_default()
     }
 
+    fn value_num(&self) -> i64 {
+        self.value_num
+    }
+    fn value_denom(&self) -> i64 {
+        self.value_denom
+    }
+
     #[cfg(not(feature = "decimal"))]
     #[allow(clippy::cast_precision_loss)]
     fn value(&self) -> f64 {
@@ -372,6 +379,8 @@ mod tests {
         assert_approx_eq!(f64, result.value(), 1.5);
         #[cfg(feature = "decimal")]
         assert_eq!(result.value(), Decimal::new(15, 1));
+        assert_eq!(result.value_num(), 3);
+        assert_eq!(result.value_denom(), 2);
     }
 
     #[test(tokio::test)]
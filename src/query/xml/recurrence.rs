@@ -0,0 +1,146 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use chrono::NaiveDate;
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::XMLQuery;
+use crate::error::Error;
+use crate::query::{RecurrenceQ, RecurrenceT};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Recurrence {
+    pub obj_guid: String,
+    pub mult: i64,
+    pub period_type: String,
+    pub period_start: NaiveDate,
+    pub weekend_adjust: String,
+}
+
+impl XMLQuery {
+    fn recurrences_map(&self) -> Result<super::RecurrencesMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.recurrences))
+    }
+}
+
+impl Recurrence {
+    /// `n` is a `gnc:recurrence` or `bgt:recurrence` element of the object `obj_guid`.
+    pub(super) fn try_from(obj_guid: &str, n: Node) -> Result<Self, Error> {
+        let child_text = |name: &str| {
+            n.children()
+                .find(|n| n.has_tag_name(name))
+                .and_then(|n| n.text())
+        };
+        let missing = |field: &str| Error::XMLMissingField {
+            model: "Recurrence".to_string(),
+            field: field.to_string(),
+        };
+
+        let mult = child_text("mult").ok_or_else(|| missing("mult"))?.parse()?;
+        let period_type = child_text("period_type")
+            .ok_or_else(|| missing("period_type"))?
+            .to_string();
+        let start = n
+            .children()
+            .find(|n| n.has_tag_name("start"))
+            .and_then(|n| n.children().find(|n| n.has_tag_name("gdate")))
+            .and_then(|n| n.text())
+            .ok_or_else(|| missing("start"))?;
+        // omitted when no adjustment is made
+        let weekend_adjust = child_text("weekend_adj").unwrap_or("none").to_string();
+
+        Ok(Self {
+            obj_guid: obj_guid.to_string(),
+            mult,
+            period_type,
+            period_start: NaiveDate::parse_from_str(start, "%Y-%m-%d")?,
+            weekend_adjust,
+        })
+    }
+}
+
+impl RecurrenceT for Recurrence {
+    fn obj_guid(&self) -> &str {
+        &self.obj_guid
+    }
+    fn mult(&self) -> i64 {
+        self.mult
+    }
+    fn period_type(&self) -> &str {
+        &self.period_type
+    }
+    fn period_start(&self) -> NaiveDate {
+        self.period_start
+    }
+    fn weekend_adjust(&self) -> &str {
+        &self.weekend_adjust
+    }
+}
+
+impl RecurrenceQ for XMLQuery {
+    type Item = Recurrence;
+
+    #[instrument(skip(self))]
+    async fn obj_guid(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching recurrences by obj_guid from xml");
+        let map = self
+            .recurrences_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map
+            .get(guid)
+            .map(|v| v.iter().map(|x| (**x).clone()).collect())
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use roxmltree::Document;
+
+    use super::*;
+
+    #[test]
+    fn test_try_from_element() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+                <gnc-v2
+                    xmlns:gnc="http://www.gnucash.org/XML/gnc"
+                    xmlns:recurrence="http://www.gnucash.org/XML/recurrence">
+                    <gnc:recurrence version="1.0.0">
+                        <recurrence:mult>2</recurrence:mult>
+                        <recurrence:period_type>week</recurrence:period_type>
+                        <recurrence:start>
+                            <gdate>2018-02-20</gdate>
+                        </recurrence:start>
+                        <recurrence:weekend_adj>back</recurrence:weekend_adj>
+                    </gnc:recurrence>
+                </gnc-v2>
+                "#;
+
+        let doc = Document::parse(data).unwrap();
+        let n = doc
+            .descendants()
+            .find(|n| n.has_tag_name("recurrence"))
+            .unwrap();
+
+        let recurrence = Recurrence::try_from("obj_guid", n).unwrap();
+
+        assert_eq!(recurrence.obj_guid(), "obj_guid");
+        assert_eq!(recurrence.mult(), 2);
+        assert_eq!(recurrence.period_type(), "week");
+        assert_eq!(
+            recurrence.period_start(),
+            NaiveDate::from_ymd_opt(2018, 2, 20).unwrap()
+        );
+        assert_eq!(recurrence.weekend_adjust(), "back");
+    }
+}
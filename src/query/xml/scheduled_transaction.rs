@@ -0,0 +1,247 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use chrono::NaiveDate;
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::XMLQuery;
+use crate::error::Error;
+use crate::query::{ScheduledTransactionQ, ScheduledTransactionT};
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct ScheduledTransaction {
+    pub guid: String,
+    pub name: Option<String>,
+    pub enabled: bool,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub last_occur: Option<NaiveDate>,
+    pub num_occur: i64,
+    pub rem_occur: i64,
+    pub auto_create: bool,
+    pub auto_notify: bool,
+    pub adv_creation: i64,
+    pub adv_notify: i64,
+    pub instance_count: i64,
+    pub template_act_guid: String,
+}
+
+impl XMLQuery {
+    fn scheduled_transaction_map(&self) -> Result<super::ScheduledTransactionMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.scheduled_transactions))
+    }
+}
+
+impl TryFrom<Node<'_, '_>> for ScheduledTransaction {
+    type Error = Error;
+    fn try_from(n: Node) -> Result<Self, Error> {
+        let mut sx = Self::default();
+
+        let text = |child: Node| child.text().unwrap_or_default().trim().to_string();
+        let gdate = |child: Node| {
+            child
+                .children()
+                .find(|n| n.has_tag_name("gdate"))
+                .and_then(|n| n.text())
+                .map(|x| NaiveDate::parse_from_str(x, "%Y-%m-%d"))
+                .transpose()
+        };
+
+        for child in n.children() {
+            match child.tag_name().name() {
+                "id" => sx.guid = text(child),
+                "name" => sx.name = child.text().map(std::string::ToString::to_string),
+                "enabled" => sx.enabled = text(child) == "y",
+                "autoCreate" => sx.auto_create = text(child) == "y",
+                "autoCreateNotify" => sx.auto_notify = text(child) == "y",
+                "advanceCreateDays" => sx.adv_creation = text(child).parse()?,
+                "advanceRemindDays" => sx.adv_notify = text(child).parse()?,
+                "instanceCount" => sx.instance_count = text(child).parse()?,
+                "start" => sx.start_date = gdate(child)?,
+                "last" => sx.last_occur = gdate(child)?,
+                "end" => sx.end_date = gdate(child)?,
+                "num-occur" => sx.num_occur = text(child).parse()?,
+                "rem-occur" => sx.rem_occur = text(child).parse()?,
+                "templ-acct" => sx.template_act_guid = text(child),
+                _ => {}
+            }
+        }
+
+        if sx.guid.is_empty() {
+            return Err(Error::XMLMissingField {
+                model: "ScheduledTransaction".to_string(),
+                field: "guid".to_string(),
+            });
+        }
+
+        Ok(sx)
+    }
+}
+
+impl ScheduledTransactionT for ScheduledTransaction {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or_default()
+    }
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+    fn start_date(&self) -> Option<NaiveDate> {
+        self.start_date
+    }
+    fn end_date(&self) -> Option<NaiveDate> {
+        self.end_date
+    }
+    fn last_occur(&self) -> Option<NaiveDate> {
+        self.last_occur
+    }
+    fn num_occur(&self) -> i64 {
+        self.num_occur
+    }
+    fn rem_occur(&self) -> i64 {
+        self.rem_occur
+    }
+    fn auto_create(&self) -> bool {
+        self.auto_create
+    }
+    fn auto_notify(&self) -> bool {
+        self.auto_notify
+    }
+    fn adv_creation(&self) -> i64 {
+        self.adv_creation
+    }
+    fn adv_notify(&self) -> i64 {
+        self.adv_notify
+    }
+    fn instance_count(&self) -> i64 {
+        self.instance_count
+    }
+    fn template_act_guid(&self) -> &str {
+        &self.template_act_guid
+    }
+}
+
+impl ScheduledTransactionQ for XMLQuery {
+    type Item = ScheduledTransaction;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all scheduled transactions from xml");
+        let map = self
+            .scheduled_transaction_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.values().map(|x| (**x).clone()).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching scheduled transaction by guid from xml");
+        let map = self
+            .scheduled_transaction_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.get(guid).map(|x| (**x).clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use roxmltree::Document;
+    use test_log::test;
+    use tokio::sync::OnceCell;
+
+    use super::*;
+
+    static Q: OnceCell<XMLQuery> = OnceCell::const_new();
+    async fn setup() -> &'static XMLQuery {
+        Q.get_or_init(|| async {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+
+            tracing::debug!("work_dir: {:?}", std::env::current_dir());
+            XMLQuery::new(path).unwrap()
+        })
+        .await
+    }
+
+    #[test]
+    fn test_try_from_element() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+                <gnc-v2
+                    xmlns:gnc="http://www.gnucash.org/XML/gnc"
+                    xmlns:sx="http://www.gnucash.org/XML/sx"
+                    xmlns:recurrence="http://www.gnucash.org/XML/recurrence">
+                    <gnc:schedxaction version="2.0.0">
+                        <sx:id type="guid">3b4c5d6e7f8091a2b3c4d5e6f7081920</sx:id>
+                        <sx:name>Rent</sx:name>
+                        <sx:enabled>y</sx:enabled>
+                        <sx:autoCreate>n</sx:autoCreate>
+                        <sx:autoCreateNotify>n</sx:autoCreateNotify>
+                        <sx:advanceCreateDays>2</sx:advanceCreateDays>
+                        <sx:advanceRemindDays>0</sx:advanceRemindDays>
+                        <sx:instanceCount>1</sx:instanceCount>
+                        <sx:start>
+                            <gdate>2018-03-01</gdate>
+                        </sx:start>
+                        <sx:last>
+                            <gdate>2018-03-01</gdate>
+                        </sx:last>
+                        <sx:num-occur>6</sx:num-occur>
+                        <sx:rem-occur>5</sx:rem-occur>
+                        <sx:templ-acct type="guid">9a8b7c6d5e4f30211203f4e5d6c7b8a9</sx:templ-acct>
+                        <sx:schedule>
+                            <gnc:recurrence version="1.0.0">
+                                <recurrence:mult>1</recurrence:mult>
+                                <recurrence:period_type>month</recurrence:period_type>
+                                <recurrence:start>
+                                    <gdate>2018-03-01</gdate>
+                                </recurrence:start>
+                            </gnc:recurrence>
+                        </sx:schedule>
+                    </gnc:schedxaction>
+                </gnc-v2>
+                "#;
+
+        let doc = Document::parse(data).unwrap();
+        let n = doc
+            .descendants()
+            .find(|n| n.has_tag_name("schedxaction"))
+            .unwrap();
+
+        let sx = ScheduledTransaction::try_from(n).unwrap();
+
+        assert_eq!(sx.guid(), "3b4c5d6e7f8091a2b3c4d5e6f7081920");
+        assert_eq!(sx.name(), "Rent");
+        assert_eq!(sx.enabled(), true);
+        assert_eq!(sx.auto_create(), false);
+        assert_eq!(sx.adv_creation(), 2);
+        assert_eq!(sx.instance_count(), 1);
+        assert_eq!(sx.start_date(), NaiveDate::from_ymd_opt(2018, 3, 1));
+        assert_eq!(sx.last_occur(), NaiveDate::from_ymd_opt(2018, 3, 1));
+        assert_eq!(sx.end_date(), None);
+        assert_eq!(sx.num_occur(), 6);
+        assert_eq!(sx.rem_occur(), 5);
+        assert_eq!(sx.template_act_guid(), "9a8b7c6d5e4f30211203f4e5d6c7b8a9");
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let query = setup().await;
+        let result = ScheduledTransactionQ::all(query).await.unwrap();
+        assert_eq!(result.len(), 0);
+    }
+}
@@ -0,0 +1,374 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use chrono::{NaiveDate, NaiveDateTime};
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::XMLQuery;
+use crate::error::Error;
+use crate::query::{SlotQ, SlotT};
+
+// `slot_type` values shared with the SQL backends
+const INT64: i64 = 1;
+const DOUBLE: i64 = 2;
+const NUMERIC: i64 = 3;
+const STRING: i64 = 4;
+const GUID: i64 = 5;
+const TIMESPEC: i64 = 6;
+const GLIST: i64 = 8;
+const FRAME: i64 = 9;
+const GDATE: i64 = 10;
+
+#[allow(clippy::struct_field_names)]
+#[derive(Default, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Slot {
+    pub obj_guid: String,
+    pub name: String,
+    pub slot_type: i64,
+    pub int64_val: Option<i64>,
+    pub string_val: Option<String>,
+    pub double_val: Option<f64>,
+    pub timespec_val: Option<NaiveDateTime>,
+    pub guid_val: Option<String>,
+    pub numeric_val_num: Option<i64>,
+    pub numeric_val_denom: Option<i64>,
+    pub gdate_val: Option<NaiveDate>,
+}
+
+impl XMLQuery {
+    fn slots_map(&self) -> Result<super::SlotsMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.slots))
+    }
+}
+
+impl Slot {
+    /// Parses the `<slot>` entries below `n`, appending them to `slots`.
+    ///
+    /// As in the SQL backends, names are full paths and the entries of a frame or list are
+    /// attached to its `guid_val`, which is derived from the path as XML has none.
+    pub(crate) fn parse_all(
+        obj_guid: &str,
+        prefix: &str,
+        n: Node,
+        slots: &mut Vec<Self>,
+    ) -> Result<(), Error> {
+        for slot in n.children().filter(|n| n.has_tag_name("slot")) {
+            let key = slot
+                .children()
+                .find(|n| n.has_tag_name("key"))
+                .and_then(|n| n.text())
+                .ok_or_else(|| Error::XMLMissingField {
+                    model: "Slot".to_string(),
+                    field: "key".to_string(),
+                })?;
+            let name = if prefix.is_empty() {
+                key.to_string()
+            } else {
+                format!("{prefix}/{key}")
+            };
+            let Some(value) = slot.children().find(|n| n.has_tag_name("value")) else {
+                continue;
+            };
+
+            Self::parse_value(obj_guid, name, &format!("{obj_guid}/{key}"), value, slots)?;
+        }
+
+        Ok(())
+    }
+
+    /// Parses a single `<slot:value>`, or an item of a list, named `name`.
+    ///
+    /// `container_guid` becomes the `guid_val` of a frame or list value.
+    fn parse_value(
+        obj_guid: &str,
+        name: String,
+        container_guid: &str,
+        value: Node,
+        slots: &mut Vec<Self>,
+    ) -> Result<(), Error> {
+        let mut item = Self {
+            obj_guid: obj_guid.to_string(),
+            name,
+            ..Self::default()
+        };
+        let text = value.text().unwrap_or_default().trim();
+        let child_text = |tag: &str| {
+            value
+                .children()
+                .find(|n| n.has_tag_name(tag))
+                .and_then(|n| n.text())
+                .unwrap_or_default()
+        };
+
+        match value.attribute("type").unwrap_or_default() {
+            "integer" => {
+                item.slot_type = INT64;
+                item.int64_val = Some(text.parse()?);
+            }
+            "double" => {
+                item.slot_type = DOUBLE;
+                item.double_val = text.parse().ok();
+            }
+            "numeric" => {
+                item.slot_type = NUMERIC;
+                let (num, denom) = text.split_once('/').unwrap_or((text, "1"));
+                item.numeric_val_num = Some(num.parse()?);
+                item.numeric_val_denom = Some(denom.parse()?);
+            }
+            "string" => {
+                item.slot_type = STRING;
+                item.string_val = Some(value.text().unwrap_or_default().to_string());
+            }
+            "guid" => {
+                item.slot_type = GUID;
+                item.guid_val = Some(text.to_string());
+            }
+            "timespec" => {
+                item.slot_type = TIMESPEC;
+                item.timespec_val = Some(NaiveDateTime::parse_from_str(
+                    child_text("date"),
+                    "%Y-%m-%d %H:%M:%S%z",
+                )?);
+            }
+            "gdate" => {
+                item.slot_type = GDATE;
+                item.gdate_val = Some(NaiveDate::parse_from_str(child_text("gdate"), "%Y-%m-%d")?);
+            }
+            "frame" => {
+                let prefix = item.name.clone();
+                item.slot_type = FRAME;
+                item.guid_val = Some(container_guid.to_string());
+                slots.push(item);
+                return Self::parse_all(container_guid, &prefix, value, slots);
+            }
+            "list" => {
+                let name = item.name.clone();
+                item.slot_type = GLIST;
+                item.guid_val = Some(container_guid.to_string());
+                slots.push(item);
+                for (i, child) in value
+                    .children()
+                    .filter(|n| n.has_tag_name("value"))
+                    .enumerate()
+                {
+                    Self::parse_value(
+                        container_guid,
+                        name.clone(),
+                        &format!("{container_guid}/{i}"),
+                        child,
+                        slots,
+                    )?;
+                }
+                return Ok(());
+            }
+            _ => return Ok(()),
+        }
+
+        slots.push(item);
+        Ok(())
+    }
+}
+
+impl SlotT for Slot {
+    fn obj_guid(&self) -> &str {
+        &self.obj_guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn slot_type(&self) -> i64 {
+        self.slot_type
+    }
+    fn int64_val(&self) -> i64 {
+        self.int64_val.unwrap_or_default()
+    }
+    fn string_val(&self) -> &str {
+        self.string_val.as_deref().unwrap_or_default()
+    }
+    fn double_val(&self) -> f64 {
+        self.double_val.unwrap_or_default()
+    }
+    fn timespec_val(&self) -> Option<NaiveDateTime> {
+        self.timespec_val
+    }
+    fn guid_val(&self) -> &str {
+        self.guid_val.as_deref().unwrap_or_default()
+    }
+    fn numeric_val_num(&self) -> i64 {
+        self.numeric_val_num.unwrap_or_default()
+    }
+    fn numeric_val_denom(&self) -> i64 {
+        self.numeric_val_denom.unwrap_or(1)
+    }
+    fn gdate_val(&self) -> Option<NaiveDate> {
+        self.gdate_val
+    }
+}
+
+impl SlotQ for XMLQuery {
+    type Item = Slot;
+
+    #[instrument(skip(self))]
+    async fn obj_guid(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching slots by obj_guid from xml");
+        let map = self
+            .slots_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map
+            .get(guid)
+            .map(|v| v.iter().map(|x| (**x).clone()).collect())
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use roxmltree::Document;
+    use test_log::test;
+    use tokio::sync::OnceCell;
+
+    use super::*;
+
+    static Q: OnceCell<XMLQuery> = OnceCell::const_new();
+    async fn setup() -> &'static XMLQuery {
+        Q.get_or_init(|| async {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+
+            tracing::debug!("work_dir: {:?}", std::env::current_dir());
+            XMLQuery::new(path).unwrap()
+        })
+        .await
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+            <trn:slots
+                xmlns:trn="http://www.gnucash.org/XML/trn"
+                xmlns:slot="http://www.gnucash.org/XML/slot"
+                xmlns:ts="http://www.gnucash.org/XML/ts">
+              <slot>
+                <slot:key>void-reason</slot:key>
+                <slot:value type="string">Entered twice</slot:value>
+              </slot>
+              <slot>
+                <slot:key>void-former-amount</slot:key>
+                <slot:value type="numeric">-1500/100</slot:value>
+              </slot>
+              <slot>
+                <slot:key>frame</slot:key>
+                <slot:value type="frame">
+                  <slot>
+                    <slot:key>count</slot:key>
+                    <slot:value type="integer">3</slot:value>
+                  </slot>
+                </slot:value>
+              </slot>
+              <slot>
+                <slot:key>time</slot:key>
+                <slot:value type="timespec">
+                  <ts:date>2024-01-02 03:04:05 +0000</ts:date>
+                </slot:value>
+              </slot>
+            </trn:slots>
+            "#;
+
+        let doc = Document::parse(data).unwrap();
+        let mut slots = Vec::new();
+        Slot::parse_all("guid", "", doc.root_element(), &mut slots).unwrap();
+
+        assert_eq!(slots.len(), 5);
+        assert_eq!(slots[0].name(), "void-reason");
+        assert_eq!(slots[0].string_val(), "Entered twice");
+        assert_eq!(slots[1].numeric_val_num(), -1500);
+        assert_eq!(slots[1].numeric_val_denom(), 100);
+        assert_eq!(slots[2].slot_type(), FRAME);
+        assert_eq!(slots[2].guid_val(), "guid/frame");
+        assert_eq!(slots[3].obj_guid(), "guid/frame");
+        assert_eq!(slots[3].name(), "frame/count");
+        assert_eq!(slots[3].int64_val(), 3);
+        assert_eq!(
+            slots[4].timespec_val(),
+            NaiveDateTime::parse_from_str("2024-01-02 03:04:05", "%Y-%m-%d %H:%M:%S").ok()
+        );
+    }
+
+    #[test]
+    fn test_parse_all_list() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+            <act:slots
+                xmlns:act="http://www.gnucash.org/XML/act"
+                xmlns:slot="http://www.gnucash.org/XML/slot">
+              <slot>
+                <slot:key>ids</slot:key>
+                <slot:value type="list">
+                  <slot:value type="string">first</slot:value>
+                  <slot:value type="frame">
+                    <slot>
+                      <slot:key>id</slot:key>
+                      <slot:value type="integer">2</slot:value>
+                    </slot>
+                  </slot:value>
+                </slot:value>
+              </slot>
+            </act:slots>
+            "#;
+
+        let doc = Document::parse(data).unwrap();
+        let mut slots = Vec::new();
+        Slot::parse_all("guid", "", doc.root_element(), &mut slots).unwrap();
+
+        assert_eq!(slots.len(), 4);
+        assert_eq!(slots[0].slot_type(), GLIST);
+        assert_eq!(slots[0].guid_val(), "guid/ids");
+        assert_eq!(slots[1].obj_guid(), "guid/ids");
+        assert_eq!(slots[1].string_val(), "first");
+        assert_eq!(slots[2].obj_guid(), "guid/ids");
+        assert_eq!(slots[2].slot_type(), FRAME);
+        assert_eq!(slots[2].guid_val(), "guid/ids/1");
+        assert_eq!(slots[3].obj_guid(), "guid/ids/1");
+        assert_eq!(slots[3].name(), "ids/id");
+        assert_eq!(slots[3].int64_val(), 2);
+    }
+
+    #[test(tokio::test)]
+    async fn test_obj_guid() {
+        let query = setup().await;
+        let result = query
+            .obj_guid("7d4ef4044fd30f41d08914a8174c2f5b")
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].name(), "features");
+        assert_eq!(result[0].slot_type(), FRAME);
+        assert_eq!(result[2].name(), "remove-color-not-set-slots");
+        assert_eq!(result[2].string_val(), "true");
+
+        let result = query.obj_guid(result[1].guid_val()).await.unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].name(), "options/Accounts");
+        assert_eq!(result[1].name(), "options/Budgeting");
+
+        let result = query
+            .obj_guid("0fac88d8412f46d5e16beb87aee0fd22")
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].slot_type(), GDATE);
+        assert_eq!(result[0].gdate_val(), NaiveDate::from_ymd_opt(2018, 2, 20));
+    }
+}
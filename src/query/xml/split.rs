@@ -18,7 +18,7 @@ pub struct Split {
     pub account_guid: String,
     pub memo: String,
     pub action: String,
-    pub reconcile_state: bool,
+    pub reconcile_state: String,
     pub reconcile_date: Option<NaiveDateTime>,
     pub value_num: i64,
     pub value_denom: i64,
@@ -50,6 +50,17 @@ impl XMLQuery {
         Ok(Arc::clone(&cache.account_splits))
     }
 
+    fn account_dated_splits_map(&self) -> Result<super::DatedSplitsMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.account_dated_splits))
+    }
+
     fn transaction_splits_map(&self) -> Result<super::SplitsMap, Error> {
         self.update_cache()?;
 
@@ -89,6 +100,9 @@ impl Split {
                         })?
                         .to_string();
                 }
+                "lot" => {
+                    split.lot_guid = child.text().map(std::string::ToString::to_string);
+                }
                 "memo" => {
                     split.memo = child
                         .text()
@@ -102,7 +116,10 @@ impl Split {
                         .unwrap_or_default();
                 }
                 "reconciled-state" => {
-                    split.reconcile_state = child.text().is_some_and(|x| x != "n");
+                    split.reconcile_state = child
+                        .text()
+                        .map(std::string::ToString::to_string)
+                        .unwrap_or_default();
                 }
                 "reconcile-date" => {
                     split.reconcile_date = child
@@ -180,7 +197,13 @@ impl SplitT for Split {
         &self.action
     }
     fn reconcile_state(&self) -> bool {
+        !self.reconcile_state.is_empty() && self.reconcile_state != "n"
+    }
+    fn reconcile_flag(&self) -> char {
         self.reconcile_state
+            .chars()
+            .next()
+            .map_or('n', |x| x.to_ascii_lowercase())
     }
     fn reconcile_datetime(&self) -> Option<NaiveDateTime> {
         let datetime = self.reconcile_date?;
@@ -193,6 +216,19 @@ impl SplitT for Split {
         self.lot_guid.as_deref().unwrap_or_default()
     }
 
+    fn value_num(&self) -> i64 {
+        self.value_num
+    }
+    fn value_denom(&self) -> i64 {
+        self.value_denom
+    }
+    fn quantity_num(&self) -> i64 {
+        self.quantity_num
+    }
+    fn quantity_denom(&self) -> i64 {
+        self.quantity_denom
+    }
+
     #[cfg(not(feature = "decimal"))]
     #[allow(clippy::cast_precision_loss)]
     fn value(&self) -> f64 {
@@ -252,6 +288,30 @@ impl SplitQ for XMLQuery {
             .unwrap_or_default())
     }
 
+    #[instrument(skip(self))]
+    async fn account_between(
+        &self,
+        guid: &str,
+        from: Option<NaiveDateTime>,
+        to: Option<NaiveDateTime>,
+    ) -> Result<Vec<(NaiveDateTime, Self::Item)>, Error> {
+        tracing::debug!("fetching splits by account_guid and post date from xml");
+        let map = self
+            .account_dated_splits_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        let Some(dated) = map.get(guid) else {
+            return Ok(Vec::new());
+        };
+        let start = from.map_or(0, |from| dated.partition_point(|(x, _)| *x < from));
+        let end = to.map_or(dated.len(), |to| dated.partition_point(|(x, _)| *x < to));
+
+        Ok(dated[start..end.max(start)]
+            .iter()
+            .map(|(date, x)| (*date, (**x).clone()))
+            .collect())
+    }
+
     #[instrument(skip(self))]
     async fn transaction(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
         tracing::debug!("fetching splits by tx_guid from xml");
@@ -347,7 +407,9 @@ mod tests {
         assert_eq!(split.account_guid, "93fc043c3062aaa1297b30e543d2cd0d");
         assert_eq!(split.memo, "");
         assert_eq!(split.action, "");
-        assert_eq!(split.reconcile_state, true);
+        assert_eq!(split.reconcile_state, "y");
+        assert_eq!(split.reconcile_state(), true);
+        assert_eq!(split.reconcile_flag(), 'y');
         assert_eq!(
             split.reconcile_date,
             Some("2013-01-23T15:59:59".parse().unwrap(),)
@@ -367,6 +429,31 @@ mod tests {
         assert_eq!(split.lot_guid, None);
     }
 
+    #[test]
+    fn test_try_from_element_lot() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+                <gnc-v2
+                    xmlns:trn="http://www.gnucash.org/XML/trn"
+                    xmlns:split="http://www.gnucash.org/XML/split">
+                    <trn:split>
+                        <split:id type="guid">e07539176a70222b16369bd246b174b9</split:id>
+                        <split:reconciled-state>n</split:reconciled-state>
+                        <split:value>120000/100</split:value>
+                        <split:quantity>1300000/10000</split:quantity>
+                        <split:account type="guid">1c089803052e85f5c6d8e786057dbaee</split:account>
+                        <split:lot type="guid">a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01</split:lot>
+                    </trn:split>
+                </gnc-v2>
+                "#;
+
+        let doc = Document::parse(data).unwrap();
+        let n = doc.descendants().find(|n| n.has_tag_name("split")).unwrap();
+
+        let split = Split::try_from(String::from("6c8876003c4a6026e38e3afb67d6f2b1"), n).unwrap();
+
+        assert_eq!(split.lot_guid(), "a6c6b50b9d1a2d7ffc8a1f3c2f8e9b01");
+    }
+
     #[test(tokio::test)]
     async fn test_split() {
         let query = setup().await;
@@ -392,6 +479,10 @@ mod tests {
         assert_approx_eq!(f64, result.quantity(), 150.0);
         #[cfg(feature = "decimal")]
         assert_eq!(result.quantity(), Decimal::new(150, 0));
+        assert_eq!(result.value_num(), 15000);
+        assert_eq!(result.value_denom(), 100);
+        assert_eq!(result.quantity_num(), 15000);
+        assert_eq!(result.quantity_denom(), 100);
     }
 
     #[test(tokio::test)]
@@ -426,6 +517,43 @@ mod tests {
         assert_eq!(result.len(), 3);
     }
 
+    #[test(tokio::test)]
+    async fn test_account_between() {
+        let query = setup().await;
+        let guid = "93fc043c3062aaa1297b30e543d2cd0d";
+        let date = |x| NaiveDateTime::parse_from_str(x, "%Y-%m-%d %H:%M:%S").unwrap();
+
+        let result = query.account_between(guid, None, None).await.unwrap();
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].0, date("2014-12-24 10:59:00"));
+        assert_eq!(result[0].1.tx_guid, "6c8876003c4a6026e38e3afb67d6f2b1");
+        assert!(result.is_sorted_by_key(|(x, _)| *x));
+        let result = query
+            .account_between(guid, None, Some(date("2018-01-01 00:00:00")))
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        let result = query
+            .account_between(guid, Some(date("2018-02-20 10:59:00")), None)
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 2);
+        let result = query
+            .account_between(
+                guid,
+                Some(date("2014-12-24 10:59:00")),
+                Some(date("2018-02-20 10:59:00")),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        let result = query
+            .account_between(guid, Some(date("2018-02-21 00:00:00")), None)
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 0);
+    }
+
     #[test(tokio::test)]
     async fn test_tx_guid() {
         let query = setup().await;
@@ -0,0 +1,277 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::{XMLQuery, parse_fraction};
+use crate::error::Error;
+use crate::query::{TaxTableEntryQ, TaxTableEntryT, TaxTableQ, TaxTableT};
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct TaxTable {
+    pub guid: String,
+    pub name: String,
+    pub refcount: i64,
+    pub invisible: bool,
+    pub parent_guid: String,
+}
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct TaxTableEntry {
+    pub taxtable_guid: String,
+    pub account_guid: String,
+    pub amount_num: i64,
+    pub amount_denom: i64,
+    pub r#type: i64,
+}
+
+impl XMLQuery {
+    fn taxtable_map(&self) -> Result<super::TaxTableMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.taxtables))
+    }
+
+    fn taxtable_entries_map(&self) -> Result<super::TaxTableEntriesMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.taxtable_entries))
+    }
+}
+
+impl TryFrom<Node<'_, '_>> for TaxTable {
+    type Error = Error;
+    fn try_from(n: Node) -> Result<Self, Error> {
+        let mut taxtable = Self::default();
+
+        for child in n.children() {
+            let text = child.text().unwrap_or_default().trim();
+            match child.tag_name().name() {
+                "guid" => taxtable.guid = text.to_string(),
+                "name" => taxtable.name = text.to_string(),
+                "refcount" => taxtable.refcount = text.parse()?,
+                "invisible" => taxtable.invisible = text == "1",
+                "parent" => taxtable.parent_guid = text.to_string(),
+                _ => {}
+            }
+        }
+
+        if taxtable.guid.is_empty() {
+            return Err(Error::XMLMissingField {
+                model: "TaxTable".to_string(),
+                field: "guid".to_string(),
+            });
+        }
+
+        Ok(taxtable)
+    }
+}
+
+impl TaxTableEntry {
+    /// The entries are nested in the tax table's `taxtable:entries` element.
+    pub(super) fn parse_all(taxtable_guid: &str, entries: Node) -> Result<Vec<Self>, Error> {
+        let mut result = Vec::new();
+
+        for n in entries
+            .children()
+            .filter(|n| n.has_tag_name("GncTaxTableEntry"))
+        {
+            let mut entry = Self {
+                taxtable_guid: taxtable_guid.to_string(),
+                amount_denom: 1,
+                ..Self::default()
+            };
+            for child in n.children() {
+                let text = child.text().unwrap_or_default().trim();
+                match child.tag_name().name() {
+                    "acct" => entry.account_guid = text.to_string(),
+                    "amount" => (entry.amount_num, entry.amount_denom) = parse_fraction(text)?,
+                    "type" => entry.r#type = if text == "VALUE" { 1 } else { 2 },
+                    _ => {}
+                }
+            }
+            result.push(entry);
+        }
+
+        Ok(result)
+    }
+}
+
+impl TaxTableT for TaxTable {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn refcount(&self) -> i64 {
+        self.refcount
+    }
+    fn invisible(&self) -> bool {
+        self.invisible
+    }
+    fn parent_guid(&self) -> &str {
+        &self.parent_guid
+    }
+}
+
+impl TaxTableEntryT for TaxTableEntry {
+    fn taxtable_guid(&self) -> &str {
+        &self.taxtable_guid
+    }
+    fn account_guid(&self) -> &str {
+        &self.account_guid
+    }
+    fn amount_num(&self) -> i64 {
+        self.amount_num
+    }
+    fn amount_denom(&self) -> i64 {
+        self.amount_denom
+    }
+    fn r#type(&self) -> i64 {
+        self.r#type
+    }
+}
+
+impl TaxTableQ for XMLQuery {
+    type Item = TaxTable;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all tax tables from xml");
+        let map = self
+            .taxtable_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.values().map(|x| (**x).clone()).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching tax table by guid from xml");
+        let map = self
+            .taxtable_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.get(guid).map(|x| (**x).clone()))
+    }
+}
+
+impl TaxTableEntryQ for XMLQuery {
+    type Item = TaxTableEntry;
+
+    #[instrument(skip(self))]
+    async fn taxtable(&self, guid: &str) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching tax table entries by taxtable from xml");
+        let map = self
+            .taxtable_entries_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map
+            .get(guid)
+            .map(|v| v.iter().map(|x| (**x).clone()).collect())
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use roxmltree::Document;
+    use test_log::test;
+    use tokio::sync::OnceCell;
+
+    use super::*;
+
+    static Q: OnceCell<XMLQuery> = OnceCell::const_new();
+    async fn setup() -> &'static XMLQuery {
+        Q.get_or_init(|| async {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+
+            tracing::debug!("work_dir: {:?}", std::env::current_dir());
+            XMLQuery::new(path).unwrap()
+        })
+        .await
+    }
+
+    #[test]
+    fn test_try_from_element() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+                <gnc-v2
+                    xmlns:gnc="http://www.gnucash.org/XML/gnc"
+                    xmlns:taxtable="http://www.gnucash.org/XML/taxtable"
+                    xmlns:tte="http://www.gnucash.org/XML/tte">
+                    <gnc:GncTaxTable version="2.0.0">
+                        <taxtable:guid type="guid">8a9b0c1d2e3f405162738495a6b7c801</taxtable:guid>
+                        <taxtable:name>VAT</taxtable:name>
+                        <taxtable:refcount>2</taxtable:refcount>
+                        <taxtable:invisible>0</taxtable:invisible>
+                        <taxtable:entries>
+                            <gnc:GncTaxTableEntry>
+                                <tte:acct type="guid">96ed7a45459fb5fe570e48fcd46f05d0</tte:acct>
+                                <tte:amount>20/1</tte:amount>
+                                <tte:type>PERCENT</tte:type>
+                            </gnc:GncTaxTableEntry>
+                            <gnc:GncTaxTableEntry>
+                                <tte:acct type="guid">96ed7a45459fb5fe570e48fcd46f05d0</tte:acct>
+                                <tte:amount>150/100</tte:amount>
+                                <tte:type>VALUE</tte:type>
+                            </gnc:GncTaxTableEntry>
+                        </taxtable:entries>
+                    </gnc:GncTaxTable>
+                </gnc-v2>
+                "#;
+
+        let doc = Document::parse(data).unwrap();
+        let n = doc
+            .descendants()
+            .find(|n| n.has_tag_name("GncTaxTable"))
+            .unwrap();
+
+        let taxtable = TaxTable::try_from(n).unwrap();
+        assert_eq!(taxtable.guid(), "8a9b0c1d2e3f405162738495a6b7c801");
+        assert_eq!(taxtable.name(), "VAT");
+        assert_eq!(taxtable.refcount(), 2);
+        assert_eq!(taxtable.invisible(), false);
+        assert_eq!(taxtable.parent_guid(), "");
+
+        let entries = n.children().find(|n| n.has_tag_name("entries")).unwrap();
+        let entries = TaxTableEntry::parse_all(&taxtable.guid, entries).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0].taxtable_guid(),
+            "8a9b0c1d2e3f405162738495a6b7c801"
+        );
+        assert_eq!(
+            entries[0].account_guid(),
+            "96ed7a45459fb5fe570e48fcd46f05d0"
+        );
+        assert_eq!(entries[0].amount_num(), 20);
+        assert_eq!(entries[0].amount_denom(), 1);
+        assert_eq!(entries[0].r#type(), 2);
+        assert_eq!(entries[1].amount_num(), 150);
+        assert_eq!(entries[1].amount_denom(), 100);
+        assert_eq!(entries[1].r#type(), 1);
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let query = setup().await;
+        let result = TaxTableQ::all(query).await.unwrap();
+        assert_eq!(result.len(), 0);
+    }
+}
@@ -0,0 +1,217 @@
+// ref: https://wiki.gnucash.org/wiki/GnuCash_XML_format
+
+use roxmltree::Node;
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::{XMLQuery, parse_address};
+use crate::error::Error;
+use crate::model::Address;
+use crate::query::{VendorQ, VendorT};
+
+#[derive(Default, Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+pub struct Vendor {
+    pub guid: String,
+    pub name: String,
+    pub id: String,
+    pub notes: String,
+    pub currency_guid: String,
+    pub active: bool,
+    pub tax_override: bool,
+    pub addr: Address,
+    pub terms_guid: String,
+    pub tax_included: String,
+    pub taxtable_guid: String,
+}
+
+impl XMLQuery {
+    fn vendor_map(&self) -> Result<super::VendorMap, Error> {
+        self.update_cache()?;
+
+        let cache = self
+            .cache
+            .read()
+            .map_err(|e| Error::Internal(format!("Cache lock poisoned: {e}")))?;
+
+        Ok(Arc::clone(&cache.vendors))
+    }
+}
+
+impl TryFrom<Node<'_, '_>> for Vendor {
+    type Error = Error;
+    fn try_from(n: Node) -> Result<Self, Error> {
+        let mut vendor = Self::default();
+
+        for child in n.children() {
+            let text = child.text().unwrap_or_default().trim();
+            match child.tag_name().name() {
+                "guid" => vendor.guid = text.to_string(),
+                "name" => vendor.name = text.to_string(),
+                "id" => vendor.id = text.to_string(),
+                "notes" => vendor.notes = text.to_string(),
+                "currency" => {
+                    vendor.currency_guid = child
+                        .children()
+                        .find(|n| n.has_tag_name("id"))
+                        .and_then(|n| n.text())
+                        .unwrap_or_default()
+                        .to_string();
+                }
+                "active" => vendor.active = text == "1",
+                "use-tt" => vendor.tax_override = text == "1",
+                "addr" => vendor.addr = parse_address(child),
+                "terms" => vendor.terms_guid = text.to_string(),
+                "taxincluded" => vendor.tax_included = text.to_string(),
+                "taxtable" => vendor.taxtable_guid = text.to_string(),
+                _ => {}
+            }
+        }
+
+        if vendor.guid.is_empty() {
+            return Err(Error::XMLMissingField {
+                model: "Vendor".to_string(),
+                field: "guid".to_string(),
+            });
+        }
+
+        Ok(vendor)
+    }
+}
+
+impl VendorT for Vendor {
+    fn guid(&self) -> &str {
+        &self.guid
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn notes(&self) -> &str {
+        &self.notes
+    }
+    fn currency_guid(&self) -> &str {
+        &self.currency_guid
+    }
+    fn active(&self) -> bool {
+        self.active
+    }
+    fn tax_override(&self) -> bool {
+        self.tax_override
+    }
+    fn addr(&self) -> Address {
+        self.addr.clone()
+    }
+    fn terms_guid(&self) -> &str {
+        &self.terms_guid
+    }
+    fn tax_included(&self) -> &str {
+        &self.tax_included
+    }
+    fn taxtable_guid(&self) -> &str {
+        &self.taxtable_guid
+    }
+}
+
+impl VendorQ for XMLQuery {
+    type Item = Vendor;
+
+    #[instrument(skip(self))]
+    async fn all(&self) -> Result<Vec<Self::Item>, Error> {
+        tracing::debug!("fetching all vendors from xml");
+        let map = self
+            .vendor_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.values().map(|x| (**x).clone()).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn guid(&self, guid: &str) -> Result<Option<Self::Item>, Error> {
+        tracing::debug!("fetching vendor by guid from xml");
+        let map = self
+            .vendor_map()
+            .inspect_err(|e| tracing::error!("failed to get map: {e}"))?;
+
+        Ok(map.get(guid).map(|x| (**x).clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use roxmltree::Document;
+    use test_log::test;
+    use tokio::sync::OnceCell;
+
+    use super::*;
+
+    static Q: OnceCell<XMLQuery> = OnceCell::const_new();
+    async fn setup() -> &'static XMLQuery {
+        Q.get_or_init(|| async {
+            let path: &str = &format!(
+                "{}/tests/db/xml/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+
+            tracing::debug!("work_dir: {:?}", std::env::current_dir());
+            XMLQuery::new(path).unwrap()
+        })
+        .await
+    }
+
+    #[test]
+    fn test_try_from_element() {
+        let data = r#"<?xml version="1.0" encoding="utf-8" ?>
+                <gnc-v2
+                    xmlns:gnc="http://www.gnucash.org/XML/gnc"
+                    xmlns:vendor="http://www.gnucash.org/XML/vendor"
+                    xmlns:addr="http://www.gnucash.org/XML/addr"
+                    xmlns:cmdty="http://www.gnucash.org/XML/cmdty">
+                    <gnc:GncVendor version="2.0.0">
+                        <vendor:guid type="guid">e5f6a7b8c9d0e1f2a3b4c5d6e7f80912</vendor:guid>
+                        <vendor:name>Paper Supplies Ltd</vendor:name>
+                        <vendor:id>000001</vendor:id>
+                        <vendor:addr version="2.0.0">
+                            <addr:name>Paper Supplies Ltd</addr:name>
+                            <addr:addr1>Mill Lane 3</addr:addr1>
+                        </vendor:addr>
+                        <vendor:taxincluded>YES</vendor:taxincluded>
+                        <vendor:active>1</vendor:active>
+                        <vendor:currency>
+                            <cmdty:space>CURRENCY</cmdty:space>
+                            <cmdty:id>EUR</cmdty:id>
+                        </vendor:currency>
+                        <vendor:use-tt>0</vendor:use-tt>
+                    </gnc:GncVendor>
+                </gnc-v2>
+                "#;
+
+        let doc = Document::parse(data).unwrap();
+        let n = doc
+            .descendants()
+            .find(|n| n.has_tag_name("GncVendor"))
+            .unwrap();
+
+        let vendor = Vendor::try_from(n).unwrap();
+
+        assert_eq!(vendor.guid(), "e5f6a7b8c9d0e1f2a3b4c5d6e7f80912");
+        assert_eq!(vendor.name(), "Paper Supplies Ltd");
+        assert_eq!(vendor.id(), "000001");
+        assert_eq!(vendor.notes(), "");
+        assert_eq!(vendor.currency_guid(), "EUR");
+        assert_eq!(vendor.active(), true);
+        assert_eq!(vendor.tax_override(), false);
+        assert_eq!(vendor.addr().addr1, "Mill Lane 3");
+        assert_eq!(vendor.tax_included(), "YES");
+        assert_eq!(vendor.taxtable_guid(), "");
+    }
+
+    #[test(tokio::test)]
+    async fn test_all() {
+        let query = setup().await;
+        let result = VendorQ::all(query).await.unwrap();
+        assert_eq!(result.len(), 0);
+    }
+}
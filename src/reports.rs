@@ -0,0 +1,97 @@
+//! Financial statements built from the account tree.
+
+mod balance_sheet;
+mod cash_flow;
+mod income_statement;
+mod section;
+
+pub use balance_sheet::{BalanceSheet, balance_sheet};
+pub use cash_flow::{CashFlow, CashFlowLine, cash_flow};
+pub use income_statement::{
+    Columns, IncomeStatement, IncomeStatementColumn, RateDate, income_statement,
+};
+pub use section::{ReportLine, ReportSection};
+
+use chrono::NaiveDateTime;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::error::Error;
+use crate::exchange::Exchange;
+use crate::model::{Account, Commodity};
+use crate::query::{CommodityQ, Query};
+
+/// Converts account amounts into the report currency at the rates known at a given moment.
+#[derive(Debug)]
+pub(crate) struct Converter<Q>
+where
+    Q: Query,
+{
+    exchange: Exchange,
+    commodities: HashMap<String, Commodity<Q>>,
+    currency: Commodity<Q>,
+}
+
+impl<Q> Converter<Q>
+where
+    Q: Query,
+{
+    pub(crate) async fn new(query: Arc<Q>, currency: &Commodity<Q>) -> Result<Self, Error> {
+        let exchange = Exchange::new(query.clone()).await?;
+        let commodities = CommodityQ::all(&*query)
+            .await
+            .inspect_err(|e| tracing::error!("failed to fetch commodities: {e}"))?
+            .into_iter()
+            .map(|x| {
+                let commodity = Commodity::from_with_query(&x, query.clone());
+                (commodity.guid.clone(), commodity)
+            })
+            .collect();
+
+        Ok(Self {
+            exchange,
+            commodities,
+            currency: currency.clone(),
+        })
+    }
+
+    /// An amount in the commodity of `account`, in the report currency at the
+    /// rate from the prices dated before `until`.
+    ///
+    /// Zero needs no rate, so accounts without a price convert as long as they are empty.
+    pub(crate) fn convert(
+        &self,
+        account: &Account<Q>,
+        amount: crate::Num,
+        until: NaiveDateTime,
+    ) -> Result<crate::Num, Error> {
+        #[cfg(not(feature = "decimal"))]
+        let is_zero = amount.abs() < f64::EPSILON;
+        #[cfg(feature = "decimal")]
+        let is_zero = amount.is_zero();
+        if is_zero {
+            return Ok(crate::Num::default());
+        }
+
+        let commodity = self
+            .commodities
+            .get(&account.commodity_guid)
+            .ok_or_else(|| Error::GuidNotFound {
+                model: "Commodity".to_string(),
+                guid: account.commodity_guid.clone(),
+            })?;
+        let rate = self
+            .exchange
+            .calculate_at(commodity, &self.currency, Some(until))
+            .ok_or_else(|| {
+                tracing::error!(
+                    from = %commodity.mnemonic,
+                    to = %self.currency.mnemonic,
+                    "No exchange rate available for currency conversion"
+                );
+                Error::NoExchangeGraph
+            })?;
+
+        Ok(amount * rate)
+    }
+}
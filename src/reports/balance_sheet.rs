@@ -0,0 +1,216 @@
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use tracing::instrument;
+
+use super::{Converter, ReportSection, section};
+use crate::Book;
+use crate::error::Error;
+use crate::model::{AccountType, Commodity, day_after};
+use crate::query::Query;
+
+const ASSETS: usize = 0;
+const LIABILITIES: usize = 1;
+const EQUITY: usize = 2;
+
+/// What the book owns and owes at the end of a day, in one currency.
+///
+/// Liabilities and equity are shown positive when the book owes them.
+#[derive(Clone, Debug)]
+pub struct BalanceSheet<Q>
+where
+    Q: Query,
+{
+    pub as_of: NaiveDate,
+    pub currency: Commodity<Q>,
+    pub assets: ReportSection<Q>,
+    pub liabilities: ReportSection<Q>,
+    /// Equity accounts, and the trading accounts of multi-currency transactions.
+    pub equity: ReportSection<Q>,
+    /// Income less expenses up to `as_of`, not closed into an equity account.
+    pub retained_earnings: crate::Num,
+    /// What is left of the assets once liabilities, equity and retained earnings are taken off.
+    ///
+    /// It is zero for a book whose accounts all share the report currency. Otherwise
+    /// it holds the gains from converting at `as_of` rates rather than at the rates
+    /// of each transaction, unless trading accounts already carry them.
+    pub imbalance: crate::Num,
+}
+
+impl<Q> BalanceSheet<Q>
+where
+    Q: Query,
+{
+    #[must_use]
+    pub fn total_liabilities_and_equity(&self) -> crate::Num {
+        self.liabilities.total + self.equity.total + self.retained_earnings
+    }
+}
+
+fn classify(account_type: &AccountType) -> Option<usize> {
+    if account_type.is_asset_like() {
+        Some(ASSETS)
+    } else if account_type.is_liability_like() {
+        Some(LIABILITIES)
+    } else if matches!(account_type, AccountType::Equity | AccountType::Trading) {
+        Some(EQUITY)
+    } else {
+        None
+    }
+}
+
+/// Builds the balance sheet of `book` at the end of `as_of`, in `report_currency`.
+///
+/// Each account's own balance is converted with the prices known at `as_of`;
+/// sub-accounts are nested below their parent with subtotals.
+#[instrument(skip(book, report_currency), fields(currency = %report_currency.mnemonic))]
+pub async fn balance_sheet<Q: Query>(
+    book: &Book<Q>,
+    as_of: NaiveDate,
+    report_currency: &Commodity<Q>,
+) -> Result<BalanceSheet<Q>, Error> {
+    tracing::debug!("building balance sheet");
+    let tree = book.account_tree().await?;
+    let converter = Converter::new(book.query.clone(), report_currency).await?;
+    let until = day_after(as_of)?;
+
+    let mut amounts = HashMap::new();
+    let mut retained_earnings = crate::Num::default();
+    for account in tree.descendants(&tree.root().guid) {
+        let is_income_statement = account.r#type.is_income_statement();
+        if classify(&account.r#type).is_none() && !is_income_statement {
+            continue;
+        }
+
+        let balance = account.own_balance_at(as_of).await?;
+        let amount = converter.convert(account, balance, until)?;
+        if is_income_statement {
+            retained_earnings -= amount;
+        } else {
+            amounts.insert(account.guid.clone(), amount);
+        }
+    }
+
+    let [assets, liabilities, equity] = section::sections(
+        &tree,
+        &amounts,
+        classify,
+        [
+            AccountType::Asset.normal_balance_sign(),
+            AccountType::Liability.normal_balance_sign(),
+            AccountType::Equity.normal_balance_sign(),
+        ],
+    );
+    let imbalance = assets.total - liabilities.total - equity.total - retained_earnings;
+    tracing::debug!(?imbalance, "balance sheet built");
+
+    Ok(BalanceSheet {
+        as_of,
+        currency: report_currency.clone(),
+        assets,
+        liabilities,
+        equity,
+        retained_earnings,
+        imbalance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::{Book, SQLiteQuery};
+
+        use super::super::*;
+
+        async fn setup() -> Book<SQLiteQuery> {
+            let uri: &str = &format!(
+                "{}/tests/db/sqlite/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let query = SQLiteQuery::new(uri).unwrap();
+            Book::new(query).await.unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_balance_sheet() {
+            let book = setup().await;
+            let eur = book
+                .currencies()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.mnemonic == "EUR")
+                .unwrap();
+            let as_of = NaiveDate::from_ymd_opt(2018, 12, 31).unwrap();
+            let report = balance_sheet(&book, as_of, &eur).await.unwrap();
+
+            let names = |x: &ReportSection<SQLiteQuery>| {
+                x.lines
+                    .iter()
+                    .map(|x| x.full_name.clone())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(names(&report.assets), vec!["Asset"]);
+            assert_eq!(names(&report.liabilities), vec!["Liability"]);
+            assert_eq!(names(&report.equity), vec!["Equity", "Mouvements"]);
+
+            let asset = &report.assets.lines[0];
+            let broker = &asset.children[0];
+            assert_eq!(broker.full_name, "Asset:Broker");
+            assert_eq!(broker.children[0].full_name, "Asset:Broker:Foo stock");
+
+            // the FOO shares are worth 0.9 AED each, and an AED 0.9 EUR
+            #[cfg(not(feature = "decimal"))]
+            {
+                assert_approx_eq!(f64, broker.amount, 0.0);
+                assert_approx_eq!(f64, broker.total, 105.3, epsilon = 1e-9);
+                assert_approx_eq!(f64, report.assets.total, 24695.3, epsilon = 1e-9);
+                assert_approx_eq!(f64, report.liabilities.total, 20900.0);
+                assert_approx_eq!(f64, report.equity.total, 3905.3, epsilon = 1e-9);
+                assert_approx_eq!(f64, report.retained_earnings, -110.0);
+                assert_approx_eq!(f64, report.imbalance, 0.0, epsilon = 1e-9);
+                assert_approx_eq!(
+                    f64,
+                    report.total_liabilities_and_equity(),
+                    24695.3,
+                    epsilon = 1e-9
+                );
+            }
+            #[cfg(feature = "decimal")]
+            {
+                assert_eq!(broker.amount, Decimal::ZERO);
+                assert_eq!(broker.total.round_dp(6), Decimal::new(1053, 1));
+                assert_eq!(report.assets.total.round_dp(6), Decimal::new(246_953, 1));
+                assert_eq!(report.liabilities.total, Decimal::new(20900, 0));
+                assert_eq!(report.equity.total.round_dp(6), Decimal::new(39053, 1));
+                assert_eq!(report.retained_earnings, Decimal::new(-110, 0));
+                assert_eq!(report.imbalance.round_dp(6), Decimal::ZERO);
+            }
+        }
+
+        #[test(tokio::test)]
+        async fn test_balance_sheet_earlier() {
+            let book = setup().await;
+            let currencies = book.currencies().await.unwrap();
+            let find = |x| currencies.iter().find(|c| c.mnemonic == x).unwrap();
+            let as_of = NaiveDate::from_ymd_opt(2014, 12, 31).unwrap();
+
+            let report = balance_sheet(&book, as_of, find("EUR")).await.unwrap();
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, report.imbalance, 0.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(report.imbalance, Decimal::ZERO);
+
+            // dollars are only priced from 2018
+            let result = balance_sheet(&book, as_of, find("USD")).await;
+            assert!(matches!(result, Err(Error::NoExchangeGraph)));
+        }
+    }
+}
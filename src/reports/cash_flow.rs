@@ -0,0 +1,325 @@
+use chrono::{NaiveDate, NaiveTime};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tracing::instrument;
+
+use crate::Book;
+use crate::error::Error;
+use crate::model::{Account, AccountType, Commodity, day_after};
+use crate::query::Query;
+
+/// The money one account sent to or took from the cash accounts, in one currency.
+#[derive(Clone, Debug)]
+pub struct CashFlowLine<Q>
+where
+    Q: Query,
+{
+    pub account: Account<Q>,
+    /// The colon-joined path of the account, e.g. `Expenses:Groceries`.
+    pub full_name: String,
+    /// The currency of the transactions the amounts come from.
+    pub currency: Commodity<Q>,
+    /// Money into the cash accounts, e.g. from an income account.
+    pub inflow: crate::Num,
+    /// Money out of the cash accounts, e.g. to an expense account.
+    pub outflow: crate::Num,
+}
+
+impl<Q> CashFlowLine<Q>
+where
+    Q: Query,
+{
+    #[must_use]
+    pub fn net(&self) -> crate::Num {
+        self.inflow - self.outflow
+    }
+}
+
+/// Where the money of some cash accounts came from and went to over a period.
+#[derive(Clone, Debug)]
+pub struct CashFlow<Q>
+where
+    Q: Query,
+{
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// One line per counterparty account and transaction currency, by full name.
+    pub lines: Vec<CashFlowLine<Q>>,
+}
+
+impl<Q> CashFlow<Q>
+where
+    Q: Query,
+{
+    #[must_use]
+    pub fn total_inflow(&self, currency: &Commodity<Q>) -> crate::Num {
+        self.in_currency(currency).map(|x| x.inflow).sum()
+    }
+
+    #[must_use]
+    pub fn total_outflow(&self, currency: &Commodity<Q>) -> crate::Num {
+        self.in_currency(currency).map(|x| x.outflow).sum()
+    }
+
+    /// How much the cash accounts gained over the period, in `currency`.
+    #[must_use]
+    pub fn net_change(&self, currency: &Commodity<Q>) -> crate::Num {
+        self.total_inflow(currency) - self.total_outflow(currency)
+    }
+
+    fn in_currency<'a>(
+        &'a self,
+        currency: &'a Commodity<Q>,
+    ) -> impl Iterator<Item = &'a CashFlowLine<Q>> {
+        self.lines
+            .iter()
+            .filter(move |x| x.currency.guid == currency.guid)
+    }
+}
+
+/// Builds the cash flow of `cash_accounts` from `from` to `to`, both days included.
+///
+/// Each transaction posted in the period which touches a cash account
+/// attributes the value of its other splits to their accounts: money in when
+/// the split is a credit, money out when it is a debit. Transfers between the
+/// cash accounts cancel out, and trading account splits, which only balance the
+/// commodities of a transaction, are left out.
+#[instrument(skip(book, cash_accounts))]
+pub async fn cash_flow<Q: Query>(
+    book: &Book<Q>,
+    from: NaiveDate,
+    to: NaiveDate,
+    cash_accounts: &[Account<Q>],
+) -> Result<CashFlow<Q>, Error> {
+    tracing::debug!("building cash flow");
+    if from > to {
+        return Err(Error::Invalid {
+            model: "CashFlow".to_string(),
+            reason: format!("the period starts on {from}, after {to}"),
+        });
+    }
+
+    let tree = book.account_tree().await?;
+    let cash: HashSet<&str> = cash_accounts.iter().map(|x| x.guid.as_str()).collect();
+    let until = day_after(to)?;
+
+    let mut seen = HashSet::new();
+    let mut currencies: HashMap<String, Commodity<Q>> = HashMap::new();
+    let mut lines: BTreeMap<(String, String), CashFlowLine<Q>> = BTreeMap::new();
+    for account in cash_accounts {
+        let splits = account
+            .splits_between(Some(from.and_time(NaiveTime::MIN)), Some(until))
+            .await?;
+
+        for split in splits {
+            if !seen.insert(split.tx_guid.clone()) {
+                continue;
+            }
+
+            let transaction = split.transaction().await?;
+            if transaction.is_voided().await? {
+                continue;
+            }
+            if !currencies.contains_key(&transaction.currency_guid) {
+                let currency = transaction.currency().await?;
+                currencies.insert(transaction.currency_guid.clone(), currency);
+            }
+
+            for other in transaction.splits().await? {
+                #[cfg(not(feature = "decimal"))]
+                let is_zero = other.value.abs() < f64::EPSILON;
+                #[cfg(feature = "decimal")]
+                let is_zero = other.value.is_zero();
+                if is_zero || cash.contains(other.account_guid.as_str()) {
+                    continue;
+                }
+                let Some(counterparty) = tree.get(&other.account_guid) else {
+                    tracing::error!("account not found");
+                    return Err(Error::GuidNotFound {
+                        model: "Account".to_string(),
+                        guid: other.account_guid.clone(),
+                    });
+                };
+                if counterparty.r#type == AccountType::Trading {
+                    continue;
+                }
+
+                let full_name = tree
+                    .full_name(&counterparty.guid)
+                    .unwrap_or_default()
+                    .to_string();
+                let line = lines
+                    .entry((full_name.clone(), transaction.currency_guid.clone()))
+                    .or_insert_with(|| CashFlowLine {
+                        account: counterparty.clone(),
+                        full_name,
+                        currency: currencies[&transaction.currency_guid].clone(),
+                        inflow: crate::Num::default(),
+                        outflow: crate::Num::default(),
+                    });
+                if other.value < crate::Num::default() {
+                    line.inflow -= other.value;
+                } else {
+                    line.outflow += other.value;
+                }
+            }
+        }
+    }
+
+    tracing::debug!(
+        transactions = seen.len(),
+        lines = lines.len(),
+        "cash flow built"
+    );
+    Ok(CashFlow {
+        from,
+        to,
+        lines: lines.into_values().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::query::sqlite::tests::setup_writable;
+        use crate::{Book, SQLiteQuery};
+
+        use super::super::*;
+
+        async fn setup() -> (Book<SQLiteQuery>, Vec<Account<SQLiteQuery>>) {
+            let uri: &str = &format!(
+                "{}/tests/db/sqlite/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let query = SQLiteQuery::new(uri).unwrap();
+            cash(Book::new(query).await.unwrap()).await
+        }
+
+        async fn cash(book: Book<SQLiteQuery>) -> (Book<SQLiteQuery>, Vec<Account<SQLiteQuery>>) {
+            let tree = book.account_tree().await.unwrap();
+            let cash = ["Cash", "Checking", "Savings"]
+                .into_iter()
+                .map(|x| tree.by_path(&format!("Asset:Current:{x}")).unwrap().clone())
+                .collect();
+            (book, cash)
+        }
+
+        fn date(s: &str) -> NaiveDate {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_cash_flow() {
+            let (book, cash) = setup().await;
+            let report = cash_flow(&book, date("2014-01-01"), date("2018-12-31"), &cash)
+                .await
+                .unwrap();
+
+            // transfers between the cash accounts and the trading splits are left out
+            let names: Vec<_> = report.lines.iter().map(|x| x.full_name.as_str()).collect();
+            assert_eq!(
+                names,
+                vec![
+                    "Asset:Broker:Foo stock",
+                    "Equity:Opening Balances - EUR",
+                    "Expense",
+                    "Income",
+                    "Liability"
+                ]
+            );
+
+            let eur = &report.lines[0].currency;
+            assert_eq!(eur.mnemonic, "EUR");
+            let liability = &report.lines[4];
+            #[cfg(not(feature = "decimal"))]
+            {
+                assert_approx_eq!(f64, report.lines[0].outflow, 1200.0);
+                assert_approx_eq!(f64, report.lines[1].inflow, 5000.0);
+                assert_approx_eq!(f64, report.lines[2].outflow, 260.0);
+                assert_approx_eq!(f64, report.lines[3].inflow, 150.0);
+                assert_approx_eq!(f64, liability.inflow, 1000.0);
+                assert_approx_eq!(f64, liability.outflow, 100.0);
+                assert_approx_eq!(f64, liability.net(), 900.0);
+                assert_approx_eq!(f64, report.total_inflow(eur), 6150.0);
+                assert_approx_eq!(f64, report.total_outflow(eur), 1560.0);
+                // the balance of Asset:Current
+                assert_approx_eq!(f64, report.net_change(eur), 4590.0);
+            }
+            #[cfg(feature = "decimal")]
+            {
+                assert_eq!(report.lines[0].outflow, Decimal::new(1200, 0));
+                assert_eq!(report.lines[1].inflow, Decimal::new(5000, 0));
+                assert_eq!(report.lines[2].outflow, Decimal::new(260, 0));
+                assert_eq!(report.lines[3].inflow, Decimal::new(150, 0));
+                assert_eq!(liability.inflow, Decimal::new(1000, 0));
+                assert_eq!(liability.outflow, Decimal::new(100, 0));
+                assert_eq!(liability.net(), Decimal::new(900, 0));
+                assert_eq!(report.total_inflow(eur), Decimal::new(6150, 0));
+                assert_eq!(report.total_outflow(eur), Decimal::new(1560, 0));
+                assert_eq!(report.net_change(eur), Decimal::new(4590, 0));
+            }
+        }
+
+        #[test(tokio::test)]
+        async fn test_cash_flow_period() {
+            let (book, cash) = setup().await;
+
+            // one day, and only the checking account: its transfers now count
+            let report = cash_flow(&book, date("2018-02-20"), date("2018-02-20"), &cash[1..2])
+                .await
+                .unwrap();
+            let names: Vec<_> = report.lines.iter().map(|x| x.full_name.as_str()).collect();
+            assert_eq!(names, vec!["Asset:Current:Cash", "Asset:Current:Savings"]);
+            let eur = &report.lines[0].currency;
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, report.net_change(eur), 150.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(report.net_change(eur), Decimal::new(150, 0));
+
+            let result = cash_flow(&book, date("2018-12-31"), date("2018-01-01"), &cash).await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+        }
+
+        #[test(tokio::test)]
+        async fn test_cash_flow_voided() {
+            let (_dir, query) = setup_writable();
+            let (book, cash) = cash(Book::new(query).await.unwrap()).await;
+            // the only two transactions between the cash accounts and Liability
+            for transaction in book.transactions().await.unwrap() {
+                if [
+                    "325537f4f0fadfd9ffb6aad3cd18e360",
+                    "5be11c009a88b4198aa65ee878ddf99d",
+                ]
+                .contains(&transaction.guid.as_str())
+                {
+                    transaction.void("entered twice").await.unwrap();
+                }
+            }
+
+            let report = cash_flow(&book, date("2014-01-01"), date("2018-12-31"), &cash)
+                .await
+                .unwrap();
+            let names: Vec<_> = report.lines.iter().map(|x| x.full_name.as_str()).collect();
+            assert_eq!(
+                names,
+                vec![
+                    "Asset:Broker:Foo stock",
+                    "Equity:Opening Balances - EUR",
+                    "Expense",
+                    "Income"
+                ]
+            );
+            #[cfg(not(feature = "decimal"))]
+            assert_approx_eq!(f64, report.lines[2].outflow, 230.0);
+            #[cfg(feature = "decimal")]
+            assert_eq!(report.lines[2].outflow, Decimal::new(230, 0));
+        }
+    }
+}
@@ -0,0 +1,395 @@
+use chrono::{Datelike, Months, NaiveDate, NaiveTime};
+use std::collections::HashMap;
+use tracing::instrument;
+
+use super::{Converter, ReportSection, section};
+use crate::Book;
+use crate::error::Error;
+use crate::model::{AccountType, Commodity, day_after};
+use crate::query::Query;
+
+const INCOME: usize = 0;
+const EXPENSES: usize = 1;
+
+/// How an income statement divides its period into columns.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Columns {
+    /// A single column for the whole period.
+    #[default]
+    Total,
+    /// One column per calendar month.
+    Monthly,
+    /// One column per calendar quarter.
+    Quarterly,
+}
+
+/// Which prices convert amounts in other commodities into the report currency.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum RateDate {
+    /// The prices known at the end of each column.
+    #[default]
+    PeriodEnd,
+    /// The prices known on the day each transaction is posted.
+    TransactionDate,
+}
+
+/// Income and expenses over part of an income statement's period, both days included.
+#[derive(Clone, Debug)]
+pub struct IncomeStatementColumn<Q>
+where
+    Q: Query,
+{
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub income: ReportSection<Q>,
+    pub expenses: ReportSection<Q>,
+    /// Income less expenses, negative for a loss.
+    pub net_income: crate::Num,
+}
+
+/// What the book earned and spent over a period, in one currency.
+///
+/// Income and expenses are both shown positive in their usual direction, so a
+/// refund shows as negative income.
+#[derive(Clone, Debug)]
+pub struct IncomeStatement<Q>
+where
+    Q: Query,
+{
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub currency: Commodity<Q>,
+    /// The columns in date order, a single one for [`Columns::Total`].
+    pub columns: Vec<IncomeStatementColumn<Q>>,
+}
+
+impl<Q> IncomeStatement<Q>
+where
+    Q: Query,
+{
+    /// The net income over the whole period.
+    #[must_use]
+    pub fn net_income(&self) -> crate::Num {
+        self.columns.iter().map(|x| x.net_income).sum()
+    }
+}
+
+fn classify(account_type: &AccountType) -> Option<usize> {
+    match account_type {
+        AccountType::Income => Some(INCOME),
+        AccountType::Expense => Some(EXPENSES),
+        _ => None,
+    }
+}
+
+fn invalid(reason: impl Into<String>) -> Error {
+    Error::Invalid {
+        model: "IncomeStatement".to_string(),
+        reason: reason.into(),
+    }
+}
+
+/// Splits `from` to `to` at the start of each calendar month or quarter.
+fn periods(
+    from: NaiveDate,
+    to: NaiveDate,
+    columns: Columns,
+) -> Result<Vec<(NaiveDate, NaiveDate)>, Error> {
+    let months = match columns {
+        Columns::Total => return Ok(vec![(from, to)]),
+        Columns::Monthly => 1,
+        Columns::Quarterly => 3,
+    };
+
+    let mut result = Vec::new();
+    let mut start = from;
+    while start <= to {
+        let first = NaiveDate::from_ymd_opt(start.year(), start.month0() / months * months + 1, 1)
+            .ok_or_else(|| invalid(format!("no period starts before {start}")))?;
+        let next = first
+            .checked_add_months(Months::new(months))
+            .ok_or_else(|| invalid(format!("no period follows {start}")))?;
+        let end = next.pred_opt().map_or(to, |x| x.min(to));
+        result.push((start, end));
+        start = next;
+    }
+
+    Ok(result)
+}
+
+/// Builds the income statement of `book` from `from` to `to`, both days included, in `report_currency`.
+///
+/// Amounts in other commodities are converted with the prices known at the end
+/// of each column, or on the day of each transaction, as `rates` chooses.
+#[instrument(skip(book, report_currency), fields(currency = %report_currency.mnemonic))]
+pub async fn income_statement<Q: Query>(
+    book: &Book<Q>,
+    from: NaiveDate,
+    to: NaiveDate,
+    report_currency: &Commodity<Q>,
+    columns: Columns,
+    rates: RateDate,
+) -> Result<IncomeStatement<Q>, Error> {
+    tracing::debug!("building income statement");
+    if from > to {
+        return Err(invalid(format!("the period starts on {from}, after {to}")));
+    }
+
+    let tree = book.account_tree().await?;
+    let converter = Converter::new(book.query.clone(), report_currency).await?;
+    let accounts: Vec<_> = tree
+        .descendants(&tree.root().guid)
+        .filter(|x| x.r#type.is_income_statement())
+        .collect();
+
+    let mut result = Vec::new();
+    for (start, end) in periods(from, to, columns)? {
+        let until = day_after(end)?;
+
+        let mut amounts = HashMap::new();
+        for account in &accounts {
+            let splits = account
+                .dated_splits_between(Some(start.and_time(NaiveTime::MIN)), Some(until))
+                .await?;
+
+            let amount = match rates {
+                RateDate::PeriodEnd => {
+                    let quantity = splits.iter().map(|(_, x)| x.quantity).sum();
+                    converter.convert(account, quantity, until)?
+                }
+                RateDate::TransactionDate => {
+                    let mut amount = crate::Num::default();
+                    for (posted, split) in &splits {
+                        amount += converter.convert(
+                            account,
+                            split.quantity,
+                            day_after(posted.date())?,
+                        )?;
+                    }
+                    amount
+                }
+            };
+            amounts.insert(account.guid.clone(), amount);
+        }
+
+        let [income, expenses] = section::sections(
+            &tree,
+            &amounts,
+            classify,
+            [
+                AccountType::Income.normal_balance_sign(),
+                AccountType::Expense.normal_balance_sign(),
+            ],
+        );
+        let net_income = income.total - expenses.total;
+        result.push(IncomeStatementColumn {
+            from: start,
+            to: end,
+            income,
+            expenses,
+            net_income,
+        });
+    }
+
+    tracing::debug!(columns = result.len(), "income statement built");
+    Ok(IncomeStatement {
+        from,
+        to,
+        currency: report_currency.clone(),
+        columns: result,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_periods() {
+        assert_eq!(
+            periods(date("2018-01-15"), date("2018-03-10"), Columns::Monthly).unwrap(),
+            vec![
+                (date("2018-01-15"), date("2018-01-31")),
+                (date("2018-02-01"), date("2018-02-28")),
+                (date("2018-03-01"), date("2018-03-10")),
+            ]
+        );
+        assert_eq!(
+            periods(date("2014-11-01"), date("2015-04-01"), Columns::Quarterly).unwrap(),
+            vec![
+                (date("2014-11-01"), date("2014-12-31")),
+                (date("2015-01-01"), date("2015-03-31")),
+                (date("2015-04-01"), date("2015-04-01")),
+            ]
+        );
+        assert_eq!(
+            periods(date("2014-11-01"), date("2015-04-01"), Columns::Total).unwrap(),
+            vec![(date("2014-11-01"), date("2015-04-01"))]
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    mod sqlite {
+        use chrono::NaiveDateTime;
+        #[cfg(not(feature = "decimal"))]
+        use float_cmp::assert_approx_eq;
+        use pretty_assertions::assert_eq;
+        #[cfg(feature = "decimal")]
+        use rust_decimal::Decimal;
+        use test_log::test;
+
+        use crate::query::sqlite::tests::setup_writable;
+        use crate::{Book, SQLiteQuery};
+
+        use super::*;
+
+        async fn setup() -> Book<SQLiteQuery> {
+            let uri: &str = &format!(
+                "{}/tests/db/sqlite/complex_sample.gnucash",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let query = SQLiteQuery::new(uri).unwrap();
+            Book::new(query).await.unwrap()
+        }
+
+        async fn currency(book: &Book<SQLiteQuery>, mnemonic: &str) -> Commodity<SQLiteQuery> {
+            book.currencies()
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|x| x.mnemonic == mnemonic)
+                .unwrap()
+        }
+
+        #[test(tokio::test)]
+        async fn test_income_statement() {
+            let book = setup().await;
+            let eur = currency(&book, "EUR").await;
+            let report = income_statement(
+                &book,
+                date("2014-01-01"),
+                date("2018-12-31"),
+                &eur,
+                Columns::Total,
+                RateDate::PeriodEnd,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(report.columns.len(), 1);
+            let column = &report.columns[0];
+            assert_eq!(column.income.lines[0].full_name, "Income");
+            assert_eq!(column.expenses.lines[0].full_name, "Expense");
+            #[cfg(not(feature = "decimal"))]
+            {
+                assert_approx_eq!(f64, column.income.total, 150.0);
+                assert_approx_eq!(f64, column.expenses.total, 260.0);
+                assert_approx_eq!(f64, report.net_income(), -110.0);
+            }
+            #[cfg(feature = "decimal")]
+            {
+                assert_eq!(column.income.total, Decimal::new(150, 0));
+                assert_eq!(column.expenses.total, Decimal::new(260, 0));
+                assert_eq!(report.net_income(), Decimal::new(-110, 0));
+            }
+        }
+
+        #[test(tokio::test)]
+        async fn test_income_statement_columns() {
+            let book = setup().await;
+            let eur = currency(&book, "EUR").await;
+            let report = income_statement(
+                &book,
+                date("2014-11-01"),
+                date("2018-03-31"),
+                &eur,
+                Columns::Quarterly,
+                RateDate::PeriodEnd,
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(report.columns.len(), 14);
+            let net: Vec<_> = report.columns.iter().map(|x| x.net_income).collect();
+            #[cfg(not(feature = "decimal"))]
+            {
+                assert_approx_eq!(f64, net[0], -80.0);
+                assert!(net[1..13].iter().all(|x| x.abs() < f64::EPSILON));
+                assert_approx_eq!(f64, net[13], -30.0);
+            }
+            #[cfg(feature = "decimal")]
+            {
+                assert_eq!(net[0], Decimal::new(-80, 0));
+                assert!(net[1..13].iter().all(Decimal::is_zero));
+                assert_eq!(net[13], Decimal::new(-30, 0));
+            }
+
+            let result = income_statement(
+                &book,
+                date("2018-03-31"),
+                date("2018-01-01"),
+                &eur,
+                Columns::Monthly,
+                RateDate::PeriodEnd,
+            )
+            .await;
+            assert!(matches!(result, Err(Error::Invalid { .. })));
+        }
+
+        #[test(tokio::test)]
+        async fn test_income_statement_rates() {
+            let (_dir, query) = setup_writable();
+            let book = Book::new(query).await.unwrap();
+
+            let eur = currency(&book, "EUR").await;
+            let aed = currency(&book, "AED").await;
+            let datetime =
+                NaiveDateTime::parse_from_str("2018-06-01 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+            book.add_price(
+                &eur,
+                &aed,
+                datetime,
+                crate::Num::from(4),
+                "user:price",
+                "last",
+            )
+            .await
+            .unwrap();
+
+            let report = |rates| {
+                income_statement(
+                    &book,
+                    date("2018-01-01"),
+                    date("2018-12-31"),
+                    &aed,
+                    Columns::Total,
+                    rates,
+                )
+            };
+            // the 30 EUR spent on 2018-02-20, when a euro is worth 1.11 AED
+            let posted = report(RateDate::TransactionDate).await.unwrap();
+            // and at the end of the year, when it is worth 4 AED
+            let year_end = report(RateDate::PeriodEnd).await.unwrap();
+
+            #[cfg(not(feature = "decimal"))]
+            {
+                assert_approx_eq!(f64, posted.columns[0].expenses.total, 33.33, epsilon = 0.01);
+                assert_approx_eq!(f64, year_end.columns[0].expenses.total, 120.0);
+            }
+            #[cfg(feature = "decimal")]
+            {
+                assert_eq!(
+                    posted.columns[0].expenses.total.round_dp(2),
+                    Decimal::new(3333, 2)
+                );
+                assert_eq!(year_end.columns[0].expenses.total, Decimal::new(120, 0));
+            }
+        }
+    }
+}
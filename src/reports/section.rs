@@ -0,0 +1,126 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::model::{Account, AccountTree, AccountType};
+use crate::query::Query;
+
+/// One account of a report, with its sub-accounts of the same section below it.
+#[derive(Clone, Debug)]
+pub struct ReportLine<Q>
+where
+    Q: Query,
+{
+    pub account: Account<Q>,
+    /// The colon-joined path of the account, e.g. `Asset:Current:Cash`.
+    pub full_name: String,
+    /// The account's own amount in the report currency, signed the way the report shows it.
+    pub amount: crate::Num,
+    /// `amount` plus the totals of the children.
+    pub total: crate::Num,
+    pub children: Vec<ReportLine<Q>>,
+}
+
+/// A group of report lines, e.g. the assets of a balance sheet, with their subtotal.
+#[derive(Clone, Debug)]
+pub struct ReportSection<Q>
+where
+    Q: Query,
+{
+    pub lines: Vec<ReportLine<Q>>,
+    pub total: crate::Num,
+}
+
+impl<Q> Default for ReportSection<Q>
+where
+    Q: Query,
+{
+    fn default() -> Self {
+        Self {
+            lines: Vec::new(),
+            total: crate::Num::default(),
+        }
+    }
+}
+
+/// Sorts the accounts below the root of `tree` into `N` sections.
+///
+/// `classify` gives the section of an account type, or `None` to leave the
+/// account out while still sorting its sub-accounts. A sub-account which falls
+/// into another section than its parent starts a top line there. Amounts are
+/// looked up by account guid and multiplied by the sign of their section.
+pub(crate) fn sections<Q, F, const N: usize>(
+    tree: &AccountTree<Q>,
+    amounts: &HashMap<String, crate::Num>,
+    classify: F,
+    signs: [crate::Num; N],
+) -> [ReportSection<Q>; N]
+where
+    Q: Query,
+    F: Fn(&AccountType) -> Option<usize>,
+{
+    let builder = Builder {
+        tree,
+        amounts,
+        classify,
+    };
+    let mut result: [ReportSection<Q>; N] = std::array::from_fn(|_| ReportSection::default());
+
+    let mut queue: VecDeque<_> = tree.children(&tree.root().guid).collect();
+    while let Some(account) = queue.pop_front() {
+        if let Some(section) = (builder.classify)(&account.r#type) {
+            let line = builder.line(account, section, signs[section], &mut queue);
+            result[section].total += line.total;
+            result[section].lines.push(line);
+        } else {
+            queue.extend(tree.children(&account.guid));
+        }
+    }
+
+    result
+}
+
+struct Builder<'a, Q, F>
+where
+    Q: Query,
+{
+    tree: &'a AccountTree<Q>,
+    amounts: &'a HashMap<String, crate::Num>,
+    classify: F,
+}
+
+impl<'a, Q, F> Builder<'a, Q, F>
+where
+    Q: Query,
+    F: Fn(&AccountType) -> Option<usize>,
+{
+    fn line(
+        &self,
+        account: &'a Account<Q>,
+        section: usize,
+        sign: crate::Num,
+        queue: &mut VecDeque<&'a Account<Q>>,
+    ) -> ReportLine<Q> {
+        let mut children = Vec::new();
+        for child in self.tree.children(&account.guid) {
+            if (self.classify)(&child.r#type) == Some(section) {
+                children.push(self.line(child, section, sign, queue));
+            } else {
+                queue.push_back(child);
+            }
+        }
+
+        let amount = self.amounts.get(&account.guid).copied().unwrap_or_default() * sign;
+        let total = amount + children.iter().map(|x| x.total).sum::<crate::Num>();
+
+        ReportLine {
+            account: account.clone(),
+            full_name: self
+                .tree
+                .full_name(&account.guid)
+                .unwrap_or_default()
+                .to_string(),
+            amount,
+            total,
+            children,
+        }
+    }
+}
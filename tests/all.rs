@@ -99,6 +99,7 @@ mod consistency {
             assert_eq!(a.memo, b.memo);
             assert_eq!(a.action, b.action);
             assert_eq!(a.reconcile_state, b.reconcile_state);
+            assert_eq!(a.reconcile_status, b.reconcile_status);
             assert_eq!(a.reconcile_datetime, b.reconcile_datetime);
             assert_eq!(a.value, b.value);
             assert_eq!(a.quantity, b.quantity);
@@ -131,7 +131,7 @@ mod account {
 
         assert_eq!(account.guid, "fcd795021c976ba75621ec39e75f6214");
         assert_eq!(account.name, "Asset");
-        assert_eq!(account.r#type, "ASSET");
+        assert_eq!(account.r#type, rucash::model::AccountType::Asset);
         assert_eq!(account.commodity_guid, "346629655191dcf59a7e2c2a85b70f69");
         assert_eq!(account.commodity_scu, 100);
         assert_eq!(account.non_std_scu, false);
@@ -269,6 +269,10 @@ mod split {
         assert_eq!(split.memo, "");
         assert_eq!(split.action, "");
         assert_eq!(split.reconcile_state, false);
+        assert_eq!(
+            split.reconcile_status,
+            rucash::model::ReconcileState::Unreconciled
+        );
         assert_eq!(split.reconcile_datetime, None);
 
         #[cfg(not(feature = "decimal"))]
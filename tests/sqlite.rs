@@ -133,7 +133,7 @@ mod account {
 
         assert_eq!(account.guid, "fcd795021c976ba75621ec39e75f6214");
         assert_eq!(account.name, "Asset");
-        assert_eq!(account.r#type, "ASSET");
+        assert_eq!(account.r#type, rucash::model::AccountType::Asset);
         assert_eq!(account.commodity_guid, "346629655191dcf59a7e2c2a85b70f69");
         assert_eq!(account.commodity_scu, 100);
         assert_eq!(account.non_std_scu, false);
@@ -272,6 +272,10 @@ mod split {
         assert_eq!(split.memo, "");
         assert_eq!(split.action, "");
         assert_eq!(split.reconcile_state, false);
+        assert_eq!(
+            split.reconcile_status,
+            rucash::model::ReconcileState::Unreconciled
+        );
         assert_eq!(split.reconcile_datetime, None);
 
         #[cfg(not(feature = "decimal"))]
@@ -382,6 +386,52 @@ mod transaction {
         let splits = transaction.splits().await.unwrap();
         assert_eq!(splits.len(), 2);
     }
+
+    #[test(tokio::test)]
+    async fn new_transaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("complex_sample.gnucash");
+        std::fs::copy(uri(), &path).unwrap();
+        let query = SQLiteQuery::new_writable(path.to_str().unwrap()).unwrap();
+        let book = Book::new(query).await.unwrap();
+
+        let currency = book
+            .currencies()
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|x| x.mnemonic == "EUR")
+            .unwrap();
+        let cash = book
+            .account_contains_name_ignore_case("Cash")
+            .await
+            .unwrap()
+            .unwrap();
+        let expense = book
+            .account_contains_name_ignore_case("Expense")
+            .await
+            .unwrap()
+            .unwrap();
+
+        #[cfg(not(feature = "decimal"))]
+        let amount = 30.0;
+        #[cfg(feature = "decimal")]
+        let amount = Decimal::new(30, 0);
+
+        let transaction = book
+            .new_transaction()
+            .currency(&currency)
+            .description("lunch")
+            .split(&cash, -amount, -amount, "", "")
+            .split(&expense, amount, amount, "", "")
+            .commit()
+            .await
+            .unwrap();
+
+        assert_eq!(transaction.description, "lunch");
+        assert_eq!(transaction.splits().await.unwrap().len(), 2);
+        assert_eq!(cash.splits().await.unwrap().len(), 4);
+    }
 }
 
 mod price {
@@ -134,7 +134,7 @@ mod account {
 
         assert_eq!(account.guid, "fcd795021c976ba75621ec39e75f6214");
         assert_eq!(account.name, "Asset");
-        assert_eq!(account.r#type, "ASSET");
+        assert_eq!(account.r#type, rucash::model::AccountType::Asset);
         assert_eq!(account.commodity_guid, "EUR");
         assert_eq!(account.commodity_scu, 100);
         assert_eq!(account.non_std_scu, false);
@@ -272,6 +272,10 @@ mod split {
         assert_eq!(split.memo, "");
         assert_eq!(split.action, "");
         assert_eq!(split.reconcile_state, false);
+        assert_eq!(
+            split.reconcile_status,
+            rucash::model::ReconcileState::Unreconciled
+        );
         assert_eq!(split.reconcile_datetime, None);
 
         #[cfg(not(feature = "decimal"))]